@@ -421,8 +421,11 @@ fn handle_app_exit(exit_info: AppExitInfo) -> anyhow::Result<()> {
 
 /// Run the update action and print the result.
 fn run_update_action(action: UpdateAction) -> anyhow::Result<()> {
+    // A self-replace already ran inside the update prompt.
+    let Some(cmd_str) = action.command_str() else {
+        return Ok(());
+    };
     println!();
-    let cmd_str = action.command_str();
     println!("Updating Codex via `{cmd_str}`...");
 
     let status = {
@@ -435,7 +438,9 @@ fn run_update_action(action: UpdateAction) -> anyhow::Result<()> {
         }
         #[cfg(not(windows))]
         {
-            let (cmd, args) = action.command_args();
+            let Some((cmd, args)) = action.command_args() else {
+                return Ok(());
+            };
             let command_path = crate::wsl_paths::normalize_for_wsl(cmd);
             let normalized_args: Vec<String> = args
                 .iter()
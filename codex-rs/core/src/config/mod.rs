@@ -17,6 +17,7 @@ use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
 use crate::config::types::SkillsConfig;
 use crate::config::types::Tui;
+use crate::config::types::UpdateManager;
 use crate::config::types::UriBasedFileOpener;
 use crate::config_loader::CloudRequirementsLoader;
 use crate::config_loader::ConfigLayerStack;
@@ -98,6 +99,12 @@ pub(crate) const DEFAULT_AGENT_MAX_THREADS: Option<usize> = Some(6);
 
 pub const CONFIG_TOML_FILE: &str = "config.toml";
 
+/// File under `codex_home` holding the TUI's reasoning translation settings.
+pub const TRANSLATION_CONFIG_FILE: &str = "translation.toml";
+
+/// Environment variable that points the translation settings at a different file.
+pub const TRANSLATION_CONFIG_ENV: &str = "CODEX_TRANSLATION_CONFIG";
+
 #[cfg(test)]
 pub(crate) fn test_config() -> Config {
     let codex_home = tempdir().expect("create temp dir");
@@ -224,6 +231,9 @@ pub struct Config {
     /// - `never`: Never use alternate screen (inline mode, preserves scrollback).
     pub tui_alternate_screen: AltScreenMode,
 
+    /// Package manager to offer first in the update prompt (`tui.preferred_update_manager`).
+    pub tui_preferred_update_manager: Option<UpdateManager>,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
@@ -274,6 +284,10 @@ pub struct Config {
     /// Directory where Codex writes log files (defaults to `$CODEX_HOME/log`).
     pub log_dir: PathBuf,
 
+    /// File holding the TUI's reasoning translation settings (defaults to
+    /// `$CODEX_HOME/translation.toml`, overridable via `CODEX_TRANSLATION_CONFIG`).
+    pub translation_config_path: PathBuf,
+
     /// Settings that govern if and what will be written to `~/.codex/history.jsonl`.
     pub history: History,
 
@@ -1576,6 +1590,7 @@ impl Config {
                 p.push("log");
                 p
             });
+        let translation_config_path = translation_config_path(&codex_home);
 
         // Ensure that every field of ConfigRequirements is applied to the final
         // Config.
@@ -1644,6 +1659,7 @@ impl Config {
             agent_max_threads,
             codex_home,
             log_dir,
+            translation_config_path,
             config_layer_stack,
             history,
             ephemeral: ephemeral.unwrap_or_default(),
@@ -1712,6 +1728,7 @@ impl Config {
                 .as_ref()
                 .map(|t| t.alternate_screen)
                 .unwrap_or_default(),
+            tui_preferred_update_manager: cfg.tui.as_ref().and_then(|t| t.preferred_update_manager),
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -1831,6 +1848,15 @@ pub fn find_codex_home() -> std::io::Result<PathBuf> {
     codex_utils_home_dir::find_codex_home()
 }
 
+/// Resolve the translation settings file: `CODEX_TRANSLATION_CONFIG` when set, otherwise
+/// [`TRANSLATION_CONFIG_FILE`] under `codex_home`.
+pub fn translation_config_path(codex_home: &Path) -> PathBuf {
+    match std::env::var_os(TRANSLATION_CONFIG_ENV).filter(|path| !path.is_empty()) {
+        Some(path) => PathBuf::from(path),
+        None => codex_home.join(TRANSLATION_CONFIG_FILE),
+    }
+}
+
 /// Returns the path to the folder where Codex logs are stored. Does not verify
 /// that the directory exists.
 pub fn log_dir(cfg: &Config) -> std::io::Result<PathBuf> {
@@ -1853,6 +1879,7 @@ mod tests {
     use super::*;
     use core_test_support::test_absolute_path;
     use pretty_assertions::assert_eq;
+    use serial_test::serial;
 
     use std::collections::BTreeMap;
     use std::collections::HashMap;
@@ -1928,6 +1955,38 @@ persistence = "none"
         );
     }
 
+    #[test]
+    #[serial(translation_config_env)]
+    fn translation_config_path_lives_under_codex_home() {
+        if std::env::var_os(TRANSLATION_CONFIG_ENV).is_some() {
+            return;
+        }
+        let codex_home = TempDir::new().expect("tempdir");
+        assert_eq!(
+            translation_config_path(codex_home.path()),
+            codex_home.path().join("translation.toml")
+        );
+    }
+
+    #[test]
+    #[serial(translation_config_env)]
+    fn translation_config_path_honors_env_var() {
+        let original = std::env::var_os(TRANSLATION_CONFIG_ENV);
+        let codex_home = TempDir::new().expect("tempdir");
+        let alternate = codex_home.path().join("profiles").join("work.yaml");
+        unsafe {
+            std::env::set_var(TRANSLATION_CONFIG_ENV, &alternate);
+        }
+        let resolved = translation_config_path(codex_home.path());
+        unsafe {
+            match original {
+                Some(value) => std::env::set_var(TRANSLATION_CONFIG_ENV, value),
+                None => std::env::remove_var(TRANSLATION_CONFIG_ENV),
+            }
+        }
+        assert_eq!(resolved, alternate);
+    }
+
     #[test]
     fn tui_config_missing_notifications_field_defaults_to_enabled() {
         let cfg = r#"
@@ -1947,10 +2006,31 @@ persistence = "none"
                 show_tooltips: true,
                 experimental_mode: None,
                 alternate_screen: AltScreenMode::Auto,
+                preferred_update_manager: None,
             }
         );
     }
 
+    #[test]
+    fn tui_preferred_update_manager_round_trips() {
+        for manager in [
+            UpdateManager::Npm,
+            UpdateManager::Pnpm,
+            UpdateManager::Bun,
+            UpdateManager::Yarn,
+            UpdateManager::Volta,
+        ] {
+            let tui = Tui {
+                preferred_update_manager: Some(manager),
+                ..Default::default()
+            };
+            let serialized = toml::to_string(&tui).expect("tui config should serialize");
+            let parsed =
+                toml::from_str::<Tui>(&serialized).expect("serialized tui config should parse");
+            assert_eq!(parsed.preferred_update_manager, Some(manager));
+        }
+    }
+
     #[test]
     fn test_sandbox_config_parsing() {
         let sandbox_full_access = r#"
@@ -3859,6 +3939,7 @@ model_verbosity = "high"
                 agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
                 codex_home: fixture.codex_home(),
                 log_dir: fixture.codex_home().join("log"),
+                translation_config_path: fixture.codex_home().join(TRANSLATION_CONFIG_FILE),
                 config_layer_stack: Default::default(),
                 history: History::default(),
                 ephemeral: false,
@@ -3897,6 +3978,7 @@ model_verbosity = "high"
                 analytics_enabled: Some(true),
                 feedback_enabled: true,
                 tui_alternate_screen: AltScreenMode::Auto,
+                tui_preferred_update_manager: None,
                 otel: OtelConfig::default(),
             },
             o3_profile_config
@@ -3945,6 +4027,7 @@ model_verbosity = "high"
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
             codex_home: fixture.codex_home(),
             log_dir: fixture.codex_home().join("log"),
+            translation_config_path: fixture.codex_home().join(TRANSLATION_CONFIG_FILE),
             config_layer_stack: Default::default(),
             history: History::default(),
             ephemeral: false,
@@ -3983,6 +4066,7 @@ model_verbosity = "high"
             analytics_enabled: Some(true),
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
+            tui_preferred_update_manager: None,
             otel: OtelConfig::default(),
         };
 
@@ -4046,6 +4130,7 @@ model_verbosity = "high"
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
             codex_home: fixture.codex_home(),
             log_dir: fixture.codex_home().join("log"),
+            translation_config_path: fixture.codex_home().join(TRANSLATION_CONFIG_FILE),
             config_layer_stack: Default::default(),
             history: History::default(),
             ephemeral: false,
@@ -4084,6 +4169,7 @@ model_verbosity = "high"
             analytics_enabled: Some(false),
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
+            tui_preferred_update_manager: None,
             otel: OtelConfig::default(),
         };
 
@@ -4133,6 +4219,7 @@ model_verbosity = "high"
             agent_max_threads: DEFAULT_AGENT_MAX_THREADS,
             codex_home: fixture.codex_home(),
             log_dir: fixture.codex_home().join("log"),
+            translation_config_path: fixture.codex_home().join(TRANSLATION_CONFIG_FILE),
             config_layer_stack: Default::default(),
             history: History::default(),
             ephemeral: false,
@@ -4171,6 +4258,7 @@ model_verbosity = "high"
             analytics_enabled: Some(true),
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
+            tui_preferred_update_manager: None,
             otel: OtelConfig::default(),
         };
 
@@ -486,6 +486,22 @@ pub struct Tui {
     /// scrollback in terminal multiplexers like Zellij that follow the xterm spec.
     #[serde(default)]
     pub alternate_screen: AltScreenMode,
+
+    /// Package manager to offer first when an update is available, if it is installed.
+    /// Defaults to the manager that installed Codex.
+    #[serde(default)]
+    pub preferred_update_manager: Option<UpdateManager>,
+}
+
+/// Package managers that can install a Codex update.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateManager {
+    Npm,
+    Pnpm,
+    Bun,
+    Yarn,
+    Volta,
 }
 
 const fn default_true() -> bool {
@@ -3,6 +3,7 @@ use clap::FromArgMatches;
 use clap::Parser;
 use clap::ValueEnum;
 use codex_common::CliConfigOverrides;
+use codex_translation::TargetLanguage;
 use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
@@ -95,12 +96,36 @@ pub struct Cli {
     #[arg(long = "output-last-message", short = 'o', value_name = "FILE")]
     pub last_message_file: Option<PathBuf>,
 
+    /// Translate reasoning and agent messages to this language before printing them, using
+    /// the provider from the translation config. With --json, translations are added as a
+    /// `translation` field. LANG is a BCP-47 code such as `ja` or `zh-TW`.
+    #[arg(
+        long = "translate",
+        value_name = "LANG",
+        global = true,
+        value_parser = parse_target_language
+    )]
+    pub translate: Option<TargetLanguage>,
+
+    /// Print the original text above each translation.
+    #[arg(
+        long = "bilingual",
+        default_value_t = false,
+        global = true,
+        requires = "translate"
+    )]
+    pub bilingual: bool,
+
     /// Initial instructions for the agent. If not provided as an argument (or
     /// if `-` is used), instructions are read from stdin.
     #[arg(value_name = "PROMPT", value_hint = clap::ValueHint::Other)]
     pub prompt: Option<String>,
 }
 
+fn parse_target_language(tag: &str) -> Result<TargetLanguage, String> {
+    TargetLanguage::try_from(tag.to_string())
+}
+
 #[derive(Debug, clap::Subcommand)]
 pub enum Command {
     /// Resume a previous session by id or pick the most recent with --last.
@@ -277,4 +302,16 @@ mod tests {
         });
         assert_eq!(effective_prompt.as_deref(), Some(PROMPT));
     }
+
+    #[test]
+    fn translate_takes_a_bcp47_code() {
+        let cli = Cli::parse_from(["codex-exec", "--translate", "ja-JP", "hi"]);
+        assert_eq!(cli.translate, Some(TargetLanguage::Japanese));
+
+        let err = Cli::try_parse_from(["codex-exec", "--translate", "Klingon", "hi"]).unwrap_err();
+        assert!(
+            err.to_string().contains("unsupported target language"),
+            "{err}"
+        );
+    }
 }
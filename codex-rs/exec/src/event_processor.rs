@@ -20,7 +20,17 @@ pub(crate) trait EventProcessor {
     );
 
     /// Handle a single event emitted by the agent.
-    fn process_event(&mut self, event: Event) -> CodexStatus;
+    fn process_event(&mut self, event: Event) -> CodexStatus {
+        self.process_translated_event(event, None)
+    }
+
+    /// Handle an event along with the `--translate` translation of its reasoning or agent
+    /// message text, if there is one.
+    fn process_translated_event(
+        &mut self,
+        event: Event,
+        translation: Option<String>,
+    ) -> CodexStatus;
 
     fn print_final_output(&mut self) {}
 }
@@ -76,6 +76,10 @@ pub(crate) struct EventProcessorWithHumanOutput {
     last_total_token_usage: Option<codex_core::protocol::TokenUsageInfo>,
     final_message: Option<String>,
     last_proposed_plan: Option<String>,
+    /// Print the original text above `--translate` translations, not just the translation.
+    bilingual: bool,
+    /// Translation of the last agent message, for the final message on stdout.
+    last_message_translation: Option<String>,
 }
 
 impl EventProcessorWithHumanOutput {
@@ -103,6 +107,8 @@ impl EventProcessorWithHumanOutput {
                 last_total_token_usage: None,
                 final_message: None,
                 last_proposed_plan: None,
+                bilingual: false,
+                last_message_translation: None,
             }
         } else {
             Self {
@@ -121,9 +127,28 @@ impl EventProcessorWithHumanOutput {
                 last_total_token_usage: None,
                 final_message: None,
                 last_proposed_plan: None,
+                bilingual: false,
+                last_message_translation: None,
             }
         }
     }
+
+    /// Show originals together with their translations (`--bilingual`).
+    pub(crate) fn with_bilingual(mut self, bilingual: bool) -> Self {
+        self.bilingual = bilingual;
+        self
+    }
+
+    /// `original` as it should be printed when `translation` is available.
+    fn translated(&self, original: &str, translation: Option<&str>) -> String {
+        match translation {
+            Some(translation) if self.bilingual => {
+                format!("{original}\n\n{}", translation.style(self.dimmed))
+            }
+            Some(translation) => translation.to_string(),
+            None => original.to_string(),
+        }
+    }
 }
 
 struct PatchApplyBegin {
@@ -174,7 +199,11 @@ impl EventProcessor for EventProcessorWithHumanOutput {
         ts_msg!(self, "{}\n{}", "user".style(self.cyan), prompt);
     }
 
-    fn process_event(&mut self, event: Event) -> CodexStatus {
+    fn process_translated_event(
+        &mut self,
+        event: Event,
+        translation: Option<String>,
+    ) -> CodexStatus {
         let Event { id: _, msg } = event;
         match msg {
             EventMsg::Error(ErrorEvent { message, .. }) => {
@@ -272,7 +301,14 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     handle_last_message(last_message, output_file);
                 }
 
-                self.final_message = last_agent_message.or_else(|| self.last_proposed_plan.clone());
+                // The translation belongs to the last agent message, not to a proposed plan.
+                let translation = self
+                    .last_message_translation
+                    .take()
+                    .filter(|_| last_agent_message.is_some());
+                self.final_message = last_agent_message
+                    .or_else(|| self.last_proposed_plan.clone())
+                    .map(|message| self.translated(&message, translation.as_deref()));
 
                 return CodexStatus::InitiateShutdown;
             }
@@ -301,8 +337,9 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     self,
                     "{}\n{}",
                     "codex".style(self.italic).style(self.magenta),
-                    message,
+                    self.translated(&message, translation.as_deref()),
                 );
+                self.last_message_translation = translation;
             }
             EventMsg::ItemCompleted(ItemCompletedEvent {
                 item: TurnItem::Plan(item),
@@ -531,7 +568,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                         self,
                         "{}\n{}",
                         "thinking".style(self.italic).style(self.magenta),
-                        agent_reasoning_event.text,
+                        self.translated(&agent_reasoning_event.text, translation.as_deref()),
                     );
                 }
             }
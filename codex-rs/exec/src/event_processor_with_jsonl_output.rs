@@ -117,10 +117,20 @@ impl EventProcessorWithJsonOutput {
     }
 
     pub fn collect_thread_events(&mut self, event: &protocol::Event) -> Vec<ThreadEvent> {
+        self.collect_translated_thread_events(event, None)
+    }
+
+    /// Like [`Self::collect_thread_events`], attaching `translation` (from `--translate`) to
+    /// the reasoning or agent message item of `event`.
+    pub fn collect_translated_thread_events(
+        &mut self,
+        event: &protocol::Event,
+        translation: Option<String>,
+    ) -> Vec<ThreadEvent> {
         match &event.msg {
             protocol::EventMsg::SessionConfigured(ev) => self.handle_session_configured(ev),
             protocol::EventMsg::ThreadNameUpdated(_) => Vec::new(),
-            protocol::EventMsg::AgentMessage(ev) => self.handle_agent_message(ev),
+            protocol::EventMsg::AgentMessage(ev) => self.handle_agent_message(ev, translation),
             protocol::EventMsg::ItemCompleted(protocol::ItemCompletedEvent {
                 item: codex_protocol::items::TurnItem::Plan(item),
                 ..
@@ -128,7 +138,7 @@ impl EventProcessorWithJsonOutput {
                 self.last_proposed_plan = Some(item.text.clone());
                 Vec::new()
             }
-            protocol::EventMsg::AgentReasoning(ev) => self.handle_reasoning_event(ev),
+            protocol::EventMsg::AgentReasoning(ev) => self.handle_reasoning_event(ev, translation),
             protocol::EventMsg::ExecCommandBegin(ev) => self.handle_exec_command_begin(ev),
             protocol::EventMsg::ExecCommandEnd(ev) => self.handle_exec_command_end(ev),
             protocol::EventMsg::TerminalInteraction(ev) => self.handle_terminal_interaction(ev),
@@ -257,24 +267,34 @@ impl EventProcessorWithJsonOutput {
         vec![]
     }
 
-    fn handle_agent_message(&self, payload: &protocol::AgentMessageEvent) -> Vec<ThreadEvent> {
+    fn handle_agent_message(
+        &self,
+        payload: &protocol::AgentMessageEvent,
+        translation: Option<String>,
+    ) -> Vec<ThreadEvent> {
         let item = ThreadItem {
             id: self.get_next_item_id(),
 
             details: ThreadItemDetails::AgentMessage(AgentMessageItem {
                 text: payload.message.clone(),
+                translation,
             }),
         };
 
         vec![ThreadEvent::ItemCompleted(ItemCompletedEvent { item })]
     }
 
-    fn handle_reasoning_event(&self, ev: &protocol::AgentReasoningEvent) -> Vec<ThreadEvent> {
+    fn handle_reasoning_event(
+        &self,
+        ev: &protocol::AgentReasoningEvent,
+        translation: Option<String>,
+    ) -> Vec<ThreadEvent> {
         let item = ThreadItem {
             id: self.get_next_item_id(),
 
             details: ThreadItemDetails::Reasoning(ReasoningItem {
                 text: ev.text.clone(),
+                translation,
             }),
         };
 
@@ -844,8 +864,12 @@ impl EventProcessor for EventProcessorWithJsonOutput {
     }
 
     #[allow(clippy::print_stdout)]
-    fn process_event(&mut self, event: protocol::Event) -> CodexStatus {
-        let aggregated = self.collect_thread_events(&event);
+    fn process_translated_event(
+        &mut self,
+        event: protocol::Event,
+        translation: Option<String>,
+    ) -> CodexStatus {
+        let aggregated = self.collect_translated_thread_events(&event, translation);
         for conv_event in aggregated {
             match serde_json::to_string(&conv_event) {
                 Ok(line) => {
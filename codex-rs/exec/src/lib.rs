@@ -9,6 +9,7 @@ mod event_processor;
 mod event_processor_with_human_output;
 pub mod event_processor_with_jsonl_output;
 pub mod exec_events;
+mod translation;
 
 pub use cli::Cli;
 pub use cli::Command;
@@ -42,6 +43,7 @@ use codex_core::protocol::SessionSource;
 use codex_protocol::approvals::ElicitationAction;
 use codex_protocol::config_types::SandboxMode;
 use codex_protocol::user_input::UserInput;
+use codex_translation::TranslationConfig;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use event_processor_with_human_output::EventProcessorWithHumanOutput;
 use event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
@@ -64,6 +66,8 @@ use uuid::Uuid;
 use crate::cli::Command as ExecCommand;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
+use crate::translation::ExecTranslator;
+use crate::translation::TranslationQueue;
 use codex_core::default_client::set_default_client_residency_requirement;
 use codex_core::default_client::set_default_originator;
 use codex_core::find_thread_path_by_id_str;
@@ -105,6 +109,8 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
         add_dir,
         color,
         last_message_file,
+        translate,
+        bilingual,
         json: json_mode,
         sandbox_mode: sandbox_mode_cli_arg,
         prompt,
@@ -297,12 +303,27 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
 
     let mut event_processor: Box<dyn EventProcessor> = match json_mode {
         true => Box::new(EventProcessorWithJsonOutput::new(last_message_file.clone())),
-        _ => Box::new(EventProcessorWithHumanOutput::create_with_ansi(
-            stdout_with_ansi,
-            &config,
-            last_message_file.clone(),
-        )),
+        _ => Box::new(
+            EventProcessorWithHumanOutput::create_with_ansi(
+                stdout_with_ansi,
+                &config,
+                last_message_file.clone(),
+            )
+            .with_bilingual(bilingual),
+        ),
     };
+    let translator = translate.and_then(|language| {
+        match ExecTranslator::new(
+            TranslationConfig::load_from_path(&config.translation_config_path),
+            language,
+        ) {
+            Ok(translator) => Some(Arc::new(translator)),
+            Err(err) => {
+                warn!("Translation unavailable, printing the original: {err}");
+                None
+            }
+        }
+    });
 
     if oss {
         // We're in the oss section, so provider_id should be Some
@@ -515,29 +536,55 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
     // Track whether a fatal error was reported by the server so we can
     // exit with a non-zero status for automation-friendly signaling.
     let mut error_seen = false;
-    while let Some(envelope) = rx.recv().await {
+    // Events wait here for their translations, so later ones are translated meanwhile.
+    let mut queue = TranslationQueue::default();
+    let mut events_open = true;
+    loop {
+        let (envelope, translation) = tokio::select! {
+            // Print what is ready before reading more.
+            biased;
+            Some(next) = queue.next(), if !queue.is_empty() => next,
+            envelope = rx.recv(), if events_open && !queue.is_full() => {
+                let Some(envelope) = envelope else {
+                    events_open = false;
+                    continue;
+                };
+                if let EventMsg::ElicitationRequest(ev) = &envelope.event.msg {
+                    // Automatically cancel elicitation requests in exec mode.
+                    envelope
+                        .thread
+                        .submit(Op::ResolveElicitation {
+                            server_name: ev.server_name.clone(),
+                            request_id: ev.id.clone(),
+                            decision: ElicitationAction::Cancel,
+                        })
+                        .await?;
+                }
+                if matches!(envelope.event.msg, EventMsg::Error(_)) {
+                    error_seen = true;
+                }
+                if envelope.thread_id != primary_thread_id
+                    && matches!(&envelope.event.msg, EventMsg::TurnComplete(_))
+                {
+                    continue;
+                }
+                let translation = match &translator {
+                    Some(translator) if envelope.thread_id == primary_thread_id => {
+                        translator.spawn_translation(&envelope.event.msg)
+                    }
+                    _ => None,
+                };
+                queue.push(envelope, translation);
+                continue;
+            }
+            else => break,
+        };
         let ThreadEventEnvelope {
             thread_id,
             thread,
             event,
         } = envelope;
-        if let EventMsg::ElicitationRequest(ev) = &event.msg {
-            // Automatically cancel elicitation requests in exec mode.
-            thread
-                .submit(Op::ResolveElicitation {
-                    server_name: ev.server_name.clone(),
-                    request_id: ev.id.clone(),
-                    decision: ElicitationAction::Cancel,
-                })
-                .await?;
-        }
-        if matches!(event.msg, EventMsg::Error(_)) {
-            error_seen = true;
-        }
-        if thread_id != primary_thread_id && matches!(&event.msg, EventMsg::TurnComplete(_)) {
-            continue;
-        }
-        let shutdown = event_processor.process_event(event);
+        let shutdown = event_processor.process_translated_event(event, translation);
         if thread_id != primary_thread_id && matches!(shutdown, CodexStatus::InitiateShutdown) {
             continue;
         }
@@ -0,0 +1,355 @@
+//! `--translate`: translate reasoning and agent messages before they are printed.
+//!
+//! Provider, model and key come from the same translation config the TUI uses; only the
+//! target language is taken from the command line. A translation that fails or takes longer
+//! than `max_wait_ms` is skipped, and the original text is printed on its own.
+//!
+//! Events are translated in the background as they arrive and printed in their original
+//! order, each once its translation is done; see [`TranslationQueue`].
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+use codex_core::protocol::EventMsg;
+use codex_translation::TargetLanguage;
+use codex_translation::TranslationClient;
+use codex_translation::TranslationConfig;
+use codex_translation::TranslationError;
+use codex_translation::client::TranslationBackend;
+use codex_translation::config::DEFAULT_MAX_WAIT_MS;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+/// Events received but not yet printed, at most. Once this many are waiting on translations,
+/// no more are read until the oldest is printed.
+const MAX_QUEUED_EVENTS: usize = 16;
+
+pub(crate) struct ExecTranslator<B = TranslationClient> {
+    config: TranslationConfig,
+    backend: B,
+    max_wait: Duration,
+}
+
+impl ExecTranslator {
+    /// Translate to `target_language` with the provider settings from `config`. One client
+    /// serves every request.
+    pub(crate) fn new(
+        config: TranslationConfig,
+        target_language: TargetLanguage,
+    ) -> Result<Self, TranslationError> {
+        let config = exec_config(config, target_language);
+        let backend = TranslationClient::from_config(&config)?;
+        Ok(Self::with_backend(config, backend))
+    }
+
+    /// Start translating the reasoning or agent message text carried by `msg`, if any.
+    pub(crate) fn spawn_translation(
+        self: &Arc<Self>,
+        msg: &EventMsg,
+    ) -> Option<JoinHandle<Option<String>>> {
+        let text = event_text(msg)?.to_string();
+        let translator = Arc::clone(self);
+        Some(tokio::spawn(
+            async move { translator.translate(&text).await },
+        ))
+    }
+}
+
+impl<B: TranslationBackend> ExecTranslator<B> {
+    fn with_backend(config: TranslationConfig, backend: B) -> Self {
+        let max_wait = Duration::from_millis(config.max_wait_ms.unwrap_or(DEFAULT_MAX_WAIT_MS));
+        Self {
+            config,
+            backend,
+            max_wait,
+        }
+    }
+
+    /// Translate `text`, or `None` when the request fails or times out.
+    async fn translate(&self, text: &str) -> Option<String> {
+        if text.trim().is_empty() {
+            return None;
+        }
+        let target_language = self.config.target_language.code();
+        let request = self.backend.translate(text, target_language, false);
+        match tokio::time::timeout(self.max_wait, request).await {
+            Ok(Ok(output)) => {
+                let translated = self.config.output_encoding.apply(output.text.trim());
+                (!translated.is_empty()).then_some(translated)
+            }
+            Ok(Err(err)) => {
+                warn!("Translation failed, printing the original: {err}");
+                None
+            }
+            Err(_) => {
+                warn!(
+                    "Translation timed out after {}ms, printing the original",
+                    self.max_wait.as_millis()
+                );
+                None
+            }
+        }
+    }
+}
+
+/// `config` with translation on and `target_language` from the command line.
+fn exec_config(
+    mut config: TranslationConfig,
+    target_language: TargetLanguage,
+) -> TranslationConfig {
+    config.enabled = true;
+    config.target_language = target_language;
+    config.unsupported_target_language = None;
+    config
+}
+
+/// The reasoning or agent message text carried by `msg`.
+fn event_text(msg: &EventMsg) -> Option<&str> {
+    match msg {
+        EventMsg::AgentReasoning(ev) => Some(&ev.text),
+        EventMsg::AgentMessage(ev) => Some(&ev.message),
+        _ => None,
+    }
+}
+
+/// Events waiting for their translations, handed out in the order they were pushed.
+///
+/// Translations run concurrently, so a slow one only holds back the events after it, not
+/// the requests for them.
+pub(crate) struct TranslationQueue<T> {
+    pending: VecDeque<(T, Option<JoinHandle<Option<String>>>)>,
+}
+
+impl<T> Default for TranslationQueue<T> {
+    fn default() -> Self {
+        Self {
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+impl<T> TranslationQueue<T> {
+    /// Queue `item`, printed once `translation` (if any) finishes.
+    pub(crate) fn push(&mut self, item: T, translation: Option<JoinHandle<Option<String>>>) {
+        self.pending.push_back((item, translation));
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Whether enough events are waiting that no more should be read yet.
+    pub(crate) fn is_full(&self) -> bool {
+        self.pending.len() >= MAX_QUEUED_EVENTS
+    }
+
+    /// The oldest item and its translation, once the translation is done.
+    ///
+    /// Cancel-safe: if the future is dropped before it completes, the item stays queued.
+    pub(crate) async fn next(&mut self) -> Option<(T, Option<String>)> {
+        let (_, translation) = self.pending.front_mut()?;
+        let translation = match translation {
+            Some(handle) => handle.await.ok().flatten(),
+            None => None,
+        };
+        self.pending
+            .pop_front()
+            .map(|(item, _)| (item, translation))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_processor_with_jsonl_output::EventProcessorWithJsonOutput;
+    use crate::exec_events::AgentMessageItem;
+    use crate::exec_events::ItemCompletedEvent;
+    use crate::exec_events::ReasoningItem;
+    use crate::exec_events::ThreadEvent;
+    use crate::exec_events::ThreadItem;
+    use crate::exec_events::ThreadItemDetails;
+    use codex_core::protocol::AgentMessageEvent;
+    use codex_core::protocol::AgentReasoningEvent;
+    use codex_core::protocol::Event;
+    use codex_translation::TranslationOutput;
+    use pretty_assertions::assert_eq;
+
+    /// Stand-in for the provider: answers every request with a canned translation, or never
+    /// answers.
+    #[derive(Debug, Clone)]
+    struct FakeBackend {
+        translation: Option<String>,
+    }
+
+    impl FakeBackend {
+        fn translating(translation: &str) -> Self {
+            Self {
+                translation: Some(translation.to_string()),
+            }
+        }
+
+        fn unresponsive() -> Self {
+            Self { translation: None }
+        }
+    }
+
+    impl TranslationBackend for FakeBackend {
+        async fn translate(
+            &self,
+            _text: &str,
+            _target_lang: &str,
+            _strict: bool,
+        ) -> Result<TranslationOutput, TranslationError> {
+            match &self.translation {
+                Some(text) => Ok(TranslationOutput {
+                    text: text.clone(),
+                    usage: None,
+                    truncated: false,
+                }),
+                None => std::future::pending().await,
+            }
+        }
+    }
+
+    fn translator(backend: FakeBackend) -> ExecTranslator<FakeBackend> {
+        let config = TranslationConfig {
+            max_wait_ms: Some(50),
+            ..Default::default()
+        };
+        ExecTranslator::with_backend(exec_config(config, TargetLanguage::Japanese), backend)
+    }
+
+    async fn translate_event(
+        translator: &ExecTranslator<FakeBackend>,
+        msg: &EventMsg,
+    ) -> Option<String> {
+        translator.translate(event_text(msg)?).await
+    }
+
+    fn agent_message(message: &str) -> EventMsg {
+        EventMsg::AgentMessage(AgentMessageEvent {
+            message: message.to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn reasoning_and_agent_messages_are_translated() {
+        let translator = translator(FakeBackend::translating(" 完了しました。\n"));
+
+        assert_eq!(
+            translate_event(&translator, &agent_message("Done.")).await,
+            Some("完了しました。".to_string())
+        );
+        let reasoning = EventMsg::AgentReasoning(AgentReasoningEvent {
+            text: "Checking the build.".to_string(),
+        });
+        assert_eq!(
+            translate_event(&translator, &reasoning).await,
+            Some("完了しました。".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn other_events_and_blank_text_are_not_translated() {
+        let translator = translator(FakeBackend::translating("翻訳"));
+
+        assert_eq!(
+            translate_event(&translator, &EventMsg::ShutdownComplete).await,
+            None
+        );
+        assert_eq!(
+            translate_event(&translator, &agent_message("  ")).await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn timeout_falls_back_to_the_original() {
+        let translator = translator(FakeBackend::unresponsive());
+
+        assert_eq!(
+            translate_event(&translator, &agent_message("Done.")).await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn queue_keeps_event_order_while_translating_concurrently() {
+        let (first_tx, first_rx) = tokio::sync::oneshot::channel::<String>();
+        let (second_tx, second_rx) = tokio::sync::oneshot::channel::<String>();
+        let mut queue = TranslationQueue::default();
+        queue.push("first", Some(tokio::spawn(async { first_rx.await.ok() })));
+        queue.push("untranslated", None);
+        let second = tokio::spawn(async { second_rx.await.ok() });
+        queue.push("second", Some(second));
+
+        // The later translation finishing first doesn't let its event jump the queue.
+        second_tx.send("二番目".to_string()).unwrap();
+        assert!(
+            tokio::time::timeout(Duration::from_millis(20), queue.next())
+                .await
+                .is_err()
+        );
+
+        first_tx.send("一番目".to_string()).unwrap();
+        assert_eq!(
+            queue.next().await,
+            Some(("first", Some("一番目".to_string())))
+        );
+        assert_eq!(queue.next().await, Some(("untranslated", None)));
+        assert_eq!(
+            queue.next().await,
+            Some(("second", Some("二番目".to_string())))
+        );
+        assert!(queue.is_empty());
+        assert_eq!(queue.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn json_output_attaches_the_translation_as_a_field() {
+        let translator = translator(FakeBackend::translating("完了しました。"));
+        let mut processor = EventProcessorWithJsonOutput::new(None);
+
+        let msg = agent_message("Done.");
+        let translation = translate_event(&translator, &msg).await;
+        let events = processor.collect_translated_thread_events(
+            &Event {
+                id: "e1".to_string(),
+                msg,
+            },
+            translation,
+        );
+        assert_eq!(
+            events,
+            vec![ThreadEvent::ItemCompleted(ItemCompletedEvent {
+                item: ThreadItem {
+                    id: "item_0".to_string(),
+                    details: ThreadItemDetails::AgentMessage(AgentMessageItem {
+                        text: "Done.".to_string(),
+                        translation: Some("完了しました。".to_string()),
+                    }),
+                },
+            })]
+        );
+
+        let untranslated = processor.collect_thread_events(&Event {
+            id: "e2".to_string(),
+            msg: EventMsg::AgentReasoning(AgentReasoningEvent {
+                text: "Checking the build.".to_string(),
+            }),
+        });
+        assert_eq!(
+            untranslated,
+            vec![ThreadEvent::ItemCompleted(ItemCompletedEvent {
+                item: ThreadItem {
+                    id: "item_1".to_string(),
+                    details: ThreadItemDetails::Reasoning(ReasoningItem {
+                        text: "Checking the build.".to_string(),
+                        translation: None,
+                    }),
+                },
+            })]
+        );
+    }
+}
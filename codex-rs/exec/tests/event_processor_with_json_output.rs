@@ -719,6 +719,7 @@ fn agent_reasoning_produces_item_completed_reasoning() {
                 id: "item_0".to_string(),
                 details: ThreadItemDetails::Reasoning(ReasoningItem {
                     text: "thinking...".to_string(),
+                    translation: None,
                 }),
             },
         })]
@@ -742,6 +743,7 @@ fn agent_message_produces_item_completed_agent_message() {
                 id: "item_0".to_string(),
                 details: ThreadItemDetails::AgentMessage(AgentMessageItem {
                     text: "hello".to_string(),
+                    translation: None,
                 }),
             },
         })]
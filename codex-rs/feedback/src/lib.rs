@@ -64,9 +64,22 @@ impl CodexFeedback {
     pub fn logger_layer<S>(&self) -> impl Layer<S> + Send + Sync + 'static
     where
         S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+    {
+        self.logger_layer_with_writer(|writer| writer)
+    }
+
+    /// Like [`Self::logger_layer`], but passes the ring-buffer writer through `wrap` first, e.g.
+    /// to redact secrets before they are captured.
+    pub fn logger_layer_with_writer<S, W>(
+        &self,
+        wrap: impl FnOnce(FeedbackMakeWriter) -> W,
+    ) -> impl Layer<S> + Send + Sync + 'static
+    where
+        S: tracing::Subscriber + for<'a> LookupSpan<'a>,
+        W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
     {
         tracing_subscriber::fmt::layer()
-            .with_writer(self.make_writer())
+            .with_writer(wrap(self.make_writer()))
             .with_ansi(false)
             .with_target(false)
             // Capture everything, regardless of the caller's `RUST_LOG`, so feedback includes the
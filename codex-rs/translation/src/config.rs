@@ -0,0 +1,1706 @@
+//! Translation configuration.
+//!
+//! Configuration is stored at `$CODEX_HOME/translation.toml` (`~/.codex` by default). The
+//! caller resolves the path (`Config::translation_config_path` in `codex-core`, which honors
+//! `CODEX_TRANSLATION_CONFIG`) and passes it in; files ending in `.yaml`/`.yml` are read
+//! and written as YAML, everything else as TOML.
+
+use codex_protocol::openai_models::ReasoningEffort;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::de::IntoDeserializer;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::path::PathBuf;
+use unicode_normalization::UnicodeNormalization;
+
+use super::language::TargetLanguage;
+use super::post_filter::invalid_pattern_reason;
+use super::provider::ACCOUNT_ID_PLACEHOLDER;
+use super::provider::DEFAULT_TRANSLATION_TEMPERATURE;
+use super::provider::PROVIDERS;
+use super::provider::Protocol;
+use super::provider::ProviderDef;
+use super::provider::ProviderId;
+use super::provider::SystemMessageRole;
+
+/// Default timeout for translation requests (in milliseconds).
+const DEFAULT_TIMEOUT_MS: u64 = 30000;
+
+/// Default time a reasoning block waits for its translation (in milliseconds).
+pub const DEFAULT_MAX_WAIT_MS: u64 = 5000;
+
+/// `max_wait_ms` values outside this range are used but reported: shorter waits rarely give
+/// a provider time to answer, longer ones hold the transcript back noticeably.
+const MAX_WAIT_MS_RANGE: RangeInclusive<u64> = 500..=60_000;
+
+/// Default number of retries for retryable translation failures.
+const DEFAULT_MAX_RETRIES: u32 = 2;
+
+/// HTTP statuses retried unless `retry_status_codes` replaces the list: rate limiting and
+/// transient server errors.
+const DEFAULT_RETRY_STATUS_CODES: [u16; 4] = [429, 500, 502, 503];
+
+/// User-Agent sent with translation requests unless `user_agent` is set.
+const DEFAULT_USER_AGENT: &str = concat!("codex-tui/", env!("CARGO_PKG_VERSION"));
+
+/// On-disk format of the configuration file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// Detect the format from a file extension, defaulting to TOML.
+    pub fn from_path(path: &Path) -> Self {
+        match path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase)
+            .as_deref()
+        {
+            Some("yaml") | Some("yml") => Self::Yaml,
+            _ => Self::Toml,
+        }
+    }
+}
+
+/// Unicode normalization applied to translated text before it is displayed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputEncoding {
+    /// Keep the provider output unchanged.
+    #[default]
+    Raw,
+    /// Normalize to Unicode Normalization Form C (composed).
+    NfcNormalized,
+    /// Normalize to Unicode Normalization Form D (decomposed).
+    NfdNormalized,
+    /// Transliterate to plain ASCII.
+    Ascii,
+}
+
+impl OutputEncoding {
+    /// Apply this encoding to translated text.
+    pub fn apply(self, text: &str) -> String {
+        match self {
+            Self::Raw => text.to_string(),
+            Self::NfcNormalized => text.nfc().collect(),
+            Self::NfdNormalized => text.nfd().collect(),
+            Self::Ascii => any_ascii::any_ascii(text),
+        }
+    }
+}
+
+/// How a completed translation is shown in the transcript.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranslationDisplayMode {
+    /// Insert a separate translation block after the reasoning cell.
+    #[default]
+    Block,
+    /// Attach the translation to the reasoning cell itself.
+    InlineAnnotation,
+    /// Show only the translation. The original reasoning stays in the transcript (Ctrl+T)
+    /// and is shown as usual when the translation fails or times out.
+    Replace,
+}
+
+/// Hard caps on translation spend for a single session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionBudget {
+    /// Maximum number of translation requests.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_requests: Option<u32>,
+    /// Maximum number of tokens (input + output) reported by the provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u64>,
+}
+
+impl SessionBudget {
+    /// Whether no limit is configured.
+    pub fn is_unlimited(&self) -> bool {
+        self.max_requests.is_none() && self.max_tokens.is_none()
+    }
+}
+
+/// Regex replacement applied to translated text before it is displayed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PostFilter {
+    /// Regex matched against the translation.
+    pub pattern: String,
+    /// Replacement for each match; `$1` and `${name}` refer to capture groups.
+    #[serde(default)]
+    pub replacement: String,
+}
+
+/// Severity of a configuration diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticSeverity {
+    /// Translation may misbehave but can still be attempted.
+    Warning,
+    /// Translation cannot work with this configuration.
+    Error,
+}
+
+/// A problem found by [`TranslationConfig::validate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigDiagnostic {
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+}
+
+impl ConfigDiagnostic {
+    fn warning(message: impl Into<String>) -> Self {
+        Self {
+            severity: DiagnosticSeverity::Warning,
+            message: message.into(),
+        }
+    }
+
+    fn error(message: impl Into<String>) -> Self {
+        Self {
+            severity: DiagnosticSeverity::Error,
+            message: message.into(),
+        }
+    }
+}
+
+/// Translation configuration.
+///
+/// Unknown keys are ignored (no `deny_unknown_fields`) so a file written by a newer version
+/// still loads after a downgrade, keeping the fields this version understands.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TranslationConfig {
+    /// Whether translation is enabled.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Target language, stored as its BCP-47 code (e.g., "zh-CN").
+    #[serde(default, deserialize_with = "deserialize_target_language")]
+    pub target_language: TargetLanguage,
+
+    /// `target_language` as found in the file when this version doesn't support it. It is
+    /// reported by [`TranslationConfig::validate`] and written back on save, so the user's
+    /// setting survives until they pick another language.
+    #[serde(skip)]
+    pub unsupported_target_language: Option<String>,
+
+    /// Provider identifier (e.g., "deepseek", "openai").
+    #[serde(default = "default_provider")]
+    pub provider: String,
+
+    /// API key for the provider (stored in config file).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+
+    /// Model name (overrides provider default).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+
+    /// Custom base URL (for proxies or self-hosted).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+
+    /// Base URLs for individual providers, keyed by provider id (e.g. `"anthropic"`); an
+    /// entry takes precedence over `base_url` for that provider.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub base_url_per_provider: HashMap<String, String>,
+
+    /// Timeout in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+
+    /// Unicode normalization applied to translated output.
+    #[serde(default, deserialize_with = "deserialize_output_encoding")]
+    pub output_encoding: OutputEncoding,
+
+    /// Collapse translations longer than this many lines behind an expand hint.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_display_lines: Option<usize>,
+
+    /// Retry when the provider answers successfully but with blank content.
+    #[serde(default = "default_retry_on_empty_response")]
+    pub retry_on_empty_response: bool,
+
+    /// Check provider responses against the expected JSON schema before parsing them, so
+    /// malformed responses report the offending field instead of a generic parse error.
+    /// Responses without any text then fail validation rather than count as empty.
+    #[serde(default)]
+    pub json_schema_validate: bool,
+
+    /// Maximum number of retries for retryable failures.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<u32>,
+
+    /// HTTP statuses whose error responses are retried. Replaces the default list (429,
+    /// 500, 502, 503) entirely, e.g. to add Cohere's 524 timeouts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_status_codes: Option<Vec<u16>>,
+
+    /// HTTP statuses that are never retried, even when listed in `retry_status_codes`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub non_retry_status_codes: Option<Vec<u16>>,
+
+    /// Workspace paths (and their subdirectories) where translation runs; empty allows all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_workspaces: Vec<PathBuf>,
+
+    /// Thread ids where translation runs; empty allows all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_threads: Vec<String>,
+
+    /// Cloudflare account ID, spliced into the Workers AI base URL.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cloudflare_account_id: Option<String>,
+
+    /// Sequences that stop generation, e.g. `["---", "Note:"]` to cut off meta-commentary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_sequences: Option<Vec<String>>,
+
+    /// Append a record of each completed translation to the history log under codex home.
+    #[serde(default)]
+    pub history_log: bool,
+
+    /// Include source and translated text in history log records.
+    #[serde(default)]
+    pub history_log_include_text: bool,
+
+    /// Save the translation history on exit and restore it on the next start.
+    #[serde(default)]
+    pub persist_between_sessions: bool,
+
+    /// Redact API keys and bearer tokens from the TUI log file.
+    #[serde(default = "default_mask_in_logs")]
+    pub mask_in_logs: bool,
+
+    /// Show translations as separate blocks or as annotations on the reasoning cell.
+    #[serde(default)]
+    pub display_mode: TranslationDisplayMode,
+
+    /// User-Agent header for API requests, for proxies and gateways that route on it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+
+    /// Extra fields merged into OpenAI-compatible request bodies (e.g. `top_p`,
+    /// `enable_thinking`); fields the client already sets are never overridden.
+    #[serde(default, skip_serializing_if = "serde_json::Map::is_empty")]
+    pub extra_body: serde_json::Map<String, serde_json::Value>,
+
+    /// Proxy for translation requests (`http://`, `https://` or `socks5://`). When unset, the
+    /// usual `HTTP_PROXY` / `HTTPS_PROXY` / `ALL_PROXY` / `NO_PROXY` variables apply.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+
+    /// Verify TLS certificates. Setting this to `false` only takes effect in builds with the
+    /// `allow-tls-skip` feature.
+    #[serde(default = "default_tls_verify")]
+    pub tls_verify: bool,
+
+    /// Pause translation once this session uses up the request or token budget.
+    #[serde(default, skip_serializing_if = "SessionBudget::is_unlimited")]
+    pub session_budget: SessionBudget,
+
+    /// Maximum translation requests in flight per provider; unset means no limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_per_provider: Option<u8>,
+
+    /// Maximum translation requests per minute across all providers; requests beyond it
+    /// wait for their turn instead of being sent in a burst. Unset means no limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requests_per_minute: Option<u32>,
+
+    /// Collect reasoning blocks until their text is longer than this many characters, then
+    /// translate them in one request. Each block is shown as soon as it arrives; the
+    /// translation follows the block that filled the batch. 0 translates every block on
+    /// its own.
+    #[serde(default)]
+    pub translate_after_n_chars: usize,
+
+    /// Maximum tokens a single translation may generate. Anthropic requires a limit and
+    /// uses 4096 when this is unset; other providers apply their own default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+
+    /// Send up to this many characters of the previous translations with each request, so
+    /// terminology and pronouns stay consistent between reasoning blocks. Unset disables it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rolling_context_chars: Option<usize>,
+
+    /// Also translate the `**Thinking**` title of reasoning blocks and show it above the
+    /// translation. By default only the body is sent.
+    #[serde(default)]
+    pub header_translation: bool,
+
+    /// Remove Markdown emphasis, headings, blockquotes and list bullets from the text
+    /// before it is sent, for models that garble formatting. Code is sent unchanged.
+    #[serde(default)]
+    pub strip_markdown: bool,
+
+    /// Also translate the agent's messages, not just its reasoning, with a shorter prompt
+    /// that carries no formatting instructions.
+    #[serde(default)]
+    pub translate_system_messages: bool,
+
+    /// Agent messages shorter than this many characters are not translated; brief
+    /// acknowledgements are not worth a request.
+    #[serde(default = "default_min_system_message_length")]
+    pub min_system_message_length: usize,
+
+    /// Translate reasoning summaries. Turn this off and `translate_final_answer` on to
+    /// translate only the answers.
+    #[serde(default = "default_translate_reasoning")]
+    pub translate_reasoning: bool,
+
+    /// Translate the agent's final answer of each turn, whether or not
+    /// `translate_system_messages` is on. Answers shorter than `min_system_message_length`
+    /// are skipped.
+    #[serde(default)]
+    pub translate_final_answer: bool,
+
+    /// Gzip large OpenAI-compatible request bodies. Ignored for local endpoints such as
+    /// Ollama on localhost.
+    #[serde(default)]
+    pub compression: bool,
+
+    /// Skip translation while the agent runs below this reasoning effort; `none` and
+    /// `minimal` reasoning is usually too short to be worth a request. Unset translates at
+    /// every effort.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_effort_to_translate: Option<ReasoningEffort>,
+
+    /// Where OpenAI-compatible requests put the instruction, for gateways and models that
+    /// ignore or reject `system` messages. Defaults to the provider's convention.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_message_role: Option<SystemMessageRole>,
+
+    /// Sampling temperature for OpenAI-compatible requests. Defaults to the provider's
+    /// recommendation, or 0.3.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+
+    /// Show the untranslated text, marked as such, when a translation times out instead of
+    /// an error block.
+    #[serde(default)]
+    pub fallback_to_source_on_timeout: bool,
+
+    /// How long a reasoning block waits for its translation, in milliseconds, before it is
+    /// shown without one. Takes precedence over `CODEX_TUI_TRANSLATION_MAX_WAIT_MS`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_wait_ms: Option<u64>,
+
+    /// Regex replacements applied, in order, to every translation before it is displayed,
+    /// e.g. to strip tags a provider appends.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub post_filters: Vec<PostFilter>,
+}
+
+/// Parse `output_encoding`, falling back to the default for values this version doesn't know.
+fn deserialize_output_encoding<'de, D>(deserializer: D) -> Result<OutputEncoding, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw = String::deserialize(deserializer)?;
+    Ok(
+        OutputEncoding::deserialize(raw.as_str().into_deserializer()).unwrap_or_else(
+            |e: serde::de::value::Error| {
+                tracing::warn!("Unknown translation output_encoding {raw:?}: {e}, using default");
+                OutputEncoding::default()
+            },
+        ),
+    )
+}
+
+/// Parse `target_language`, falling back to the default for languages this version doesn't
+/// support rather than discarding the whole config.
+fn deserialize_target_language<'de, D>(deserializer: D) -> Result<TargetLanguage, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let raw = String::deserialize(deserializer)?;
+    Ok(TargetLanguage::try_from(raw).unwrap_or_else(|e| {
+        tracing::warn!("{e}, using default");
+        TargetLanguage::default()
+    }))
+}
+
+/// `target_language` as written in `content` when it is not a language this version supports.
+fn unsupported_target_language(content: &str, format: ConfigFormat) -> Option<String> {
+    #[derive(Deserialize)]
+    struct RawTargetLanguage {
+        target_language: Option<String>,
+    }
+
+    let raw = match format {
+        ConfigFormat::Toml => toml::from_str::<RawTargetLanguage>(content).ok()?,
+        ConfigFormat::Yaml => serde_yaml::from_str::<RawTargetLanguage>(content).ok()?,
+    }
+    .target_language?;
+    TargetLanguage::from_bcp47(&raw).is_none().then_some(raw)
+}
+
+fn default_retry_on_empty_response() -> bool {
+    true
+}
+
+fn default_mask_in_logs() -> bool {
+    true
+}
+
+fn default_tls_verify() -> bool {
+    true
+}
+
+fn default_min_system_message_length() -> usize {
+    100
+}
+
+fn default_translate_reasoning() -> bool {
+    true
+}
+
+fn default_provider() -> String {
+    ProviderId::default().as_str().to_string()
+}
+
+impl Default for TranslationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            target_language: TargetLanguage::default(),
+            unsupported_target_language: None,
+            provider: default_provider(),
+            api_key: None,
+            model: None,
+            base_url: None,
+            base_url_per_provider: HashMap::new(),
+            timeout_ms: None,
+            output_encoding: OutputEncoding::default(),
+            max_display_lines: None,
+            retry_on_empty_response: default_retry_on_empty_response(),
+            json_schema_validate: false,
+            max_retries: None,
+            retry_status_codes: None,
+            non_retry_status_codes: None,
+            allowed_workspaces: Vec::new(),
+            allowed_threads: Vec::new(),
+            cloudflare_account_id: None,
+            stop_sequences: None,
+            history_log: false,
+            history_log_include_text: false,
+            persist_between_sessions: false,
+            mask_in_logs: default_mask_in_logs(),
+            display_mode: TranslationDisplayMode::default(),
+            user_agent: None,
+            extra_body: serde_json::Map::new(),
+            proxy: None,
+            tls_verify: default_tls_verify(),
+            session_budget: SessionBudget::default(),
+            max_concurrent_per_provider: None,
+            requests_per_minute: None,
+            translate_after_n_chars: 0,
+            max_output_tokens: None,
+            rolling_context_chars: None,
+            header_translation: false,
+            strip_markdown: false,
+            translate_system_messages: false,
+            min_system_message_length: default_min_system_message_length(),
+            translate_reasoning: default_translate_reasoning(),
+            translate_final_answer: false,
+            compression: false,
+            min_effort_to_translate: None,
+            system_message_role: None,
+            temperature: None,
+            fallback_to_source_on_timeout: false,
+            max_wait_ms: None,
+            post_filters: Vec::new(),
+        }
+    }
+}
+
+impl TranslationConfig {
+    /// Parse configuration from a YAML string.
+    pub fn from_yaml(s: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(s)
+    }
+
+    /// Serialize configuration to a YAML string.
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(self)
+    }
+
+    /// Load configuration from `path`, detecting the format from its extension.
+    /// Returns the default configuration if the file is missing or invalid.
+    pub fn load_from_path(path: &Path) -> Self {
+        if !path.exists() {
+            return Self::default();
+        }
+
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => {
+                tracing::warn!("Failed to read translation config: {}, using default", e);
+                return Self::default();
+            }
+        };
+
+        let format = ConfigFormat::from_path(path);
+        let parsed = match format {
+            ConfigFormat::Toml => {
+                toml::from_str::<TranslationConfig>(&content).map_err(|e| e.to_string())
+            }
+            ConfigFormat::Yaml => Self::from_yaml(&content).map_err(|e| e.to_string()),
+        };
+        match parsed {
+            Ok(mut config) => {
+                config.drop_invalid_post_filters();
+                config.unsupported_target_language = unsupported_target_language(&content, format);
+                config
+            }
+            Err(e) => {
+                tracing::warn!("Failed to parse translation config: {}, using default", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Save configuration to `path`, using the format implied by its extension.
+    pub fn save_to_path(&self, path: &Path) -> std::io::Result<()> {
+        // Ensure parent directory exists
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let content = match (ConfigFormat::from_path(path), self.saved_value()) {
+            (ConfigFormat::Toml, None) => toml::to_string_pretty(self).map_err(|e| e.to_string()),
+            (ConfigFormat::Yaml, None) => self.to_yaml().map_err(|e| e.to_string()),
+            (ConfigFormat::Toml, Some(value)) => {
+                toml::to_string_pretty(&value).map_err(|e| e.to_string())
+            }
+            (ConfigFormat::Yaml, Some(value)) => {
+                serde_yaml::to_string(&value).map_err(|e| e.to_string())
+            }
+        }
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+        fs::write(path, &content)?;
+
+        // Set restrictive permissions on Unix (600 - owner read/write only)
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let permissions = std::fs::Permissions::from_mode(0o600);
+            let _ = fs::set_permissions(path, permissions);
+        }
+
+        Ok(())
+    }
+
+    /// `self` as saved when an unsupported `target_language` has to be written back as it was
+    /// found rather than as the default it was loaded as.
+    fn saved_value(&self) -> Option<serde_json::Value> {
+        let raw = self.unsupported_target_language.as_ref()?;
+        let mut value = serde_json::to_value(self).ok()?;
+        // Fields keep their order (`preserve_order`), so only the value changes.
+        value
+            .as_object_mut()?
+            .insert("target_language".to_string(), raw.clone().into());
+        Some(value)
+    }
+
+    /// Get the effective provider ID.
+    pub fn effective_provider(&self) -> ProviderId {
+        self.provider.parse().unwrap_or_default()
+    }
+
+    /// Get the effective API key.
+    pub fn effective_api_key(&self) -> Option<&str> {
+        self.api_key.as_deref().filter(|k| !k.is_empty())
+    }
+
+    /// Get the effective Cloudflare account ID.
+    pub fn effective_cloudflare_account_id(&self) -> Option<&str> {
+        self.cloudflare_account_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+    }
+
+    /// Get the effective base URL: the provider's entry in `base_url_per_provider`, then
+    /// `base_url`, then the provider default.
+    ///
+    /// An `{account_id}` placeholder is replaced with the Cloudflare account ID when one is set.
+    pub fn effective_base_url(&self, provider: &ProviderDef) -> Cow<'_, str> {
+        let base_url = self
+            .base_url_per_provider
+            .get(provider.id.as_str())
+            .map(String::as_str)
+            .filter(|u| !u.is_empty())
+            .or_else(|| self.base_url.as_deref().filter(|u| !u.is_empty()))
+            .unwrap_or(provider.default_base_url);
+        match self.effective_cloudflare_account_id() {
+            Some(account_id) if base_url.contains(ACCOUNT_ID_PLACEHOLDER) => {
+                Cow::Owned(base_url.replace(ACCOUNT_ID_PLACEHOLDER, account_id))
+            }
+            _ => Cow::Borrowed(base_url),
+        }
+    }
+
+    /// Get the effective model name.
+    pub fn effective_model(&self, provider: &ProviderDef) -> &str {
+        self.model
+            .as_deref()
+            .filter(|m| !m.is_empty())
+            .unwrap_or(provider.default_model)
+    }
+
+    /// Get where the instruction goes in OpenAI-compatible requests.
+    pub fn effective_system_message_role(&self, provider: &ProviderDef) -> SystemMessageRole {
+        self.system_message_role
+            .unwrap_or(provider.system_message_role)
+    }
+
+    /// Get the sampling temperature: the configured one, else the provider's default.
+    pub fn effective_temperature(&self, provider: &ProviderDef) -> f32 {
+        self.temperature
+            .or(provider.default_translation_temperature)
+            .unwrap_or(DEFAULT_TRANSLATION_TEMPERATURE)
+    }
+
+    /// Get the effective User-Agent, defaulting to `codex-tui/<version>`.
+    pub fn effective_user_agent(&self) -> &str {
+        self.user_agent
+            .as_deref()
+            .filter(|ua| !ua.is_empty())
+            .unwrap_or(DEFAULT_USER_AGENT)
+    }
+
+    /// Get the effective timeout in milliseconds.
+    pub fn effective_timeout_ms(&self) -> u64 {
+        self.timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS)
+    }
+
+    /// Get the non-empty stop sequences, if any are configured.
+    pub fn effective_stop_sequences(&self) -> Option<Vec<String>> {
+        let stop_sequences: Vec<String> = self
+            .stop_sequences
+            .iter()
+            .flatten()
+            .filter(|s| !s.is_empty())
+            .cloned()
+            .collect();
+        if stop_sequences.is_empty() {
+            None
+        } else {
+            Some(stop_sequences)
+        }
+    }
+
+    /// Get the effective maximum number of retries.
+    pub fn effective_max_retries(&self) -> u32 {
+        self.max_retries.unwrap_or(DEFAULT_MAX_RETRIES)
+    }
+
+    /// Whether an error response with HTTP `status` is retried.
+    ///
+    /// The candidates are `retry_status_codes` when set, otherwise
+    /// [`DEFAULT_RETRY_STATUS_CODES`]; the two are never merged. `non_retry_status_codes`
+    /// is then subtracted, so a status listed in both is not retried.
+    pub fn retries_status(&self, status: u16) -> bool {
+        let retried = match &self.retry_status_codes {
+            Some(codes) => codes.contains(&status),
+            None => DEFAULT_RETRY_STATUS_CODES.contains(&status),
+        };
+        let excluded = self
+            .non_retry_status_codes
+            .as_ref()
+            .is_some_and(|codes| codes.contains(&status));
+        retried && !excluded
+    }
+
+    /// Check whether translation should run for a thread in the given workspace.
+    ///
+    /// With no allowlist configured every context is allowed. Otherwise the thread must be
+    /// listed in `allowed_threads` or the workspace must lie inside one of `allowed_workspaces`.
+    pub fn allows_context(&self, thread_id: &str, workspace: Option<&Path>) -> bool {
+        if self.allowed_workspaces.is_empty() && self.allowed_threads.is_empty() {
+            return true;
+        }
+        if self.allowed_threads.iter().any(|id| id == thread_id) {
+            return true;
+        }
+        workspace.is_some_and(|workspace| {
+            self.allowed_workspaces
+                .iter()
+                .any(|allowed| workspace.starts_with(allowed))
+        })
+    }
+
+    /// Check whether reasoning produced at `effort` should be translated. An unknown effort
+    /// is translated.
+    pub fn allows_effort(&self, effort: Option<ReasoningEffort>) -> bool {
+        match (self.min_effort_to_translate, effort) {
+            (Some(min), Some(effort)) => effort_rank(effort) >= effort_rank(min),
+            _ => true,
+        }
+    }
+
+    /// Check the configuration for problems that would prevent or degrade translation.
+    pub fn validate(&self) -> Vec<ConfigDiagnostic> {
+        let mut diagnostics = Vec::new();
+        let provider = self.effective_provider();
+
+        if provider == ProviderId::Cloudflare && self.effective_cloudflare_account_id().is_none() {
+            diagnostics.push(ConfigDiagnostic::error(
+                "Cloudflare requires cloudflare_account_id to be set",
+            ));
+        }
+
+        if let Some(Err(e)) = self
+            .unsupported_target_language
+            .clone()
+            .map(TargetLanguage::try_from)
+        {
+            diagnostics.push(ConfigDiagnostic::warning(format!(
+                "{e}; translating to {} until another language is chosen",
+                self.target_language.name()
+            )));
+        }
+
+        if !self.tls_verify {
+            let message = if cfg!(feature = "allow-tls-skip") {
+                "tls_verify = false disables certificate verification; use only for development"
+            } else {
+                "tls_verify = false is ignored: built without the allow-tls-skip feature"
+            };
+            diagnostics.push(ConfigDiagnostic::warning(message));
+        }
+
+        if let Some(warning) = self.max_wait_ms.and_then(max_wait_ms_warning) {
+            diagnostics.push(ConfigDiagnostic::warning(warning));
+        }
+
+        if let Some(warning) = self.base_url_warning() {
+            diagnostics.push(ConfigDiagnostic::warning(warning));
+        }
+
+        for filter in &self.post_filters {
+            if let Some(reason) = invalid_pattern_reason(&filter.pattern) {
+                diagnostics.push(ConfigDiagnostic::warning(format!(
+                    "post_filters pattern {:?} is not a valid regex: {reason}",
+                    filter.pattern
+                )));
+            }
+        }
+
+        diagnostics
+    }
+
+    /// [`base_url_protocol_warning`] for the base URL the selected provider will use.
+    pub fn base_url_warning(&self) -> Option<String> {
+        let provider = self.effective_provider().definition();
+        base_url_protocol_warning(&self.effective_base_url(provider), provider)
+    }
+
+    /// Remove `post_filters` whose pattern doesn't compile, warning about each.
+    fn drop_invalid_post_filters(&mut self) {
+        self.post_filters
+            .retain(|filter| match invalid_pattern_reason(&filter.pattern) {
+                Some(reason) => {
+                    tracing::warn!(
+                        "Ignoring translation post filter {:?}: {reason}",
+                        filter.pattern
+                    );
+                    false
+                }
+                None => true,
+            });
+    }
+
+    /// Check if API key is configured.
+    pub fn has_api_key(&self) -> bool {
+        self.effective_api_key().is_some()
+    }
+
+    /// Check if the configuration is valid for translation.
+    pub fn is_valid(&self) -> bool {
+        let provider = self.effective_provider();
+        let def = provider.definition();
+        !def.requires_api_key || self.has_api_key()
+    }
+}
+
+/// Warning for a `max_wait_ms` outside the recommended range, if it is.
+pub fn max_wait_ms_warning(ms: u64) -> Option<String> {
+    (!MAX_WAIT_MS_RANGE.contains(&ms)).then(|| {
+        format!(
+            "max_wait_ms = {ms} is outside the recommended {}-{} ms",
+            MAX_WAIT_MS_RANGE.start(),
+            MAX_WAIT_MS_RANGE.end()
+        )
+    })
+}
+
+/// Warning for a base URL that looks written for a different API than `provider` speaks,
+/// e.g. an OpenAI-compatible gateway with Anthropic selected, or one that already ends in
+/// the endpoint path the client appends. Such URLs typically fail with a 404.
+///
+/// This is a guess from the URL's host and path, so it is only ever a warning.
+pub fn base_url_protocol_warning(base_url: &str, provider: &ProviderDef) -> Option<String> {
+    let normalized = base_url.trim().trim_end_matches('/').to_ascii_lowercase();
+    let endpoint = match provider.protocol {
+        Protocol::OpenAI => Some("/chat/completions"),
+        Protocol::Anthropic => Some("/messages"),
+        Protocol::Gemini => None,
+    };
+    if let Some(endpoint) = endpoint
+        && normalized.ends_with(endpoint)
+    {
+        return Some(format!(
+            "base_url {base_url} should not end in {endpoint}; it is added to every request"
+        ));
+    }
+
+    let looks_like = guess_url_protocol(&normalized, provider.protocol)?;
+    (looks_like != provider.protocol).then(|| {
+        format!(
+            "base_url {base_url} looks like {}, but {} uses {}",
+            protocol_description(looks_like),
+            provider.name,
+            protocol_description(provider.protocol)
+        )
+    })
+}
+
+/// The API a lowercased base URL seems to be for, if anything gives it away: the endpoint
+/// in its path, an OpenAI-compatibility path segment, or a known provider's host.
+fn guess_url_protocol(url: &str, expected: Protocol) -> Option<Protocol> {
+    let (host, path) = split_url(url);
+    if path.contains("/chat/completions")
+        || path
+            .split('/')
+            .any(|segment| matches!(segment, "openai" | "compatible-mode"))
+    {
+        return Some(Protocol::OpenAI);
+    }
+    if path.ends_with("/messages") {
+        return Some(Protocol::Anthropic);
+    }
+    if path.contains(":generatecontent") {
+        return Some(Protocol::Gemini);
+    }
+    if let Some(provider) = PROVIDERS.iter().find(|provider| {
+        let (known_host, _) = split_url(provider.default_base_url);
+        // Local servers share hosts with whatever else runs there.
+        !known_host.starts_with("localhost") && known_host == host
+    }) {
+        return Some(provider.protocol);
+    }
+    // OpenAI-compatible gateways mount under `/v1`. Anthropic's own API does too, so only
+    // Gemini can tell from this.
+    (expected == Protocol::Gemini && path.ends_with("/v1")).then_some(Protocol::OpenAI)
+}
+
+/// Host and path of a URL; the path starts with `/` unless empty.
+fn split_url(url: &str) -> (&str, &str) {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    match without_scheme.find('/') {
+        Some(slash) => without_scheme.split_at(slash),
+        None => (without_scheme, ""),
+    }
+}
+
+fn protocol_description(protocol: Protocol) -> &'static str {
+    match protocol {
+        Protocol::OpenAI => "an OpenAI-compatible endpoint",
+        Protocol::Anthropic => "an Anthropic Messages endpoint",
+        Protocol::Gemini => "a Gemini endpoint",
+    }
+}
+
+/// Position of `effort` from least to most reasoning.
+fn effort_rank(effort: ReasoningEffort) -> u8 {
+    match effort {
+        ReasoningEffort::None => 0,
+        ReasoningEffort::Minimal => 1,
+        ReasoningEffort::Low => 2,
+        ReasoningEffort::Medium => 3,
+        ReasoningEffort::High => 4,
+        ReasoningEffort::XHigh => 5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translation_config_serialization() {
+        let config = TranslationConfig {
+            enabled: true,
+            target_language: TargetLanguage::Japanese,
+            unsupported_target_language: None,
+            provider: "deepseek".to_string(),
+            api_key: Some("sk-test123".to_string()),
+            model: Some("deepseek-chat".to_string()),
+            base_url: None,
+            base_url_per_provider: HashMap::from([(
+                "anthropic".to_string(),
+                "http://localhost:8080/v1".to_string(),
+            )]),
+            timeout_ms: Some(15000),
+            output_encoding: OutputEncoding::NfcNormalized,
+            max_display_lines: Some(40),
+            retry_on_empty_response: false,
+            json_schema_validate: true,
+            max_retries: Some(5),
+            retry_status_codes: Some(vec![429, 524]),
+            non_retry_status_codes: Some(vec![400]),
+            allowed_workspaces: vec![PathBuf::from("/work/app")],
+            allowed_threads: vec!["thread-1".to_string()],
+            cloudflare_account_id: Some("acct".to_string()),
+            stop_sequences: Some(vec!["---".to_string()]),
+            history_log: true,
+            history_log_include_text: true,
+            persist_between_sessions: true,
+            mask_in_logs: false,
+            display_mode: TranslationDisplayMode::InlineAnnotation,
+            user_agent: Some("corp-gateway/1.0".to_string()),
+            extra_body: serde_json::Map::from_iter([(
+                "enable_thinking".to_string(),
+                serde_json::Value::Bool(false),
+            )]),
+            proxy: Some("socks5://127.0.0.1:1080".to_string()),
+            tls_verify: false,
+            session_budget: SessionBudget {
+                max_requests: Some(100),
+                max_tokens: Some(200_000),
+            },
+            max_concurrent_per_provider: Some(2),
+            requests_per_minute: Some(20),
+            translate_after_n_chars: 400,
+            max_output_tokens: Some(8192),
+            rolling_context_chars: Some(1_000),
+            header_translation: true,
+            strip_markdown: true,
+            translate_system_messages: true,
+            min_system_message_length: 40,
+            translate_reasoning: false,
+            translate_final_answer: true,
+            compression: true,
+            min_effort_to_translate: Some(ReasoningEffort::Low),
+            system_message_role: Some(SystemMessageRole::PrependToFirstUser),
+            temperature: Some(0.5),
+            fallback_to_source_on_timeout: true,
+            max_wait_ms: Some(8_000),
+            post_filters: vec![PostFilter {
+                pattern: r"\s*（翻译）$".to_string(),
+                replacement: String::new(),
+            }],
+        };
+
+        let toml_str = toml::to_string(&config).unwrap();
+        let parsed: TranslationConfig = toml::from_str(&toml_str).unwrap();
+
+        assert_eq!(parsed.enabled, config.enabled);
+        assert_eq!(parsed.target_language, config.target_language);
+        assert_eq!(parsed.provider, config.provider);
+        assert_eq!(parsed.api_key, config.api_key);
+        assert_eq!(parsed.model, config.model);
+        assert_eq!(parsed.base_url_per_provider, config.base_url_per_provider);
+        assert_eq!(parsed.timeout_ms, config.timeout_ms);
+        assert_eq!(parsed.output_encoding, config.output_encoding);
+        assert_eq!(
+            parsed.retry_on_empty_response,
+            config.retry_on_empty_response
+        );
+        assert_eq!(parsed.json_schema_validate, config.json_schema_validate);
+        assert_eq!(parsed.max_retries, config.max_retries);
+        assert_eq!(parsed.retry_status_codes, config.retry_status_codes);
+        assert_eq!(parsed.non_retry_status_codes, config.non_retry_status_codes);
+        assert_eq!(parsed.allowed_workspaces, config.allowed_workspaces);
+        assert_eq!(parsed.allowed_threads, config.allowed_threads);
+        assert_eq!(parsed.cloudflare_account_id, config.cloudflare_account_id);
+        assert_eq!(parsed.stop_sequences, config.stop_sequences);
+        assert_eq!(parsed.history_log, config.history_log);
+        assert_eq!(
+            parsed.history_log_include_text,
+            config.history_log_include_text
+        );
+        assert_eq!(
+            parsed.persist_between_sessions,
+            config.persist_between_sessions
+        );
+        assert_eq!(parsed.mask_in_logs, config.mask_in_logs);
+        assert_eq!(parsed.display_mode, config.display_mode);
+        assert_eq!(parsed.user_agent, config.user_agent);
+        assert_eq!(parsed.extra_body, config.extra_body);
+        assert_eq!(parsed.proxy, config.proxy);
+        assert_eq!(parsed.tls_verify, config.tls_verify);
+        assert_eq!(parsed.session_budget, config.session_budget);
+        assert_eq!(
+            parsed.max_concurrent_per_provider,
+            config.max_concurrent_per_provider
+        );
+        assert_eq!(parsed.requests_per_minute, config.requests_per_minute);
+        assert_eq!(
+            parsed.translate_after_n_chars,
+            config.translate_after_n_chars
+        );
+        assert_eq!(parsed.max_output_tokens, config.max_output_tokens);
+        assert_eq!(parsed.rolling_context_chars, config.rolling_context_chars);
+        assert_eq!(parsed.header_translation, config.header_translation);
+        assert_eq!(parsed.strip_markdown, config.strip_markdown);
+        assert_eq!(
+            parsed.translate_system_messages,
+            config.translate_system_messages
+        );
+        assert_eq!(
+            parsed.min_system_message_length,
+            config.min_system_message_length
+        );
+        assert_eq!(parsed.translate_reasoning, config.translate_reasoning);
+        assert_eq!(parsed.translate_final_answer, config.translate_final_answer);
+        assert_eq!(parsed.compression, config.compression);
+        assert_eq!(
+            parsed.min_effort_to_translate,
+            config.min_effort_to_translate
+        );
+        assert_eq!(parsed.system_message_role, config.system_message_role);
+        assert_eq!(parsed.temperature, config.temperature);
+        assert_eq!(
+            parsed.fallback_to_source_on_timeout,
+            config.fallback_to_source_on_timeout
+        );
+        assert_eq!(parsed.max_wait_ms, config.max_wait_ms);
+        assert_eq!(parsed.post_filters, config.post_filters);
+        assert_eq!(parsed.max_display_lines, config.max_display_lines);
+    }
+
+    #[test]
+    fn config_format_detected_from_extension() {
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("translation.toml")),
+            ConfigFormat::Toml
+        );
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("translation.yaml")),
+            ConfigFormat::Yaml
+        );
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("translation.YML")),
+            ConfigFormat::Yaml
+        );
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("translation")),
+            ConfigFormat::Toml
+        );
+    }
+
+    #[test]
+    fn translation_config_yaml_round_trip() {
+        let config = TranslationConfig {
+            enabled: true,
+            target_language: TargetLanguage::Japanese,
+            api_key: Some("sk-test123".to_string()),
+            timeout_ms: Some(15000),
+            ..Default::default()
+        };
+
+        let yaml = config.to_yaml().unwrap();
+        let parsed = TranslationConfig::from_yaml(&yaml).unwrap();
+
+        assert_eq!(parsed.enabled, config.enabled);
+        assert_eq!(parsed.target_language, config.target_language);
+        assert_eq!(parsed.api_key, config.api_key);
+        assert_eq!(parsed.timeout_ms, config.timeout_ms);
+    }
+
+    #[test]
+    fn translation_config_file_round_trip_in_every_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = TranslationConfig {
+            enabled: true,
+            provider: "openai".to_string(),
+            model: Some("gpt-4o".to_string()),
+            output_encoding: OutputEncoding::Ascii,
+            ..Default::default()
+        };
+
+        for name in ["translation.toml", "translation.yaml", "translation.yml"] {
+            let path = dir.path().join(name);
+            config.save_to_path(&path).unwrap();
+            let loaded = TranslationConfig::load_from_path(&path);
+
+            assert_eq!(loaded.enabled, config.enabled, "{name}");
+            assert_eq!(loaded.provider, config.provider, "{name}");
+            assert_eq!(loaded.model, config.model, "{name}");
+            assert_eq!(loaded.output_encoding, config.output_encoding, "{name}");
+        }
+    }
+
+    #[test]
+    fn target_language_accepts_bcp47_tags() {
+        let parsed: TranslationConfig = toml::from_str("target_language = \"zh-Hant\"").unwrap();
+        assert_eq!(parsed.target_language, TargetLanguage::ChineseTraditional);
+
+        // An unsupported language keeps the rest of the config.
+        let parsed: TranslationConfig =
+            toml::from_str("enabled = true\ntarget_language = \"tlh\"").unwrap();
+        assert!(parsed.enabled);
+        assert_eq!(parsed.target_language, TargetLanguage::default());
+    }
+
+    #[test]
+    fn unsupported_target_language_is_reported_and_kept_on_save() {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, raw) in [("translation.toml", "pl"), ("translation.yaml", "Japanese")] {
+            let path = dir.path().join(name);
+            let content = if name.ends_with(".toml") {
+                format!("enabled = true\ntarget_language = \"{raw}\"\n")
+            } else {
+                format!("enabled: true\ntarget_language: {raw}\n")
+            };
+            fs::write(&path, content).unwrap();
+
+            let loaded = TranslationConfig::load_from_path(&path);
+            assert_eq!(loaded.target_language, TargetLanguage::default(), "{name}");
+            assert_eq!(loaded.unsupported_target_language.as_deref(), Some(raw));
+            let diagnostics = loaded.validate();
+            assert_eq!(diagnostics.len(), 1, "{name}");
+            assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Warning);
+            assert!(diagnostics[0].message.contains(raw), "{name}");
+
+            // Saving other changes leaves the language as the user wrote it.
+            TranslationConfig {
+                model: Some("gpt-4o".to_string()),
+                ..loaded
+            }
+            .save_to_path(&path)
+            .unwrap();
+            let reloaded = TranslationConfig::load_from_path(&path);
+            assert_eq!(reloaded.unsupported_target_language.as_deref(), Some(raw));
+            assert_eq!(reloaded.model.as_deref(), Some("gpt-4o"), "{name}");
+            assert!(reloaded.enabled, "{name}");
+
+            // Picking a supported language replaces it.
+            TranslationConfig {
+                target_language: TargetLanguage::Korean,
+                unsupported_target_language: None,
+                ..reloaded
+            }
+            .save_to_path(&path)
+            .unwrap();
+            let reloaded = TranslationConfig::load_from_path(&path);
+            assert_eq!(reloaded.target_language, TargetLanguage::Korean, "{name}");
+            assert_eq!(reloaded.unsupported_target_language, None, "{name}");
+        }
+    }
+
+    #[test]
+    fn load_from_missing_path_returns_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let loaded = TranslationConfig::load_from_path(&dir.path().join("missing.yaml"));
+        assert!(!loaded.enabled);
+        assert_eq!(loaded.target_language, TargetLanguage::ChineseSimplified);
+    }
+
+    #[test]
+    fn invalid_post_filters_are_dropped_on_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("translation.toml");
+        fs::write(
+            &path,
+            r#"
+[[post_filters]]
+pattern = "(unclosed"
+
+[[post_filters]]
+pattern = '\s*（翻译）$'
+replacement = ""
+"#,
+        )
+        .unwrap();
+
+        let loaded = TranslationConfig::load_from_path(&path);
+        assert_eq!(
+            loaded.post_filters,
+            vec![PostFilter {
+                pattern: r"\s*（翻译）$".to_string(),
+                replacement: String::new(),
+            }]
+        );
+        assert!(loaded.validate().is_empty());
+
+        let unloaded = TranslationConfig {
+            post_filters: vec![PostFilter {
+                pattern: "(unclosed".to_string(),
+                replacement: String::new(),
+            }],
+            ..Default::default()
+        };
+        let diagnostics = unloaded.validate();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Warning);
+        assert!(diagnostics[0].message.contains("post_filters"));
+    }
+
+    #[test]
+    fn unknown_keys_are_ignored() {
+        let toml_str = r#"
+enabled = true
+provider = "openai"
+future_option = "value"
+
+[future_section]
+nested = 1
+"#;
+        let parsed: TranslationConfig = toml::from_str(toml_str).unwrap();
+        assert!(parsed.enabled);
+        assert_eq!(parsed.provider, "openai");
+
+        let yaml_str = "enabled: true\nmodel: gpt-4o\nfuture_option: [1, 2]\n";
+        let parsed = TranslationConfig::from_yaml(yaml_str).unwrap();
+        assert!(parsed.enabled);
+        assert_eq!(parsed.model.as_deref(), Some("gpt-4o"));
+    }
+
+    #[test]
+    fn unknown_output_encoding_falls_back_to_default() {
+        let toml_str = "enabled = true\noutput_encoding = \"nfkc_normalized\"\n";
+        let parsed: TranslationConfig = toml::from_str(toml_str).unwrap();
+        assert!(parsed.enabled);
+        assert_eq!(parsed.output_encoding, OutputEncoding::Raw);
+    }
+
+    #[test]
+    fn output_encoding_defaults_to_raw() {
+        let parsed: TranslationConfig = toml::from_str("enabled = true").unwrap();
+        assert_eq!(parsed.output_encoding, OutputEncoding::Raw);
+
+        let parsed: TranslationConfig =
+            toml::from_str("output_encoding = \"nfd_normalized\"").unwrap();
+        assert_eq!(parsed.output_encoding, OutputEncoding::NfdNormalized);
+    }
+
+    #[test]
+    fn empty_allowlist_allows_every_context() {
+        let config = TranslationConfig::default();
+        assert!(config.allows_context("any-thread", None));
+        assert!(config.allows_context("any-thread", Some(Path::new("/anywhere"))));
+    }
+
+    #[test]
+    fn allowlist_restricts_workspaces_and_threads() {
+        let config = TranslationConfig {
+            allowed_workspaces: vec![PathBuf::from("/repo/frontend")],
+            allowed_threads: vec!["pinned".to_string()],
+            ..Default::default()
+        };
+
+        assert!(config.allows_context("t1", Some(Path::new("/repo/frontend"))));
+        assert!(config.allows_context("t1", Some(Path::new("/repo/frontend/src"))));
+        assert!(!config.allows_context("t1", Some(Path::new("/repo/backend"))));
+        assert!(!config.allows_context("t1", Some(Path::new("/repo/frontend-old"))));
+        assert!(!config.allows_context("t1", None));
+        assert!(config.allows_context("pinned", Some(Path::new("/repo/backend"))));
+    }
+
+    #[test]
+    fn reasoning_effort_gates_translation() {
+        let config = TranslationConfig::default();
+        assert!(config.allows_effort(Some(ReasoningEffort::None)));
+        assert!(config.allows_effort(None));
+
+        let config: TranslationConfig =
+            toml::from_str("min_effort_to_translate = \"low\"").unwrap();
+        assert_eq!(config.min_effort_to_translate, Some(ReasoningEffort::Low));
+        for (effort, translate) in [
+            (ReasoningEffort::None, false),
+            (ReasoningEffort::Minimal, false),
+            (ReasoningEffort::Low, true),
+            (ReasoningEffort::Medium, true),
+            (ReasoningEffort::High, true),
+            (ReasoningEffort::XHigh, true),
+        ] {
+            assert_eq!(config.allows_effort(Some(effort)), translate, "{effort}");
+        }
+        // Before the session reports its effort, translate as usual.
+        assert!(config.allows_effort(None));
+    }
+
+    #[test]
+    fn empty_stop_sequences_are_dropped() {
+        let config = TranslationConfig {
+            stop_sequences: Some(vec![String::new(), "Note:".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.effective_stop_sequences(),
+            Some(vec!["Note:".to_string()])
+        );
+
+        let config = TranslationConfig {
+            stop_sequences: Some(vec![String::new()]),
+            ..Default::default()
+        };
+        assert_eq!(config.effective_stop_sequences(), None);
+        assert_eq!(
+            TranslationConfig::default().effective_stop_sequences(),
+            None
+        );
+    }
+
+    #[test]
+    fn retry_on_empty_response_defaults_to_true() {
+        let parsed: TranslationConfig = toml::from_str("enabled = true").unwrap();
+        assert!(parsed.retry_on_empty_response);
+        assert_eq!(parsed.effective_max_retries(), DEFAULT_MAX_RETRIES);
+
+        let parsed: TranslationConfig =
+            toml::from_str("retry_on_empty_response = false\nmax_retries = 0").unwrap();
+        assert!(!parsed.retry_on_empty_response);
+        assert_eq!(parsed.effective_max_retries(), 0);
+    }
+
+    #[test]
+    fn reasoning_and_final_answer_toggles_are_independent() {
+        let parsed: TranslationConfig = toml::from_str("enabled = true").unwrap();
+        assert!(parsed.translate_reasoning);
+        assert!(!parsed.translate_final_answer);
+
+        let parsed: TranslationConfig =
+            toml::from_str("translate_reasoning = false\ntranslate_final_answer = true").unwrap();
+        assert!(!parsed.translate_reasoning);
+        assert!(parsed.translate_final_answer);
+        assert!(!parsed.translate_system_messages);
+    }
+
+    #[test]
+    fn retry_status_codes_replace_the_defaults() {
+        let defaults = TranslationConfig::default();
+        assert!(defaults.retries_status(429));
+        assert!(defaults.retries_status(503));
+        assert!(!defaults.retries_status(524));
+        assert!(!defaults.retries_status(400));
+
+        let parsed: TranslationConfig = toml::from_str("retry_status_codes = [524, 400]").unwrap();
+        assert!(parsed.retries_status(524));
+        assert!(parsed.retries_status(400));
+        assert!(!parsed.retries_status(429));
+
+        let parsed: TranslationConfig =
+            toml::from_str("retry_status_codes = [524, 400]\nnon_retry_status_codes = [400]")
+                .unwrap();
+        assert!(parsed.retries_status(524));
+        assert!(!parsed.retries_status(400));
+
+        let parsed: TranslationConfig = toml::from_str("non_retry_status_codes = [503]").unwrap();
+        assert!(parsed.retries_status(429));
+        assert!(!parsed.retries_status(503));
+    }
+
+    #[test]
+    fn output_encoding_applies_to_japanese_text() {
+        // "ガ" as a single composed code point vs. "カ" + combining dakuten.
+        let composed = "ガギグ、こんにちは";
+        let decomposed = "\u{30AB}\u{3099}\u{30AD}\u{3099}\u{30AF}\u{3099}、こんにちは";
+
+        assert_eq!(OutputEncoding::Raw.apply(decomposed), decomposed);
+        assert_eq!(OutputEncoding::NfcNormalized.apply(decomposed), composed);
+        assert_eq!(OutputEncoding::NfdNormalized.apply(composed), decomposed);
+        assert_eq!(OutputEncoding::Ascii.apply(composed), "gagigu,konnichiha");
+    }
+
+    #[test]
+    fn translation_config_effective_values() {
+        let config = TranslationConfig {
+            provider: "openai".to_string(),
+            api_key: Some("sk-xxx".to_string()),
+            model: None,
+            base_url: None,
+            ..Default::default()
+        };
+
+        assert_eq!(config.effective_provider(), ProviderId::OpenAI);
+        assert_eq!(config.effective_api_key(), Some("sk-xxx"));
+
+        let provider_def = config.effective_provider().definition();
+        assert_eq!(config.effective_model(provider_def), "gpt-4o-mini");
+        assert_eq!(
+            config.effective_base_url(provider_def),
+            "https://api.openai.com/v1"
+        );
+    }
+
+    #[test]
+    fn provider_base_url_overrides_the_global_one() {
+        let config = TranslationConfig {
+            base_url: Some("https://gateway.example.com/v1".to_string()),
+            base_url_per_provider: HashMap::from([
+                (
+                    "anthropic".to_string(),
+                    "http://localhost:8080/v1".to_string(),
+                ),
+                ("openai".to_string(), String::new()),
+            ]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.effective_base_url(ProviderId::Anthropic.definition()),
+            "http://localhost:8080/v1"
+        );
+        // An empty entry doesn't count.
+        assert_eq!(
+            config.effective_base_url(ProviderId::OpenAI.definition()),
+            "https://gateway.example.com/v1"
+        );
+        assert_eq!(
+            config.effective_base_url(ProviderId::DeepSeek.definition()),
+            "https://gateway.example.com/v1"
+        );
+
+        let per_provider_only = TranslationConfig {
+            base_url: None,
+            ..config
+        };
+        assert_eq!(
+            per_provider_only.effective_base_url(ProviderId::DeepSeek.definition()),
+            "https://api.deepseek.com/v1"
+        );
+    }
+
+    #[test]
+    fn provider_default_temperature_applies_when_unset() {
+        for &provider in ProviderId::ALL {
+            let def = provider.definition();
+            let config = TranslationConfig {
+                provider: provider.as_str().to_string(),
+                ..Default::default()
+            };
+            assert_eq!(
+                config.effective_temperature(def),
+                def.default_translation_temperature
+                    .unwrap_or(DEFAULT_TRANSLATION_TEMPERATURE),
+                "{provider}"
+            );
+
+            let configured = TranslationConfig {
+                temperature: Some(0.0),
+                ..config
+            };
+            assert_eq!(configured.effective_temperature(def), 0.0, "{provider}");
+        }
+
+        let deepseek = ProviderId::DeepSeek.definition();
+        assert_eq!(
+            TranslationConfig::default().effective_temperature(deepseek),
+            1.3
+        );
+        let openai = ProviderId::OpenAI.definition();
+        assert_eq!(
+            TranslationConfig::default().effective_temperature(openai),
+            0.3
+        );
+    }
+
+    #[test]
+    fn cloudflare_account_id_is_spliced_into_base_url() {
+        let config = TranslationConfig {
+            provider: "cloudflare".to_string(),
+            cloudflare_account_id: Some("abc123".to_string()),
+            ..Default::default()
+        };
+
+        let provider_def = config.effective_provider().definition();
+        assert_eq!(
+            config.effective_base_url(provider_def),
+            "https://api.cloudflare.com/client/v4/accounts/abc123/ai/v1"
+        );
+        assert!(config.validate().is_empty());
+    }
+
+    #[test]
+    fn cloudflare_without_account_id_is_an_error() {
+        let config = TranslationConfig {
+            provider: "cloudflare".to_string(),
+            cloudflare_account_id: Some("  ".to_string()),
+            ..Default::default()
+        };
+
+        let diagnostics = config.validate();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Error);
+        assert!(diagnostics[0].message.contains("cloudflare_account_id"));
+
+        // Other providers don't need an account ID.
+        let config = TranslationConfig {
+            provider: "openai".to_string(),
+            ..Default::default()
+        };
+        assert!(config.validate().is_empty());
+    }
+
+    #[test]
+    fn disabled_tls_verification_is_a_warning() {
+        let config = TranslationConfig {
+            tls_verify: false,
+            ..Default::default()
+        };
+
+        let diagnostics = config.validate();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Warning);
+        assert!(diagnostics[0].message.contains("tls_verify"));
+
+        let parsed: TranslationConfig = toml::from_str("enabled = true").unwrap();
+        assert!(parsed.tls_verify);
+    }
+
+    #[test]
+    fn max_wait_outside_the_range_is_a_warning() {
+        let parsed: TranslationConfig = toml::from_str("max_wait_ms = 8000").unwrap();
+        assert_eq!(parsed.max_wait_ms, Some(8_000));
+        assert!(parsed.validate().is_empty());
+
+        for ms in [100, 120_000] {
+            let config = TranslationConfig {
+                max_wait_ms: Some(ms),
+                ..Default::default()
+            };
+            let diagnostics = config.validate();
+            assert_eq!(diagnostics.len(), 1);
+            assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Warning);
+            assert!(diagnostics[0].message.contains("max_wait_ms"));
+        }
+    }
+
+    #[test]
+    fn base_url_protocol_heuristic() {
+        // (provider, base_url, warning expected)
+        let cases = [
+            (ProviderId::Anthropic, "https://api.anthropic.com/v1", false),
+            (ProviderId::Anthropic, "http://localhost:8080/v1", false),
+            (
+                ProviderId::Anthropic,
+                "https://gateway.example.com/v1/chat/completions",
+                true,
+            ),
+            (ProviderId::Anthropic, "https://openrouter.ai/api/v1", true),
+            (ProviderId::Anthropic, "https://api.openai.com/v1/", true),
+            (
+                ProviderId::Anthropic,
+                "https://api.anthropic.com/v1/messages",
+                true,
+            ),
+            (
+                ProviderId::Gemini,
+                "https://generativelanguage.googleapis.com/v1beta",
+                false,
+            ),
+            (
+                ProviderId::Gemini,
+                "https://generativelanguage.googleapis.com/v1beta/openai",
+                true,
+            ),
+            (ProviderId::Gemini, "https://gateway.example.com/v1", true),
+            (ProviderId::Gemini, "https://api.anthropic.com/v1", true),
+            (ProviderId::OpenAI, "https://gateway.example.com/v1", false),
+            (ProviderId::OpenAI, "https://api.anthropic.com/v1", true),
+            (
+                ProviderId::OpenAI,
+                "https://generativelanguage.googleapis.com/v1beta",
+                true,
+            ),
+            (
+                ProviderId::OpenAI,
+                "https://gateway.example.com/v1/chat/completions",
+                true,
+            ),
+            (
+                ProviderId::DeepSeek,
+                "https://proxy.example.com/v1/messages",
+                true,
+            ),
+            (
+                ProviderId::Groq,
+                "https://generativelanguage.googleapis.com/v1beta/openai",
+                false,
+            ),
+        ];
+        for (provider, base_url, expected) in cases {
+            let warning = base_url_protocol_warning(base_url, provider.definition());
+            assert_eq!(
+                warning.is_some(),
+                expected,
+                "{provider} {base_url}: {warning:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn provider_default_base_urls_match_their_protocol() {
+        for &provider in ProviderId::ALL {
+            let def = provider.definition();
+            assert_eq!(
+                base_url_protocol_warning(def.default_base_url, def),
+                None,
+                "{provider}"
+            );
+        }
+    }
+
+    #[test]
+    fn mismatched_base_url_is_a_warning() {
+        let config = TranslationConfig {
+            provider: "anthropic".to_string(),
+            base_url_per_provider: HashMap::from([(
+                "anthropic".to_string(),
+                "https://openrouter.ai/api/v1".to_string(),
+            )]),
+            ..Default::default()
+        };
+
+        let diagnostics = config.validate();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Warning);
+        assert_eq!(
+            diagnostics[0].message,
+            "base_url https://openrouter.ai/api/v1 looks like an OpenAI-compatible endpoint, \
+             but Anthropic uses an Anthropic Messages endpoint"
+        );
+    }
+
+    #[test]
+    fn translation_config_is_valid() {
+        // Config with API key for provider that requires it
+        let valid_config = TranslationConfig {
+            provider: "openai".to_string(),
+            api_key: Some("sk-xxx".to_string()),
+            ..Default::default()
+        };
+        assert!(valid_config.is_valid());
+
+        // Config without API key for provider that requires it
+        let invalid_config = TranslationConfig {
+            provider: "openai".to_string(),
+            api_key: None,
+            ..Default::default()
+        };
+        assert!(!invalid_config.is_valid());
+
+        // Ollama doesn't require API key
+        let ollama_config = TranslationConfig {
+            provider: "ollama".to_string(),
+            api_key: None,
+            ..Default::default()
+        };
+        assert!(ollama_config.is_valid());
+    }
+}
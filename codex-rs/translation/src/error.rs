@@ -0,0 +1,273 @@
+//! Translation error types.
+
+use std::fmt;
+use std::time::Duration;
+
+/// Message carried by [`TranslationError::Parse`] when a provider returns blank content.
+const EMPTY_RESPONSE_MESSAGE: &str = "Empty response";
+
+/// Explanations of common provider errors, keyed on lowercase substrings of the error
+/// message. Providers word these differently, so each entry lists several phrasings.
+const EXPLANATIONS: &[(&[&str], &str)] = &[
+    (
+        &[
+            "insufficient_quota",
+            "insufficient quota",
+            "exceeded your current quota",
+            "insufficient balance",
+            "credit balance is too low",
+            "余额不足",
+        ],
+        "The provider account is out of credit. Add funds or raise the quota on the \
+         provider's billing page.",
+    ),
+    (
+        &[
+            "model_not_found",
+            "model not found",
+            "no such model",
+            "unknown model",
+            "invalid model",
+            "model does not exist",
+        ],
+        "The provider does not offer this model to your account. Pick another model in \
+         /translate.",
+    ),
+    (
+        &[
+            "unsupported_country",
+            "country, region, or territory",
+            "not available in your region",
+            "unsupported region",
+            "user location is not supported",
+        ],
+        "The provider does not serve your region. Use a proxy or another provider.",
+    ),
+    (
+        &[
+            "context_length_exceeded",
+            "context length",
+            "maximum context",
+        ],
+        "The reasoning block is too long for this model. Pick a model with a larger context \
+         window.",
+    ),
+];
+
+/// Shown for provider errors that match no entry in [`EXPLANATIONS`].
+const GENERIC_EXPLANATION: &str =
+    "The provider rejected the request. Check its status page and your account settings.";
+
+/// Translation error.
+#[derive(Debug)]
+pub enum TranslationError {
+    /// API key not found or not configured.
+    ApiKeyNotFound(String),
+
+    /// Network error during API call.
+    Network(reqwest::Error),
+
+    /// API returned an error response.
+    Api {
+        status: u16,
+        message: String,
+        /// How long the provider asked us to wait before retrying, from `Retry-After` or
+        /// the rate limit reset.
+        retry_after: Option<Duration>,
+    },
+
+    /// Failed to parse API response.
+    Parse(String),
+
+    /// Translation request timed out.
+    Timeout,
+
+    /// Invalid configuration.
+    InvalidConfig(String),
+
+    /// The output did not look like a translation of the source, even with the strict prompt.
+    Rejected(String),
+}
+
+impl TranslationError {
+    /// Error for a successful response that carried no usable content.
+    pub fn empty_response() -> Self {
+        Self::Parse(EMPTY_RESPONSE_MESSAGE.to_string())
+    }
+
+    /// Returns true if the provider rejected the API key (HTTP 401).
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(self, Self::Api { status: 401, .. })
+    }
+
+    /// Returns true if the provider could not be reached (connection failure or timeout).
+    pub fn is_network(&self) -> bool {
+        matches!(self, Self::Network(_) | Self::Timeout)
+    }
+
+    /// Returns true if the provider answered successfully but with blank content.
+    pub fn is_empty_response(&self) -> bool {
+        matches!(self, Self::Parse(msg) if msg == EMPTY_RESPONSE_MESSAGE)
+    }
+
+    /// Short guidance for an error response from the provider; `None` for other errors,
+    /// whose message already says what went wrong.
+    pub fn explanation(&self) -> Option<&'static str> {
+        let Self::Api { message, .. } = self else {
+            return None;
+        };
+        Some(explain_provider_message(message))
+    }
+}
+
+impl fmt::Display for TranslationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ApiKeyNotFound(provider) => {
+                write!(f, "API key not configured for {provider}")
+            }
+            Self::Network(e) => write!(f, "Network error: {e}"),
+            Self::Api {
+                status, message, ..
+            } => {
+                write!(f, "API error ({status}): {message}")
+            }
+            Self::Parse(msg) => write!(f, "Parse error: {msg}"),
+            Self::Timeout => write!(f, "Translation timeout"),
+            Self::InvalidConfig(msg) => write!(f, "Invalid configuration: {msg}"),
+            Self::Rejected(reason) => {
+                write!(
+                    f,
+                    "Translation rejected (possible prompt leakage): {reason}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for TranslationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Network(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for TranslationError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            Self::Timeout
+        } else {
+            Self::Network(e)
+        }
+    }
+}
+
+/// Explanation for a provider error message, falling back to [`GENERIC_EXPLANATION`].
+fn explain_provider_message(message: &str) -> &'static str {
+    let message = message.to_lowercase();
+    EXPLANATIONS
+        .iter()
+        .find(|(patterns, _)| patterns.iter().any(|pattern| message.contains(pattern)))
+        .map_or(GENERIC_EXPLANATION, |(_, explanation)| explanation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_display() {
+        let err = TranslationError::ApiKeyNotFound("DeepSeek".to_string());
+        assert!(err.to_string().contains("DeepSeek"));
+
+        let err = TranslationError::Timeout;
+        assert!(err.to_string().contains("timeout"));
+
+        let err = TranslationError::Api {
+            status: 401,
+            message: "Unauthorized".to_string(),
+            retry_after: None,
+        };
+        assert!(err.to_string().contains("401"));
+        assert!(err.to_string().contains("Unauthorized"));
+    }
+
+    #[test]
+    fn empty_response_detection() {
+        assert!(TranslationError::empty_response().is_empty_response());
+        assert!(!TranslationError::Parse("missing field".to_string()).is_empty_response());
+        assert!(!TranslationError::Timeout.is_empty_response());
+    }
+
+    #[test]
+    fn unauthorized_detection() {
+        let unauthorized = TranslationError::Api {
+            status: 401,
+            message: "Unauthorized".to_string(),
+            retry_after: None,
+        };
+        let forbidden = TranslationError::Api {
+            status: 403,
+            message: "Forbidden".to_string(),
+            retry_after: None,
+        };
+        assert!(unauthorized.is_unauthorized());
+        assert!(!forbidden.is_unauthorized());
+        assert!(!TranslationError::Timeout.is_unauthorized());
+    }
+
+    fn api_error(status: u16, message: &str) -> TranslationError {
+        TranslationError::Api {
+            status,
+            message: message.to_string(),
+            retry_after: None,
+        }
+    }
+
+    #[test]
+    fn provider_errors_are_explained() {
+        let cases = [
+            (
+                429,
+                r#"{"error":{"message":"You exceeded your current quota, please check your plan and billing details.","type":"insufficient_quota"}}"#,
+                "out of credit",
+            ),
+            (
+                402,
+                r#"{"error":{"message":"Insufficient Balance","type":"unknown_error"}}"#,
+                "out of credit",
+            ),
+            (
+                404,
+                r#"{"error":{"message":"The model `gpt-5-turbo` does not exist or you do not have access to it.","code":"model_not_found"}}"#,
+                "does not offer this model",
+            ),
+            (
+                403,
+                r#"{"error":{"code":"unsupported_country_region_territory","message":"Country, region, or territory not supported"}}"#,
+                "does not serve your region",
+            ),
+            (
+                400,
+                r#"{"error":{"code":400,"message":"User location is not supported for the API use.","status":"FAILED_PRECONDITION"}}"#,
+                "does not serve your region",
+            ),
+        ];
+        for (status, body, expected) in cases {
+            let explanation = api_error(status, body).explanation().unwrap();
+            assert!(explanation.contains(expected), "{body}: {explanation}");
+        }
+    }
+
+    #[test]
+    fn unknown_provider_errors_get_a_generic_explanation() {
+        assert_eq!(
+            api_error(500, "upstream connect error").explanation(),
+            Some(GENERIC_EXPLANATION)
+        );
+        assert_eq!(TranslationError::Timeout.explanation(), None);
+        assert_eq!(TranslationError::empty_response().explanation(), None);
+    }
+}
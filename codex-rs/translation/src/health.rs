@@ -0,0 +1,220 @@
+//! Connection health: request counts and recent latencies.
+//!
+//! A [`TranslationClient`](crate::TranslationClient) records every translation request in
+//! its [`ConnectionHealth`]. Clients are cheap and often built per request, so callers that
+//! want session-wide numbers share one `ConnectionHealth` between them with
+//! [`TranslationClient::with_connection_health`](crate::TranslationClient::with_connection_health).
+
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Instant;
+
+/// Latencies kept for the percentiles; older ones are overwritten.
+const LATENCY_SAMPLES: usize = 100;
+
+/// Point-in-time view of a [`ConnectionHealth`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConnectionMetrics {
+    /// Requests currently waiting for a response.
+    pub active_connections: usize,
+    /// Open connections not carrying a request. reqwest does not expose its pool, and each
+    /// client closes its connections when dropped, so this is always 0 for now.
+    pub idle_connections: usize,
+    /// Requests that completed, successfully or not. Cancelled requests are not counted.
+    pub total_requests: u64,
+    /// Completed requests that returned an error.
+    pub failed_requests: u64,
+    /// Median latency of the last 100 requests; 0 before the first one.
+    pub p50_latency_ms: u64,
+    /// 99th percentile latency of the last 100 requests; 0 before the first one.
+    pub p99_latency_ms: u64,
+}
+
+/// Latencies of the most recent requests, in a fixed-size ring buffer.
+#[derive(Debug, Clone)]
+pub struct LatencyHistogram {
+    samples: [u64; LATENCY_SAMPLES],
+    /// Number of valid samples, at most `LATENCY_SAMPLES`.
+    len: usize,
+    /// Slot the next sample goes into.
+    next: usize,
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self {
+            samples: [0; LATENCY_SAMPLES],
+            len: 0,
+            next: 0,
+        }
+    }
+}
+
+impl LatencyHistogram {
+    /// Record a latency, replacing the oldest once the buffer is full.
+    pub fn push(&mut self, latency_ms: u64) {
+        self.samples[self.next] = latency_ms;
+        self.next = (self.next + 1) % LATENCY_SAMPLES;
+        self.len = (self.len + 1).min(LATENCY_SAMPLES);
+    }
+
+    pub fn p50_latency_ms(&self) -> u64 {
+        self.percentile(50)
+    }
+
+    pub fn p99_latency_ms(&self) -> u64 {
+        self.percentile(99)
+    }
+
+    /// Nearest-rank percentile over a sorted copy of the samples.
+    fn percentile(&self, percent: usize) -> u64 {
+        if self.len == 0 {
+            return 0;
+        }
+        // Until the buffer wraps the samples fill it from the start; after that every slot
+        // is valid. Either way they are the first `len` slots.
+        let mut sorted = self.samples[..self.len].to_vec();
+        sorted.sort_unstable();
+        let rank = (percent * self.len).div_ceil(100).max(1);
+        sorted[rank - 1]
+    }
+}
+
+/// Request counters and latencies, shared between clients and read with
+/// [`ConnectionHealth::snapshot`].
+#[derive(Debug, Default)]
+pub struct ConnectionHealth {
+    active: AtomicUsize,
+    total_requests: AtomicU64,
+    failed_requests: AtomicU64,
+    latencies: Mutex<LatencyHistogram>,
+}
+
+impl ConnectionHealth {
+    /// Mark a request as started. It counts as active until the returned guard is finished
+    /// or dropped.
+    pub(crate) fn start(&self) -> InFlightRequest<'_> {
+        self.active.fetch_add(1, Ordering::Relaxed);
+        InFlightRequest {
+            health: self,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn snapshot(&self) -> ConnectionMetrics {
+        let latencies = self
+            .latencies
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        ConnectionMetrics {
+            active_connections: self.active.load(Ordering::Relaxed),
+            idle_connections: 0,
+            total_requests: self.total_requests.load(Ordering::Relaxed),
+            failed_requests: self.failed_requests.load(Ordering::Relaxed),
+            p50_latency_ms: latencies.p50_latency_ms(),
+            p99_latency_ms: latencies.p99_latency_ms(),
+        }
+    }
+
+    fn record(&self, latency_ms: u64, succeeded: bool) {
+        self.total_requests.fetch_add(1, Ordering::Relaxed);
+        if !succeeded {
+            self.failed_requests.fetch_add(1, Ordering::Relaxed);
+        }
+        self.latencies
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(latency_ms);
+    }
+}
+
+/// A request counted as active; see [`ConnectionHealth::start`].
+pub(crate) struct InFlightRequest<'a> {
+    health: &'a ConnectionHealth,
+    started: Instant,
+}
+
+impl InFlightRequest<'_> {
+    /// The response arrived: count the request and its latency.
+    pub(crate) fn finish(self, succeeded: bool) {
+        let latency_ms = u64::try_from(self.started.elapsed().as_millis()).unwrap_or(u64::MAX);
+        self.health.record(latency_ms, succeeded);
+    }
+}
+
+impl Drop for InFlightRequest<'_> {
+    fn drop(&mut self) {
+        self.health.active.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn empty_histogram_reports_zero() {
+        let histogram = LatencyHistogram::default();
+        assert_eq!(histogram.p50_latency_ms(), 0);
+        assert_eq!(histogram.p99_latency_ms(), 0);
+    }
+
+    #[test]
+    fn percentiles_use_the_nearest_rank() {
+        let mut histogram = LatencyHistogram::default();
+        for latency in [40, 10, 30, 20] {
+            histogram.push(latency);
+        }
+        assert_eq!(histogram.p50_latency_ms(), 20);
+        assert_eq!(histogram.p99_latency_ms(), 40);
+
+        let mut histogram = LatencyHistogram::default();
+        for latency in 1..=100 {
+            histogram.push(latency);
+        }
+        assert_eq!(histogram.p50_latency_ms(), 50);
+        assert_eq!(histogram.p99_latency_ms(), 99);
+    }
+
+    #[test]
+    fn only_the_last_hundred_latencies_count() {
+        let mut histogram = LatencyHistogram::default();
+        for _ in 0..LATENCY_SAMPLES {
+            histogram.push(5_000);
+        }
+        for _ in 0..LATENCY_SAMPLES {
+            histogram.push(100);
+        }
+        assert_eq!(histogram.p50_latency_ms(), 100);
+        assert_eq!(histogram.p99_latency_ms(), 100);
+
+        histogram.push(900);
+        assert_eq!(histogram.p99_latency_ms(), 100);
+        histogram.push(900);
+        assert_eq!(histogram.p99_latency_ms(), 900);
+    }
+
+    #[test]
+    fn requests_are_active_until_finished_or_dropped() {
+        let health = ConnectionHealth::default();
+
+        let first = health.start();
+        let second = health.start();
+        assert_eq!(health.snapshot().active_connections, 2);
+
+        first.finish(true);
+        // A cancelled request leaves the active count without being counted.
+        drop(second);
+        let third = health.start();
+        third.finish(false);
+
+        let metrics = health.snapshot();
+        assert_eq!(metrics.active_connections, 0);
+        assert_eq!(metrics.total_requests, 2);
+        assert_eq!(metrics.failed_requests, 1);
+    }
+}
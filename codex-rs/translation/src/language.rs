@@ -0,0 +1,221 @@
+//! Supported target languages, stored in configs as BCP-47 codes (e.g. `"zh-CN"`).
+
+use std::fmt;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Supported target languages.
+///
+/// Serialized as its BCP-47 code. Deserialization goes through [`TargetLanguage::from_bcp47`],
+/// so tags that only differ in case or region (`"ja-JP"`, `"zh-Hant"`) still load.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum TargetLanguage {
+    #[default]
+    ChineseSimplified,
+    ChineseTraditional,
+    Japanese,
+    Korean,
+    English,
+    Spanish,
+    French,
+    German,
+    Russian,
+    Portuguese,
+    Italian,
+    Arabic,
+    Hindi,
+    Vietnamese,
+    Thai,
+}
+
+impl TargetLanguage {
+    pub const ALL: &'static [Self] = &[
+        Self::ChineseSimplified,
+        Self::ChineseTraditional,
+        Self::Japanese,
+        Self::Korean,
+        Self::English,
+        Self::Spanish,
+        Self::French,
+        Self::German,
+        Self::Russian,
+        Self::Portuguese,
+        Self::Italian,
+        Self::Arabic,
+        Self::Hindi,
+        Self::Vietnamese,
+        Self::Thai,
+    ];
+
+    /// BCP-47 code, as stored in the config and sent to providers.
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::ChineseSimplified => "zh-CN",
+            Self::ChineseTraditional => "zh-TW",
+            Self::Japanese => "ja",
+            Self::Korean => "ko",
+            Self::English => "en",
+            Self::Spanish => "es",
+            Self::French => "fr",
+            Self::German => "de",
+            Self::Russian => "ru",
+            Self::Portuguese => "pt",
+            Self::Italian => "it",
+            Self::Arabic => "ar",
+            Self::Hindi => "hi",
+            Self::Vietnamese => "vi",
+            Self::Thai => "th",
+        }
+    }
+
+    /// English name, for display.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::ChineseSimplified => "Chinese (Simplified)",
+            Self::ChineseTraditional => "Chinese (Traditional)",
+            Self::Japanese => "Japanese",
+            Self::Korean => "Korean",
+            Self::English => "English",
+            Self::Spanish => "Spanish",
+            Self::French => "French",
+            Self::German => "German",
+            Self::Russian => "Russian",
+            Self::Portuguese => "Portuguese",
+            Self::Italian => "Italian",
+            Self::Arabic => "Arabic",
+            Self::Hindi => "Hindi",
+            Self::Vietnamese => "Vietnamese",
+            Self::Thai => "Thai",
+        }
+    }
+
+    /// The language for a BCP-47 tag, ignoring case. A tag that is not one of the codes
+    /// above falls back to its primary language subtag (`"pt-BR"` is Portuguese); for
+    /// Chinese, a `Hant` script or a `TW`/`HK`/`MO` region selects Traditional.
+    pub fn from_bcp47(tag: &str) -> Option<Self> {
+        let tag = tag.trim().replace('_', "-");
+        if let Some(language) = Self::ALL
+            .iter()
+            .find(|language| language.code().eq_ignore_ascii_case(&tag))
+        {
+            return Some(*language);
+        }
+
+        let mut subtags = tag.split('-').map(str::to_ascii_lowercase);
+        let primary = subtags.next()?;
+        if primary == "zh" {
+            let traditional =
+                subtags.any(|subtag| matches!(subtag.as_str(), "hant" | "tw" | "hk" | "mo"));
+            return Some(if traditional {
+                Self::ChineseTraditional
+            } else {
+                Self::ChineseSimplified
+            });
+        }
+        Self::ALL
+            .iter()
+            .find(|language| language.code() == primary)
+            .copied()
+    }
+}
+
+impl fmt::Display for TargetLanguage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl TryFrom<String> for TargetLanguage {
+    type Error = String;
+
+    fn try_from(tag: String) -> Result<Self, Self::Error> {
+        Self::from_bcp47(&tag).ok_or_else(|| {
+            let supported: Vec<&str> = Self::ALL.iter().map(|language| language.code()).collect();
+            format!(
+                "unsupported target language {tag:?}; expected one of {}",
+                supported.join(", ")
+            )
+        })
+    }
+}
+
+impl From<TargetLanguage> for String {
+    fn from(language: TargetLanguage) -> Self {
+        language.code().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Wrapper {
+        target_language: TargetLanguage,
+    }
+
+    #[test]
+    fn every_language_round_trips_as_its_code() {
+        for &language in TargetLanguage::ALL {
+            let wrapper = Wrapper {
+                target_language: language,
+            };
+
+            let toml = toml::to_string(&wrapper).unwrap();
+            assert_eq!(
+                toml.trim(),
+                format!("target_language = \"{}\"", language.code())
+            );
+            assert_eq!(toml::from_str::<Wrapper>(&toml).unwrap(), wrapper);
+
+            let json = serde_json::to_string(&language).unwrap();
+            assert_eq!(json, format!("\"{}\"", language.code()));
+            assert_eq!(
+                serde_json::from_str::<TargetLanguage>(&json).unwrap(),
+                language
+            );
+        }
+    }
+
+    #[test]
+    fn tags_match_ignoring_case_and_region() {
+        assert_eq!(
+            TargetLanguage::from_bcp47("ZH-cn"),
+            Some(TargetLanguage::ChineseSimplified)
+        );
+        assert_eq!(
+            TargetLanguage::from_bcp47("ja-JP"),
+            Some(TargetLanguage::Japanese)
+        );
+        assert_eq!(
+            TargetLanguage::from_bcp47("pt_BR"),
+            Some(TargetLanguage::Portuguese)
+        );
+        assert_eq!(
+            TargetLanguage::from_bcp47("zh"),
+            Some(TargetLanguage::ChineseSimplified)
+        );
+        assert_eq!(
+            TargetLanguage::from_bcp47("zh-Hant"),
+            Some(TargetLanguage::ChineseTraditional)
+        );
+        assert_eq!(
+            TargetLanguage::from_bcp47("zh-HK"),
+            Some(TargetLanguage::ChineseTraditional)
+        );
+    }
+
+    #[test]
+    fn unknown_tags_are_rejected() {
+        assert_eq!(TargetLanguage::from_bcp47("tlh"), None);
+        assert_eq!(TargetLanguage::from_bcp47(""), None);
+        let err = toml::from_str::<Wrapper>("target_language = \"Klingon\"").unwrap_err();
+        assert!(
+            err.to_string().contains("unsupported target language"),
+            "{err}"
+        );
+    }
+}
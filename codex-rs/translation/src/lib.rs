@@ -0,0 +1,41 @@
+//! Translation of text through LLM provider APIs.
+//!
+//! This crate has no terminal UI dependencies, so any binary in the workspace can translate:
+//!
+//! ```no_run
+//! # async fn run(config_path: &std::path::Path) -> Result<(), codex_translation::TranslationError> {
+//! use codex_translation::TranslationClient;
+//! use codex_translation::TranslationConfig;
+//!
+//! let config = TranslationConfig::load_from_path(config_path);
+//! let client = TranslationClient::from_config(&config)?;
+//! let output = client.translate("Hello", config.target_language.code()).await?;
+//! println!("{}", output.text);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! - `TranslationConfig` - Configuration for translation settings
+//! - `TranslationClient` - HTTP client for translation APIs
+//! - `ProviderId` - Supported LLM provider identifiers
+//! - `TargetLanguage` - Supported target languages and their BCP-47 codes
+//! - `TranslationError` - Failures, with explanations for common provider errors
+//! - `ConnectionHealth` - Request counts and latency percentiles, shareable across clients
+//!
+//! The TUI layers its reasoning-specific behavior (ordering, retries, history) on top.
+
+pub mod client;
+pub mod config;
+pub mod error;
+pub mod health;
+pub mod language;
+pub mod post_filter;
+pub mod provider;
+pub mod rate_limit;
+
+pub use client::TranslationClient;
+pub use client::TranslationOutput;
+pub use config::TranslationConfig;
+pub use error::TranslationError;
+pub use language::TargetLanguage;
+pub use provider::ProviderId;
@@ -0,0 +1,87 @@
+//! User-defined regex replacements applied to translated text before it is displayed.
+//!
+//! Providers leave their own artifacts in translations, such as a trailing "（翻译）" tag.
+//! `post_filters` in the translation config lists `(pattern, replacement)` pairs that strip
+//! them; [`PostFilters`] compiles the list once and applies it in order.
+
+use regex_lite::Regex;
+
+use super::config::PostFilter;
+
+/// Compiled `post_filters`, in configuration order.
+#[derive(Debug, Default)]
+pub struct PostFilters {
+    filters: Vec<(Regex, String)>,
+}
+
+impl PostFilters {
+    /// Compile `filters`, skipping patterns that are not valid regexes. Configs loaded from
+    /// disk have those removed already, with a warning.
+    pub fn compile(filters: &[PostFilter]) -> Self {
+        Self {
+            filters: filters
+                .iter()
+                .filter_map(|filter| {
+                    let regex = Regex::new(&filter.pattern).ok()?;
+                    Some((regex, filter.replacement.clone()))
+                })
+                .collect(),
+        }
+    }
+
+    /// `text` with every filter applied, each to the output of the previous one.
+    /// Replacements may refer to capture groups as `$1` or `${name}`.
+    pub fn apply(&self, text: String) -> String {
+        self.filters
+            .iter()
+            .fold(text, |text, (regex, replacement)| {
+                regex.replace_all(&text, replacement.as_str()).into_owned()
+            })
+    }
+}
+
+/// Why `pattern` can't be used as a post-filter, if it can't.
+pub(crate) fn invalid_pattern_reason(pattern: &str) -> Option<String> {
+    Regex::new(pattern).err().map(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn filter(pattern: &str, replacement: &str) -> PostFilter {
+        PostFilter {
+            pattern: pattern.to_string(),
+            replacement: replacement.to_string(),
+        }
+    }
+
+    #[test]
+    fn filters_apply_in_order() {
+        let filters = PostFilters::compile(&[
+            filter(r"\s*（翻译）$", ""),
+            filter(r"译文[:：]\s*", ""),
+            filter(r"(\d+) 个", "$1 项"),
+        ]);
+
+        assert_eq!(
+            filters.apply("译文：修复了 3 个测试（翻译）".to_string()),
+            "修复了 3 项测试"
+        );
+    }
+
+    #[test]
+    fn later_filters_see_earlier_replacements() {
+        let filters = PostFilters::compile(&[filter("foo", "bar"), filter("bar", "baz")]);
+        assert_eq!(filters.apply("foo bar".to_string()), "baz baz");
+    }
+
+    #[test]
+    fn invalid_patterns_are_skipped() {
+        let filters = PostFilters::compile(&[filter("(unclosed", ""), filter("x", "y")]);
+        assert_eq!(filters.apply("(unclosed x".to_string()), "(unclosed y");
+        assert!(invalid_pattern_reason("(unclosed").is_some());
+        assert_eq!(invalid_pattern_reason(r"\d+"), None);
+    }
+}
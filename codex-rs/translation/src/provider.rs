@@ -0,0 +1,579 @@
+//! Translation provider definitions.
+//!
+//! This module defines supported LLM providers for translation,
+//! including their default configurations and protocol types.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Protocol type for API communication.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// OpenAI-compatible API (used by most providers).
+    OpenAI,
+    /// Anthropic's native API.
+    Anthropic,
+    /// Google's Gemini API.
+    Gemini,
+}
+
+/// How OpenAI-compatible requests carry the translation instruction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SystemMessageRole {
+    /// A `system` message followed by the `user` message.
+    #[default]
+    System,
+    /// One `user` message holding the instruction and then the text.
+    User,
+    /// One `user` message with the instruction as a preamble, set off by a separator line.
+    PrependToFirstUser,
+}
+
+/// Sampling temperature for providers without a recommendation of their own.
+pub const DEFAULT_TRANSLATION_TEMPERATURE: f32 = 0.3;
+
+/// Placeholder in a base URL that is replaced with the configured account ID.
+pub const ACCOUNT_ID_PLACEHOLDER: &str = "{account_id}";
+
+/// Provider identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[derive(Default)]
+pub enum ProviderId {
+    OpenAI,
+    Anthropic,
+    #[default]
+    DeepSeek,
+    Moonshot,
+    ZhipuAI,
+    Qwen,
+    Groq,
+    Gemini,
+    Mistral,
+    Cohere,
+    Ollama,
+    OpenRouter,
+    TogetherAI,
+    Perplexity,
+    SiliconFlow,
+    Cloudflare,
+    #[serde(rename = "xai")]
+    Xai,
+}
+
+impl ProviderId {
+    /// Get all provider IDs.
+    pub const ALL: &'static [Self] = &[
+        Self::OpenAI,
+        Self::Anthropic,
+        Self::DeepSeek,
+        Self::Moonshot,
+        Self::ZhipuAI,
+        Self::Qwen,
+        Self::Groq,
+        Self::Gemini,
+        Self::Mistral,
+        Self::Cohere,
+        Self::Ollama,
+        Self::OpenRouter,
+        Self::TogetherAI,
+        Self::Perplexity,
+        Self::SiliconFlow,
+        Self::Cloudflare,
+        Self::Xai,
+    ];
+
+    /// Get the provider definition.
+    pub fn definition(self) -> &'static ProviderDef {
+        match self {
+            Self::OpenAI => &OPENAI,
+            Self::Anthropic => &ANTHROPIC,
+            Self::DeepSeek => &DEEPSEEK,
+            Self::Moonshot => &MOONSHOT,
+            Self::ZhipuAI => &ZHIPUAI,
+            Self::Qwen => &QWEN,
+            Self::Groq => &GROQ,
+            Self::Gemini => &GEMINI,
+            Self::Mistral => &MISTRAL,
+            Self::Cohere => &COHERE,
+            Self::Ollama => &OLLAMA,
+            Self::OpenRouter => &OPENROUTER,
+            Self::TogetherAI => &TOGETHERAI,
+            Self::Perplexity => &PERPLEXITY,
+            Self::SiliconFlow => &SILICONFLOW,
+            Self::Cloudflare => &CLOUDFLARE,
+            Self::Xai => &XAI,
+        }
+    }
+
+    /// Convert to lowercase string identifier.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::OpenAI => "openai",
+            Self::Anthropic => "anthropic",
+            Self::DeepSeek => "deepseek",
+            Self::Moonshot => "moonshot",
+            Self::ZhipuAI => "zhipuai",
+            Self::Qwen => "qwen",
+            Self::Groq => "groq",
+            Self::Gemini => "gemini",
+            Self::Mistral => "mistral",
+            Self::Cohere => "cohere",
+            Self::Ollama => "ollama",
+            Self::OpenRouter => "openrouter",
+            Self::TogetherAI => "togetherai",
+            Self::Perplexity => "perplexity",
+            Self::SiliconFlow => "siliconflow",
+            Self::Cloudflare => "cloudflare",
+            Self::Xai => "xai",
+        }
+    }
+}
+
+impl std::fmt::Display for ProviderId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.definition().name)
+    }
+}
+
+impl std::str::FromStr for ProviderId {
+    type Err = String;
+
+    /// Parse a provider ID, case-insensitively and accepting common aliases (e.g. `grok`).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "openai" => Ok(Self::OpenAI),
+            "anthropic" => Ok(Self::Anthropic),
+            "deepseek" => Ok(Self::DeepSeek),
+            "moonshot" => Ok(Self::Moonshot),
+            "zhipuai" | "zhipu" => Ok(Self::ZhipuAI),
+            "qwen" | "dashscope" => Ok(Self::Qwen),
+            "groq" => Ok(Self::Groq),
+            "gemini" | "google" => Ok(Self::Gemini),
+            "mistral" => Ok(Self::Mistral),
+            "cohere" => Ok(Self::Cohere),
+            "ollama" => Ok(Self::Ollama),
+            "openrouter" => Ok(Self::OpenRouter),
+            "togetherai" | "together" => Ok(Self::TogetherAI),
+            "perplexity" => Ok(Self::Perplexity),
+            "siliconflow" => Ok(Self::SiliconFlow),
+            "cloudflare" | "workers-ai" => Ok(Self::Cloudflare),
+            "xai" | "grok" | "x.ai" => Ok(Self::Xai),
+            _ => Err(format!("unknown translation provider {s:?}")),
+        }
+    }
+}
+
+/// Provider definition with default configuration.
+#[derive(Debug)]
+pub struct ProviderDef {
+    /// Provider identifier.
+    pub id: ProviderId,
+    /// Display name.
+    pub name: &'static str,
+    /// Default base URL.
+    pub default_base_url: &'static str,
+    /// Default model name.
+    pub default_model: &'static str,
+    /// API protocol type.
+    pub protocol: Protocol,
+    /// Whether API key is required.
+    pub requires_api_key: bool,
+    /// Description of the provider.
+    pub description: &'static str,
+    /// Pricing page; empty for local providers.
+    pub pricing_url: &'static str,
+    /// Where OpenAI-compatible requests put the instruction.
+    pub system_message_role: SystemMessageRole,
+    /// Temperature the provider recommends for translation, if it differs from
+    /// [`DEFAULT_TRANSLATION_TEMPERATURE`].
+    pub default_translation_temperature: Option<f32>,
+    /// Models served from a different endpoint, as `(model_prefix, endpoint_template)` pairs
+    /// checked in order. `{model}` in a template is replaced with the model name. The first
+    /// segment of a template is an API version that takes the place of the version segment
+    /// (the last segment of `default_base_url`) in the base URL, so gateway prefixes are kept;
+    /// a base URL without that segment uses the standard endpoint.
+    pub endpoint_overrides: &'static [(&'static str, &'static str)],
+}
+
+impl ProviderDef {
+    /// Endpoint template for `model` from [`ProviderDef::endpoint_overrides`], if any prefix
+    /// matches.
+    pub fn endpoint_override(&self, model: &str) -> Option<&'static str> {
+        self.endpoint_overrides
+            .iter()
+            .find(|(prefix, _)| model.starts_with(prefix))
+            .map(|&(_, template)| template)
+    }
+}
+
+// Provider definitions
+static OPENAI: ProviderDef = ProviderDef {
+    id: ProviderId::OpenAI,
+    name: "OpenAI",
+    default_base_url: "https://api.openai.com/v1",
+    default_model: "gpt-4o-mini",
+    protocol: Protocol::OpenAI,
+    requires_api_key: true,
+    description: "OpenAI GPT models",
+    pricing_url: "https://openai.com/pricing",
+    system_message_role: SystemMessageRole::System,
+    default_translation_temperature: None,
+    endpoint_overrides: &[],
+};
+
+static ANTHROPIC: ProviderDef = ProviderDef {
+    id: ProviderId::Anthropic,
+    name: "Anthropic",
+    default_base_url: "https://api.anthropic.com/v1",
+    default_model: "claude-3-haiku-20240307",
+    protocol: Protocol::Anthropic,
+    requires_api_key: true,
+    description: "Anthropic Claude models",
+    pricing_url: "https://www.anthropic.com/pricing",
+    system_message_role: SystemMessageRole::System,
+    default_translation_temperature: None,
+    endpoint_overrides: &[],
+};
+
+static DEEPSEEK: ProviderDef = ProviderDef {
+    id: ProviderId::DeepSeek,
+    name: "DeepSeek",
+    default_base_url: "https://api.deepseek.com/v1",
+    default_model: "deepseek-chat",
+    protocol: Protocol::OpenAI,
+    requires_api_key: true,
+    description: "DeepSeek AI models",
+    pricing_url: "https://api-docs.deepseek.com/quick_start/pricing",
+    system_message_role: SystemMessageRole::System,
+    // DeepSeek recommends 1.3 for translation.
+    default_translation_temperature: Some(1.3),
+    endpoint_overrides: &[],
+};
+
+static MOONSHOT: ProviderDef = ProviderDef {
+    id: ProviderId::Moonshot,
+    name: "Moonshot",
+    default_base_url: "https://api.moonshot.cn/v1",
+    default_model: "moonshot-v1-8k",
+    protocol: Protocol::OpenAI,
+    requires_api_key: true,
+    description: "Moonshot (Kimi) AI models",
+    pricing_url: "https://platform.moonshot.cn/docs/pricing/chat",
+    system_message_role: SystemMessageRole::System,
+    default_translation_temperature: None,
+    endpoint_overrides: &[],
+};
+
+static ZHIPUAI: ProviderDef = ProviderDef {
+    id: ProviderId::ZhipuAI,
+    name: "ZhipuAI",
+    default_base_url: "https://open.bigmodel.cn/api/paas/v4",
+    default_model: "glm-4-flash",
+    protocol: Protocol::OpenAI,
+    requires_api_key: true,
+    description: "Zhipu GLM models",
+    pricing_url: "https://open.bigmodel.cn/pricing",
+    system_message_role: SystemMessageRole::System,
+    default_translation_temperature: None,
+    endpoint_overrides: &[],
+};
+
+static QWEN: ProviderDef = ProviderDef {
+    id: ProviderId::Qwen,
+    name: "Qwen",
+    default_base_url: "https://dashscope.aliyuncs.com/compatible-mode/v1",
+    default_model: "qwen-turbo",
+    protocol: Protocol::OpenAI,
+    requires_api_key: true,
+    description: "Alibaba Qwen models (DashScope)",
+    pricing_url: "https://help.aliyun.com/zh/model-studio/models",
+    system_message_role: SystemMessageRole::User,
+    default_translation_temperature: None,
+    endpoint_overrides: &[],
+};
+
+static GROQ: ProviderDef = ProviderDef {
+    id: ProviderId::Groq,
+    name: "Groq",
+    default_base_url: "https://api.groq.com/openai/v1",
+    default_model: "llama-3.1-8b-instant",
+    protocol: Protocol::OpenAI,
+    requires_api_key: true,
+    description: "Groq LPU inference",
+    pricing_url: "https://groq.com/pricing",
+    system_message_role: SystemMessageRole::System,
+    default_translation_temperature: None,
+    endpoint_overrides: &[],
+};
+
+static GEMINI: ProviderDef = ProviderDef {
+    id: ProviderId::Gemini,
+    name: "Gemini",
+    default_base_url: "https://generativelanguage.googleapis.com/v1beta",
+    default_model: "gemini-1.5-flash",
+    protocol: Protocol::Gemini,
+    requires_api_key: true,
+    description: "Google Gemini models",
+    pricing_url: "https://ai.google.dev/pricing",
+    system_message_role: SystemMessageRole::System,
+    default_translation_temperature: None,
+    // Preview models are served from the v1 API rather than v1beta.
+    endpoint_overrides: &[("gemini-2.0-flash-exp", "v1/models/{model}:generateContent")],
+};
+
+static MISTRAL: ProviderDef = ProviderDef {
+    id: ProviderId::Mistral,
+    name: "Mistral",
+    default_base_url: "https://api.mistral.ai/v1",
+    default_model: "mistral-small-latest",
+    protocol: Protocol::OpenAI,
+    requires_api_key: true,
+    description: "Mistral AI models",
+    pricing_url: "https://mistral.ai/pricing",
+    system_message_role: SystemMessageRole::System,
+    default_translation_temperature: None,
+    endpoint_overrides: &[],
+};
+
+static COHERE: ProviderDef = ProviderDef {
+    id: ProviderId::Cohere,
+    name: "Cohere",
+    default_base_url: "https://api.cohere.ai/v1",
+    default_model: "command-r",
+    protocol: Protocol::OpenAI,
+    requires_api_key: true,
+    description: "Cohere Command models",
+    pricing_url: "https://cohere.com/pricing",
+    system_message_role: SystemMessageRole::System,
+    default_translation_temperature: None,
+    endpoint_overrides: &[],
+};
+
+static OLLAMA: ProviderDef = ProviderDef {
+    id: ProviderId::Ollama,
+    name: "Ollama",
+    default_base_url: "http://localhost:11434/v1",
+    default_model: "llama3",
+    protocol: Protocol::OpenAI,
+    requires_api_key: false,
+    description: "Ollama local models",
+    pricing_url: "",
+    system_message_role: SystemMessageRole::System,
+    default_translation_temperature: None,
+    endpoint_overrides: &[],
+};
+
+static OPENROUTER: ProviderDef = ProviderDef {
+    id: ProviderId::OpenRouter,
+    name: "OpenRouter",
+    default_base_url: "https://openrouter.ai/api/v1",
+    default_model: "openai/gpt-4o-mini",
+    protocol: Protocol::OpenAI,
+    requires_api_key: true,
+    description: "OpenRouter unified API",
+    pricing_url: "https://openrouter.ai/models",
+    system_message_role: SystemMessageRole::System,
+    default_translation_temperature: None,
+    endpoint_overrides: &[],
+};
+
+static TOGETHERAI: ProviderDef = ProviderDef {
+    id: ProviderId::TogetherAI,
+    name: "TogetherAI",
+    default_base_url: "https://api.together.xyz/v1",
+    default_model: "meta-llama/Llama-3-8b-chat-hf",
+    protocol: Protocol::OpenAI,
+    requires_api_key: true,
+    description: "Together AI inference",
+    pricing_url: "https://www.together.ai/pricing",
+    system_message_role: SystemMessageRole::System,
+    default_translation_temperature: None,
+    endpoint_overrides: &[],
+};
+
+static PERPLEXITY: ProviderDef = ProviderDef {
+    id: ProviderId::Perplexity,
+    name: "Perplexity",
+    default_base_url: "https://api.perplexity.ai",
+    default_model: "llama-3.1-sonar-small-128k-online",
+    protocol: Protocol::OpenAI,
+    requires_api_key: true,
+    description: "Perplexity AI models",
+    pricing_url: "https://docs.perplexity.ai/guides/pricing",
+    system_message_role: SystemMessageRole::System,
+    default_translation_temperature: None,
+    endpoint_overrides: &[],
+};
+
+static SILICONFLOW: ProviderDef = ProviderDef {
+    id: ProviderId::SiliconFlow,
+    name: "SiliconFlow",
+    default_base_url: "https://api.siliconflow.cn/v1",
+    default_model: "Qwen/Qwen2.5-7B-Instruct",
+    protocol: Protocol::OpenAI,
+    requires_api_key: true,
+    description: "SiliconFlow inference",
+    pricing_url: "https://siliconflow.cn/pricing",
+    system_message_role: SystemMessageRole::System,
+    default_translation_temperature: None,
+    endpoint_overrides: &[],
+};
+
+static CLOUDFLARE: ProviderDef = ProviderDef {
+    id: ProviderId::Cloudflare,
+    name: "Cloudflare",
+    default_base_url: "https://api.cloudflare.com/client/v4/accounts/{account_id}/ai/v1",
+    default_model: "@cf/meta/llama-3.1-8b-instruct",
+    protocol: Protocol::OpenAI,
+    requires_api_key: true,
+    description: "Cloudflare Workers AI",
+    pricing_url: "https://developers.cloudflare.com/workers-ai/platform/pricing/",
+    system_message_role: SystemMessageRole::System,
+    default_translation_temperature: None,
+    endpoint_overrides: &[],
+};
+
+static XAI: ProviderDef = ProviderDef {
+    id: ProviderId::Xai,
+    name: "xAI",
+    default_base_url: "https://api.x.ai/v1",
+    default_model: "grok-beta",
+    protocol: Protocol::OpenAI,
+    requires_api_key: true,
+    description: "xAI Grok models",
+    pricing_url: "https://docs.x.ai/docs/models",
+    system_message_role: SystemMessageRole::System,
+    default_translation_temperature: None,
+    endpoint_overrides: &[],
+};
+
+/// Get all provider definitions.
+pub static PROVIDERS: &[&ProviderDef] = &[
+    &OPENAI,
+    &ANTHROPIC,
+    &DEEPSEEK,
+    &MOONSHOT,
+    &ZHIPUAI,
+    &QWEN,
+    &GROQ,
+    &GEMINI,
+    &MISTRAL,
+    &COHERE,
+    &OLLAMA,
+    &OPENROUTER,
+    &TOGETHERAI,
+    &PERPLEXITY,
+    &SILICONFLOW,
+    &CLOUDFLARE,
+    &XAI,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn provider_id_from_str() {
+        assert_eq!(ProviderId::from_str("openai"), Ok(ProviderId::OpenAI));
+        assert_eq!(ProviderId::from_str("DEEPSEEK"), Ok(ProviderId::DeepSeek));
+        assert_eq!(ProviderId::from_str("zhipu"), Ok(ProviderId::ZhipuAI));
+        assert!(ProviderId::from_str("unknown").is_err());
+    }
+
+    #[test]
+    fn provider_id_as_str() {
+        assert_eq!(ProviderId::OpenAI.as_str(), "openai");
+        assert_eq!(ProviderId::DeepSeek.as_str(), "deepseek");
+    }
+
+    #[test]
+    fn provider_definition() {
+        let def = ProviderId::DeepSeek.definition();
+        assert_eq!(def.name, "DeepSeek");
+        assert!(def.requires_api_key);
+        assert_eq!(def.protocol, Protocol::OpenAI);
+    }
+
+    #[test]
+    fn endpoint_overrides_match_by_model_prefix() {
+        let gemini = ProviderId::Gemini.definition();
+        assert_eq!(
+            gemini.endpoint_override("gemini-2.0-flash-exp"),
+            Some("v1/models/{model}:generateContent")
+        );
+        assert_eq!(gemini.endpoint_override("gemini-1.5-pro"), None);
+        assert_eq!(
+            ProviderId::OpenAI.definition().endpoint_override("gpt-4o"),
+            None
+        );
+    }
+
+    #[test]
+    fn cloudflare_base_url_has_account_placeholder() {
+        assert_eq!(
+            ProviderId::from_str("cloudflare"),
+            Ok(ProviderId::Cloudflare)
+        );
+        let def = ProviderId::Cloudflare.definition();
+        assert_eq!(def.protocol, Protocol::OpenAI);
+        assert!(def.default_base_url.contains(ACCOUNT_ID_PLACEHOLDER));
+    }
+
+    #[test]
+    fn hosted_providers_link_their_pricing() {
+        for def in PROVIDERS {
+            assert_eq!(
+                def.pricing_url.starts_with("https://"),
+                def.requires_api_key,
+                "{}",
+                def.name
+            );
+        }
+    }
+
+    #[test]
+    fn instruction_roles() {
+        assert_eq!(
+            ProviderId::Qwen.definition().system_message_role,
+            SystemMessageRole::User
+        );
+        assert_eq!(
+            ProviderId::ZhipuAI.definition().system_message_role,
+            SystemMessageRole::System
+        );
+    }
+
+    #[test]
+    fn xai_aliases() {
+        for alias in ["xai", "grok", "x.ai", "XAI", "Grok"] {
+            assert_eq!(ProviderId::from_str(alias), Ok(ProviderId::Xai), "{alias}");
+        }
+        let def = ProviderId::Xai.definition();
+        assert_eq!(def.protocol, Protocol::OpenAI);
+        assert_eq!(def.default_base_url, "https://api.x.ai/v1");
+        assert_eq!(def.default_model, "grok-beta");
+        assert!(def.requires_api_key);
+        assert_eq!(ProviderId::Xai.to_string(), "xAI");
+        assert_eq!(serde_json::to_string(&ProviderId::Xai).unwrap(), "\"xai\"");
+    }
+
+    #[test]
+    fn as_str_round_trips_through_from_str() {
+        for &provider in ProviderId::ALL {
+            assert_eq!(
+                ProviderId::from_str(provider.as_str()),
+                Ok(provider),
+                "{provider}"
+            );
+            assert_eq!(provider.definition().id, provider);
+        }
+    }
+
+    #[test]
+    fn provider_count() {
+        assert_eq!(ProviderId::ALL.len(), PROVIDERS.len());
+    }
+}
@@ -0,0 +1,224 @@
+//! Rate limit headers reported by OpenAI-compatible providers.
+//!
+//! Responses carry `x-ratelimit-remaining-requests`, `x-ratelimit-remaining-tokens` and
+//! `x-ratelimit-reset-requests`. [`RateLimitState`] keeps the latest values so translation
+//! can warn before the provider starts answering 429, and [`retry_after`] tells the retry loop
+//! how long a rejected request should wait.
+
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Utc;
+use reqwest::header::HeaderMap;
+use reqwest::header::RETRY_AFTER;
+
+const REMAINING_REQUESTS_HEADER: &str = "x-ratelimit-remaining-requests";
+const REMAINING_TOKENS_HEADER: &str = "x-ratelimit-remaining-tokens";
+const RESET_REQUESTS_HEADER: &str = "x-ratelimit-reset-requests";
+
+/// Remaining requests below which the quota is reported as nearly exhausted.
+const LOW_REMAINING_REQUESTS: u64 = 5;
+
+/// Rate limit quota from the most recent response that reported one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitState {
+    pub remaining_requests: Option<u64>,
+    pub remaining_tokens: Option<u64>,
+    /// When the request quota refills.
+    pub reset_requests_at: Option<DateTime<Utc>>,
+}
+
+impl RateLimitState {
+    /// Parse the rate limit headers of a response received at `now`. Returns `None` when
+    /// the provider sent none of them.
+    pub(super) fn from_headers(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Self> {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::trim)
+        };
+        let state = Self {
+            remaining_requests: header(REMAINING_REQUESTS_HEADER).and_then(|v| v.parse().ok()),
+            remaining_tokens: header(REMAINING_TOKENS_HEADER).and_then(|v| v.parse().ok()),
+            reset_requests_at: header(RESET_REQUESTS_HEADER)
+                .and_then(parse_reset_duration)
+                .and_then(|reset| chrono::Duration::from_std(reset).ok())
+                .and_then(|reset| now.checked_add_signed(reset)),
+        };
+        (state != Self::default()).then_some(state)
+    }
+
+    /// The request quota is nearly used up.
+    pub fn is_low(&self) -> bool {
+        self.remaining_requests
+            .is_some_and(|remaining| remaining < LOW_REMAINING_REQUESTS)
+    }
+}
+
+/// How long a response received at `now` asks the client to wait: its `Retry-After` (seconds or
+/// an HTTP date), otherwise the time until the request quota resets.
+pub(super) fn retry_after(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Duration> {
+    let value = headers
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim);
+    if let Some(seconds) = value.and_then(|value| value.parse().ok()) {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = value
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+        .map(|at| at.with_timezone(&Utc))
+        .or_else(|| RateLimitState::from_headers(headers, now)?.reset_requests_at)?;
+    (at - now).to_std().ok()
+}
+
+/// Parse a reset duration such as `"20ms"`, `"1s"`, `"6m0s"` or `"1h2m3.5s"`.
+fn parse_reset_duration(value: &str) -> Option<Duration> {
+    let mut rest = value.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    let mut total = Duration::ZERO;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        let number: f64 = rest[..number_len].parse().ok()?;
+        rest = &rest[number_len..];
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit() || c == '.')
+            .unwrap_or(rest.len());
+        let seconds = match &rest[..unit_len] {
+            "ms" => number / 1000.0,
+            "s" => number,
+            "m" => number * 60.0,
+            "h" => number * 3600.0,
+            _ => return None,
+        };
+        rest = &rest[unit_len..];
+        total = total.checked_add(Duration::try_from_secs_f64(seconds).ok()?)?;
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use reqwest::header::HeaderName;
+    use reqwest::header::HeaderValue;
+
+    fn headers(fixture: &str) -> HeaderMap {
+        fixture
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(name, value)| {
+                (
+                    HeaderName::from_bytes(name.trim().as_bytes()).unwrap(),
+                    HeaderValue::from_str(value.trim()).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn openai_headers_are_parsed() {
+        let now = Utc::now();
+        let fixture = "x-ratelimit-limit-requests: 500\n\
+                       x-ratelimit-remaining-requests: 3\n\
+                       x-ratelimit-remaining-tokens: 149984\n\
+                       x-ratelimit-reset-requests: 6m0s\n\
+                       x-ratelimit-reset-tokens: 6ms";
+
+        let state = RateLimitState::from_headers(&headers(fixture), now).unwrap();
+
+        assert_eq!(
+            state,
+            RateLimitState {
+                remaining_requests: Some(3),
+                remaining_tokens: Some(149_984),
+                reset_requests_at: Some(now + chrono::Duration::minutes(6)),
+            }
+        );
+        assert!(state.is_low());
+    }
+
+    #[test]
+    fn missing_or_malformed_headers() {
+        let now = Utc::now();
+        assert_eq!(
+            RateLimitState::from_headers(&headers("content-type: application/json"), now),
+            None
+        );
+
+        let state = RateLimitState::from_headers(
+            &headers("x-ratelimit-remaining-requests: 120\nx-ratelimit-reset-requests: soon"),
+            now,
+        )
+        .unwrap();
+        assert_eq!(state.remaining_requests, Some(120));
+        assert_eq!(state.reset_requests_at, None);
+        assert!(!state.is_low());
+    }
+
+    #[test]
+    fn retry_after_prefers_the_header_over_the_quota_reset() {
+        let now = DateTime::parse_from_rfc3339("2026-03-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let reset = "x-ratelimit-remaining-requests: 0\nx-ratelimit-reset-requests: 20s";
+
+        assert_eq!(
+            retry_after(&headers(&format!("retry-after: 3\n{reset}")), now),
+            Some(Duration::from_secs(3))
+        );
+        assert_eq!(
+            retry_after(&headers("retry-after: Sun, 01 Mar 2026 12:01:30 GMT"), now),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(
+            retry_after(&headers(reset), now),
+            Some(Duration::from_secs(20))
+        );
+        // A date in the past or an unparseable value gives no hint.
+        assert_eq!(
+            retry_after(&headers("retry-after: Sun, 01 Mar 2026 11:00:00 GMT"), now),
+            None
+        );
+        assert_eq!(retry_after(&headers("retry-after: later"), now), None);
+        assert_eq!(retry_after(&HeaderMap::new(), now), None);
+    }
+
+    #[test]
+    fn reset_durations() {
+        assert_eq!(
+            parse_reset_duration("20ms"),
+            Some(Duration::from_millis(20))
+        );
+        assert_eq!(parse_reset_duration("1s"), Some(Duration::from_secs(1)));
+        assert_eq!(
+            parse_reset_duration("1h2m3.5s"),
+            Some(Duration::from_millis(3_723_500))
+        );
+        assert_eq!(parse_reset_duration(""), None);
+        assert_eq!(parse_reset_duration("5 minutes"), None);
+        assert_eq!(
+            parse_reset_duration("5000000000000000h5000000000000000h"),
+            None
+        );
+    }
+
+    #[test]
+    fn absurd_reset_is_ignored() {
+        let now = Utc::now();
+        let state = RateLimitState::from_headers(
+            &headers("x-ratelimit-remaining-requests: 2\nx-ratelimit-reset-requests: 9999999999h"),
+            now,
+        )
+        .unwrap();
+
+        assert_eq!(state.remaining_requests, Some(2));
+        assert_eq!(state.reset_requests_at, None);
+    }
+}
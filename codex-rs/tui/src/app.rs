@@ -20,7 +20,6 @@ use crate::external_editor;
 use crate::file_search::FileSearchManager;
 use crate::history_cell;
 use crate::history_cell::HistoryCell;
-#[cfg(not(debug_assertions))]
 use crate::history_cell::UpdateAvailableHistoryCell;
 use crate::model_migration::ModelMigrationOutcome;
 use crate::model_migration::migration_copy_for_models;
@@ -547,6 +546,8 @@ pub(crate) struct App {
     feedback_audience: FeedbackAudience,
     /// Set when the user confirms an update; propagated on exit.
     pub(crate) pending_update_action: Option<UpdateAction>,
+    /// Latest version already announced by an update banner this session.
+    update_banner_version: Option<String>,
 
     /// Ignore the next ShutdownComplete event when we're intentionally
     /// stopping a thread (e.g., before starting a new one).
@@ -1070,8 +1071,8 @@ impl App {
         chat_widget.maybe_prompt_windows_sandbox_enable();
 
         let file_search = FileSearchManager::new(config.cwd.clone(), app_event_tx.clone());
-        #[cfg(not(debug_assertions))]
-        let upgrade_version = crate::updates::get_upgrade_version(&config);
+        let upgrade_version =
+            crate::updates::get_upgrade_version(&config, Some(app_event_tx.clone()));
 
         let mut app = Self {
             server: thread_manager.clone(),
@@ -1097,6 +1098,7 @@ impl App {
             feedback: feedback.clone(),
             feedback_audience,
             pending_update_action: None,
+            update_banner_version: upgrade_version.clone(),
             suppress_shutdown_complete: false,
             windows_sandbox: WindowsSandboxState::default(),
             thread_event_channels: HashMap::new(),
@@ -1132,18 +1134,20 @@ impl App {
             }
         }
 
-        #[cfg(not(debug_assertions))]
         if let Some(latest_version) = upgrade_version {
             let control = app
                 .handle_event(
                     tui,
                     AppEvent::InsertHistoryCell(Box::new(UpdateAvailableHistoryCell::new(
                         latest_version,
-                        crate::update_action::get_update_actions(),
+                        crate::update_action::get_update_actions(
+                            app.config.tui_preferred_update_manager,
+                        ),
                     ))),
                 )
                 .await?;
             if let AppRunControl::Exit(exit_reason) = control {
+                app.chat_widget.shutdown_translation().await;
                 return Ok(AppExitInfo {
                     token_usage: app.token_usage(),
                     thread_id: app.chat_widget.thread_id(),
@@ -1205,6 +1209,7 @@ impl App {
                 AppRunControl::Exit(reason) => break reason,
             }
         };
+        app.chat_widget.shutdown_translation().await;
         tui.terminal.clear()?;
         Ok(AppExitInfo {
             token_usage: app.token_usage(),
@@ -1237,9 +1242,7 @@ impl App {
                 }
                 TuiEvent::Draw => {
                     // Process translation results and timeouts
-                    if self.chat_widget.translation_draw_tick() {
-                        tui.frame_requester().schedule_frame();
-                    }
+                    self.chat_widget.translation_draw_tick();
                     if self.backtrack_render_pending {
                         self.backtrack_render_pending = false;
                         self.render_transcript_once(tui);
@@ -1564,6 +1567,10 @@ impl App {
             AppEvent::StatuslineGitPreviewUpdated(preview) => {
                 self.chat_widget.set_statusline_git_preview(preview);
             }
+            AppEvent::StatuslineConfigReloaded(config) => {
+                self.chat_widget.set_statusline_config(config);
+                tui.frame_requester().schedule_frame();
+            }
             AppEvent::ConnectorsLoaded(result) => {
                 self.chat_widget.on_connectors_loaded(result);
             }
@@ -2218,10 +2225,38 @@ impl App {
             AppEvent::OpenTranslateConfig => {
                 // Open translation configuration overlay
                 let config = self.chat_widget.get_translation_config();
+                let save_path = self.chat_widget.translation_config_path();
+                let _ = tui.enter_alt_screen();
+                self.overlay = Some(Overlay::new_translate(config, save_path, None));
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::EditTranslationApiKey(provider) => {
+                let config = self.chat_widget.get_translation_config();
+                let save_path = self.chat_widget.translation_config_path();
                 let _ = tui.enter_alt_screen();
-                self.overlay = Some(Overlay::new_translate(config));
+                self.overlay = Some(Overlay::new_translate(config, save_path, Some(provider)));
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::TranslationAuthFailed(provider) => {
+                self.chat_widget.on_translation_auth_failed(provider);
+            }
+            AppEvent::UpdateRateLimitState(state) => {
+                self.chat_widget.on_translation_rate_limit(state);
+            }
+            AppEvent::UpdateVersionFetched(latest_version) => {
+                // Skip if the banner for this version is already in the transcript.
+                if self.update_banner_version.as_deref() != Some(latest_version.as_str()) {
+                    self.update_banner_version = Some(latest_version.clone());
+                    self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                        UpdateAvailableHistoryCell::new(
+                            latest_version,
+                            crate::update_action::get_update_actions(
+                                self.config.tui_preferred_update_manager,
+                            ),
+                        ),
+                    )));
+                }
+            }
         }
         Ok(AppRunControl::Continue)
     }
@@ -2653,6 +2688,7 @@ mod tests {
             feedback: codex_feedback::CodexFeedback::new(),
             feedback_audience: FeedbackAudience::External,
             pending_update_action: None,
+            update_banner_version: None,
             suppress_shutdown_complete: false,
             windows_sandbox: WindowsSandboxState::default(),
             thread_event_channels: HashMap::new(),
@@ -2706,6 +2742,7 @@ mod tests {
                 feedback: codex_feedback::CodexFeedback::new(),
                 feedback_audience: FeedbackAudience::External,
                 pending_update_action: None,
+                update_banner_version: None,
                 suppress_shutdown_complete: false,
                 windows_sandbox: WindowsSandboxState::default(),
                 thread_event_channels: HashMap::new(),
@@ -20,6 +20,7 @@ use codex_protocol::openai_models::ModelPreset;
 
 use crate::bottom_pane::ApprovalRequest;
 use crate::history_cell::HistoryCell;
+use crate::statusline::CxLineConfig;
 use crate::statusline::GitPreviewData;
 
 use codex_core::features::Feature;
@@ -99,6 +100,9 @@ pub(crate) enum AppEvent {
     /// Result of refreshing statusline Git info
     StatuslineGitPreviewUpdated(GitPreviewData),
 
+    /// The statusline config file changed on disk and was reloaded.
+    StatuslineConfigReloaded(CxLineConfig),
+
     /// Result of prefetching connectors.
     ConnectorsLoaded(Result<ConnectorsSnapshot, String>),
 
@@ -302,6 +306,18 @@ pub(crate) enum AppEvent {
 
     /// Open the translation configuration screen (full-screen).
     OpenTranslateConfig,
+
+    /// Open the translation configuration screen editing the API key of `provider`.
+    EditTranslationApiKey(crate::translation::ProviderId),
+
+    /// The translation provider rejected its API key (HTTP 401).
+    TranslationAuthFailed(crate::translation::ProviderId),
+
+    /// The translation provider reported its rate limit quota.
+    UpdateRateLimitState(crate::translation::RateLimitState),
+
+    /// A background update check found a release newer than the running binary.
+    UpdateVersionFetched(String),
 }
 
 /// The exit strategy requested by the UI layer.
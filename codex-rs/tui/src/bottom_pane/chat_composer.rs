@@ -86,10 +86,20 @@ use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::key_hint::has_ctrl_or_alt;
 use crate::statusline::CxLineConfig;
+use crate::statusline::DiffStats;
 use crate::statusline::GitPreviewData;
+use crate::statusline::HostInfo;
+use crate::statusline::HostOptions;
+use crate::statusline::McpHealth;
+use crate::statusline::ModelDisplay;
+use crate::statusline::ProfileInfo;
+use crate::statusline::ProfileOptions;
 use crate::statusline::StatusLineContext;
 use crate::statusline::StatusLineWidget;
 use crate::statusline::build_statusline;
+use crate::translation::BarrierInfo;
+use crate::translation::PendingCounts;
+use crate::translation::RateLimitState;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
@@ -311,6 +321,17 @@ pub(crate) struct ChatComposer {
     statusline_weekly_rate_limit_percent: Option<f64>,
     statusline_weekly_rate_limit_resets_at: Option<String>,
     statusline_git_preview: Option<GitPreviewData>,
+    statusline_agent_running: bool,
+    statusline_translations: PendingCounts,
+    statusline_background_tasks: usize,
+    statusline_translation_barrier: Option<BarrierInfo>,
+    statusline_translation_offline: bool,
+    statusline_translation_rate_limit: Option<RateLimitState>,
+    statusline_last_turn_duration: Option<Duration>,
+    statusline_last_command_exit_code: Option<i32>,
+    statusline_profile: ProfileInfo,
+    statusline_diff_stats: DiffStats,
+    statusline_mcp_health: McpHealth,
 }
 
 #[derive(Clone, Debug)]
@@ -410,6 +431,17 @@ impl ChatComposer {
             statusline_weekly_rate_limit_percent: None,
             statusline_weekly_rate_limit_resets_at: None,
             statusline_git_preview: Some(GitPreviewData::empty()),
+            statusline_agent_running: false,
+            statusline_translations: PendingCounts::default(),
+            statusline_background_tasks: 0,
+            statusline_translation_barrier: None,
+            statusline_translation_offline: false,
+            statusline_translation_rate_limit: None,
+            statusline_last_turn_duration: None,
+            statusline_last_command_exit_code: None,
+            statusline_profile: ProfileInfo::default(),
+            statusline_diff_stats: DiffStats::default(),
+            statusline_mcp_health: McpHealth::default(),
         };
         // Apply configuration via the setter to keep side-effects centralized.
         this.set_disable_paste_burst(disable_paste_burst);
@@ -501,6 +533,64 @@ impl ChatComposer {
         self.statusline_weekly_rate_limit_resets_at = weekly_rate_limit_resets_at;
     }
 
+    /// 设置状态栏 Activity 计数，返回是否有变化
+    pub fn set_statusline_activity(
+        &mut self,
+        agent_running: bool,
+        translations: PendingCounts,
+        background_tasks: usize,
+    ) -> bool {
+        let activity = (agent_running, translations, background_tasks);
+        let current = (
+            self.statusline_agent_running,
+            self.statusline_translations,
+            self.statusline_background_tasks,
+        );
+        if activity == current {
+            return false;
+        }
+        self.statusline_agent_running = agent_running;
+        self.statusline_translations = translations;
+        self.statusline_background_tasks = background_tasks;
+        true
+    }
+
+    /// 设置上一轮 turn 的耗时与最后一条命令的退出码
+    pub fn set_statusline_last_turn(&mut self, duration: Duration, exit_code: Option<i32>) {
+        self.statusline_last_turn_duration = Some(duration);
+        self.statusline_last_command_exit_code = exit_code;
+    }
+
+    /// 设置当前 profile 与账号信息
+    pub fn set_statusline_profile(&mut self, profile: ProfileInfo) {
+        self.statusline_profile = profile;
+    }
+
+    /// 设置本会话累计的增删行数
+    pub fn set_statusline_diff_stats(&mut self, stats: DiffStats) {
+        self.statusline_diff_stats = stats;
+    }
+
+    /// 设置 MCP server 连接统计
+    pub fn set_statusline_mcp_health(&mut self, health: McpHealth) {
+        self.statusline_mcp_health = health;
+    }
+
+    /// 设置状态栏翻译 barrier 信息
+    pub fn set_statusline_translation_barrier(&mut self, info: Option<BarrierInfo>) {
+        self.statusline_translation_barrier = info;
+    }
+
+    /// 设置状态栏翻译离线状态
+    pub fn set_statusline_translation_offline(&mut self, offline: bool) {
+        self.statusline_translation_offline = offline;
+    }
+
+    /// 设置状态栏翻译速率限额
+    pub fn set_statusline_translation_rate_limit(&mut self, state: Option<RateLimitState>) {
+        self.statusline_translation_rate_limit = state;
+    }
+
     /// 获取当前状态栏配置
     pub fn get_statusline_config(&self) -> CxLineConfig {
         self.statusline_config.clone()
@@ -3312,11 +3402,35 @@ impl ChatComposer {
         {
             let mut ctx = StatusLineContext::new(&self.statusline_model, &self.statusline_cwd)
                 .with_reasoning_effort(self.statusline_reasoning_effort)
+                .with_model_display(ModelDisplay::from_config(&self.statusline_config))
                 .with_context(self.context_window_used_tokens, self.context_window_size)
                 .with_rate_limit(
                     self.statusline_hourly_rate_limit_percent,
                     self.statusline_weekly_rate_limit_percent,
                     self.statusline_weekly_rate_limit_resets_at.clone(),
+                )
+                .with_activity(
+                    self.statusline_agent_running,
+                    self.statusline_translations.in_flight,
+                    self.statusline_background_tasks,
+                )
+                .with_deferred_cells(self.statusline_translations.deferred)
+                .with_translation_barrier(self.statusline_translation_barrier)
+                .with_translation_offline(self.statusline_translation_offline)
+                .with_translation_rate_limit(self.statusline_translation_rate_limit)
+                .with_last_turn(
+                    self.statusline_last_turn_duration,
+                    self.statusline_last_command_exit_code,
+                )
+                .with_profile(
+                    &self.statusline_profile,
+                    ProfileOptions::from_config(&self.statusline_config),
+                )
+                .with_diff_stats(self.statusline_diff_stats)
+                .with_mcp_health(self.statusline_mcp_health)
+                .with_host(
+                    HostInfo::current(),
+                    HostOptions::from_config(&self.statusline_config),
                 );
             if let Some(preview) = &self.statusline_git_preview {
                 ctx = ctx.with_git_preview(
@@ -657,6 +657,70 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// 设置状态栏 Activity 计数
+    pub(crate) fn set_statusline_activity(
+        &mut self,
+        agent_running: bool,
+        translations: crate::translation::PendingCounts,
+        background_tasks: usize,
+    ) {
+        if self
+            .composer
+            .set_statusline_activity(agent_running, translations, background_tasks)
+        {
+            self.request_redraw();
+        }
+    }
+
+    /// 设置上一轮 turn 的耗时与最后一条命令的退出码
+    pub(crate) fn set_statusline_last_turn(
+        &mut self,
+        duration: std::time::Duration,
+        exit_code: Option<i32>,
+    ) {
+        self.composer.set_statusline_last_turn(duration, exit_code);
+        self.request_redraw();
+    }
+
+    /// 设置当前 profile 与账号信息
+    pub(crate) fn set_statusline_profile(&mut self, profile: crate::statusline::ProfileInfo) {
+        self.composer.set_statusline_profile(profile);
+        self.request_redraw();
+    }
+
+    /// 设置本会话累计的增删行数
+    pub(crate) fn set_statusline_diff_stats(&mut self, stats: crate::statusline::DiffStats) {
+        self.composer.set_statusline_diff_stats(stats);
+        self.request_redraw();
+    }
+
+    /// 设置 MCP server 连接统计
+    pub(crate) fn set_statusline_mcp_health(&mut self, health: crate::statusline::McpHealth) {
+        self.composer.set_statusline_mcp_health(health);
+        self.request_redraw();
+    }
+
+    /// 设置状态栏翻译 barrier 信息（每个 draw tick 更新）
+    pub(crate) fn set_statusline_translation_barrier(
+        &mut self,
+        info: Option<crate::translation::BarrierInfo>,
+    ) {
+        self.composer.set_statusline_translation_barrier(info);
+    }
+
+    /// 设置状态栏翻译离线状态（每个 draw tick 更新）
+    pub(crate) fn set_statusline_translation_offline(&mut self, offline: bool) {
+        self.composer.set_statusline_translation_offline(offline);
+    }
+
+    /// 设置状态栏翻译服务的剩余速率限额
+    pub(crate) fn set_statusline_translation_rate_limit(
+        &mut self,
+        state: Option<crate::translation::RateLimitState>,
+    ) {
+        self.composer.set_statusline_translation_rate_limit(state);
+    }
+
     /// 设置状态栏数据
     pub(crate) fn set_statusline_data(
         &mut self,
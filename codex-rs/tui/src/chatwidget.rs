@@ -183,8 +183,12 @@ use crate::render::renderable::RenderableExt;
 use crate::render::renderable::RenderableItem;
 use crate::slash_command::SlashCommand;
 use crate::status::RateLimitSnapshotDisplay;
+use crate::status::StatusTranslationData;
+use crate::statusline::CxLineConfig;
+use crate::statusline::DiffStats;
 use crate::statusline::GitPreviewData;
 use crate::statusline::collect_git_preview;
+use crate::statusline::config::CxLineConfigWatcher;
 use crate::text_formatting::truncate_text;
 use crate::tui::FrameRequester;
 mod interrupts;
@@ -293,6 +297,7 @@ const RATE_LIMIT_WARNING_THRESHOLDS: [f64; 3] = [75.0, 90.0, 95.0];
 const NUDGE_MODEL_SLUG: &str = "gpt-5.1-codex-mini";
 const RATE_LIMIT_SWITCH_PROMPT_THRESHOLD: f64 = 90.0;
 const STATUSLINE_GIT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const TRANSLATION_STATUS_REFRESH_INTERVAL: Duration = Duration::from_millis(100);
 
 #[derive(Default)]
 struct RateLimitWarningState {
@@ -513,12 +518,26 @@ pub(crate) struct ChatWidget {
     /// This is kept separate from `mcp_startup_status` so that MCP startup progress (or completion)
     /// can update the status header without accidentally clearing the spinner for an active turn.
     agent_turn_running: bool,
+    /// When the running agent turn started, for the last-turn statusline segment.
+    turn_started_at: Option<Instant>,
+    /// Exit code of the most recent command executed in the current turn.
+    turn_last_exit_code: Option<i32>,
+    /// Lines added/removed by turns that have completed in this session.
+    session_diff_stats: DiffStats,
+    /// Lines added/removed by the running turn.
+    turn_diff_stats: DiffStats,
+    /// Whether `turn_diff_stats` comes from a turn diff (net of reverted hunks) rather than
+    /// summed patch results.
+    turn_diff_is_net: bool,
     /// Tracks per-server MCP startup state while startup is in progress.
     ///
     /// The map is `Some(_)` from the first `McpStartupUpdate` until `McpStartupComplete`, and the
     /// bottom pane is treated as "running" while this is populated, even if no agent turn is
     /// currently executing.
     mcp_startup_status: Option<HashMap<String, McpStartupStatus>>,
+    /// Last known startup state of each MCP server, kept after startup completes for the
+    /// statusline Mcp segment.
+    mcp_server_states: HashMap<String, McpStartupStatus>,
     connectors_cache: ConnectorsCacheState,
     // Queue of interruptive UI events deferred during an active write cycle
     interrupts: InterruptManager,
@@ -528,6 +547,8 @@ pub(crate) struct ChatWidget {
     full_reasoning_buffer: String,
     // Orchestrator for agent reasoning translation
     agent_reasoning_translation: crate::translation::ReasoningTranslator,
+    // Provider whose translation API key was rejected; Alt+K opens the key editor for it.
+    translation_rejected_key: Option<crate::translation::ProviderId>,
     // Current status header shown in the status indicator.
     current_status_header: String,
     // Previous status header to restore after a transient stream retry.
@@ -757,6 +778,16 @@ impl ChatWidget {
     fn update_task_running_state(&mut self) {
         self.bottom_pane
             .set_task_running(self.agent_turn_running || self.mcp_startup_status.is_some());
+        self.update_statusline_activity();
+    }
+
+    /// Push in-flight agent, background and translation counts to the statusline.
+    fn update_statusline_activity(&mut self) {
+        self.bottom_pane.set_statusline_activity(
+            self.agent_turn_running,
+            self.agent_reasoning_translation.pending_count(),
+            self.unified_exec_processes.len(),
+        );
     }
 
     fn restore_reasoning_status_header(&mut self) {
@@ -813,6 +844,13 @@ impl ChatWidget {
             .set_history_metadata(event.history_log_id, event.history_entry_count);
         self.set_skills(None);
         self.bottom_pane.set_connectors_snapshot(None);
+        if self
+            .thread_id
+            .is_some_and(|thread_id| thread_id != event.session_id)
+        {
+            // Translations pending from the previous session must not leak into this one.
+            self.agent_reasoning_translation.reset();
+        }
         self.thread_id = Some(event.session_id);
         self.thread_name = event.thread_name.clone();
         self.forked_from = event.forked_from_id;
@@ -928,9 +966,21 @@ impl ChatWidget {
         // If we have a stream_controller, then the final agent message is redundant and will be a
         // duplicate of what has already been streamed.
         if self.stream_controller.is_none() && !message.is_empty() {
-            self.handle_streaming_delta(message);
+            self.handle_streaming_delta(message.clone());
         }
         self.flush_answer_stream_with_separator();
+        if self
+            .agent_reasoning_translation
+            .config()
+            .translate_system_messages
+        {
+            // Queued behind the message's last lines, so the translation follows them.
+            self.agent_reasoning_translation.queue_system_message(
+                self.thread_id,
+                self.frame_requester.clone(),
+                crate::translation::SystemMessage::new(message),
+            );
+        }
         self.handle_stream_finished();
         self.request_redraw();
     }
@@ -1025,6 +1075,7 @@ impl ChatWidget {
                     self.frame_requester.clone(),
                     cell,
                 );
+            self.update_statusline_activity();
         }
         self.reasoning_buffer.clear();
         self.full_reasoning_buffer.clear();
@@ -1042,6 +1093,8 @@ impl ChatWidget {
 
     fn on_task_started(&mut self) {
         self.agent_turn_running = true;
+        self.turn_started_at = Some(Instant::now());
+        self.turn_last_exit_code = None;
         self.saw_plan_update_this_turn = false;
         self.saw_plan_item_this_turn = false;
         self.plan_delta_buffer.clear();
@@ -1058,6 +1111,7 @@ impl ChatWidget {
         self.set_status_header(String::from("Working"));
         self.full_reasoning_buffer.clear();
         self.reasoning_buffer.clear();
+        self.agent_reasoning_translation.on_turn_started();
         self.request_redraw();
     }
 
@@ -1070,6 +1124,25 @@ impl ChatWidget {
             self.add_boxed_history(cell);
         }
         self.flush_unified_exec_wait_streak();
+        // Replayed turns are not translated again.
+        if !from_replay {
+            self.agent_reasoning_translation
+                .flush_pending_text(self.thread_id, self.frame_requester.clone());
+        }
+        if !from_replay
+            && self
+                .agent_reasoning_translation
+                .config()
+                .translate_final_answer
+            && let Some(message) = last_agent_message.as_ref()
+        {
+            // Queued ahead of the separator, so the translation sits under the answer.
+            self.agent_reasoning_translation.queue_system_message(
+                self.thread_id,
+                self.frame_requester.clone(),
+                crate::translation::SystemMessage::final_answer(message.clone()),
+            );
+        }
         if !from_replay {
             let runtime_metrics = self.otel_manager.runtime_metrics_summary();
             if runtime_metrics.is_some() {
@@ -1085,6 +1158,15 @@ impl ChatWidget {
             self.needs_final_message_separator = false;
             self.had_work_activity = false;
         }
+        if let Some(started_at) = self.turn_started_at.take()
+            && !from_replay
+        {
+            self.bottom_pane
+                .set_statusline_last_turn(started_at.elapsed(), self.turn_last_exit_code);
+        }
+        self.session_diff_stats = self.session_diff_stats + self.turn_diff_stats;
+        self.turn_diff_stats = DiffStats::default();
+        self.turn_diff_is_net = false;
         // Mark task stopped and request redraw now that all content is in history.
         self.agent_turn_running = false;
         self.update_task_running_state();
@@ -1233,6 +1315,8 @@ impl ChatWidget {
         // Get current model and reasoning effort from effective collaboration mode
         let reasoning_effort = self.effective_reasoning_effort();
         let current_model = self.current_model().to_string();
+        self.agent_reasoning_translation
+            .set_reasoning_effort(reasoning_effort);
 
         self.bottom_pane.set_statusline_data(
             &current_model,
@@ -1242,6 +1326,49 @@ impl ChatWidget {
             weekly_percent,
             weekly_resets_at,
         );
+        self.bottom_pane
+            .set_statusline_profile(self.statusline_profile_info());
+        self.bottom_pane
+            .set_statusline_mcp_health(self.statusline_mcp_health());
+    }
+
+    /// Connection counts of the enabled MCP servers for the statusline Mcp segment.
+    ///
+    /// Servers without a reported state yet count as starting.
+    fn statusline_mcp_health(&self) -> crate::statusline::McpHealth {
+        let mut health = crate::statusline::McpHealth::default();
+        for (name, server) in self.config.mcp_servers.iter() {
+            if !server.enabled {
+                continue;
+            }
+            health.total += 1;
+            match self.mcp_server_states.get(name) {
+                Some(McpStartupStatus::Ready) => health.connected += 1,
+                None | Some(McpStartupStatus::Starting) => health.starting += 1,
+                Some(McpStartupStatus::Failed { .. } | McpStartupStatus::Cancelled) => {}
+            }
+        }
+        health
+    }
+
+    /// Active profile and account for the statusline Profile segment.
+    fn statusline_profile_info(&self) -> crate::statusline::ProfileInfo {
+        let configured_profiles = self
+            .config
+            .config_layer_stack
+            .effective_config()
+            .get("profiles")
+            .and_then(toml::Value::as_table)
+            .map_or(0, toml::map::Map::len);
+        let auth = self.auth_manager.auth_cached();
+        crate::statusline::ProfileInfo::new(
+            self.config.active_profile.clone(),
+            configured_profiles,
+            auth.as_ref()
+                .and_then(CodexAuth::get_account_email)
+                .as_deref(),
+            auth.as_ref().and_then(CodexAuth::get_account_id).as_deref(),
+        )
     }
 
     fn start_statusline_git_poller(&mut self) {
@@ -1259,9 +1386,14 @@ impl ChatWidget {
         let poller = Some(handle.spawn(async move {
             let mut interval = tokio::time::interval(STATUSLINE_GIT_POLL_INTERVAL);
             let mut last_preview: Option<GitPreviewData> = None;
+            // The statusline config is live-reloaded on the same cadence as git info.
+            let mut config_watcher = CxLineConfigWatcher::new(CxLineConfig::config_path());
 
             loop {
                 interval.tick().await;
+                if let Some(config) = config_watcher.poll() {
+                    app_event_tx.send(AppEvent::StatuslineConfigReloaded(config));
+                }
                 let cwd_clone = cwd.clone();
                 let preview = tokio::task::spawn_blocking(move || collect_git_preview(&cwd_clone))
                     .await
@@ -1386,6 +1518,8 @@ impl ChatWidget {
         self.adaptive_chunking.reset();
         self.stream_controller = None;
         self.plan_stream_controller = None;
+        self.agent_reasoning_translation
+            .flush_pending_text(self.thread_id, self.frame_requester.clone());
         self.maybe_show_pending_rate_limit_prompt();
     }
 
@@ -1426,8 +1560,12 @@ impl ChatWidget {
         if let McpStartupStatus::Failed { error } = &ev.status {
             self.on_warning(error);
         }
+        self.mcp_server_states
+            .insert(ev.server.clone(), ev.status.clone());
         status.insert(ev.server, ev.status);
         self.mcp_startup_status = Some(status);
+        self.bottom_pane
+            .set_statusline_mcp_health(self.statusline_mcp_health());
         self.update_task_running_state();
         if let Some(current) = &self.mcp_startup_status {
             let total = current.len();
@@ -1483,6 +1621,25 @@ impl ChatWidget {
             self.on_warning(format!("MCP startup incomplete ({})", parts.join("; ")));
         }
 
+        for server in &ev.ready {
+            self.mcp_server_states
+                .insert(server.clone(), McpStartupStatus::Ready);
+        }
+        for failure in &ev.failed {
+            self.mcp_server_states.insert(
+                failure.server.clone(),
+                McpStartupStatus::Failed {
+                    error: failure.error.clone(),
+                },
+            );
+        }
+        for server in &ev.cancelled {
+            self.mcp_server_states
+                .insert(server.clone(), McpStartupStatus::Cancelled);
+        }
+        self.bottom_pane
+            .set_statusline_mcp_health(self.statusline_mcp_health());
+
         self.mcp_startup_status = None;
         self.update_task_running_state();
         self.maybe_send_next_queued_input();
@@ -1781,6 +1938,7 @@ impl ChatWidget {
             .map(|process| process.command_display.clone())
             .collect();
         self.bottom_pane.set_unified_exec_processes(processes);
+        self.update_statusline_activity();
     }
 
     /// Record recent stdout/stderr lines for the unified exec footer.
@@ -1891,6 +2049,15 @@ impl ChatWidget {
 
     fn on_turn_diff(&mut self, unified_diff: String) {
         debug!("TurnDiffEvent: {unified_diff}");
+        // The turn diff reflects the latest applied state, so reverted hunks cancel out.
+        self.turn_diff_stats = DiffStats::from_unified_diff(&unified_diff);
+        self.turn_diff_is_net = true;
+        self.update_statusline_diff_stats();
+    }
+
+    fn update_statusline_diff_stats(&mut self) {
+        self.bottom_pane
+            .set_statusline_diff_stats(self.session_diff_stats + self.turn_diff_stats);
     }
 
     fn on_deprecation_notice(&mut self, event: DeprecationNoticeEvent) {
@@ -2076,6 +2243,9 @@ impl ChatWidget {
         };
         let is_unified_exec_interaction =
             matches!(source, ExecCommandSource::UnifiedExecInteraction);
+        if !is_unified_exec_interaction {
+            self.turn_last_exit_code = Some(ev.exit_code);
+        }
 
         let needs_new = self
             .active_cell
@@ -2132,6 +2302,13 @@ impl ChatWidget {
         // Otherwise, add a failure block.
         if !event.success {
             self.add_to_history(history_cell::new_patch_apply_failure(event.stderr));
+        } else if !self.turn_diff_is_net {
+            // Fallback until the turn diff arrives, which supersedes these counts.
+            for change in event.changes.values() {
+                let (added, removed) = crate::diff_render::line_counts(change);
+                self.turn_diff_stats = self.turn_diff_stats + DiffStats::new(added, removed);
+            }
+            self.update_statusline_diff_stats();
         }
         // Mark that actual work was done (patch applied)
         self.had_work_activity = true;
@@ -2356,6 +2533,8 @@ impl ChatWidget {
 
         let active_cell = Some(Self::placeholder_session_header_cell(&config));
 
+        let translation_config =
+            crate::translation::TranslationConfig::load_from_path(&config.translation_config_path);
         let mut widget = Self {
             app_event_tx: app_event_tx.clone(),
             frame_requester: frame_requester.clone(),
@@ -2399,12 +2578,19 @@ impl ChatWidget {
             task_complete_pending: false,
             unified_exec_processes: Vec::new(),
             agent_turn_running: false,
+            turn_started_at: None,
+            turn_last_exit_code: None,
+            session_diff_stats: DiffStats::default(),
+            turn_diff_stats: DiffStats::default(),
+            turn_diff_is_net: false,
             mcp_startup_status: None,
+            mcp_server_states: HashMap::new(),
             connectors_cache: ConnectorsCacheState::default(),
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
             full_reasoning_buffer: String::new(),
             agent_reasoning_translation: crate::translation::ReasoningTranslator::default(),
+            translation_rejected_key: None,
             current_status_header: String::from("Working"),
             retry_status_header: None,
             thread_id: None,
@@ -2430,7 +2616,7 @@ impl ChatWidget {
             feedback_audience,
             current_rollout_path: None,
             external_editor_state: ExternalEditorState::Closed,
-            translation_config: crate::translation::TranslationConfig::load(),
+            translation_config,
         };
 
         // 初始化状态栏数据
@@ -2441,6 +2627,9 @@ impl ChatWidget {
         widget
             .agent_reasoning_translation
             .update_config(widget.translation_config.clone());
+        widget
+            .agent_reasoning_translation
+            .set_workspace(widget.config.cwd.clone());
 
         widget.prefetch_rate_limits();
         widget
@@ -2514,6 +2703,8 @@ impl ChatWidget {
 
         let active_cell = Some(Self::placeholder_session_header_cell(&config));
 
+        let translation_config =
+            crate::translation::TranslationConfig::load_from_path(&config.translation_config_path);
         let mut widget = Self {
             app_event_tx: app_event_tx.clone(),
             frame_requester: frame_requester.clone(),
@@ -2556,12 +2747,19 @@ impl ChatWidget {
             task_complete_pending: false,
             unified_exec_processes: Vec::new(),
             agent_turn_running: false,
+            turn_started_at: None,
+            turn_last_exit_code: None,
+            session_diff_stats: DiffStats::default(),
+            turn_diff_stats: DiffStats::default(),
+            turn_diff_is_net: false,
             mcp_startup_status: None,
+            mcp_server_states: HashMap::new(),
             connectors_cache: ConnectorsCacheState::default(),
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
             full_reasoning_buffer: String::new(),
             agent_reasoning_translation: crate::translation::ReasoningTranslator::default(),
+            translation_rejected_key: None,
             current_status_header: String::from("Working"),
             retry_status_header: None,
             thread_id: None,
@@ -2588,13 +2786,16 @@ impl ChatWidget {
             current_rollout_path: None,
             external_editor_state: ExternalEditorState::Closed,
             statusline_git_poller: None,
-            translation_config: crate::translation::TranslationConfig::load(),
+            translation_config,
         };
 
         // Sync translation orchestrator with loaded config
         widget
             .agent_reasoning_translation
             .update_config(widget.translation_config.clone());
+        widget
+            .agent_reasoning_translation
+            .set_workspace(widget.config.cwd.clone());
 
         widget.prefetch_rate_limits();
         widget
@@ -2657,6 +2858,8 @@ impl ChatWidget {
             settings: fallback_default,
         };
 
+        let translation_config =
+            crate::translation::TranslationConfig::load_from_path(&config.translation_config_path);
         let mut widget = Self {
             app_event_tx: app_event_tx.clone(),
             frame_requester: frame_requester.clone(),
@@ -2700,12 +2903,19 @@ impl ChatWidget {
             task_complete_pending: false,
             unified_exec_processes: Vec::new(),
             agent_turn_running: false,
+            turn_started_at: None,
+            turn_last_exit_code: None,
+            session_diff_stats: DiffStats::default(),
+            turn_diff_stats: DiffStats::default(),
+            turn_diff_is_net: false,
             mcp_startup_status: None,
+            mcp_server_states: HashMap::new(),
             connectors_cache: ConnectorsCacheState::default(),
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
             full_reasoning_buffer: String::new(),
             agent_reasoning_translation: crate::translation::ReasoningTranslator::default(),
+            translation_rejected_key: None,
             current_status_header: String::from("Working"),
             retry_status_header: None,
             thread_id: None,
@@ -2731,7 +2941,7 @@ impl ChatWidget {
             feedback_audience,
             current_rollout_path: None,
             external_editor_state: ExternalEditorState::Closed,
-            translation_config: crate::translation::TranslationConfig::load(),
+            translation_config,
         };
 
         // 初始化状态栏数据
@@ -2742,6 +2952,9 @@ impl ChatWidget {
         widget
             .agent_reasoning_translation
             .update_config(widget.translation_config.clone());
+        widget
+            .agent_reasoning_translation
+            .set_workspace(widget.config.cwd.clone());
 
         widget.prefetch_rate_limits();
         widget
@@ -2815,6 +3028,30 @@ impl ChatWidget {
                 }
                 return;
             }
+            KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers,
+                kind: KeyEventKind::Press,
+                ..
+            } if modifiers.contains(KeyModifiers::ALT) && c.eq_ignore_ascii_case(&'y') => {
+                self.copy_last_translation();
+                return;
+            }
+            KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers,
+                kind: KeyEventKind::Press,
+                ..
+            } if modifiers.contains(KeyModifiers::ALT)
+                && c.eq_ignore_ascii_case(&'k')
+                && self.translation_rejected_key.is_some() =>
+            {
+                if let Some(provider) = self.translation_rejected_key.take() {
+                    self.app_event_tx
+                        .send(AppEvent::EditTranslationApiKey(provider));
+                }
+                return;
+            }
             other if other.kind == KeyEventKind::Press => {
                 self.bottom_pane.clear_quit_shortcut_hint();
                 self.quit_shortcut_expires_at = None;
@@ -3101,6 +3338,9 @@ impl ChatWidget {
             SlashCommand::Translate => {
                 self.app_event_tx.send(AppEvent::OpenTranslateConfig);
             }
+            SlashCommand::Translation => {
+                self.add_translation_queue_output();
+            }
             SlashCommand::Quit | SlashCommand::Exit => {
                 self.request_quit_without_confirmation();
             }
@@ -3271,6 +3511,21 @@ impl ChatWidget {
                     self.queue_user_message(user_message);
                 }
             }
+            SlashCommand::Translation if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                match prepared_args.as_str() {
+                    "queue" => self.add_translation_queue_output(),
+                    "flush" => self.flush_translation_queue(),
+                    other => self.add_error_message(format!(
+                        "Unknown /translation subcommand '{other}'. Use 'queue' or 'flush'."
+                    )),
+                }
+                self.bottom_pane.drain_pending_submission_state();
+            }
             SlashCommand::Review if !trimmed.is_empty() => {
                 let Some((prepared_args, _prepared_elements)) =
                     self.bottom_pane.prepare_inline_args_submission(false)
@@ -3346,14 +3601,28 @@ impl ChatWidget {
     }
 
     /// Process translation results and timeouts on each draw tick.
-    /// Returns true if a redraw is needed.
-    pub(crate) fn translation_draw_tick(&mut self) -> bool {
-        let result = self.agent_reasoning_translation.on_draw_tick(
+    /// Follow-up redraws are requested (and coalesced) by the orchestrator.
+    pub(crate) fn translation_draw_tick(&mut self) {
+        self.agent_reasoning_translation.on_draw_tick(
             self.thread_id,
             &self.app_event_tx,
             self.frame_requester.clone(),
         );
-        result.needs_redraw
+        self.update_statusline_activity();
+
+        let barrier_info = self.agent_reasoning_translation.get_pending_barrier_info();
+        if barrier_info.is_some() {
+            // Keep the elapsed time in the statusline ticking while the barrier is pending.
+            self.frame_requester
+                .schedule_frame_in(TRANSLATION_STATUS_REFRESH_INTERVAL);
+        }
+        self.bottom_pane
+            .set_statusline_translation_barrier(barrier_info);
+        let offline = self
+            .agent_reasoning_translation
+            .offline_retry_in()
+            .is_some();
+        self.bottom_pane.set_statusline_translation_offline(offline);
     }
 
     fn flush_active_cell(&mut self) {
@@ -3916,6 +4185,17 @@ impl ChatWidget {
             .unwrap_or(&default_usage);
         let collaboration_mode = self.collaboration_mode_label();
         let reasoning_effort_override = Some(self.effective_reasoning_effort());
+        // Only once translation has been set up: enabled, or a config file saved.
+        let translation = &self.agent_reasoning_translation;
+        let translation = (translation.config().enabled
+            || self.config.translation_config_path.exists())
+        .then(|| {
+            StatusTranslationData::new(
+                translation.config(),
+                translation.configured_max_wait(),
+                &translation.stats(),
+            )
+        });
         self.add_to_history(crate::status::new_status_output(
             &self.config,
             self.auth_manager.as_ref(),
@@ -3930,13 +4210,40 @@ impl ChatWidget {
             self.model_display_name(),
             collaboration_mode,
             reasoning_effort_override,
+            translation.as_ref(),
         ));
+        if let Some(retry_in) = self.agent_reasoning_translation.offline_retry_in() {
+            self.add_info_message(
+                format!(
+                    "Translation offline: the provider is unreachable. Next retry in {}.",
+                    format_duration_short(retry_in.as_secs())
+                ),
+                None,
+            );
+        }
     }
 
     pub(crate) fn add_debug_config_output(&mut self) {
         self.add_to_history(crate::debug_config::new_debug_config_output(&self.config));
     }
 
+    fn add_translation_queue_output(&mut self) {
+        let snapshot = self.agent_reasoning_translation.queue_snapshot();
+        self.add_to_history(history_cell::new_translation_queue_output(&snapshot));
+    }
+
+    /// Cancel pending translations and insert every held-back cell right away.
+    fn flush_translation_queue(&mut self) {
+        let released = self
+            .agent_reasoning_translation
+            .clear_queue(&self.app_event_tx);
+        let noun = if released == 1 { "cell" } else { "cells" };
+        self.add_info_message(
+            format!("Translation queue flushed; released {released} held-back {noun}."),
+            None,
+        );
+    }
+
     pub(crate) fn add_ps_output(&mut self) {
         let processes = self
             .unified_exec_processes
@@ -5401,12 +5708,49 @@ impl ChatWidget {
         self.translation_config.clone()
     }
 
+    /// File the translation config is loaded from and saved to.
+    pub(crate) fn translation_config_path(&self) -> PathBuf {
+        self.config.translation_config_path.clone()
+    }
+
+    /// Persist translation state before the TUI exits.
+    pub(crate) async fn shutdown_translation(&self) {
+        self.agent_reasoning_translation.shutdown().await;
+    }
+
+    /// The translation provider rejected its API key; offer Alt+K to enter a new one.
+    pub(crate) fn on_translation_auth_failed(&mut self, provider: crate::translation::ProviderId) {
+        if self.translation_rejected_key == Some(provider) {
+            return;
+        }
+        self.translation_rejected_key = Some(provider);
+        self.add_info_message(
+            format!(
+                "{} rejected the translation API key.",
+                provider.definition().name
+            ),
+            Some("Press Alt+K to enter a new one.".to_string()),
+        );
+    }
+
+    /// The translation provider reported its remaining rate limit quota.
+    pub(crate) fn on_translation_rate_limit(&mut self, state: crate::translation::RateLimitState) {
+        self.bottom_pane
+            .set_statusline_translation_rate_limit(Some(state));
+        self.request_redraw();
+    }
+
     /// Set the translation config and sync orchestrator state.
     pub(crate) fn set_translation_config(&mut self, config: crate::translation::TranslationConfig) {
         // Sync full config with orchestrator
         self.agent_reasoning_translation
             .update_config(config.clone());
         self.translation_config = config;
+        // Report a key rejected after this change afresh.
+        self.translation_rejected_key = None;
+        // Cleared when the provider changes.
+        self.bottom_pane
+            .set_statusline_translation_rate_limit(self.agent_reasoning_translation.rate_limit());
     }
 
     /// Set the sandbox policy in the widget's config copy.
@@ -5786,6 +6130,26 @@ impl ChatWidget {
         }
     }
 
+    /// Copy the most recent reasoning translation to the system clipboard (Alt+Y).
+    fn copy_last_translation(&mut self) {
+        let Some(text) = self.agent_reasoning_translation.last_translation() else {
+            self.add_info_message("No translation to copy yet.".to_string(), None);
+            return;
+        };
+        match crate::clipboard_copy::copy_text_to_clipboard(text) {
+            Ok(()) => {
+                self.add_info_message(
+                    "Copied the last translation to the clipboard.".to_string(),
+                    None,
+                );
+            }
+            Err(err) => {
+                tracing::warn!("failed to copy translation: {err}");
+                self.add_error_message(format!("Failed to copy translation: {err}"));
+            }
+        }
+    }
+
     pub(crate) fn add_info_message(&mut self, message: String, hint: Option<String>) {
         self.add_to_history(history_cell::new_info_event(message, hint));
         self.request_redraw();
@@ -882,12 +882,19 @@ async fn make_chatwidget_manual(
         task_complete_pending: false,
         unified_exec_processes: Vec::new(),
         agent_turn_running: false,
+        turn_started_at: None,
+        turn_last_exit_code: None,
+        session_diff_stats: DiffStats::default(),
+        turn_diff_stats: DiffStats::default(),
+        turn_diff_is_net: false,
         mcp_startup_status: None,
+        mcp_server_states: HashMap::new(),
         connectors_cache: ConnectorsCacheState::default(),
         interrupts: InterruptManager::new(),
         reasoning_buffer: String::new(),
         full_reasoning_buffer: String::new(),
         agent_reasoning_translation: crate::translation::ReasoningTranslator::default(),
+        translation_rejected_key: None,
         current_status_header: String::from("Working"),
         retry_status_header: None,
         thread_id: None,
@@ -5285,3 +5292,151 @@ async fn review_queues_user_messages_snapshot() {
     .unwrap();
     assert_snapshot!(term.backend().vt100().screen().contents());
 }
+
+/// Translate reasoning on an active thread, answering requests with `backend`.
+fn enable_fake_translation(chat: &mut ChatWidget, backend: crate::translation::FakeBackend) {
+    chat.thread_id = Some(ThreadId::new());
+    chat.agent_reasoning_translation = crate::translation::ReasoningTranslator::from_config(
+        crate::translation::TranslationConfig {
+            enabled: true,
+            ..Default::default()
+        },
+    )
+    .with_fake_backend(backend);
+}
+
+/// Insert a reasoning cell the way `on_agent_reasoning_final` does.
+fn emit_reasoning(chat: &mut ChatWidget, markdown: &str) {
+    chat.agent_reasoning_translation
+        .emit_history_cell_with_translation_hook(
+            &chat.app_event_tx,
+            chat.thread_id,
+            chat.frame_requester.clone(),
+            history_cell::new_reasoning_summary_block(markdown.to_string()),
+        );
+}
+
+/// Run draw ticks until the translation barrier is released.
+async fn settle_translation(chat: &mut ChatWidget) {
+    for _ in 0..100 {
+        tokio::task::yield_now().await;
+        chat.translation_draw_tick();
+        if chat
+            .agent_reasoning_translation
+            .get_pending_barrier_info()
+            .is_none()
+        {
+            return;
+        }
+    }
+    panic!("translation barrier was never released");
+}
+
+/// Render the inserted history into a vt100 screen and return its contents.
+fn render_history(rx: &mut tokio::sync::mpsc::UnboundedReceiver<AppEvent>) -> String {
+    let width: u16 = 80;
+    let height: u16 = 30;
+    let backend = VT100Backend::new(width, height);
+    let mut term = crate::custom_terminal::Terminal::with_options(backend).expect("terminal");
+    term.set_viewport_area(Rect::new(0, height - 1, width, 1));
+    for lines in drain_insert_history(rx) {
+        crate::insert_history::insert_history_lines(&mut term, lines)
+            .expect("Failed to insert history lines in test");
+    }
+    term.backend().vt100().screen().contents()
+}
+
+/// Assert that `needles` appear in `screen` in the given order.
+fn assert_in_order(screen: &str, needles: &[&str]) {
+    let positions: Vec<usize> = needles
+        .iter()
+        .map(|needle| {
+            screen
+                .find(needle)
+                .unwrap_or_else(|| panic!("{needle:?} missing from:\n{screen}"))
+        })
+        .collect();
+    assert!(
+        positions.windows(2).all(|pair| pair[0] < pair[1]),
+        "expected {needles:?} in order, got:\n{screen}"
+    );
+}
+
+#[tokio::test]
+async fn translation_is_inserted_right_after_its_reasoning() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    enable_fake_translation(
+        &mut chat,
+        crate::translation::FakeBackend::translating("正在检查构建。"),
+    );
+
+    emit_reasoning(&mut chat, "**Thinking**\n\nChecking the build.");
+    chat.add_to_history(history_cell::new_info_event("cell A".to_string(), None));
+    chat.add_to_history(history_cell::new_info_event("cell B".to_string(), None));
+    // Later cells wait for the translation.
+    let barrier = chat
+        .agent_reasoning_translation
+        .get_pending_barrier_info()
+        .expect("translation barrier");
+    assert_eq!(barrier.deferred_count, 2);
+
+    settle_translation(&mut chat).await;
+
+    assert_in_order(
+        &render_history(&mut rx),
+        &["Checking the build.", "正在检查构建。", "cell A", "cell B"],
+    );
+}
+
+#[tokio::test]
+async fn reasoning_cells_keep_their_order_across_translations() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    enable_fake_translation(
+        &mut chat,
+        crate::translation::FakeBackend::translating("译文"),
+    );
+
+    emit_reasoning(&mut chat, "**Thinking**\n\nChecking the build.");
+    chat.add_to_history(history_cell::new_info_event("cell A".to_string(), None));
+    emit_reasoning(&mut chat, "**Planning**\n\nFixing the lockfile.");
+    chat.add_to_history(history_cell::new_info_event("cell B".to_string(), None));
+
+    // Releasing the first barrier starts the second block's; settling waits for both.
+    settle_translation(&mut chat).await;
+
+    let screen = render_history(&mut rx);
+    assert_in_order(
+        &screen,
+        &[
+            "Checking the build.",
+            "译文",
+            "cell A",
+            "Fixing the lockfile.",
+            "cell B",
+        ],
+    );
+    assert_eq!(screen.matches("译文").count(), 2, "{screen}");
+}
+
+#[tokio::test]
+async fn timed_out_translation_releases_later_cells_in_order() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    enable_fake_translation(&mut chat, crate::translation::FakeBackend::unresponsive());
+
+    emit_reasoning(&mut chat, "**Thinking**\n\nChecking the build.");
+    chat.add_to_history(history_cell::new_info_event("cell A".to_string(), None));
+    chat.add_to_history(history_cell::new_info_event("cell B".to_string(), None));
+
+    chat.agent_reasoning_translation.expire_barrier();
+    settle_translation(&mut chat).await;
+
+    assert_in_order(
+        &render_history(&mut rx),
+        &[
+            "Checking the build.",
+            "Translation timeout",
+            "cell A",
+            "cell B",
+        ],
+    );
+}
@@ -0,0 +1,27 @@
+//! Copy text to the system clipboard.
+
+/// On Linux the copied text is served by the process that set it and disappears when that
+/// clipboard handle is dropped, so one handle is kept for the lifetime of the TUI.
+#[cfg(not(target_os = "android"))]
+static CLIPBOARD: std::sync::Mutex<Option<arboard::Clipboard>> = std::sync::Mutex::new(None);
+
+/// Put `text` on the system clipboard.
+#[cfg(not(target_os = "android"))]
+pub fn copy_text_to_clipboard(text: &str) -> Result<(), String> {
+    let mut clipboard = CLIPBOARD
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let cb = match clipboard.take() {
+        Some(cb) => cb,
+        None => arboard::Clipboard::new().map_err(|e| format!("clipboard unavailable: {e}"))?,
+    };
+    let cb = clipboard.insert(cb);
+    cb.set_text(text.to_string())
+        .map_err(|e| format!("could not set clipboard text: {e}"))
+}
+
+/// Android/Termux does not support arboard; return a clear error.
+#[cfg(target_os = "android")]
+pub fn copy_text_to_clipboard(_text: &str) -> Result<(), String> {
+    Err("clipboard copy is unsupported on Android".to_string())
+}
@@ -0,0 +1,152 @@
+//! Fallback styling for terminals that should not get colors.
+//!
+//! `NO_COLOR` (see <https://no-color.org>), `TERM=dumb` and terminals that report no color
+//! support get [`ColorMode::Monochrome`]. Screens that rely on color to mark selections and
+//! errors render as usual and then pass their area through [`strip_colors`].
+
+use std::env;
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum ColorMode {
+    #[default]
+    Color,
+    Monochrome,
+}
+
+impl ColorMode {
+    /// Color mode for stdout, from the environment and the terminal's reported color depth.
+    pub(crate) fn detect() -> Self {
+        let terminal_has_colors =
+            supports_color::on_cached(supports_color::Stream::Stdout).is_some();
+        detect_with(|name| env::var(name).ok(), terminal_has_colors)
+    }
+}
+
+fn detect_with(env: impl Fn(&str) -> Option<String>, terminal_has_colors: bool) -> ColorMode {
+    // An empty NO_COLOR does not count, per the convention.
+    let no_color = env("NO_COLOR").is_some_and(|value| !value.is_empty());
+    let dumb = env("TERM").as_deref() == Some("dumb");
+    if no_color || dumb || !terminal_has_colors {
+        ColorMode::Monochrome
+    } else {
+        ColorMode::Color
+    }
+}
+
+/// Replace the colors of every cell in `area` with text attributes: red (errors) becomes
+/// bold and underlined, other accent colors bold, dark gray dim, and a background reverse
+/// video. Green, yellow and grays become plain text.
+pub(crate) fn strip_colors(buf: &mut Buffer, area: Rect) {
+    let area = area.intersection(buf.area);
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &mut buf[(x, y)];
+            let mut modifier = fallback_modifier(cell.fg);
+            if cell.bg != Color::Reset {
+                modifier |= Modifier::REVERSED;
+            }
+            cell.set_style(
+                Style::default()
+                    .fg(Color::Reset)
+                    .bg(Color::Reset)
+                    .add_modifier(modifier),
+            );
+        }
+    }
+}
+
+fn fallback_modifier(color: Color) -> Modifier {
+    match color {
+        Color::Red | Color::LightRed => Modifier::BOLD | Modifier::UNDERLINED,
+        Color::Cyan
+        | Color::LightCyan
+        | Color::Blue
+        | Color::LightBlue
+        | Color::Magenta
+        | Color::LightMagenta => Modifier::BOLD,
+        Color::DarkGray => Modifier::DIM,
+        _ => Modifier::empty(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use ratatui::style::Stylize;
+    use ratatui::text::Line;
+
+    fn env_of(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: Vec<(String, String)> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        move |name| {
+            vars.iter()
+                .find(|(var, _)| var == name)
+                .map(|(_, value)| value.clone())
+        }
+    }
+
+    #[test]
+    fn no_color_and_dumb_terminals_are_monochrome() {
+        assert_eq!(detect_with(env_of(&[]), true), ColorMode::Color);
+        assert_eq!(
+            detect_with(env_of(&[("NO_COLOR", "1")]), true),
+            ColorMode::Monochrome
+        );
+        assert_eq!(
+            detect_with(env_of(&[("NO_COLOR", "")]), true),
+            ColorMode::Color
+        );
+        assert_eq!(
+            detect_with(env_of(&[("TERM", "dumb")]), true),
+            ColorMode::Monochrome
+        );
+        assert_eq!(
+            detect_with(env_of(&[("TERM", "xterm-256color")]), false),
+            ColorMode::Monochrome
+        );
+    }
+
+    #[test]
+    fn colors_become_text_attributes() {
+        let area = Rect::new(0, 0, 4, 1);
+        let mut buf = Buffer::empty(area);
+        let line = Line::from(vec![
+            "a".red(),
+            "b".cyan().bold(),
+            "c".green(),
+            "d".on_blue(),
+        ]);
+        buf.set_line(0, 0, &line, 4);
+
+        strip_colors(&mut buf, area);
+
+        let styles: Vec<(Color, Color, Modifier)> = (0..4)
+            .map(|x| {
+                let cell = &buf[(x, 0)];
+                (cell.fg, cell.bg, cell.modifier)
+            })
+            .collect();
+        assert_eq!(
+            styles,
+            vec![
+                (
+                    Color::Reset,
+                    Color::Reset,
+                    Modifier::BOLD | Modifier::UNDERLINED
+                ),
+                (Color::Reset, Color::Reset, Modifier::BOLD),
+                (Color::Reset, Color::Reset, Modifier::empty()),
+                (Color::Reset, Color::Reset, Modifier::REVERSED),
+            ]
+        );
+    }
+}
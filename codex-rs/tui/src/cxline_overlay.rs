@@ -85,17 +85,12 @@ impl CxlineOverlay {
     pub fn new(config: CxLineConfig) -> Self {
         let original_theme = config.theme.clone();
         let original_config = config.clone();
+        let segment_order = config.segment_order();
         Self {
             config,
             original_config,
             original_theme,
-            segment_order: vec![
-                SegmentId::Model,
-                SegmentId::Directory,
-                SegmentId::Git,
-                SegmentId::Context,
-                SegmentId::Usage,
-            ],
+            segment_order,
             selected_segment: 0,
             selected_panel: Panel::SegmentList,
             selected_field: FieldSelection::Enabled,
@@ -445,6 +440,13 @@ impl CxlineOverlay {
             SegmentId::Git => "Git",
             SegmentId::Context => "Context Window",
             SegmentId::Usage => "Usage",
+            SegmentId::Activity => "Activity",
+            SegmentId::TranslationStatus => "Translation Status",
+            SegmentId::Host => "Host",
+            SegmentId::LastTurn => "Last Turn",
+            SegmentId::Profile => "Profile",
+            SegmentId::DiffStats => "Diff Stats",
+            SegmentId::Mcp => "MCP",
         }
     }
 
@@ -710,17 +712,35 @@ impl CxlineOverlay {
     }
 
     fn render_preview(&self, area: Rect, buf: &mut Buffer) {
+        use crate::statusline::DiffStats;
+        use crate::statusline::HostInfo;
+        use crate::statusline::HostOptions;
+        use crate::statusline::McpHealth;
+        use crate::statusline::ModelDisplay;
+        use crate::statusline::collect_segment;
         use crate::statusline::renderer::StatusLineRenderer;
-        use crate::statusline::segment::Segment;
-        use crate::statusline::segments::*;
         use codex_protocol::openai_models::ReasoningEffort;
 
+        let host = HostInfo {
+            user: Some("user".to_string()),
+            hostname: Some("devbox".to_string()),
+            ssh: true,
+        };
         let ctx =
             StatusLineContext::new("gpt-5.2-codex", std::path::Path::new("/home/user/Cxline"))
                 .with_reasoning_effort(Some(ReasoningEffort::Medium))
+                .with_model_display(ModelDisplay::from_config(&self.config))
                 .with_context(Some(50000), Some(128000))
                 .with_rate_limit(Some(25.0), Some(15.0), Some("1-28-14".to_string()))
-                .with_git_preview("main", "✓", 0, 0);
+                .with_activity(true, 2, 0)
+                .with_git_preview("main", "✓", 0, 0)
+                .with_diff_stats(DiffStats::new(120, 34))
+                .with_mcp_health(McpHealth {
+                    connected: 3,
+                    starting: 0,
+                    total: 4,
+                })
+                .with_host(&host, HostOptions::from_config(&self.config));
 
         // 按 segment_order 顺序构建预览
         let mut renderer = StatusLineRenderer::new(&self.config);
@@ -730,13 +750,7 @@ impl CxlineOverlay {
                 continue;
             }
 
-            let data = match segment_id {
-                SegmentId::Model => ModelSegment.collect(&ctx),
-                SegmentId::Directory => DirectorySegment.collect(&ctx),
-                SegmentId::Git => GitSegment.collect(&ctx),
-                SegmentId::Context => ContextSegment.collect(&ctx),
-                SegmentId::Usage => UsageSegment.collect(&ctx),
-            };
+            let data = collect_segment(segment_id, &ctx);
 
             if let Some(data) = data {
                 renderer.add_segment(segment_id, data);
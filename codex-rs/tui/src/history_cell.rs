@@ -72,6 +72,8 @@ use std::collections::HashMap;
 use std::io::Cursor;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::time::Duration;
 use std::time::Instant;
 use tracing::error;
@@ -132,6 +134,40 @@ pub(crate) trait HistoryCell: std::fmt::Debug + Send + Sync + Any {
     fn transcript_animation_tick(&self) -> Option<u64> {
         None
     }
+
+    /// The text this cell shows for reasoning translation, if it is a reasoning or
+    /// translation cell.
+    #[cfg(test)]
+    fn translation_content(&self) -> Option<&dyn TranslationContent> {
+        None
+    }
+}
+
+/// Plain text of the cells involved in reasoning translation, read back without rendering.
+///
+/// Tests assert on this instead of rendered lines, so they don't break when styling,
+/// prefixes or wrapping change.
+#[cfg(test)]
+pub(crate) trait TranslationContent {
+    /// Title shown with the text (e.g. a translated `**Thinking**` header).
+    fn title(&self) -> Option<&str> {
+        None
+    }
+
+    /// Reasoning text in the source language.
+    fn original_text(&self) -> Option<&str> {
+        None
+    }
+
+    /// Translation of the reasoning.
+    fn translated_text(&self) -> Option<&str> {
+        None
+    }
+
+    /// Why the translation failed, for error cells.
+    fn error_text(&self) -> Option<&str> {
+        None
+    }
 }
 
 impl Renderable for Box<dyn HistoryCell> {
@@ -282,6 +318,8 @@ pub(crate) struct ReasoningSummaryCell {
     _header: String,
     content: String,
     transcript_only: bool,
+    /// Translation rendered underneath the summary (`inline_annotation` display mode).
+    translation: Option<String>,
 }
 
 impl ReasoningSummaryCell {
@@ -290,9 +328,21 @@ impl ReasoningSummaryCell {
             _header: header,
             content,
             transcript_only,
+            translation: None,
         }
     }
 
+    /// Attach a translation to show as an annotation below the summary.
+    pub(crate) fn set_translation(&mut self, translation: String) {
+        self.translation = Some(translation);
+    }
+
+    /// Keep the summary out of the main view, showing it only in the transcript (`replace`
+    /// display mode).
+    pub(crate) fn set_transcript_only(&mut self) {
+        self.transcript_only = true;
+    }
+
     /// Returns the full markdown for translation, if this cell should be translated.
     /// Returns header + content combined for the translator to process.
     pub(crate) fn full_markdown_for_translation(&self) -> Option<String> {
@@ -323,12 +373,35 @@ impl ReasoningSummaryCell {
             })
             .collect::<Vec<_>>();
 
-        word_wrap_lines(
+        let mut out = word_wrap_lines(
             &summary_lines,
             RtOptions::new(width as usize)
                 .initial_indent("• ".dim().into())
                 .subsequent_indent("  ".into()),
-        )
+        );
+
+        if let Some(translation) = &self.translation {
+            let mut translation_lines: Vec<Line<'static>> = Vec::new();
+            append_markdown(
+                translation,
+                Some((width as usize).saturating_sub(4).max(1)),
+                &mut translation_lines,
+            );
+            let translation_style = Style::default().dim();
+            let translation_lines = translation_lines
+                .into_iter()
+                .map(|mut line| {
+                    line.spans = line
+                        .spans
+                        .into_iter()
+                        .map(|span| span.patch_style(translation_style))
+                        .collect();
+                    line
+                })
+                .collect::<Vec<_>>();
+            out.extend(prefix_lines(translation_lines, "  └ ".dim(), "    ".into()));
+        }
+        out
     }
 }
 
@@ -356,6 +429,22 @@ impl HistoryCell for ReasoningSummaryCell {
     fn desired_transcript_height(&self, width: u16) -> u16 {
         self.lines(width).len() as u16
     }
+
+    #[cfg(test)]
+    fn translation_content(&self) -> Option<&dyn TranslationContent> {
+        Some(self)
+    }
+}
+
+#[cfg(test)]
+impl TranslationContent for ReasoningSummaryCell {
+    fn original_text(&self) -> Option<&str> {
+        Some(self.content.trim())
+    }
+
+    fn translated_text(&self) -> Option<&str> {
+        self.translation.as_deref()
+    }
 }
 
 #[derive(Debug)]
@@ -409,19 +498,22 @@ impl HistoryCell for PlainHistoryCell {
     }
 }
 
-#[cfg_attr(debug_assertions, allow(dead_code))]
 #[derive(Debug)]
 pub(crate) struct UpdateAvailableHistoryCell {
     latest_version: String,
-    update_actions: Vec<UpdateAction>,
+    /// Commands that update codex. A self-replace only runs from the startup update prompt,
+    /// so it is not listed.
+    update_commands: Vec<String>,
 }
 
-#[cfg_attr(debug_assertions, allow(dead_code))]
 impl UpdateAvailableHistoryCell {
     pub(crate) fn new(latest_version: String, update_actions: Vec<UpdateAction>) -> Self {
         Self {
             latest_version,
-            update_actions,
+            update_commands: update_actions
+                .into_iter()
+                .filter_map(UpdateAction::command_str)
+                .collect(),
         }
     }
 }
@@ -430,29 +522,21 @@ impl HistoryCell for UpdateAvailableHistoryCell {
     fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
         use ratatui_macros::line;
         use ratatui_macros::text;
-        let update_instruction = match self.update_actions.as_slice() {
+        let update_instruction = match self.update_commands.as_slice() {
             [] => line![
                 "See ",
                 "https://github.com/wenwen12345/codex".cyan().underlined(),
                 " for installation options."
             ],
-            [action] => line!["Run ", action.command_str().cyan(), " to update."],
+            [command] => line!["Run ", command.clone().cyan(), " to update."],
             [first, second] => line![
                 "Run ",
-                first.command_str().cyan(),
+                first.clone().cyan(),
                 " or ",
-                second.command_str().cyan(),
+                second.clone().cyan(),
                 " to update."
             ],
-            actions => {
-                let joined = actions
-                    .iter()
-                    .copied()
-                    .map(UpdateAction::command_str)
-                    .collect::<Vec<_>>()
-                    .join(" or ");
-                line!["Run ", joined.cyan(), " to update."]
-            }
+            commands => line!["Run ", commands.join(" or ").cyan(), " to update."],
         };
 
         let content = text![
@@ -719,6 +803,49 @@ pub(crate) fn new_unified_exec_processes_output(
     CompositeHistoryCell::new(vec![Box::new(command), Box::new(summary)])
 }
 
+/// Output of `/translation queue`: reasoning blocks waiting on translation, with their ages.
+pub(crate) fn new_translation_queue_output(
+    snapshot: &crate::translation::QueueSnapshot,
+) -> CompositeHistoryCell {
+    let command = PlainHistoryCell::new(vec!["/translation queue".magenta().into()]);
+    let mut lines: Vec<Line<'static>> = vec![vec!["Translation queue".bold()].into(), "".into()];
+    if snapshot.blocks.is_empty() {
+        lines.push("  • No reasoning blocks waiting.".italic().into());
+    }
+    for block in &snapshot.blocks {
+        let state = if block.translating {
+            "translating"
+        } else {
+            "queued"
+        };
+        lines.push(Line::from(vec![
+            "  • ".dim(),
+            block
+                .title
+                .clone()
+                .unwrap_or_else(|| "Reasoning".to_string())
+                .into(),
+            format!(" {state} · {:.1}s", block.age.as_secs_f64()).dim(),
+        ]));
+    }
+    if snapshot.other_cells > 0 {
+        let noun = if snapshot.other_cells == 1 {
+            "cell"
+        } else {
+            "cells"
+        };
+        lines.push(
+            format!("  {} other {noun} held back", snapshot.other_cells)
+                .dim()
+                .into(),
+        );
+    }
+    CompositeHistoryCell::new(vec![
+        Box::new(command),
+        Box::new(PlainHistoryCell::new(lines)),
+    ])
+}
+
 fn truncate_exec_snippet(full_cmd: &str) -> String {
     let mut snippet = match full_cmd.split_once('\n') {
         Some((first, _)) => format!("{first} ..."),
@@ -2148,11 +2275,18 @@ pub(crate) fn new_reasoning_summary_block(full_reasoning_buffer: String) -> Box<
 }
 
 /// Create a translation result cell for reasoning content.
+///
+/// When `max_display_lines` is set, longer translations are collapsed behind an
+/// expand hint; the full text is kept in the cell.
 pub(crate) fn new_agent_reasoning_translation_block(
     title: Option<String>,
     translated: String,
+    max_display_lines: Option<usize>,
 ) -> Box<dyn HistoryCell> {
-    Box::new(AgentReasoningTranslationCell::new(title, translated, false))
+    Box::new(
+        AgentReasoningTranslationCell::new(title, translated, false)
+            .with_max_display_lines(max_display_lines),
+    )
 }
 
 /// Create a translation error cell for reasoning content.
@@ -2169,6 +2303,10 @@ pub(crate) struct AgentReasoningTranslationCell {
     title: Option<String>,
     content: String,
     is_error: bool,
+    /// Collapse successful translations longer than this many rendered lines.
+    max_display_lines: Option<usize>,
+    /// Whether a collapsed translation has been expanded (e.g. via `e` in the transcript).
+    expanded: AtomicBool,
 }
 
 impl AgentReasoningTranslationCell {
@@ -2177,9 +2315,62 @@ impl AgentReasoningTranslationCell {
             title,
             content,
             is_error,
+            max_display_lines: None,
+            expanded: AtomicBool::new(false),
         }
     }
 
+    pub(crate) fn with_max_display_lines(mut self, max_display_lines: Option<usize>) -> Self {
+        self.max_display_lines = max_display_lines;
+        self
+    }
+
+    /// Expand (or re-collapse) a translation that exceeds `max_display_lines`.
+    pub(crate) fn set_expanded(&self, expanded: bool) {
+        self.expanded.store(expanded, Ordering::Relaxed);
+    }
+
+    pub(crate) fn is_expanded(&self) -> bool {
+        self.expanded.load(Ordering::Relaxed)
+    }
+
+    /// Whether `max_display_lines` applies to this cell, i.e. expanding it can change what is
+    /// shown (for long enough content).
+    pub(crate) fn is_collapsible(&self) -> bool {
+        !self.is_error && self.max_display_lines.is_some()
+    }
+
+    /// Collapse `lines` to `max_display_lines`, appending an expand hint wrapped to `width`.
+    fn collapse(
+        &self,
+        mut lines: Vec<Line<'static>>,
+        width: u16,
+        expand_hint: &'static str,
+    ) -> Vec<Line<'static>> {
+        let Some(max) = self.max_display_lines else {
+            return lines;
+        };
+        if self.is_error || self.is_expanded() || lines.len() <= max {
+            return lines;
+        }
+        let hidden = lines.len() - max;
+        lines.truncate(max);
+        let hint = Line::from(vec![
+            format!("[+{hidden} lines] ").dim(),
+            expand_hint.dim().italic(),
+        ]);
+        // Scrollback rows can't be rewrapped later, so the hint must fit the width it was
+        // measured at.
+        let wrapped = word_wrap_line(
+            &hint,
+            RtOptions::new(usize::from(width).max(5))
+                .initial_indent(Line::from("    "))
+                .subsequent_indent(Line::from("    ")),
+        );
+        push_owned_lines(&wrapped, &mut lines);
+        lines
+    }
+
     fn lines(&self, width: u16) -> Vec<Line<'static>> {
         let mut md_lines: Vec<Line<'static>> = Vec::new();
         append_markdown(
@@ -2217,26 +2408,54 @@ impl AgentReasoningTranslationCell {
         }
 
         // Success: output translation content directly without extra title line,
-        // to avoid having one more line than the original content.
+        // to avoid having one more line than the original content. A translated title
+        // (`header_translation`) is shown on the first line when present.
+        if let Some(title) = &self.title {
+            let mut out = vec![Line::from(vec!["  └ ".dim(), title.clone().bold().dim()])];
+            out.extend(prefix_lines(styled_md_lines, "    ".into(), "    ".into()));
+            return out;
+        }
         prefix_lines(styled_md_lines, "  └ ".dim(), "    ".into())
     }
 }
 
 impl HistoryCell for AgentReasoningTranslationCell {
     fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
-        self.lines(width)
+        // Scrollback can't be re-rendered, so point users at the transcript view.
+        self.collapse(self.lines(width), width, "press ctrl+t then e to expand")
     }
 
     fn desired_height(&self, width: u16) -> u16 {
-        self.lines(width).len() as u16
+        self.display_lines(width).len() as u16
     }
 
     fn transcript_lines(&self, width: u16) -> Vec<Line<'static>> {
-        self.lines(width)
+        self.collapse(self.lines(width), width, "press e to expand")
     }
 
     fn desired_transcript_height(&self, width: u16) -> u16 {
-        self.lines(width).len() as u16
+        self.transcript_lines(width).len() as u16
+    }
+
+    #[cfg(test)]
+    fn translation_content(&self) -> Option<&dyn TranslationContent> {
+        Some(self)
+    }
+}
+
+/// The full text, also when the cell is collapsed to `max_display_lines`.
+#[cfg(test)]
+impl TranslationContent for AgentReasoningTranslationCell {
+    fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    fn translated_text(&self) -> Option<&str> {
+        (!self.is_error).then_some(self.content.as_str())
+    }
+
+    fn error_text(&self) -> Option<&str> {
+        self.is_error.then_some(self.content.as_str())
     }
 }
 
@@ -3593,4 +3812,190 @@ mod tests {
             ]
         );
     }
+
+    fn long_translation() -> String {
+        (1..=6)
+            .map(|i| format!("第{i}段翻译内容。"))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    #[test]
+    fn translation_block_content_reads_back_without_rendering() {
+        let cell = new_agent_reasoning_translation_block(
+            Some("思考".to_string()),
+            long_translation(),
+            Some(2),
+        );
+        let content = cell.translation_content().unwrap();
+        assert_eq!(content.title(), Some("思考"));
+        // Collapsing only affects rendering.
+        assert_eq!(content.translated_text(), Some(long_translation().as_str()));
+        assert_eq!(content.original_text(), None);
+        assert_eq!(content.error_text(), None);
+
+        let untitled = new_agent_reasoning_translation_block(None, "翻译".to_string(), None);
+        let content = untitled.translation_content().unwrap();
+        assert_eq!(content.title(), None);
+        assert_eq!(content.translated_text(), Some("翻译"));
+    }
+
+    #[test]
+    fn translation_error_content_reads_back_as_an_error() {
+        let cell = new_agent_reasoning_translation_error_block(
+            Some("Thinking".to_string()),
+            "Translation timeout (5000ms)".to_string(),
+        );
+        let content = cell.translation_content().unwrap();
+        assert_eq!(content.title(), Some("Thinking"));
+        assert_eq!(content.translated_text(), None);
+        assert_eq!(content.error_text(), Some("Translation timeout (5000ms)"));
+    }
+
+    #[test]
+    fn reasoning_content_pairs_original_and_annotation() {
+        let mut cell = ReasoningSummaryCell::new(
+            "**Thinking**".to_string(),
+            "\n\nChecking the build.".to_string(),
+            false,
+        );
+        assert_eq!(
+            cell.translation_content().unwrap().original_text(),
+            Some("Checking the build.")
+        );
+        assert_eq!(cell.translation_content().unwrap().translated_text(), None);
+
+        cell.set_translation("正在检查构建。".to_string());
+        let content = cell.translation_content().unwrap();
+        assert_eq!(content.original_text(), Some("Checking the build."));
+        assert_eq!(content.translated_text(), Some("正在检查构建。"));
+        assert!(
+            new_info_event("not reasoning".to_string(), None)
+                .translation_content()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn translation_cell_collapses_beyond_max_display_lines() {
+        let full = render_transcript(&AgentReasoningTranslationCell::new(
+            None,
+            long_translation(),
+            false,
+        ));
+        assert!(full.len() > 3);
+
+        // At the threshold nothing is collapsed.
+        let at_threshold = AgentReasoningTranslationCell::new(None, long_translation(), false)
+            .with_max_display_lines(Some(full.len()));
+        assert_eq!(render_transcript(&at_threshold), full);
+
+        // Beyond the threshold the tail is replaced by an expand hint.
+        let collapsed = AgentReasoningTranslationCell::new(None, long_translation(), false)
+            .with_max_display_lines(Some(3));
+        let rendered = render_transcript(&collapsed);
+        assert_eq!(rendered.len(), 4);
+        assert_eq!(rendered[..3], full[..3]);
+        let hidden = full.len() - 3;
+        assert_eq!(
+            rendered[3],
+            format!("    [+{hidden} lines] press e to expand")
+        );
+        assert_eq!(
+            collapsed.desired_transcript_height(u16::MAX),
+            rendered.len() as u16
+        );
+
+        let display = render_lines(&collapsed.display_lines(u16::MAX));
+        assert_eq!(
+            display.last().map(String::as_str),
+            Some(format!("    [+{hidden} lines] press ctrl+t then e to expand").as_str())
+        );
+    }
+
+    #[test]
+    fn translation_cell_expansion_reveals_full_content() {
+        let full = render_transcript(&AgentReasoningTranslationCell::new(
+            None,
+            long_translation(),
+            false,
+        ));
+        let cell = AgentReasoningTranslationCell::new(None, long_translation(), false)
+            .with_max_display_lines(Some(2));
+        assert!(!cell.is_expanded());
+
+        cell.set_expanded(true);
+        assert!(cell.is_expanded());
+        assert_eq!(render_transcript(&cell), full);
+        assert!(full.iter().any(|line| line.contains("第6段翻译内容")));
+    }
+
+    #[test]
+    fn translation_cell_height_matches_rendering_at_each_width() {
+        for width in [12_u16, 24, 40, 80] {
+            let full = AgentReasoningTranslationCell::new(None, long_translation(), false)
+                .display_lines(width)
+                .len();
+            let cell = AgentReasoningTranslationCell::new(None, long_translation(), false)
+                .with_max_display_lines(Some(3));
+
+            for (lines, height) in [
+                (cell.display_lines(width), cell.desired_height(width)),
+                (
+                    cell.transcript_lines(width),
+                    cell.desired_transcript_height(width),
+                ),
+            ] {
+                assert_eq!(usize::from(height), lines.len(), "width {width}");
+                assert!(
+                    lines.iter().all(|line| line.width() <= usize::from(width)),
+                    "line wider than {width}: {:?}",
+                    render_lines(&lines)
+                );
+                // Three content rows, then the hint (wrapped on narrow terminals).
+                let hint_rows = &render_lines(&lines)[3..];
+                assert!(!hint_rows.is_empty(), "width {width}");
+                assert!(hint_rows[0].starts_with("    [+"), "width {width}");
+                assert!(
+                    hint_rows
+                        .last()
+                        .is_some_and(|row| row.trim_end().ends_with("expand")),
+                    "width {width}"
+                );
+            }
+
+            cell.set_expanded(true);
+            assert_eq!(
+                usize::from(cell.desired_height(width)),
+                full,
+                "width {width}"
+            );
+        }
+    }
+
+    #[test]
+    fn translation_cell_is_collapsible_only_with_a_limit() {
+        assert!(
+            !AgentReasoningTranslationCell::new(None, long_translation(), false).is_collapsible()
+        );
+        assert!(
+            AgentReasoningTranslationCell::new(None, long_translation(), false)
+                .with_max_display_lines(Some(3))
+                .is_collapsible()
+        );
+        assert!(
+            !AgentReasoningTranslationCell::new(None, long_translation(), true)
+                .with_max_display_lines(Some(3))
+                .is_collapsible()
+        );
+    }
+
+    #[test]
+    fn translation_error_cell_is_never_collapsed() {
+        let cell = AgentReasoningTranslationCell::new(None, long_translation(), true)
+            .with_max_display_lines(Some(1));
+        let rendered = render_transcript(&cell);
+        assert!(rendered.len() > 2);
+        assert!(!rendered.iter().any(|line| line.contains("to expand")));
+    }
 }
@@ -0,0 +1,84 @@
+//! OSC 8 hyperlinks for terminals that render them.
+
+use std::env;
+
+/// Whether the terminal is known to turn OSC 8 sequences into clickable links.
+pub(crate) fn supports_hyperlinks() -> bool {
+    supports_hyperlinks_with(|name| env::var(name).ok())
+}
+
+fn supports_hyperlinks_with(env: impl Fn(&str) -> Option<String>) -> bool {
+    if matches!(
+        env("TERM_PROGRAM").as_deref(),
+        Some("iTerm.app" | "WezTerm" | "kitty" | "ghostty")
+    ) {
+        return true;
+    }
+    // iTerm and kitty keep their session variables under tmux/ssh, where TERM_PROGRAM is
+    // rewritten.
+    if env("ITERM_SESSION_ID").is_some() || env("KITTY_WINDOW_ID").is_some() {
+        return true;
+    }
+    matches!(
+        env("TERM").as_deref(),
+        Some("xterm-kitty" | "wezterm" | "wezterm-mux")
+    ) || env("COLORTERM").as_deref() == Some("kitty")
+}
+
+/// `text` wrapped in an OSC 8 link to `url`. Only the text takes up columns on screen.
+pub(crate) fn osc8(url: &str, text: &str) -> String {
+    format!("\u{1b}]8;;{url}\u{7}{text}\u{1b}]8;;\u{7}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn env_of(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: Vec<(String, String)> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        move |name| {
+            vars.iter()
+                .find(|(var, _)| var == name)
+                .map(|(_, value)| value.clone())
+        }
+    }
+
+    #[test]
+    fn known_terminals_support_hyperlinks() {
+        assert!(supports_hyperlinks_with(env_of(&[(
+            "TERM_PROGRAM",
+            "iTerm.app"
+        )])));
+        assert!(supports_hyperlinks_with(env_of(&[(
+            "TERM_PROGRAM",
+            "WezTerm"
+        )])));
+        assert!(supports_hyperlinks_with(env_of(&[("TERM", "xterm-kitty")])));
+        assert!(supports_hyperlinks_with(env_of(&[
+            ("TERM_PROGRAM", "tmux"),
+            ("ITERM_SESSION_ID", "w0t0p0:1234"),
+        ])));
+    }
+
+    #[test]
+    fn other_terminals_get_plain_text() {
+        assert!(!supports_hyperlinks_with(env_of(&[])));
+        assert!(!supports_hyperlinks_with(env_of(&[
+            ("TERM_PROGRAM", "Apple_Terminal"),
+            ("TERM", "xterm-256color"),
+            ("COLORTERM", "truecolor"),
+        ])));
+    }
+
+    #[test]
+    fn osc8_wraps_the_text() {
+        assert_eq!(
+            osc8("https://openai.com/pricing", "pricing"),
+            "\u{1b}]8;;https://openai.com/pricing\u{7}pricing\u{1b}]8;;\u{7}"
+        );
+    }
+}
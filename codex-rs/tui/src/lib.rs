@@ -49,6 +49,8 @@ use std::path::PathBuf;
 use tracing::error;
 use tracing_appender::non_blocking;
 use tracing_subscriber::EnvFilter;
+use tracing_subscriber::Layer;
+use tracing_subscriber::Registry;
 use tracing_subscriber::prelude::*;
 use uuid::Uuid;
 
@@ -61,10 +63,12 @@ mod ascii_animation;
 mod bottom_pane;
 mod chatwidget;
 mod cli;
+mod clipboard_copy;
 mod clipboard_paste;
 mod collab;
 mod collaboration_modes;
 mod color;
+mod color_mode;
 pub mod custom_terminal;
 mod cwd_prompt;
 mod cxline_overlay;
@@ -77,6 +81,7 @@ mod file_search;
 mod frames;
 mod get_git_diff;
 mod history_cell;
+mod hyperlink;
 pub mod insert_history;
 mod key_hint;
 pub mod live_wrap;
@@ -92,6 +97,7 @@ pub mod public_widgets;
 mod render;
 mod resume_picker;
 mod selection_list;
+mod self_update;
 mod session_log;
 mod shimmer;
 mod skills_helpers;
@@ -331,7 +337,7 @@ pub async fn run_main(
         })
     };
 
-    let file_layer = tracing_subscriber::fmt::layer()
+    let file_fmt_layer = tracing_subscriber::fmt::layer()
         .with_writer(non_blocking)
         // `with_target(true)` is the default, but we previously disabled it for file output.
         // Keep it enabled so we can selectively enable targets via `RUST_LOG=...` and then
@@ -341,11 +347,24 @@ pub async fn run_main(
         .with_span_events(
             tracing_subscriber::fmt::format::FmtSpan::NEW
                 | tracing_subscriber::fmt::format::FmtSpan::CLOSE,
-        )
-        .with_filter(env_filter());
+        );
+    // Translation requests log URLs and headers at debug level; redact keys unless opted out.
+    let mask_in_logs =
+        translation::TranslationConfig::load_from_path(&config.translation_config_path)
+            .mask_in_logs;
+    let file_layer: Box<dyn Layer<Registry> + Send + Sync> = if mask_in_logs {
+        Box::new(translation::MaskingLayer::new(file_fmt_layer).with_filter(env_filter()))
+    } else {
+        Box::new(file_fmt_layer.with_filter(env_filter()))
+    };
 
     let feedback = codex_feedback::CodexFeedback::new();
-    let feedback_layer = feedback.logger_layer();
+    // The feedback buffer captures TRACE output and is uploaded with reports, so mask it too.
+    let feedback_layer: Box<dyn Layer<_> + Send + Sync> = if mask_in_logs {
+        Box::new(feedback.logger_layer_with_writer(translation::MaskingWriter::new))
+    } else {
+        Box::new(feedback.logger_layer())
+    };
     let feedback_metadata_layer = feedback.metadata_layer();
 
     if cli.oss && model_provider_override.is_some() {
@@ -423,6 +442,8 @@ async fn run_ratatui_app(
     color_eyre::install()?;
 
     tooltips::announcement::prewarm();
+    #[cfg(windows)]
+    self_update::remove_replaced_binary();
 
     // Forward panic reports through tracing so they appear in the UI status
     // line, but do not swallow the default/color-eyre panic handler.
@@ -438,7 +459,6 @@ async fn run_ratatui_app(
 
     let mut tui = Tui::new(terminal);
 
-    #[cfg(not(debug_assertions))]
     {
         use crate::update_prompt::UpdatePromptOutcome;
 
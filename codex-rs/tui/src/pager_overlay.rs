@@ -19,6 +19,7 @@ use std::io::Result;
 use std::sync::Arc;
 
 use crate::chatwidget::ActiveCellTranscriptKey;
+use crate::history_cell::AgentReasoningTranslationCell;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::UserHistoryCell;
 use crate::key_hint;
@@ -49,7 +50,7 @@ pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
     Static(StaticOverlay),
     Cxline(Box<crate::cxline_overlay::CxlineOverlay>),
-    Translate(Box<crate::translate_overlay::TranslateOverlay>),
+    Translate(Box<crate::translate_overlay::FocusTrap>),
 }
 
 impl Overlay {
@@ -100,10 +101,19 @@ impl Overlay {
     }
 
     /// 创建 Translation 配置 Overlay
-    pub(crate) fn new_translate(config: crate::translation::TranslationConfig) -> Self {
-        Self::Translate(Box::new(crate::translate_overlay::TranslateOverlay::new(
-            &config,
-        )))
+    /// `edit_api_key_for` opens the overlay already editing that provider's API key.
+    pub(crate) fn new_translate(
+        config: crate::translation::TranslationConfig,
+        save_path: std::path::PathBuf,
+        edit_api_key_for: Option<crate::translation::ProviderId>,
+    ) -> Self {
+        let mut overlay = crate::translate_overlay::TranslateOverlay::new(&config)
+            .with_save_path(save_path)
+            .with_color_mode(crate::color_mode::ColorMode::detect());
+        if let Some(provider) = edit_api_key_for {
+            overlay = overlay.editing_api_key(provider);
+        }
+        Self::Translate(Box::new(crate::translate_overlay::FocusTrap::new(overlay)))
     }
 
     /// 如果是 Translate Overlay，获取配置
@@ -111,7 +121,7 @@ impl Overlay {
         &mut self,
     ) -> Option<crate::translation::TranslationConfig> {
         match self {
-            Overlay::Translate(o) => Some(o.config()),
+            Overlay::Translate(o) => Some(o.overlay().config()),
             _ => None,
         }
     }
@@ -134,6 +144,7 @@ const KEY_CTRL_D: KeyBinding = key_hint::ctrl(KeyCode::Char('d'));
 const KEY_CTRL_B: KeyBinding = key_hint::ctrl(KeyCode::Char('b'));
 const KEY_CTRL_U: KeyBinding = key_hint::ctrl(KeyCode::Char('u'));
 const KEY_Q: KeyBinding = key_hint::plain(KeyCode::Char('q'));
+const KEY_E: KeyBinding = key_hint::plain(KeyCode::Char('e'));
 const KEY_ESC: KeyBinding = key_hint::plain(KeyCode::Esc);
 const KEY_ENTER: KeyBinding = key_hint::plain(KeyCode::Enter);
 const KEY_CTRL_T: KeyBinding = key_hint::ctrl(KeyCode::Char('t'));
@@ -639,6 +650,25 @@ impl TranscriptOverlay {
         self.view.is_scrolled_to_bottom()
     }
 
+    /// Translation cells that `max_display_lines` applies to.
+    fn collapsible_translations(&self) -> impl Iterator<Item = &AgentReasoningTranslationCell> {
+        self.cells.iter().filter_map(|cell| {
+            cell.as_any()
+                .downcast_ref::<AgentReasoningTranslationCell>()
+                .filter(|translation| translation.is_collapsible())
+        })
+    }
+
+    /// Expand the most recent translation cell collapsed by `max_display_lines`, or collapse
+    /// it again. Older translations keep their state.
+    fn toggle_latest_translation(&mut self) {
+        let Some(translation) = self.collapsible_translations().last() else {
+            return;
+        };
+        translation.set_expanded(!translation.is_expanded());
+        self.rebuild_renderables();
+    }
+
     fn rebuild_renderables(&mut self) {
         let tail_renderable = self.take_live_tail_renderable();
         self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
@@ -682,6 +712,13 @@ impl TranscriptOverlay {
         } else {
             pairs.push((&[KEY_ESC], "to edit prev"));
         }
+        match self.collapsible_translations().last() {
+            Some(translation) if translation.is_expanded() => {
+                pairs.push((&[KEY_E], "to collapse latest translation"));
+            }
+            Some(_) => pairs.push((&[KEY_E], "to expand latest translation")),
+            None => {}
+        }
         render_key_hints(line2, buf, &pairs);
     }
 
@@ -702,6 +739,11 @@ impl TranscriptOverlay {
                     self.is_done = true;
                     Ok(())
                 }
+                e if KEY_E.is_press(e) => {
+                    self.toggle_latest_translation();
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
                 other => self.view.handle_key_event(tui, other),
             },
             TuiEvent::Draw => {
@@ -907,6 +949,73 @@ mod tests {
         assert_snapshot!(term.backend());
     }
 
+    #[test]
+    fn transcript_overlay_e_toggles_collapsed_translations() {
+        let translation = Arc::new(
+            AgentReasoningTranslationCell::new(
+                None,
+                "one\n\ntwo\n\nthree\n\nfour".to_string(),
+                false,
+            )
+            .with_max_display_lines(Some(2)),
+        );
+        let mut overlay = TranscriptOverlay::new(vec![translation.clone() as Arc<dyn HistoryCell>]);
+        let render = |overlay: &mut TranscriptOverlay| {
+            let mut term = Terminal::new(TestBackend::new(80, 16)).expect("term");
+            term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+                .expect("draw");
+            term.backend().to_string()
+        };
+
+        let collapsed = render(&mut overlay);
+        assert!(collapsed.contains("press e to expand"));
+        assert!(!collapsed.contains("four"));
+
+        assert!(collapsed.contains("e to expand latest translation"));
+
+        overlay.toggle_latest_translation();
+        assert!(translation.is_expanded());
+        let expanded = render(&mut overlay);
+        assert!(!expanded.contains("press e to expand"));
+        assert!(expanded.contains("four"));
+        assert!(expanded.contains("e to collapse latest translation"));
+
+        overlay.toggle_latest_translation();
+        assert!(!translation.is_expanded());
+        assert_eq!(render(&mut overlay), collapsed);
+    }
+
+    #[test]
+    fn transcript_overlay_e_expands_only_the_latest_translation() {
+        let collapsed_translation = || {
+            Arc::new(
+                AgentReasoningTranslationCell::new(
+                    None,
+                    "one\n\ntwo\n\nthree\n\nfour".to_string(),
+                    false,
+                )
+                .with_max_display_lines(Some(2)),
+            )
+        };
+        let older = collapsed_translation();
+        let latest = collapsed_translation();
+        let mut overlay = TranscriptOverlay::new(vec![
+            older.clone() as Arc<dyn HistoryCell>,
+            Arc::new(TestCell {
+                lines: vec![Line::from("tool output")],
+            }),
+            latest.clone() as Arc<dyn HistoryCell>,
+        ]);
+
+        overlay.toggle_latest_translation();
+        assert!(latest.is_expanded());
+        assert!(!older.is_expanded());
+
+        overlay.toggle_latest_translation();
+        assert!(!latest.is_expanded());
+        assert!(!older.is_expanded());
+    }
+
     #[test]
     fn transcript_overlay_renders_live_tail() {
         let mut overlay = TranscriptOverlay::new(vec![Arc::new(TestCell {
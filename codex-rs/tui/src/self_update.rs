@@ -0,0 +1,376 @@
+//! Replace the running binary with a release build downloaded from GitHub.
+//!
+//! Offered for installs that no package manager owns. The platform asset is checked
+//! against the release's `SHA256SUMS` before anything touches disk, then staged next to
+//! the current executable and swapped in with a rename, so a failure at any step leaves
+//! the installed binary as it was.
+
+use std::io::Write as _;
+use std::path::Component;
+use std::path::Path;
+
+use anyhow::Context as _;
+use anyhow::bail;
+use codex_core::default_client::create_client;
+use sha2::Digest;
+use sha2::Sha256;
+use tempfile::NamedTempFile;
+
+use crate::updates::GITHUB_REPO;
+
+/// Release asset listing the SHA-256 of every other asset, in `sha256sum` format.
+const CHECKSUMS_ASSET: &str = "SHA256SUMS";
+
+/// Path components of directories that a package manager owns.
+const MANAGED_COMPONENTS: [&str; 4] = ["node_modules", "Cellar", "homebrew", "linuxbrew"];
+
+/// Stage of a running self-update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Progress {
+    Downloading { received: u64, total: Option<u64> },
+    Verifying,
+    Installing,
+}
+
+impl Progress {
+    pub(crate) fn describe(self) -> String {
+        match self {
+            Self::Downloading {
+                received,
+                total: Some(total),
+            } if total > 0 => format!("Downloading… {}%", received.min(total) * 100 / total),
+            Self::Downloading { received, .. } => {
+                format!("Downloading… {} KB", received / 1024)
+            }
+            Self::Verifying => "Verifying checksum…".to_string(),
+            Self::Installing => "Installing…".to_string(),
+        }
+    }
+}
+
+/// Whether `exe` can be replaced in place: a release binary exists for this platform, no
+/// package manager owns the install, and both the directory and the file are writable.
+pub(crate) fn can_self_replace(exe: &Path) -> bool {
+    release_target().is_some()
+        && !is_package_managed(exe)
+        && exe
+            .parent()
+            .is_some_and(|dir| tempfile::tempfile_in(dir).is_ok())
+        && std::fs::metadata(exe).is_ok_and(|metadata| !metadata.permissions().readonly())
+}
+
+/// Download the `version` release binary for this platform, verify it and replace `exe`
+/// with it.
+pub(crate) async fn self_update(
+    version: &str,
+    exe: &Path,
+    report: impl FnMut(Progress),
+) -> anyhow::Result<()> {
+    let Some(target) = release_target() else {
+        bail!("no release binary is published for this platform");
+    };
+    let base_url = format!("https://github.com/{GITHUB_REPO}/releases/download/rust-v{version}");
+    download_and_install(&base_url, &asset_name(target), exe, report).await
+}
+
+/// Delete the binary a Windows self-update moved aside; it could not be removed while it
+/// was running.
+#[cfg(windows)]
+pub(crate) fn remove_replaced_binary() {
+    if let Ok(exe) = std::env::current_exe() {
+        let _ = std::fs::remove_file(replaced_path(&exe));
+    }
+}
+
+/// Target triple of the release asset for this platform. Linux installs get the static
+/// musl build, which runs regardless of the system libc.
+fn release_target() -> Option<&'static str> {
+    if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
+        Some("aarch64-apple-darwin")
+    } else if cfg!(all(target_os = "macos", target_arch = "x86_64")) {
+        Some("x86_64-apple-darwin")
+    } else if cfg!(all(target_os = "linux", target_arch = "aarch64")) {
+        Some("aarch64-unknown-linux-musl")
+    } else if cfg!(all(target_os = "linux", target_arch = "x86_64")) {
+        Some("x86_64-unknown-linux-musl")
+    } else if cfg!(all(windows, target_arch = "x86_64")) {
+        Some("x86_64-pc-windows-msvc")
+    } else {
+        None
+    }
+}
+
+/// Windows releases ship the raw executable; everything else is zstd-compressed.
+fn asset_name(target: &str) -> String {
+    if cfg!(windows) {
+        format!("codex-{target}.exe")
+    } else {
+        format!("codex-{target}.zst")
+    }
+}
+
+fn is_package_managed(exe: &Path) -> bool {
+    exe.starts_with("/nix/store")
+        || exe.components().any(|component| {
+            matches!(component, Component::Normal(name)
+                if MANAGED_COMPONENTS.iter().any(|managed| name == *managed))
+        })
+}
+
+async fn download_and_install(
+    base_url: &str,
+    asset: &str,
+    exe: &Path,
+    mut report: impl FnMut(Progress),
+) -> anyhow::Result<()> {
+    let client = create_client();
+    let sums = client
+        .get(format!("{base_url}/{CHECKSUMS_ASSET}"))
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+    let Some(expected) = expected_checksum(&sums, asset) else {
+        bail!("{CHECKSUMS_ASSET} has no entry for {asset}");
+    };
+
+    let mut response = client
+        .get(format!("{base_url}/{asset}"))
+        .send()
+        .await?
+        .error_for_status()?;
+    let total = response.content_length();
+    let mut download = Vec::new();
+    report(Progress::Downloading { received: 0, total });
+    while let Some(chunk) = response.chunk().await? {
+        download.extend_from_slice(&chunk);
+        report(Progress::Downloading {
+            received: download.len() as u64,
+            total,
+        });
+    }
+
+    report(Progress::Verifying);
+    verify_sha256(&download, &expected)?;
+
+    report(Progress::Installing);
+    let compressed = asset.ends_with(".zst");
+    let exe = exe.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let binary = if compressed {
+            zstd::stream::decode_all(download.as_slice())
+                .context("could not decompress the release binary")?
+        } else {
+            download
+        };
+        install_binary(&exe, &binary)
+    })
+    .await?
+}
+
+/// Checksum listed for `asset` in a `sha256sum` output, lowercased.
+fn expected_checksum(sums: &str, asset: &str) -> Option<String> {
+    sums.lines().find_map(|line| {
+        let (hash, name) = line.trim().split_once(char::is_whitespace)?;
+        // `sha256sum --binary` marks names with a leading `*`.
+        let name = name.trim_start().trim_start_matches('*');
+        (name == asset && hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+            .then(|| hash.to_ascii_lowercase())
+    })
+}
+
+fn verify_sha256(bytes: &[u8], expected: &str) -> anyhow::Result<()> {
+    let actual = format!("{:x}", Sha256::digest(bytes));
+    if actual != expected {
+        bail!("checksum mismatch: expected {expected}, downloaded {actual}");
+    }
+    Ok(())
+}
+
+/// Stage `binary` in the directory of `exe`, with the same permissions, and swap it in.
+fn install_binary(exe: &Path, binary: &[u8]) -> anyhow::Result<()> {
+    let dir = exe
+        .parent()
+        .context("the executable has no parent directory")?;
+    let mut staged = tempfile::Builder::new()
+        .prefix(".codex-update")
+        .tempfile_in(dir)?;
+    staged.write_all(binary)?;
+    staged
+        .as_file()
+        .set_permissions(std::fs::metadata(exe)?.permissions())?;
+    staged.as_file().sync_all()?;
+    swap_in(staged, exe)
+}
+
+/// Rename over the running executable; the process keeps the old inode until it exits.
+#[cfg(not(windows))]
+fn swap_in(staged: NamedTempFile, exe: &Path) -> anyhow::Result<()> {
+    staged.persist(exe)?;
+    Ok(())
+}
+
+/// Windows can't replace a running executable but can rename it, so the old binary moves
+/// aside and [`remove_replaced_binary`] deletes it on the next start.
+#[cfg(windows)]
+fn swap_in(staged: NamedTempFile, exe: &Path) -> anyhow::Result<()> {
+    let replaced = replaced_path(exe);
+    let _ = std::fs::remove_file(&replaced);
+    std::fs::rename(exe, &replaced)?;
+    if let Err(err) = staged.persist(exe) {
+        std::fs::rename(&replaced, exe)?;
+        return Err(err.into());
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+fn replaced_path(exe: &Path) -> std::path::PathBuf {
+    let mut path = exe.as_os_str().to_owned();
+    path.push(".old");
+    path.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+    use wiremock::Mock;
+    use wiremock::MockServer;
+    use wiremock::ResponseTemplate;
+    use wiremock::matchers::method;
+    use wiremock::matchers::path;
+
+    const NEW_BINARY: &[u8] = b"#!/bin/sh\necho new\n";
+
+    fn sha256(bytes: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(bytes))
+    }
+
+    fn installed_exe(dir: &Path) -> PathBuf {
+        let exe = dir.join("codex");
+        std::fs::write(&exe, b"old").unwrap();
+        exe
+    }
+
+    async fn mock_release(asset: &str, body: Vec<u8>, sums: String) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("/{CHECKSUMS_ASSET}")))
+            .respond_with(ResponseTemplate::new(200).set_body_string(sums))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("/{asset}")))
+            .respond_with(ResponseTemplate::new(200).set_body_bytes(body))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[test]
+    fn checksums_are_looked_up_by_asset_name() {
+        let hash = "A".repeat(64);
+        let sums = format!(
+            "{}  codex-x86_64-unknown-linux-gnu.zst\n{hash} *codex-x86_64-unknown-linux-musl.zst\n",
+            "b".repeat(64)
+        );
+        assert_eq!(
+            expected_checksum(&sums, "codex-x86_64-unknown-linux-musl.zst"),
+            Some("a".repeat(64))
+        );
+        assert_eq!(
+            expected_checksum(&sums, "codex-aarch64-apple-darwin.zst"),
+            None
+        );
+        assert_eq!(expected_checksum("abc  codex.zst", "codex.zst"), None);
+    }
+
+    #[test]
+    fn package_managed_installs_are_not_replaced() {
+        assert!(is_package_managed(Path::new(
+            "/usr/local/lib/node_modules/@echoflux537/codex/bin/codex"
+        )));
+        assert!(is_package_managed(Path::new(
+            "/opt/homebrew/Cellar/codex/0.1.0/bin/codex"
+        )));
+        assert!(is_package_managed(Path::new(
+            "/nix/store/abc-codex/bin/codex"
+        )));
+        assert!(!is_package_managed(Path::new("/home/dev/.local/bin/codex")));
+    }
+
+    #[test]
+    fn install_replaces_the_binary_and_keeps_permissions() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = installed_exe(dir.path());
+        let permissions = std::fs::metadata(&exe).unwrap().permissions();
+
+        install_binary(&exe, NEW_BINARY).unwrap();
+
+        assert_eq!(std::fs::read(&exe).unwrap(), NEW_BINARY);
+        assert_eq!(std::fs::metadata(&exe).unwrap().permissions(), permissions);
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn verified_download_replaces_the_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = installed_exe(dir.path());
+        let asset = "codex-test.zst";
+        let compressed = zstd::stream::encode_all(NEW_BINARY, 0).unwrap();
+        let sums = format!("{}  {asset}\n", sha256(&compressed));
+        let server = mock_release(asset, compressed, sums).await;
+
+        let mut stages = Vec::new();
+        download_and_install(&server.uri(), asset, &exe, |progress| stages.push(progress))
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(&exe).unwrap(), NEW_BINARY);
+        assert_eq!(
+            stages[stages.len() - 2..],
+            [Progress::Verifying, Progress::Installing]
+        );
+    }
+
+    #[tokio::test]
+    async fn checksum_mismatch_leaves_the_binary_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = installed_exe(dir.path());
+        let asset = "codex-test.zst";
+        let compressed = zstd::stream::encode_all(NEW_BINARY, 0).unwrap();
+        let sums = format!("{}  {asset}\n", sha256(b"something else"));
+        let server = mock_release(asset, compressed, sums).await;
+
+        let err = download_and_install(&server.uri(), asset, &exe, |_| {})
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("checksum mismatch"), "{err}");
+        assert_eq!(std::fs::read(&exe).unwrap(), b"old");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn download_progress_is_described() {
+        assert_eq!(
+            Progress::Downloading {
+                received: 512,
+                total: Some(2048)
+            }
+            .describe(),
+            "Downloading… 25%"
+        );
+        assert_eq!(
+            Progress::Downloading {
+                received: 4096,
+                total: None
+            }
+            .describe(),
+            "Downloading… 4 KB"
+        );
+    }
+}
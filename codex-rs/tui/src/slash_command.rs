@@ -28,6 +28,7 @@ pub enum SlashCommand {
     Compact,
     Cxline,
     Translate,
+    Translation,
     Plan,
     Collab,
     Agent,
@@ -79,6 +80,7 @@ impl SlashCommand {
             SlashCommand::Experimental => "toggle experimental features",
             SlashCommand::Cxline => "configure statusline appearance",
             SlashCommand::Translate => "configure reasoning translation",
+            SlashCommand::Translation => "show or flush reasoning blocks waiting on translation",
             SlashCommand::Mcp => "list configured MCP tools",
             SlashCommand::Apps => "manage apps",
             SlashCommand::Logout => "log out of Codex",
@@ -97,7 +99,10 @@ impl SlashCommand {
     pub fn supports_inline_args(self) -> bool {
         matches!(
             self,
-            SlashCommand::Review | SlashCommand::Rename | SlashCommand::Plan
+            SlashCommand::Review
+                | SlashCommand::Rename
+                | SlashCommand::Plan
+                | SlashCommand::Translation
         )
     }
 
@@ -126,6 +131,7 @@ impl SlashCommand {
             | SlashCommand::Mention
             | SlashCommand::Skills
             | SlashCommand::Status
+            | SlashCommand::Translation
             | SlashCommand::DebugConfig
             | SlashCommand::Ps
             | SlashCommand::Mcp
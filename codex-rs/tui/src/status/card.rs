@@ -38,6 +38,7 @@ use super::rate_limits::StatusRateLimitValue;
 use super::rate_limits::compose_rate_limit_data;
 use super::rate_limits::format_status_limit_summary;
 use super::rate_limits::render_status_limit_progress_bar;
+use super::translation::StatusTranslationData;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_lines;
 use codex_core::AuthManager;
@@ -73,6 +74,7 @@ struct StatusHistoryCell {
     forked_from: Option<String>,
     token_usage: StatusTokenUsageData,
     rate_limits: StatusRateLimitData,
+    translation: Option<StatusTranslationData>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -90,6 +92,7 @@ pub(crate) fn new_status_output(
     model_name: &str,
     collaboration_mode: Option<&str>,
     reasoning_effort_override: Option<Option<ReasoningEffort>>,
+    translation: Option<&StatusTranslationData>,
 ) -> CompositeHistoryCell {
     let command = PlainHistoryCell::new(vec!["/status".magenta().into()]);
     let card = StatusHistoryCell::new(
@@ -106,6 +109,7 @@ pub(crate) fn new_status_output(
         model_name,
         collaboration_mode,
         reasoning_effort_override,
+        translation,
     );
 
     CompositeHistoryCell::new(vec![Box::new(command), Box::new(card)])
@@ -127,6 +131,7 @@ impl StatusHistoryCell {
         model_name: &str,
         collaboration_mode: Option<&str>,
         reasoning_effort_override: Option<Option<ReasoningEffort>>,
+        translation: Option<&StatusTranslationData>,
     ) -> Self {
         let mut config_entries = vec![
             ("workdir", config.cwd.display().to_string()),
@@ -206,6 +211,7 @@ impl StatusHistoryCell {
             forked_from,
             token_usage,
             rate_limits,
+            translation: translation.cloned(),
         }
     }
 
@@ -408,6 +414,9 @@ impl HistoryCell for StatusHistoryCell {
         }
 
         self.collect_rate_limit_labels(&mut seen, &mut labels);
+        if self.translation.is_some() {
+            push_label(&mut labels, &mut seen, "Translation");
+        }
 
         let formatter = FieldFormatter::from_labels(labels.iter().map(String::as_str));
         let value_width = formatter.value_width(available_inner_width);
@@ -478,6 +487,11 @@ impl HistoryCell for StatusHistoryCell {
 
         lines.extend(self.rate_limit_lines(available_inner_width, &formatter));
 
+        if let Some(translation) = &self.translation {
+            lines.push(Line::from(Vec::<Span<'static>>::new()));
+            lines.extend(translation.lines(&formatter));
+        }
+
         let content_width = lines.iter().map(line_display_width).max().unwrap_or(0);
         let inner_width = content_width.min(available_inner_width);
         let truncated_lines: Vec<Line<'static>> = lines
@@ -509,7 +523,7 @@ fn format_model_provider(config: &Config) -> Option<String> {
     })
 }
 
-fn sanitize_base_url(raw: &str) -> Option<String> {
+pub(super) fn sanitize_base_url(raw: &str) -> Option<String> {
     let trimmed = raw.trim();
     if trimmed.is_empty() {
         return None;
@@ -3,11 +3,13 @@ mod card;
 mod format;
 mod helpers;
 mod rate_limits;
+mod translation;
 
 pub(crate) use card::new_status_output;
 pub(crate) use helpers::format_tokens_compact;
 pub(crate) use rate_limits::RateLimitSnapshotDisplay;
 pub(crate) use rate_limits::rate_limit_snapshot_display;
+pub(crate) use translation::StatusTranslationData;
 
 #[cfg(test)]
 mod tests;
@@ -1,6 +1,9 @@
+use super::StatusTranslationData;
 use super::new_status_output;
 use super::rate_limit_snapshot_display;
 use crate::history_cell::HistoryCell;
+use crate::translation::TranslationConfig;
+use crate::translation::TranslationStats;
 use chrono::Duration as ChronoDuration;
 use chrono::TimeZone;
 use chrono::Utc;
@@ -17,9 +20,11 @@ use codex_core::protocol::TokenUsageInfo;
 use codex_protocol::ThreadId;
 use codex_protocol::config_types::ReasoningSummary;
 use codex_protocol::openai_models::ReasoningEffort;
+use codex_translation::health::ConnectionMetrics;
 use insta::assert_snapshot;
 use ratatui::prelude::*;
 use std::path::PathBuf;
+use std::time::Duration;
 use tempfile::TempDir;
 
 async fn test_config(temp_home: &TempDir) -> Config {
@@ -155,6 +160,7 @@ async fn status_snapshot_includes_reasoning_details() {
         &model_slug,
         None,
         reasoning_effort_override,
+        None,
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -209,6 +215,7 @@ async fn status_snapshot_includes_forked_from() {
         &model_slug,
         None,
         None,
+        None,
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -269,6 +276,7 @@ async fn status_snapshot_includes_monthly_limit() {
         &model_slug,
         None,
         None,
+        None,
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -317,6 +325,7 @@ async fn status_snapshot_shows_unlimited_credits() {
         &model_slug,
         None,
         None,
+        None,
     );
     let rendered = render_lines(&composite.display_lines(120));
     assert!(
@@ -364,6 +373,7 @@ async fn status_snapshot_shows_positive_credits() {
         &model_slug,
         None,
         None,
+        None,
     );
     let rendered = render_lines(&composite.display_lines(120));
     assert!(
@@ -411,6 +421,7 @@ async fn status_snapshot_hides_zero_credits() {
         &model_slug,
         None,
         None,
+        None,
     );
     let rendered = render_lines(&composite.display_lines(120));
     assert!(
@@ -456,6 +467,7 @@ async fn status_snapshot_hides_when_has_no_credits_flag() {
         &model_slug,
         None,
         None,
+        None,
     );
     let rendered = render_lines(&composite.display_lines(120));
     assert!(
@@ -501,6 +513,7 @@ async fn status_card_token_usage_excludes_cached_tokens() {
         &model_slug,
         None,
         None,
+        None,
     );
     let rendered = render_lines(&composite.display_lines(120));
 
@@ -561,6 +574,7 @@ async fn status_snapshot_truncates_in_narrow_terminal() {
         &model_slug,
         None,
         reasoning_effort_override,
+        None,
     );
     let mut rendered_lines = render_lines(&composite.display_lines(70));
     if cfg!(windows) {
@@ -610,6 +624,75 @@ async fn status_snapshot_shows_missing_limits_message() {
         &model_slug,
         None,
         None,
+        None,
+    );
+    let mut rendered_lines = render_lines(&composite.display_lines(80));
+    if cfg!(windows) {
+        for line in &mut rendered_lines {
+            *line = line.replace('\\', "/");
+        }
+    }
+    let sanitized = sanitize_directory(rendered_lines).join("\n");
+    assert_snapshot!(sanitized);
+}
+
+#[tokio::test]
+async fn status_snapshot_includes_translation() {
+    let temp_home = TempDir::new().expect("temp home");
+    let mut config = test_config(&temp_home).await;
+    config.model = Some("gpt-5.1-codex-max".to_string());
+    config.cwd = PathBuf::from("/workspace/tests");
+
+    let auth_manager = test_auth_manager(&config);
+    let usage = TokenUsage {
+        input_tokens: 500,
+        cached_input_tokens: 0,
+        output_tokens: 250,
+        reasoning_output_tokens: 0,
+        total_tokens: 750,
+    };
+
+    let now = chrono::Local
+        .with_ymd_and_hms(2024, 2, 3, 4, 5, 6)
+        .single()
+        .expect("timestamp");
+
+    let translation_config = TranslationConfig {
+        enabled: true,
+        provider: "deepseek".to_string(),
+        api_key: Some("sk-1234567890abcdef".to_string()),
+        ..Default::default()
+    };
+    let stats = TranslationStats {
+        connections: ConnectionMetrics {
+            total_requests: 12,
+            failed_requests: 1,
+            p50_latency_ms: 820,
+            p99_latency_ms: 2_100,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let translation =
+        StatusTranslationData::new(&translation_config, Duration::from_millis(5_000), &stats);
+
+    let model_slug = ModelsManager::get_model_offline(config.model.as_deref());
+    let token_info = token_info_for(&model_slug, &config, &usage);
+    let composite = new_status_output(
+        &config,
+        &auth_manager,
+        Some(&token_info),
+        &usage,
+        &None,
+        None,
+        None,
+        None,
+        None,
+        now,
+        &model_slug,
+        None,
+        None,
+        Some(&translation),
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -677,6 +760,7 @@ async fn status_snapshot_includes_credits_and_limits() {
         &model_slug,
         None,
         None,
+        None,
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -732,6 +816,7 @@ async fn status_snapshot_shows_empty_limits_message() {
         &model_slug,
         None,
         None,
+        None,
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -796,6 +881,7 @@ async fn status_snapshot_shows_stale_limits_message() {
         &model_slug,
         None,
         None,
+        None,
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -864,6 +950,7 @@ async fn status_snapshot_cached_limits_hide_credits_without_flag() {
         &model_slug,
         None,
         None,
+        None,
     );
     let mut rendered_lines = render_lines(&composite.display_lines(80));
     if cfg!(windows) {
@@ -922,6 +1009,7 @@ async fn status_context_window_uses_last_usage() {
         &model_slug,
         None,
         None,
+        None,
     );
     let rendered_lines = render_lines(&composite.display_lines(80));
     let context_line = rendered_lines
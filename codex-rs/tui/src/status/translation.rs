@@ -0,0 +1,113 @@
+use std::time::Duration;
+
+use ratatui::prelude::*;
+use ratatui::style::Stylize;
+
+use super::card::sanitize_base_url;
+use super::format::FieldFormatter;
+use crate::translate_overlay::TranslateOverlay;
+use crate::translation::TranslationConfig;
+use crate::translation::TranslationStats;
+
+/// The "Translation" section of `/status`: the settings that matter in a bug report, and
+/// how requests have gone this session.
+#[derive(Debug, Clone)]
+pub(crate) struct StatusTranslationData {
+    enabled: bool,
+    /// Provider, model and target language.
+    summary: String,
+    /// Base URL, marked custom or default.
+    base_url: String,
+    /// Barrier wait, request timeout and API key.
+    limits: String,
+    /// Requests made this session, once there are any.
+    activity: Option<String>,
+}
+
+impl StatusTranslationData {
+    /// `max_wait` is the configured barrier wait, before any shortening after timeouts.
+    pub(crate) fn new(
+        config: &TranslationConfig,
+        max_wait: Duration,
+        stats: &TranslationStats,
+    ) -> Self {
+        let provider = config.effective_provider().definition();
+        let summary = format!(
+            "{} · {} · {}",
+            provider.name,
+            config.effective_model(provider),
+            config.target_language.code()
+        );
+
+        let base_url = config.effective_base_url(provider);
+        let origin = if base_url == provider.default_base_url {
+            "default"
+        } else {
+            "custom"
+        };
+        // Gateways sometimes carry credentials in the URL.
+        let base_url = sanitize_base_url(&base_url).unwrap_or_else(|| base_url.to_string());
+        let base_url = format!("base URL {base_url} ({origin})");
+
+        let mut limits = format!("wait {}ms", max_wait.as_millis());
+        if let Some(timeout_ms) = config.timeout_ms {
+            limits.push_str(&format!(" · request timeout {timeout_ms}ms"));
+        }
+        let key = match config.effective_api_key() {
+            Some(key) => format!("API key {}", TranslateOverlay::mask_api_key(key)),
+            None if provider.requires_api_key => "no API key".to_string(),
+            None => "no API key needed".to_string(),
+        };
+        limits.push_str(&format!(" · {key}"));
+
+        let connections = &stats.connections;
+        let activity = (connections.total_requests > 0 || stats.late_results > 0).then(|| {
+            let mut activity = format!(
+                "{} requests, {} failed · p50 {}ms · p99 {}ms",
+                connections.total_requests,
+                connections.failed_requests,
+                connections.p50_latency_ms,
+                connections.p99_latency_ms
+            );
+            if stats.late_results > 0 {
+                activity.push_str(&format!(
+                    " · {} late (slowest {}ms)",
+                    stats.late_results, stats.slowest_late_result_ms
+                ));
+            }
+            activity
+        });
+
+        Self {
+            enabled: config.enabled,
+            summary,
+            base_url,
+            limits,
+            activity,
+        }
+    }
+
+    pub(super) fn lines(&self, formatter: &FieldFormatter) -> Vec<Line<'static>> {
+        let state = if self.enabled {
+            Span::from("enabled")
+        } else {
+            Span::from("disabled").dim()
+        };
+        let mut lines = vec![
+            formatter.line(
+                "Translation",
+                vec![
+                    state,
+                    Span::from(" · ").dim(),
+                    Span::from(self.summary.clone()),
+                ],
+            ),
+            formatter.continuation(vec![Span::from(self.base_url.clone()).dim()]),
+            formatter.continuation(vec![Span::from(self.limits.clone()).dim()]),
+        ];
+        if let Some(activity) = &self.activity {
+            lines.push(formatter.continuation(vec![Span::from(activity.clone()).dim()]));
+        }
+        lines
+    }
+}
@@ -11,7 +11,9 @@ use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::fs;
+use std::path::Path;
 use std::path::PathBuf;
+use std::time::SystemTime;
 
 /// 状态栏配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +37,10 @@ pub struct CxLineConfig {
     /// 各 segment 配置
     #[serde(default)]
     pub segments: SegmentsConfig,
+
+    /// Segment 显示顺序（segment 名称）；未列出的 segment 按默认顺序排在后面
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub order: Vec<String>,
 }
 
 fn default_true() -> bool {
@@ -66,6 +72,27 @@ pub struct SegmentsConfig {
 
     #[serde(default = "SegmentItemConfig::default_usage")]
     pub usage: SegmentItemConfig,
+
+    #[serde(default = "SegmentItemConfig::default_activity")]
+    pub activity: SegmentItemConfig,
+
+    #[serde(default = "SegmentItemConfig::default_translation_status")]
+    pub translation_status: SegmentItemConfig,
+
+    #[serde(default = "SegmentItemConfig::default_host")]
+    pub host: SegmentItemConfig,
+
+    #[serde(default = "SegmentItemConfig::default_last_turn")]
+    pub last_turn: SegmentItemConfig,
+
+    #[serde(default = "SegmentItemConfig::default_profile")]
+    pub profile: SegmentItemConfig,
+
+    #[serde(default = "SegmentItemConfig::default_diff_stats")]
+    pub diff_stats: SegmentItemConfig,
+
+    #[serde(default = "SegmentItemConfig::default_mcp")]
+    pub mcp: SegmentItemConfig,
 }
 
 impl Default for SegmentsConfig {
@@ -123,6 +150,34 @@ impl SegmentItemConfig {
     pub fn default_usage() -> Self {
         ThemePresets::get_default().segments.usage
     }
+
+    pub fn default_activity() -> Self {
+        ThemePresets::get_default().segments.activity
+    }
+
+    pub fn default_translation_status() -> Self {
+        ThemePresets::get_default().segments.translation_status
+    }
+
+    pub fn default_host() -> Self {
+        ThemePresets::get_default().segments.host
+    }
+
+    pub fn default_last_turn() -> Self {
+        ThemePresets::get_default().segments.last_turn
+    }
+
+    pub fn default_profile() -> Self {
+        ThemePresets::get_default().segments.profile
+    }
+
+    pub fn default_diff_stats() -> Self {
+        ThemePresets::get_default().segments.diff_stats
+    }
+
+    pub fn default_mcp() -> Self {
+        ThemePresets::get_default().segments.mcp
+    }
 }
 
 impl Default for CxLineConfig {
@@ -176,7 +231,10 @@ impl CxLineConfig {
 
         match fs::read_to_string(&path) {
             Ok(content) => match toml::from_str::<CxLineConfig>(&content) {
-                Ok(config) => config,
+                Ok(config) => {
+                    config.warn_invalid_segment_names();
+                    config
+                }
                 Err(e) => {
                     tracing::warn!("解析 cxline 配置失败: {}, 使用默认配置", e);
                     Self::default()
@@ -218,6 +276,30 @@ impl CxLineConfig {
         self.segments = theme.segments;
     }
 
+    /// 按配置的顺序返回所有 segment，忽略无效或重复的名称
+    pub fn segment_order(&self) -> Vec<SegmentId> {
+        let mut order: Vec<SegmentId> = Vec::with_capacity(SegmentId::ALL.len());
+        let listed = self
+            .order
+            .iter()
+            .filter_map(|name| SegmentId::from_name(name));
+        for id in listed.chain(SegmentId::ALL.iter().copied()) {
+            if !order.contains(&id) {
+                order.push(id);
+            }
+        }
+        order
+    }
+
+    /// 记录 `order` 中无效的 segment 名称
+    fn warn_invalid_segment_names(&self) {
+        for name in &self.order {
+            if SegmentId::from_name(name).is_none() {
+                tracing::warn!("cxline 配置中存在无效的 segment 名称: {name}，已忽略");
+            }
+        }
+    }
+
     /// 获取指定 segment 的配置
     pub fn get_segment_config(&self, id: SegmentId) -> &SegmentItemConfig {
         match id {
@@ -226,6 +308,13 @@ impl CxLineConfig {
             SegmentId::Git => &self.segments.git,
             SegmentId::Context => &self.segments.context,
             SegmentId::Usage => &self.segments.usage,
+            SegmentId::Activity => &self.segments.activity,
+            SegmentId::TranslationStatus => &self.segments.translation_status,
+            SegmentId::Host => &self.segments.host,
+            SegmentId::LastTurn => &self.segments.last_turn,
+            SegmentId::Profile => &self.segments.profile,
+            SegmentId::DiffStats => &self.segments.diff_stats,
+            SegmentId::Mcp => &self.segments.mcp,
         }
     }
 
@@ -237,6 +326,159 @@ impl CxLineConfig {
             SegmentId::Git => &mut self.segments.git,
             SegmentId::Context => &mut self.segments.context,
             SegmentId::Usage => &mut self.segments.usage,
+            SegmentId::Activity => &mut self.segments.activity,
+            SegmentId::TranslationStatus => &mut self.segments.translation_status,
+            SegmentId::Host => &mut self.segments.host,
+            SegmentId::LastTurn => &mut self.segments.last_turn,
+            SegmentId::Profile => &mut self.segments.profile,
+            SegmentId::DiffStats => &mut self.segments.diff_stats,
+            SegmentId::Mcp => &mut self.segments.mcp,
         }
     }
 }
+
+/// 配置文件热重载：通过 mtime 检测变更
+#[derive(Debug)]
+pub struct CxLineConfigWatcher {
+    path: Option<PathBuf>,
+    last_modified: Option<SystemTime>,
+}
+
+impl CxLineConfigWatcher {
+    pub fn new(path: Option<PathBuf>) -> Self {
+        let last_modified = path.as_deref().and_then(modified_time);
+        Self {
+            path,
+            last_modified,
+        }
+    }
+
+    /// 若配置文件发生变化则重新加载
+    ///
+    /// 解析失败时记录警告并返回 None，保留当前配置
+    pub fn poll(&mut self) -> Option<CxLineConfig> {
+        let path = self.path.as_deref()?;
+        let modified = modified_time(path);
+        if modified.is_none() || modified == self.last_modified {
+            return None;
+        }
+        self.last_modified = modified;
+
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => {
+                tracing::warn!("读取 cxline 配置失败: {}, 保留当前配置", e);
+                return None;
+            }
+        };
+        match toml::from_str::<CxLineConfig>(&content) {
+            Ok(config) => {
+                config.warn_invalid_segment_names();
+                Some(config)
+            }
+            Err(e) => {
+                tracing::warn!("解析 cxline 配置失败: {}, 保留当前配置", e);
+                None
+            }
+        }
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statusline::StatusLineContext;
+    use crate::statusline::build_statusline;
+    use std::time::Duration;
+
+    fn write_config(path: &Path, order: &[&str], modified: SystemTime) {
+        let config = CxLineConfig {
+            order: order.iter().map(ToString::to_string).collect(),
+            ..ThemePresets::get_builtin("default").unwrap()
+        };
+        fs::write(path, toml::to_string_pretty(&config).unwrap()).unwrap();
+        // 显式设置 mtime，避免文件系统时间精度导致变更未被检测
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+
+    fn render(config: &CxLineConfig) -> String {
+        let ctx = StatusLineContext::new("gpt-5", Path::new("/tmp/project"))
+            .with_git_preview("", "", 0, 0);
+        build_statusline(config, &ctx)
+            .render_line()
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[test]
+    fn test_segment_order_skips_invalid_names() {
+        let config = CxLineConfig {
+            order: vec![
+                "git".to_string(),
+                "bogus".to_string(),
+                "model".to_string(),
+                "git".to_string(),
+            ],
+            ..CxLineConfig::default()
+        };
+        let order = config.segment_order();
+        assert_eq!(order.len(), SegmentId::ALL.len());
+        assert_eq!(
+            &order[..3],
+            &[SegmentId::Git, SegmentId::Model, SegmentId::Directory]
+        );
+    }
+
+    #[test]
+    fn test_watcher_reloads_changed_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let t0 = SystemTime::now();
+        write_config(&path, &["model", "directory"], t0);
+
+        let mut watcher = CxLineConfigWatcher::new(Some(path.clone()));
+        assert!(watcher.poll().is_none());
+        let initial: CxLineConfig = toml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let before = render(&initial);
+        assert!(before.find("GPT 5") < before.find("project"));
+
+        write_config(&path, &["directory", "model"], t0 + Duration::from_secs(2));
+        let reloaded = watcher.poll().expect("changed config is reloaded");
+        let after = render(&reloaded);
+        assert_ne!(before, after);
+        assert!(after.find("project") < after.find("GPT 5"));
+
+        // 未变化时不重复加载
+        assert!(watcher.poll().is_none());
+    }
+
+    #[test]
+    fn test_watcher_keeps_config_on_parse_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let t0 = SystemTime::now();
+        write_config(&path, &["model"], t0);
+        let mut watcher = CxLineConfigWatcher::new(Some(path.clone()));
+
+        fs::write(&path, "segments = [").unwrap();
+        fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(t0 + Duration::from_secs(2))
+            .unwrap();
+
+        assert!(watcher.poll().is_none());
+    }
+}
@@ -6,6 +6,7 @@ pub mod config;
 pub mod icon_selector;
 pub mod name_input;
 pub mod renderer;
+pub mod scheduler;
 pub mod segment;
 pub mod segments;
 pub mod separator_editor;
@@ -13,9 +14,13 @@ pub mod style;
 pub mod themes;
 
 use std::path::Path;
+use std::time::Duration;
 
 use codex_protocol::openai_models::ReasoningEffort;
 
+use crate::translation::BarrierInfo;
+use crate::translation::RateLimitState;
+
 pub use color_picker::ColorPicker;
 pub use color_picker::ColorTarget;
 pub use config::CxLineConfig;
@@ -23,10 +28,19 @@ pub use icon_selector::IconSelector;
 pub use name_input::NameInputDialog;
 pub use renderer::StatusLineRenderer;
 pub use renderer::StatusLineWidget;
+pub use scheduler::SegmentScheduler;
+pub use scheduler::SegmentSnapshot;
 pub use segment::Segment;
 pub use segment::SegmentData;
 pub use segment::SegmentId;
 pub use segment::SegmentStyle;
+pub use segments::DiffStats;
+pub use segments::HostInfo;
+pub use segments::HostOptions;
+pub use segments::McpHealth;
+pub use segments::ModelDisplay;
+pub use segments::ProfileInfo;
+pub use segments::ProfileOptions;
 pub use separator_editor::SeparatorEditor;
 pub use style::StyleMode;
 
@@ -68,6 +82,57 @@ pub struct StatusLineContext<'a> {
 
     /// Git 预览数据（用于配置页预览，覆盖实际 git 检测）
     pub git_preview: Option<GitPreviewData>,
+
+    /// Agent turn 是否正在运行
+    pub agent_running: bool,
+
+    /// 进行中的翻译请求数
+    pub queued_translations: usize,
+
+    /// 被翻译 barrier 暂缓的 history cell 数
+    pub deferred_cells: usize,
+
+    /// 后台任务数（如后台终端）
+    pub background_tasks: usize,
+
+    /// 当前翻译 barrier 信息（用于 TranslationStatus segment）
+    pub translation_barrier_info: Option<BarrierInfo>,
+
+    /// 翻译服务不可达、翻译已暂停
+    pub translation_offline: bool,
+
+    /// 翻译服务最近报告的速率限额
+    pub translation_rate_limit: Option<RateLimitState>,
+
+    /// 启动时缓存的用户与主机信息（用于 Host segment）
+    pub host: Option<&'a HostInfo>,
+
+    /// Host segment 显示选项
+    pub host_options: HostOptions,
+
+    /// 上一轮 agent turn 耗时
+    pub last_turn_duration: Option<Duration>,
+
+    /// 上一轮中最后执行命令的退出码
+    pub last_command_exit_code: Option<i32>,
+
+    /// 当前 profile 与账号信息（用于 Profile segment）
+    pub profile: Option<&'a ProfileInfo>,
+
+    /// Profile segment 显示选项
+    pub profile_options: ProfileOptions,
+
+    /// 本会话累计的增删行数（用于 DiffStats segment）
+    pub diff_stats: DiffStats,
+
+    /// MCP server 连接统计（用于 Mcp segment）
+    pub mcp_health: McpHealth,
+
+    /// 模型名称显示方式
+    pub model_display: ModelDisplay,
+
+    /// 后台轮询的 segment 数据（有结果时优先于同步收集）
+    pub polled: Option<&'a SegmentSnapshot>,
 }
 
 impl<'a> StatusLineContext<'a> {
@@ -82,6 +147,23 @@ impl<'a> StatusLineContext<'a> {
             weekly_rate_limit_percent: None,
             weekly_rate_limit_resets_at: None,
             git_preview: None,
+            agent_running: false,
+            queued_translations: 0,
+            deferred_cells: 0,
+            background_tasks: 0,
+            translation_barrier_info: None,
+            translation_offline: false,
+            translation_rate_limit: None,
+            host: None,
+            host_options: HostOptions::default(),
+            last_turn_duration: None,
+            last_command_exit_code: None,
+            profile: None,
+            profile_options: ProfileOptions::default(),
+            diff_stats: DiffStats::default(),
+            mcp_health: McpHealth::default(),
+            model_display: ModelDisplay::default(),
+            polled: None,
         }
     }
 
@@ -90,6 +172,12 @@ impl<'a> StatusLineContext<'a> {
         self
     }
 
+    /// 设置模型名称显示方式
+    pub fn with_model_display(mut self, display: ModelDisplay) -> Self {
+        self.model_display = display;
+        self
+    }
+
     pub fn with_context(mut self, used_tokens: Option<i64>, window_size: Option<i64>) -> Self {
         self.context_used_tokens = used_tokens;
         self.context_window_size = window_size;
@@ -108,6 +196,82 @@ impl<'a> StatusLineContext<'a> {
         self
     }
 
+    /// 设置进行中的任务计数（用于 Activity segment）
+    pub fn with_activity(
+        mut self,
+        agent_running: bool,
+        queued_translations: usize,
+        background_tasks: usize,
+    ) -> Self {
+        self.agent_running = agent_running;
+        self.queued_translations = queued_translations;
+        self.background_tasks = background_tasks;
+        self
+    }
+
+    /// 设置被翻译 barrier 暂缓的 cell 数
+    pub fn with_deferred_cells(mut self, deferred_cells: usize) -> Self {
+        self.deferred_cells = deferred_cells;
+        self
+    }
+
+    /// 设置翻译 barrier 信息
+    pub fn with_translation_barrier(mut self, info: Option<BarrierInfo>) -> Self {
+        self.translation_barrier_info = info;
+        self
+    }
+
+    /// 设置翻译离线状态
+    pub fn with_translation_offline(mut self, offline: bool) -> Self {
+        self.translation_offline = offline;
+        self
+    }
+
+    /// 设置翻译速率限额
+    pub fn with_translation_rate_limit(mut self, state: Option<RateLimitState>) -> Self {
+        self.translation_rate_limit = state;
+        self
+    }
+
+    /// 设置用户与主机信息
+    pub fn with_host(mut self, host: &'a HostInfo, options: HostOptions) -> Self {
+        self.host = Some(host);
+        self.host_options = options;
+        self
+    }
+
+    /// 设置上一轮 turn 的耗时与最后一条命令的退出码
+    pub fn with_last_turn(mut self, duration: Option<Duration>, exit_code: Option<i32>) -> Self {
+        self.last_turn_duration = duration;
+        self.last_command_exit_code = exit_code;
+        self
+    }
+
+    /// 设置 profile 与账号信息
+    pub fn with_profile(mut self, profile: &'a ProfileInfo, options: ProfileOptions) -> Self {
+        self.profile = Some(profile);
+        self.profile_options = options;
+        self
+    }
+
+    /// 设置本会话累计的增删行数
+    pub fn with_diff_stats(mut self, stats: DiffStats) -> Self {
+        self.diff_stats = stats;
+        self
+    }
+
+    /// 设置 MCP server 连接统计
+    pub fn with_mcp_health(mut self, health: McpHealth) -> Self {
+        self.mcp_health = health;
+        self
+    }
+
+    /// 设置后台轮询结果快照
+    pub fn with_polled(mut self, snapshot: &'a SegmentSnapshot) -> Self {
+        self.polled = Some(snapshot);
+        self
+    }
+
     /// 设置 Git 预览数据（用于配置页预览）
     pub fn with_git_preview(mut self, branch: &str, status: &str, ahead: u32, behind: u32) -> Self {
         self.git_preview = Some(GitPreviewData {
@@ -132,56 +296,48 @@ impl GitPreviewData {
 }
 
 /// 构建状态栏
-/// 收集所有 segment 数据并返回渲染器
+/// 按配置的顺序收集所有启用的 segment 数据并返回渲染器
 pub fn build_statusline<'a>(
     config: &'a CxLineConfig,
     ctx: &StatusLineContext<'_>,
 ) -> StatusLineRenderer<'a> {
-    use segments::*;
-
     let mut renderer = StatusLineRenderer::new(config);
 
-    // Model segment
-    if config.segments.model.enabled {
-        let segment = ModelSegment;
-        if let Some(data) = segment.collect(ctx) {
-            renderer.add_segment(SegmentId::Model, data);
+    for id in config.segment_order() {
+        if !config.get_segment_config(id).enabled {
+            continue;
         }
-    }
-
-    // Directory segment
-    if config.segments.directory.enabled {
-        let segment = DirectorySegment;
-        if let Some(data) = segment.collect(ctx) {
-            renderer.add_segment(SegmentId::Directory, data);
+        // 已轮询过的 segment 直接使用快照，渲染路径上不做耗时收集
+        let data = match ctx.polled.and_then(|snapshot| snapshot.get(id)) {
+            Some(polled) => polled,
+            None => collect_segment(id, ctx),
+        };
+        if let Some(data) = data {
+            renderer.add_segment(id, data);
         }
     }
 
-    // Git segment
-    if config.segments.git.enabled {
-        let segment = GitSegment;
-        if let Some(data) = segment.collect(ctx) {
-            renderer.add_segment(SegmentId::Git, data);
-        }
-    }
+    renderer
+}
 
-    // Context segment
-    if config.segments.context.enabled {
-        let segment = ContextSegment;
-        if let Some(data) = segment.collect(ctx) {
-            renderer.add_segment(SegmentId::Context, data);
-        }
-    }
+/// 收集指定 segment 的数据
+pub fn collect_segment(id: SegmentId, ctx: &StatusLineContext<'_>) -> Option<SegmentData> {
+    use segments::*;
 
-    // Usage segment
-    if config.segments.usage.enabled {
-        let segment = UsageSegment;
-        if let Some(data) = segment.collect(ctx) {
-            renderer.add_segment(SegmentId::Usage, data);
-        }
+    match id {
+        SegmentId::Model => ModelSegment.collect(ctx),
+        SegmentId::Directory => DirectorySegment.collect(ctx),
+        SegmentId::Git => GitSegment.collect(ctx),
+        SegmentId::Context => ContextSegment.collect(ctx),
+        SegmentId::Usage => UsageSegment.collect(ctx),
+        SegmentId::Activity => ActivitySegment.collect(ctx),
+        SegmentId::TranslationStatus => TranslationStatusSegment.collect(ctx),
+        SegmentId::Host => HostSegment.collect(ctx),
+        SegmentId::LastTurn => LastTurnSegment.collect(ctx),
+        SegmentId::Profile => ProfileSegment.collect(ctx),
+        SegmentId::DiffStats => DiffStatsSegment.collect(ctx),
+        SegmentId::Mcp => McpSegment.collect(ctx),
     }
-
-    renderer
 }
 
 /// 异步更新用的 Git 预览数据收集（避免在 render 中执行 git 命令）
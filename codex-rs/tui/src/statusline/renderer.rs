@@ -5,6 +5,7 @@ use super::config::CxLineConfig;
 use super::segment::SegmentData;
 use super::segment::SegmentId;
 use super::style::StyleMode;
+use super::style::color_from_name;
 use super::style::separators;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
@@ -73,7 +74,7 @@ impl<'a> StatusLineRenderer<'a> {
 
             // 渲染主要内容
             let mut text_style = Style::default();
-            if let Some(color) = segment_config.colors.text_color() {
+            if let Some(color) = Self::text_color(data).or(segment_config.colors.text_color()) {
                 text_style = text_style.fg(color);
             }
             if segment_config.styles.text_bold {
@@ -83,7 +84,14 @@ impl<'a> StatusLineRenderer<'a> {
 
             // 渲染次要内容
             if !data.secondary.is_empty() {
-                spans.push(Span::styled(format!(" {}", data.secondary), text_style));
+                let secondary_style = match Self::secondary_color(data) {
+                    Some(color) => text_style.fg(color),
+                    None => text_style,
+                };
+                spans.push(Span::styled(
+                    format!(" {}", data.secondary),
+                    secondary_style,
+                ));
             }
         }
 
@@ -108,7 +116,7 @@ impl<'a> StatusLineRenderer<'a> {
 
             // 获取背景色
             let bg_color = segment_config.colors.background_color();
-            let text_color = segment_config.colors.text_color();
+            let text_color = Self::text_color(data).or(segment_config.colors.text_color());
             let icon_color = segment_config.colors.icon_color();
 
             // 构建 segment 样式
@@ -141,7 +149,14 @@ impl<'a> StatusLineRenderer<'a> {
 
             // 渲染次要内容
             if !data.secondary.is_empty() {
-                spans.push(Span::styled(format!(" {}", data.secondary), segment_style));
+                let secondary_style = match Self::secondary_color(data) {
+                    Some(color) => segment_style.fg(color),
+                    None => segment_style,
+                };
+                spans.push(Span::styled(
+                    format!(" {}", data.secondary),
+                    secondary_style,
+                ));
             }
 
             // 添加右边距
@@ -174,6 +189,20 @@ impl<'a> StatusLineRenderer<'a> {
         }
     }
 
+    /// 获取动态文本颜色（从元数据，覆盖配置颜色）
+    fn text_color(data: &SegmentData) -> Option<ratatui::style::Color> {
+        data.metadata
+            .get("dynamic_color")
+            .map(|name| color_from_name(name))
+    }
+
+    /// 获取次要内容颜色（从元数据，仅作用于 secondary）
+    fn secondary_color(data: &SegmentData) -> Option<ratatui::style::Color> {
+        data.metadata
+            .get("secondary_color")
+            .map(|name| color_from_name(name))
+    }
+
     /// 获取图标
     fn get_icon(&self, id: SegmentId, data: &SegmentData) -> String {
         // 优先使用动态图标（从元数据）
@@ -0,0 +1,247 @@
+// 状态栏异步收集调度器
+//
+// git / 自定义命令等 segment 的数据收集较慢，不能放在渲染路径上。
+// 每个实现了 `Segment::poll` 的 segment 由独立的 tokio 任务按各自的刷新间隔轮询，
+// 结果写入共享快照；同步渲染只读取快照，不会被慢速收集阻塞。
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tokio_util::sync::CancellationToken;
+
+use super::config::SegmentItemConfig;
+use super::segment::Segment;
+use super::segment::SegmentData;
+use super::segment::SegmentId;
+
+/// 刷新间隔下限，避免配置过小导致频繁执行外部命令
+pub const MIN_REFRESH_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 读取 `segments.<id>.options.refresh_interval_ms`，未配置时使用 `default`，并限制不低于下限
+pub fn refresh_interval(config: &SegmentItemConfig, default: Duration) -> Duration {
+    config
+        .options
+        .get("refresh_interval_ms")
+        .and_then(serde_json::Value::as_u64)
+        .map(Duration::from_millis)
+        .unwrap_or(default)
+        .max(MIN_REFRESH_INTERVAL)
+}
+
+/// 后台轮询结果的共享快照
+#[derive(Debug, Clone, Default)]
+pub struct SegmentSnapshot {
+    inner: Arc<RwLock<HashMap<SegmentId, Option<SegmentData>>>>,
+}
+
+impl SegmentSnapshot {
+    /// 最近一次轮询结果；外层 None 表示该 segment 尚未完成轮询
+    pub fn get(&self, id: SegmentId) -> Option<Option<SegmentData>> {
+        self.inner
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&id)
+            .cloned()
+    }
+
+    /// 写入轮询结果，返回内容是否发生变化
+    fn update(&self, id: SegmentId, data: Option<SegmentData>) -> bool {
+        let mut inner = self.inner.write().unwrap_or_else(PoisonError::into_inner);
+        if inner.get(&id) == Some(&data) {
+            return false;
+        }
+        inner.insert(id, data);
+        true
+    }
+}
+
+/// 按 segment 启动轮询任务并管理其生命周期
+pub struct SegmentScheduler {
+    snapshot: SegmentSnapshot,
+    cancel: CancellationToken,
+    tasks: Vec<JoinHandle<()>>,
+    /// 快照变化时调用（通常用于请求重绘）
+    on_update: Arc<dyn Fn() + Send + Sync>,
+}
+
+impl SegmentScheduler {
+    pub fn new(on_update: impl Fn() + Send + Sync + 'static) -> Self {
+        Self {
+            snapshot: SegmentSnapshot::default(),
+            cancel: CancellationToken::new(),
+            tasks: Vec::new(),
+            on_update: Arc::new(on_update),
+        }
+    }
+
+    /// 供渲染读取的快照
+    pub fn snapshot(&self) -> SegmentSnapshot {
+        self.snapshot.clone()
+    }
+
+    /// 为 segment 启动轮询任务；需在 tokio runtime 内调用
+    pub fn spawn(&mut self, segment: Arc<dyn Segment + Send + Sync>, interval: Duration) {
+        let interval = interval.max(MIN_REFRESH_INTERVAL);
+        let snapshot = self.snapshot.clone();
+        let cancel = self.cancel.clone();
+        let on_update = Arc::clone(&self.on_update);
+
+        self.tasks.push(tokio::spawn(async move {
+            let id = segment.id();
+            let mut ticker = tokio::time::interval(interval);
+            // 轮询耗时超过间隔时顺延，而不是连续补跑
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    _ = cancel.cancelled() => break,
+                    _ = ticker.tick() => {}
+                }
+
+                let segment = Arc::clone(&segment);
+                let data = tokio::select! {
+                    // 关闭时不等待进行中的轮询，阻塞线程会自行结束
+                    _ = cancel.cancelled() => break,
+                    result = tokio::task::spawn_blocking(move || segment.poll()) => match result {
+                        Ok(data) => data,
+                        // poll 发生 panic：保留上一次的结果
+                        Err(_) => continue,
+                    },
+                };
+
+                if snapshot.update(id, data) {
+                    on_update();
+                }
+            }
+        }));
+    }
+
+    /// 停止所有轮询任务并等待其退出
+    pub async fn shutdown(mut self) {
+        self.cancel.cancel();
+        for task in self.tasks.drain(..) {
+            let _ = task.await;
+        }
+    }
+}
+
+impl Drop for SegmentScheduler {
+    fn drop(&mut self) {
+        // 未调用 shutdown 时，任务在下一个 await 点退出
+        self.cancel.cancel();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statusline::CxLineConfig;
+    use crate::statusline::StatusLineContext;
+    use crate::statusline::build_statusline;
+    use std::path::Path;
+    use std::time::Instant;
+    use tokio::sync::Notify;
+
+    /// 每次轮询都要耗时很久的 segment
+    struct SlowSegment {
+        delay: Duration,
+    }
+
+    impl Segment for SlowSegment {
+        fn collect(&self, _ctx: &StatusLineContext) -> Option<SegmentData> {
+            None
+        }
+
+        fn id(&self) -> SegmentId {
+            SegmentId::Git
+        }
+
+        fn poll(&self) -> Option<SegmentData> {
+            std::thread::sleep(self.delay);
+            Some(SegmentData::new("polled"))
+        }
+    }
+
+    fn render(config: &CxLineConfig, snapshot: &SegmentSnapshot) -> String {
+        let ctx = StatusLineContext::new("gpt-5", Path::new("/tmp"))
+            .with_git_preview("main", "✓", 0, 0)
+            .with_polled(snapshot);
+        build_statusline(config, &ctx)
+            .render_line()
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn slow_poll_never_blocks_render() {
+        let updated = Arc::new(Notify::new());
+        let notify = Arc::clone(&updated);
+        let mut scheduler = SegmentScheduler::new(move || notify.notify_one());
+        scheduler.spawn(
+            Arc::new(SlowSegment {
+                delay: Duration::from_millis(300),
+            }),
+            MIN_REFRESH_INTERVAL,
+        );
+        let snapshot = scheduler.snapshot();
+        let config = CxLineConfig::default();
+
+        // 轮询进行中：渲染立即返回，并回退到同步收集的数据
+        let started = Instant::now();
+        for _ in 0..10 {
+            let line = render(&config, &snapshot);
+            assert!(line.contains("main") && !line.contains("polled"), "{line}");
+        }
+        assert!(started.elapsed() < Duration::from_millis(100));
+
+        tokio::time::timeout(Duration::from_secs(5), updated.notified())
+            .await
+            .expect("poll result should arrive");
+        let line = render(&config, &snapshot);
+        assert!(line.contains("polled") && !line.contains("main"), "{line}");
+
+        // 第二次轮询进行中也能及时关闭
+        tokio::time::sleep(MIN_REFRESH_INTERVAL).await;
+        tokio::time::timeout(Duration::from_millis(100), scheduler.shutdown())
+            .await
+            .expect("shutdown should not wait for an in-flight poll");
+    }
+
+    #[test]
+    fn snapshot_reports_changes_only() {
+        let snapshot = SegmentSnapshot::default();
+        assert_eq!(snapshot.get(SegmentId::Git), None);
+
+        assert!(snapshot.update(SegmentId::Git, Some(SegmentData::new("a"))));
+        assert!(!snapshot.update(SegmentId::Git, Some(SegmentData::new("a"))));
+        assert!(snapshot.update(SegmentId::Git, None));
+        assert_eq!(snapshot.get(SegmentId::Git), Some(None));
+    }
+
+    #[test]
+    fn refresh_interval_respects_minimum() {
+        let mut config = CxLineConfig::default().segments.git;
+        let default = Duration::from_secs(2);
+        assert_eq!(refresh_interval(&config, default), default);
+
+        config
+            .options
+            .insert("refresh_interval_ms".to_string(), 5_000.into());
+        assert_eq!(
+            refresh_interval(&config, default),
+            Duration::from_millis(5_000)
+        );
+
+        config
+            .options
+            .insert("refresh_interval_ms".to_string(), 10.into());
+        assert_eq!(refresh_interval(&config, default), MIN_REFRESH_INTERVAL);
+    }
+}
@@ -5,7 +5,7 @@ use ratatui::style::Color;
 use std::collections::HashMap;
 
 /// Segment 数据，由各 Segment 实现收集后返回
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct SegmentData {
     /// 主要内容
     pub primary: String,
@@ -76,9 +76,37 @@ pub enum SegmentId {
     Git,
     Context,
     Usage,
+    Activity,
+    TranslationStatus,
+    Host,
+    LastTurn,
+    Profile,
+    DiffStats,
+    Mcp,
 }
 
 impl SegmentId {
+    /// 所有 segment，按默认显示顺序
+    pub const ALL: &'static [SegmentId] = &[
+        Self::Model,
+        Self::Directory,
+        Self::Git,
+        Self::Context,
+        Self::Usage,
+        Self::Activity,
+        Self::TranslationStatus,
+        Self::Host,
+        Self::LastTurn,
+        Self::Profile,
+        Self::DiffStats,
+        Self::Mcp,
+    ];
+
+    /// 从名称解析（与 `as_str` 对应）
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|id| id.as_str() == name)
+    }
+
     pub fn as_str(&self) -> &'static str {
         match self {
             Self::Model => "model",
@@ -86,6 +114,13 @@ impl SegmentId {
             Self::Git => "git",
             Self::Context => "context",
             Self::Usage => "usage",
+            Self::Activity => "activity",
+            Self::TranslationStatus => "translation_status",
+            Self::Host => "host",
+            Self::LastTurn => "last_turn",
+            Self::Profile => "profile",
+            Self::DiffStats => "diff_stats",
+            Self::Mcp => "mcp",
         }
     }
 }
@@ -97,4 +132,10 @@ pub trait Segment {
 
     /// 返回 segment ID
     fn id(&self) -> SegmentId;
+
+    /// 后台轮询收集数据（由 `SegmentScheduler` 在 blocking 线程中按刷新间隔调用）。
+    /// 返回 None 表示该次轮询后隐藏此 segment；未接入调度器的 segment 无需实现
+    fn poll(&self) -> Option<SegmentData> {
+        None
+    }
 }
@@ -0,0 +1,84 @@
+// Activity Segment - 显示进行中的 agent 任务和翻译数量
+
+use crate::statusline::StatusLineContext;
+use crate::statusline::segment::Segment;
+use crate::statusline::segment::SegmentData;
+use crate::statusline::segment::SegmentId;
+
+pub struct ActivitySegment;
+
+impl Segment for ActivitySegment {
+    fn collect(&self, ctx: &StatusLineContext) -> Option<SegmentData> {
+        // agent turn 与后台任务合并计数
+        let agent_tasks = usize::from(ctx.agent_running) + ctx.background_tasks;
+        let display = format_activity(agent_tasks, ctx.queued_translations)?;
+
+        Some(
+            SegmentData::new(display)
+                .with_metadata("agent_tasks", agent_tasks.to_string())
+                .with_metadata("queued_translations", ctx.queued_translations.to_string())
+                .with_metadata("deferred_cells", ctx.deferred_cells.to_string()),
+        )
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Activity
+    }
+}
+
+/// 格式化计数，如 "⚙1 译2"；全部空闲时返回 None 以隐藏 segment
+fn format_activity(agent_tasks: usize, queued_translations: usize) -> Option<String> {
+    let mut parts = Vec::new();
+    if agent_tasks > 0 {
+        parts.push(format!("⚙{agent_tasks}"));
+    }
+    if queued_translations > 0 {
+        parts.push(format!("译{queued_translations}"));
+    }
+    if parts.is_empty() {
+        None
+    } else {
+        Some(parts.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn collect(agent_running: bool, queued: usize, background: usize) -> Option<String> {
+        let ctx = StatusLineContext::new("gpt-5", Path::new("/tmp")).with_activity(
+            agent_running,
+            queued,
+            background,
+        );
+        ActivitySegment.collect(&ctx).map(|data| data.primary)
+    }
+
+    #[test]
+    fn test_hidden_when_idle() {
+        assert_eq!(collect(false, 0, 0), None);
+    }
+
+    #[test]
+    fn test_format_permutations() {
+        assert_eq!(collect(true, 0, 0).as_deref(), Some("⚙1"));
+        assert_eq!(collect(false, 2, 0).as_deref(), Some("译2"));
+        assert_eq!(collect(true, 2, 0).as_deref(), Some("⚙1 译2"));
+        assert_eq!(collect(false, 0, 3).as_deref(), Some("⚙3"));
+        assert_eq!(collect(true, 1, 2).as_deref(), Some("⚙3 译1"));
+    }
+
+    #[test]
+    fn test_deferred_cells_metadata() {
+        let ctx = StatusLineContext::new("gpt-5", Path::new("/tmp"))
+            .with_activity(false, 1, 0)
+            .with_deferred_cells(3);
+        let data = ActivitySegment.collect(&ctx).unwrap();
+        assert_eq!(
+            data.metadata.get("deferred_cells").map(String::as_str),
+            Some("3")
+        );
+    }
+}
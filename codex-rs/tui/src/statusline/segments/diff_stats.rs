@@ -0,0 +1,204 @@
+// DiffStats Segment - 显示本会话中 agent 改动的行数（+新增 −删除）
+//
+// 每轮 turn 内取 core 上报的 turn diff（反映最新应用状态，撤销的 hunk 会被抵消）；
+// 跨 turn 则按轮累加，后一轮回退前一轮的改动会计为新的增删。
+
+use std::ops::Add;
+
+use crate::statusline::StatusLineContext;
+use crate::statusline::segment::Segment;
+use crate::statusline::segment::SegmentData;
+use crate::statusline::segment::SegmentId;
+
+/// 新增/删除行数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiffStats {
+    pub added: usize,
+    pub removed: usize,
+}
+
+impl DiffStats {
+    pub fn new(added: usize, removed: usize) -> Self {
+        Self { added, removed }
+    }
+
+    /// 统计 unified diff（可包含多个文件）中的增删行
+    ///
+    /// 按 `@@` 头记录的行数跟踪 hunk 范围：只有 hunk 之外的 `+++` / `---` 才是文件头，
+    /// hunk 内的 `-- SQL 注释`、`++counter` 之类仍计为增删。
+    pub fn from_unified_diff(diff: &str) -> Self {
+        let mut stats = Self::default();
+        // 当前 hunk 剩余的旧/新行数，均为 0 时处于 hunk 之外
+        let (mut old_left, mut new_left) = (0usize, 0usize);
+        for line in diff.lines() {
+            if old_left == 0 && new_left == 0 {
+                if let Some((old, new)) = parse_hunk_header(line) {
+                    (old_left, new_left) = (old, new);
+                }
+                continue;
+            }
+            match line.as_bytes().first() {
+                Some(b'+') => {
+                    stats.added += 1;
+                    new_left = new_left.saturating_sub(1);
+                }
+                Some(b'-') => {
+                    stats.removed += 1;
+                    old_left = old_left.saturating_sub(1);
+                }
+                // `\ No newline at end of file`
+                Some(b'\\') => {}
+                // 上下文行（空行是去掉了行首空格的上下文）
+                _ => {
+                    old_left = old_left.saturating_sub(1);
+                    new_left = new_left.saturating_sub(1);
+                }
+            }
+        }
+        stats
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added == 0 && self.removed == 0
+    }
+}
+
+/// 解析 `@@ -a,b +c,d @@` 中的旧/新行数，省略时为 1
+fn parse_hunk_header(line: &str) -> Option<(usize, usize)> {
+    let mut ranges = line.strip_prefix("@@ -")?.split(' ');
+    let count = |range: &str| match range.split_once(',') {
+        Some((_, count)) => count.parse().ok(),
+        None => Some(1),
+    };
+    let old = count(ranges.next()?)?;
+    let new = count(ranges.next()?.strip_prefix('+')?)?;
+    Some((old, new))
+}
+
+impl Add for DiffStats {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            added: self.added + other.added,
+            removed: self.removed + other.removed,
+        }
+    }
+}
+
+pub struct DiffStatsSegment;
+
+impl Segment for DiffStatsSegment {
+    fn collect(&self, ctx: &StatusLineContext) -> Option<SegmentData> {
+        let stats = ctx.diff_stats;
+        if stats.is_empty() {
+            return None;
+        }
+        Some(
+            SegmentData::new(format!("+{}", stats.added))
+                .with_secondary(format!("−{}", stats.removed))
+                .with_metadata("dynamic_color", "green")
+                .with_metadata("secondary_color", "red"),
+        )
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::DiffStats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn collect(stats: DiffStats) -> Option<SegmentData> {
+        let ctx = StatusLineContext::new("gpt-5", Path::new("/tmp")).with_diff_stats(stats);
+        DiffStatsSegment.collect(&ctx)
+    }
+
+    #[test]
+    fn test_hidden_at_zero() {
+        assert!(collect(DiffStats::default()).is_none());
+    }
+
+    #[test]
+    fn test_format() {
+        let data = collect(DiffStats::new(120, 34)).unwrap();
+        assert_eq!(data.primary, "+120");
+        assert_eq!(data.secondary, "−34");
+        assert_eq!(
+            data.metadata.get("dynamic_color").map(String::as_str),
+            Some("green")
+        );
+        assert_eq!(
+            data.metadata.get("secondary_color").map(String::as_str),
+            Some("red")
+        );
+
+        let data = collect(DiffStats::new(0, 5)).unwrap();
+        assert_eq!(data.primary, "+0");
+        assert_eq!(data.secondary, "−5");
+    }
+
+    #[test]
+    fn test_from_unified_diff() {
+        let diff = "\
+diff --git a/src/lib.rs b/src/lib.rs
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,4 @@
+ fn main() {
+-    old();
++    new();
++    more();
+ }
+diff --git a/README.md b/README.md
+--- a/README.md
++++ b/README.md
+@@ -1 +1 @@
+-hello
++world
+";
+        assert_eq!(DiffStats::from_unified_diff(diff), DiffStats::new(3, 2));
+        assert_eq!(DiffStats::from_unified_diff(""), DiffStats::default());
+    }
+
+    #[test]
+    fn test_from_unified_diff_content_like_headers() {
+        let diff = "\
+--- a/schema.sql
++++ b/schema.sql
+@@ -1,3 +1,3 @@
+--- legacy table
++-- users table
+ CREATE TABLE users (id INT);
+ 
+--- a/counter.c
++++ b/counter.c
+@@ -2,2 +2,3 @@
+ int main() {
+-    counter++;
++++counter;
++    return 0;
+\\ No newline at end of file
+";
+        assert_eq!(DiffStats::from_unified_diff(diff), DiffStats::new(3, 2));
+    }
+
+    #[test]
+    fn test_parse_hunk_header() {
+        assert_eq!(parse_hunk_header("@@ -1,3 +1,4 @@ fn main()"), Some((3, 4)));
+        assert_eq!(parse_hunk_header("@@ -1 +1 @@"), Some((1, 1)));
+        assert_eq!(parse_hunk_header("@@ -0,0 +1,2 @@"), Some((0, 2)));
+        assert_eq!(parse_hunk_header("--- a/file"), None);
+    }
+
+    #[test]
+    fn test_add() {
+        assert_eq!(
+            DiffStats::new(1, 2) + DiffStats::new(10, 20),
+            DiffStats::new(11, 22)
+        );
+    }
+}
@@ -0,0 +1,253 @@
+// Host Segment - 显示 user@hostname，便于区分远程会话
+
+use std::sync::OnceLock;
+
+use unicode_width::UnicodeWidthChar;
+use unicode_width::UnicodeWidthStr;
+
+use crate::statusline::CxLineConfig;
+use crate::statusline::StatusLineContext;
+use crate::statusline::segment::Segment;
+use crate::statusline::segment::SegmentData;
+use crate::statusline::segment::SegmentId;
+
+/// 默认最大显示宽度
+const DEFAULT_MAX_WIDTH: usize = 24;
+
+/// 启动时查询一次的用户与主机信息
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HostInfo {
+    pub user: Option<String>,
+    pub hostname: Option<String>,
+    /// 是否处于 SSH 会话（SSH_CONNECTION / SSH_TTY）
+    pub ssh: bool,
+}
+
+impl HostInfo {
+    /// 进程内只检测一次的当前用户与主机信息
+    pub fn current() -> &'static HostInfo {
+        static CURRENT: OnceLock<HostInfo> = OnceLock::new();
+        CURRENT.get_or_init(Self::detect)
+    }
+
+    /// 从当前进程环境与系统主机名检测
+    pub fn detect() -> Self {
+        Self::from_env(|key| std::env::var(key).ok(), system_hostname())
+    }
+
+    /// 使用注入的环境变量查询构建；`hostname` 为空时回退到 HOSTNAME / COMPUTERNAME
+    pub fn from_env(env: impl Fn(&str) -> Option<String>, hostname: Option<String>) -> Self {
+        let non_empty = |key: &str| env(key).filter(|value| !value.is_empty());
+        Self {
+            user: non_empty("USER").or_else(|| non_empty("USERNAME")),
+            hostname: hostname
+                .filter(|name| !name.is_empty())
+                .or_else(|| non_empty("HOSTNAME"))
+                .or_else(|| non_empty("COMPUTERNAME")),
+            ssh: non_empty("SSH_CONNECTION").is_some() || non_empty("SSH_TTY").is_some(),
+        }
+    }
+}
+
+/// Host segment 的显示选项（来自配置）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HostOptions {
+    /// 最大显示宽度，超出时以 … 截断
+    pub max_width: usize,
+    /// 非 SSH 会话也显示（在 `order` 中显式列出 host 时）
+    pub always: bool,
+}
+
+impl Default for HostOptions {
+    fn default() -> Self {
+        Self {
+            max_width: DEFAULT_MAX_WIDTH,
+            always: false,
+        }
+    }
+}
+
+impl HostOptions {
+    /// 读取 `segments.host.options.max_width` 与 `order` 中的 host
+    pub fn from_config(config: &CxLineConfig) -> Self {
+        let max_width = config
+            .segments
+            .host
+            .options
+            .get("max_width")
+            .and_then(serde_json::Value::as_u64)
+            .and_then(|width| usize::try_from(width).ok())
+            .unwrap_or(DEFAULT_MAX_WIDTH);
+        let always = config
+            .order
+            .iter()
+            .any(|name| name == SegmentId::Host.as_str());
+        Self { max_width, always }
+    }
+}
+
+pub struct HostSegment;
+
+impl Segment for HostSegment {
+    fn collect(&self, ctx: &StatusLineContext) -> Option<SegmentData> {
+        let info = ctx.host?;
+        if !info.ssh && !ctx.host_options.always {
+            return None;
+        }
+        let display = format_host(info, ctx.host_options.max_width)?;
+        Some(SegmentData::new(display).with_metadata("ssh", info.ssh.to_string()))
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Host
+    }
+}
+
+/// 格式化为 user@host（主机名取第一个 . 之前的部分），并按最大宽度截断
+fn format_host(info: &HostInfo, max_width: usize) -> Option<String> {
+    let hostname = info.hostname.as_deref()?;
+    let short = hostname.split('.').next().unwrap_or(hostname);
+    let display = match info.user.as_deref() {
+        Some(user) => format!("{user}@{short}"),
+        None => short.to_string(),
+    };
+    Some(truncate_to_width(&display, max_width))
+}
+
+fn truncate_to_width(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+    let budget = max_width.saturating_sub(1);
+    let mut width = 0;
+    let mut out = String::new();
+    for ch in text.chars() {
+        let ch_width = ch.width().unwrap_or(0);
+        if width + ch_width > budget {
+            break;
+        }
+        width += ch_width;
+        out.push(ch);
+    }
+    out.push('…');
+    out
+}
+
+#[cfg(not(unix))]
+fn system_hostname() -> Option<String> {
+    None
+}
+
+#[cfg(unix)]
+fn system_hostname() -> Option<String> {
+    let mut buf = [0u8; 256];
+    // SAFETY: buf 可写且长度正确；gethostname 最多写入 buf.len() 字节
+    let rc = unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) };
+    if rc != 0 {
+        return None;
+    }
+    let end = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    String::from_utf8(buf[..end].to_vec()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    fn info_from(vars: &[(&str, &str)], hostname: Option<&str>) -> HostInfo {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        HostInfo::from_env(|key| vars.get(key).cloned(), hostname.map(str::to_string))
+    }
+
+    fn collect(info: &HostInfo, options: HostOptions) -> Option<String> {
+        let ctx = StatusLineContext::new("gpt-5", Path::new("/tmp")).with_host(info, options);
+        HostSegment.collect(&ctx).map(|data| data.primary)
+    }
+
+    #[test]
+    fn test_ssh_detection() {
+        assert!(!info_from(&[("USER", "dev")], Some("box")).ssh);
+        assert!(info_from(&[("SSH_CONNECTION", "10.0.0.1 5000 10.0.0.2 22")], None).ssh);
+        assert!(info_from(&[("SSH_TTY", "/dev/pts/3")], None).ssh);
+        assert!(!info_from(&[("SSH_TTY", "")], None).ssh);
+    }
+
+    #[test]
+    fn test_env_fallbacks() {
+        let info = info_from(
+            &[("USERNAME", "dev"), ("COMPUTERNAME", "WORKSTATION")],
+            None,
+        );
+        assert_eq!(info.user.as_deref(), Some("dev"));
+        assert_eq!(info.hostname.as_deref(), Some("WORKSTATION"));
+
+        let info = info_from(&[("HOSTNAME", "env-host")], Some("sys-host"));
+        assert_eq!(info.hostname.as_deref(), Some("sys-host"));
+    }
+
+    #[test]
+    fn test_format_shortens_hostname() {
+        let info = info_from(&[("USER", "dev")], Some("build-01.eu.example.com"));
+        assert_eq!(format_host(&info, 24).as_deref(), Some("dev@build-01"));
+
+        let info = info_from(&[], Some("build-01.eu.example.com"));
+        assert_eq!(format_host(&info, 24).as_deref(), Some("build-01"));
+
+        assert_eq!(format_host(&info_from(&[("USER", "dev")], None), 24), None);
+    }
+
+    #[test]
+    fn test_format_truncates_to_max_width() {
+        let info = info_from(&[("USER", "developer")], Some("very-long-hostname"));
+        assert_eq!(format_host(&info, 12).as_deref(), Some("developer@v…"));
+        assert_eq!(
+            format_host(&info, 28).as_deref(),
+            Some("developer@very-long-hostname")
+        );
+    }
+
+    #[test]
+    fn test_hidden_outside_ssh_unless_always() {
+        let local = info_from(&[("USER", "dev")], Some("laptop"));
+        assert_eq!(collect(&local, HostOptions::default()), None);
+        let always = HostOptions {
+            always: true,
+            ..Default::default()
+        };
+        assert_eq!(collect(&local, always).as_deref(), Some("dev@laptop"));
+
+        let remote = info_from(
+            &[("USER", "dev"), ("SSH_TTY", "/dev/pts/0")],
+            Some("server"),
+        );
+        assert_eq!(
+            collect(&remote, HostOptions::default()).as_deref(),
+            Some("dev@server")
+        );
+    }
+
+    #[test]
+    fn test_options_from_config() {
+        let mut config = CxLineConfig::default();
+        assert_eq!(HostOptions::from_config(&config), HostOptions::default());
+
+        config
+            .segments
+            .host
+            .options
+            .insert("max_width".to_string(), serde_json::json!(16));
+        config.order = vec!["model".to_string(), "host".to_string()];
+        assert_eq!(
+            HostOptions::from_config(&config),
+            HostOptions {
+                max_width: 16,
+                always: true,
+            }
+        );
+    }
+}
@@ -0,0 +1,101 @@
+// LastTurn Segment - 显示上一轮 agent turn 耗时及最后一条命令的退出码
+
+use std::time::Duration;
+
+use crate::statusline::StatusLineContext;
+use crate::statusline::segment::Segment;
+use crate::statusline::segment::SegmentData;
+use crate::statusline::segment::SegmentId;
+
+pub struct LastTurnSegment;
+
+impl Segment for LastTurnSegment {
+    fn collect(&self, ctx: &StatusLineContext) -> Option<SegmentData> {
+        let duration = ctx.last_turn_duration?;
+        let mut data = SegmentData::new(format_duration(duration))
+            .with_metadata("duration_ms", duration.as_millis().to_string());
+        if let Some(exit_code) = ctx.last_command_exit_code {
+            data = data.with_metadata("exit_code", exit_code.to_string());
+            if exit_code != 0 {
+                // 失败时追加红色退出码，类似 shell 提示符的 $?
+                data = data
+                    .with_secondary(format!("✗ exit {exit_code}"))
+                    .with_metadata("secondary_color", "red");
+            }
+        }
+        Some(data)
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::LastTurn
+    }
+}
+
+/// 格式化耗时：不足 1s 显示毫秒，不足 1min 保留一位小数，更长时显示分秒/时分
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs == 0 {
+        format!("{}ms", duration.as_millis())
+    } else if secs < 60 {
+        format!("{:.1}s", duration.as_secs_f64())
+    } else if secs < 3600 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else {
+        format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn collect(duration: Option<Duration>, exit_code: Option<i32>) -> Option<SegmentData> {
+        let ctx =
+            StatusLineContext::new("gpt-5", Path::new("/tmp")).with_last_turn(duration, exit_code);
+        LastTurnSegment.collect(&ctx)
+    }
+
+    #[test]
+    fn test_hidden_before_first_turn() {
+        assert!(collect(None, None).is_none());
+    }
+
+    #[test]
+    fn test_sub_second() {
+        assert_eq!(format_duration(Duration::from_millis(420)), "420ms");
+        assert_eq!(format_duration(Duration::ZERO), "0ms");
+    }
+
+    #[test]
+    fn test_seconds() {
+        assert_eq!(format_duration(Duration::from_millis(4_200)), "4.2s");
+        assert_eq!(format_duration(Duration::from_millis(59_940)), "59.9s");
+    }
+
+    #[test]
+    fn test_minutes_and_hours() {
+        assert_eq!(format_duration(Duration::from_secs(65)), "1m05s");
+        assert_eq!(format_duration(Duration::from_secs(12 * 60 + 30)), "12m30s");
+        assert_eq!(format_duration(Duration::from_secs(3_720)), "1h02m");
+    }
+
+    #[test]
+    fn test_successful_command() {
+        let data = collect(Some(Duration::from_millis(4_200)), Some(0)).unwrap();
+        assert_eq!(data.primary, "4.2s");
+        assert!(data.secondary.is_empty());
+        assert!(!data.metadata.contains_key("secondary_color"));
+    }
+
+    #[test]
+    fn test_failed_command() {
+        let data = collect(Some(Duration::from_secs(75)), Some(1)).unwrap();
+        assert_eq!(data.primary, "1m15s");
+        assert_eq!(data.secondary, "✗ exit 1");
+        assert_eq!(
+            data.metadata.get("secondary_color").map(String::as_str),
+            Some("red")
+        );
+    }
+}
@@ -0,0 +1,102 @@
+// Mcp Segment - 显示 MCP server 连接状况（已连接/已配置）
+
+use crate::statusline::StatusLineContext;
+use crate::statusline::segment::Segment;
+use crate::statusline::segment::SegmentData;
+use crate::statusline::segment::SegmentId;
+
+/// 有 server 断开时的文本颜色
+const DEGRADED_COLOR: &str = "red";
+
+/// 已启用 MCP server 的连接统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct McpHealth {
+    /// 已就绪的 server 数
+    pub connected: usize,
+    /// 仍在启动中的 server 数（不算作断开）
+    pub starting: usize,
+    /// 已启用的 server 总数
+    pub total: usize,
+}
+
+impl McpHealth {
+    /// 启动失败或被取消的 server 数
+    pub fn disconnected(&self) -> usize {
+        self.total.saturating_sub(self.connected + self.starting)
+    }
+}
+
+pub struct McpSegment;
+
+impl Segment for McpSegment {
+    fn collect(&self, ctx: &StatusLineContext) -> Option<SegmentData> {
+        let health = ctx.mcp_health;
+        if health.total == 0 {
+            return None;
+        }
+        let mut data = SegmentData::new(format!("MCP {}/{}", health.connected, health.total));
+        if health.disconnected() > 0 {
+            data = data.with_metadata("dynamic_color", DEGRADED_COLOR);
+        }
+        Some(data)
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Mcp
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn collect(health: McpHealth) -> Option<SegmentData> {
+        let ctx = StatusLineContext::new("gpt-5", Path::new("/tmp")).with_mcp_health(health);
+        McpSegment.collect(&ctx)
+    }
+
+    #[test]
+    fn test_hidden_without_servers() {
+        assert!(collect(McpHealth::default()).is_none());
+    }
+
+    #[test]
+    fn test_all_healthy() {
+        let data = collect(McpHealth {
+            connected: 4,
+            starting: 0,
+            total: 4,
+        })
+        .unwrap();
+        assert_eq!(data.primary, "MCP 4/4");
+        assert!(!data.metadata.contains_key("dynamic_color"));
+    }
+
+    #[test]
+    fn test_starting_is_not_degraded() {
+        let data = collect(McpHealth {
+            connected: 1,
+            starting: 2,
+            total: 3,
+        })
+        .unwrap();
+        assert_eq!(data.primary, "MCP 1/3");
+        assert!(!data.metadata.contains_key("dynamic_color"));
+    }
+
+    #[test]
+    fn test_degraded() {
+        let data = collect(McpHealth {
+            connected: 3,
+            starting: 0,
+            total: 4,
+        })
+        .unwrap();
+        assert_eq!(data.primary, "MCP 3/4");
+        assert_eq!(
+            data.metadata.get("dynamic_color").map(String::as_str),
+            Some(DEGRADED_COLOR)
+        );
+    }
+}
@@ -1,13 +1,34 @@
 // Segments 模块入口
 
+mod activity;
 mod context;
+mod diff_stats;
 mod directory;
 mod git;
+mod host;
+mod last_turn;
+mod mcp;
 mod model;
+mod profile;
+mod translation_status;
 mod usage;
 
+pub use activity::ActivitySegment;
 pub use context::ContextSegment;
+pub use diff_stats::DiffStats;
+pub use diff_stats::DiffStatsSegment;
 pub use directory::DirectorySegment;
 pub use git::GitSegment;
+pub use host::HostInfo;
+pub use host::HostOptions;
+pub use host::HostSegment;
+pub use last_turn::LastTurnSegment;
+pub use mcp::McpHealth;
+pub use mcp::McpSegment;
+pub use model::ModelDisplay;
 pub use model::ModelSegment;
+pub use profile::ProfileInfo;
+pub use profile::ProfileOptions;
+pub use profile::ProfileSegment;
+pub use translation_status::TranslationStatusSegment;
 pub use usage::UsageSegment;
@@ -1,11 +1,41 @@
 // Model Segment - 显示当前模型名称
 
+use crate::statusline::CxLineConfig;
 use crate::statusline::StatusLineContext;
 use crate::statusline::segment::Segment;
 use crate::statusline::segment::SegmentData;
 use crate::statusline::segment::SegmentId;
 use codex_protocol::openai_models::ReasoningEffort;
 
+/// 模型名称显示方式（`segments.model.options.model_display`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ModelDisplay {
+    /// 美化名称 + 推理强度后缀
+    #[default]
+    Pretty,
+    /// 仅美化名称
+    Short,
+    /// 原始模型 ID（含日期后缀），便于排查 provider 路由
+    Raw,
+}
+
+impl ModelDisplay {
+    /// 读取 `segments.model.options.model_display`，未知取值回退到 pretty
+    pub fn from_config(config: &CxLineConfig) -> Self {
+        match config
+            .segments
+            .model
+            .options
+            .get("model_display")
+            .and_then(serde_json::Value::as_str)
+        {
+            Some("short") => Self::Short,
+            Some("raw") => Self::Raw,
+            _ => Self::Pretty,
+        }
+    }
+}
+
 pub struct ModelSegment;
 
 impl Segment for ModelSegment {
@@ -15,19 +45,13 @@ impl Segment for ModelSegment {
             return None;
         }
 
-        // 简化模型名称显示
-        let display_name = simplify_model_name(model_name);
-
-        // Append reasoning effort suffix if present
-        let display_name = if let Some(effort) = ctx.reasoning_effort {
-            let effort_suffix = reasoning_effort_suffix(effort);
-            if effort_suffix.is_empty() {
-                display_name
-            } else {
-                format!("{display_name} {effort_suffix}")
+        let display_name = match ctx.model_display {
+            ModelDisplay::Raw => model_name.to_string(),
+            // 简化模型名称显示
+            ModelDisplay::Short => simplify_model_name(model_name),
+            ModelDisplay::Pretty => {
+                with_effort_suffix(simplify_model_name(model_name), ctx.reasoning_effort)
             }
-        } else {
-            display_name
         };
 
         Some(SegmentData::new(display_name).with_metadata("model_id", model_name))
@@ -38,6 +62,20 @@ impl Segment for ModelSegment {
     }
 }
 
+/// Append reasoning effort suffix if present
+fn with_effort_suffix(display_name: String, effort: Option<ReasoningEffort>) -> String {
+    if let Some(effort) = effort {
+        let effort_suffix = reasoning_effort_suffix(effort);
+        if effort_suffix.is_empty() {
+            display_name
+        } else {
+            format!("{display_name} {effort_suffix}")
+        }
+    } else {
+        display_name
+    }
+}
+
 /// Get short suffix for reasoning effort level
 fn reasoning_effort_suffix(effort: ReasoningEffort) -> &'static str {
     match effort {
@@ -92,6 +130,62 @@ fn simplify_model_name(name: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::path::Path;
+
+    fn display(model: &str, mode: ModelDisplay) -> String {
+        let ctx = StatusLineContext::new(model, Path::new("/tmp"))
+            .with_reasoning_effort(Some(ReasoningEffort::High))
+            .with_model_display(mode);
+        ModelSegment.collect(&ctx).unwrap().primary
+    }
+
+    #[test]
+    fn test_display_modes() {
+        let model = "gpt-5.1-codex-max-20250101";
+        assert_eq!(
+            display(model, ModelDisplay::Pretty),
+            "GPT 5.1 Codex Max High"
+        );
+        assert_eq!(display(model, ModelDisplay::Short), "GPT 5.1 Codex Max");
+        assert_eq!(display(model, ModelDisplay::Raw), model);
+    }
+
+    #[test]
+    fn test_display_modes_custom_model() {
+        let model = "acme/llama-70b-2024-11-30";
+        assert_eq!(display(model, ModelDisplay::Pretty), "acme/llama-70b High");
+        assert_eq!(display(model, ModelDisplay::Short), "acme/llama-70b");
+        assert_eq!(display(model, ModelDisplay::Raw), model);
+    }
+
+    #[test]
+    fn test_model_id_metadata_is_raw() {
+        let ctx = StatusLineContext::new("gpt-5-2025-08-07", Path::new("/tmp"));
+        let data = ModelSegment.collect(&ctx).unwrap();
+        assert_eq!(
+            data.metadata.get("model_id").map(String::as_str),
+            Some("gpt-5-2025-08-07")
+        );
+    }
+
+    #[test]
+    fn test_display_from_config() {
+        let mut config = CxLineConfig::default();
+        assert_eq!(ModelDisplay::from_config(&config), ModelDisplay::Pretty);
+        for (value, expected) in [
+            ("raw", ModelDisplay::Raw),
+            ("short", ModelDisplay::Short),
+            ("pretty", ModelDisplay::Pretty),
+            ("fancy", ModelDisplay::Pretty),
+        ] {
+            config
+                .segments
+                .model
+                .options
+                .insert("model_display".to_string(), serde_json::json!(value));
+            assert_eq!(ModelDisplay::from_config(&config), expected, "{value}");
+        }
+    }
 
     #[test]
     fn test_simplify_model_name() {
@@ -0,0 +1,163 @@
+// Profile Segment - 显示当前 profile 及（可选）账号标识
+
+use crate::statusline::CxLineConfig;
+use crate::statusline::StatusLineContext;
+use crate::statusline::segment::Segment;
+use crate::statusline::segment::SegmentData;
+use crate::statusline::segment::SegmentId;
+
+/// 非默认 profile 的文本颜色
+const NON_DEFAULT_COLOR: &str = "yellow";
+
+/// 账号标识最大长度（不含 @ 之后的组织部分）
+const MAX_ACCOUNT_ID_CHARS: usize = 8;
+
+/// 来自 core 配置与认证状态的 profile 信息
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProfileInfo {
+    /// 当前生效的 profile（None 表示默认配置）
+    pub active_profile: Option<String>,
+    /// config.toml 中定义的 profile 数量
+    pub configured_profiles: usize,
+    /// 已缩短的账号/组织标识
+    pub account: Option<String>,
+}
+
+impl ProfileInfo {
+    pub fn new(
+        active_profile: Option<String>,
+        configured_profiles: usize,
+        account_email: Option<&str>,
+        account_id: Option<&str>,
+    ) -> Self {
+        let account = account_email
+            .and_then(shorten_email)
+            .or_else(|| account_id.map(shorten_account_id));
+        Self {
+            active_profile,
+            configured_profiles,
+            account,
+        }
+    }
+}
+
+/// Profile segment 的显示选项（来自配置）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProfileOptions {
+    /// 是否显示账号标识（`segments.profile.options.show_account`）
+    pub show_account: bool,
+}
+
+impl ProfileOptions {
+    pub fn from_config(config: &CxLineConfig) -> Self {
+        let show_account = config
+            .segments
+            .profile
+            .options
+            .get("show_account")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false);
+        Self { show_account }
+    }
+}
+
+pub struct ProfileSegment;
+
+impl Segment for ProfileSegment {
+    fn collect(&self, ctx: &StatusLineContext) -> Option<SegmentData> {
+        let info = ctx.profile?;
+        // 只有默认配置时无需提示
+        if info.active_profile.is_none() && info.configured_profiles == 0 {
+            return None;
+        }
+
+        let name = info.active_profile.as_deref().unwrap_or("default");
+        let mut data = SegmentData::new(name);
+        if ctx.profile_options.show_account
+            && let Some(account) = &info.account
+        {
+            data = data.with_secondary(format!("· {account}"));
+        }
+        if info.active_profile.is_some() {
+            data = data.with_metadata("dynamic_color", NON_DEFAULT_COLOR);
+        }
+        Some(data)
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::Profile
+    }
+}
+
+/// "alice@acme.example.com" -> "alice@acme"
+fn shorten_email(email: &str) -> Option<String> {
+    let (user, domain) = email.split_once('@')?;
+    let org = domain.split('.').next().unwrap_or(domain);
+    Some(format!("{user}@{org}"))
+}
+
+/// 账号 ID 只保留前几位
+fn shorten_account_id(account_id: &str) -> String {
+    if account_id.chars().count() <= MAX_ACCOUNT_ID_CHARS {
+        return account_id.to_string();
+    }
+    let prefix: String = account_id.chars().take(MAX_ACCOUNT_ID_CHARS).collect();
+    format!("{prefix}…")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn collect(info: &ProfileInfo, show_account: bool) -> Option<SegmentData> {
+        let ctx = StatusLineContext::new("gpt-5", Path::new("/tmp"))
+            .with_profile(info, ProfileOptions { show_account });
+        ProfileSegment.collect(&ctx)
+    }
+
+    #[test]
+    fn test_hidden_with_only_default_profile() {
+        let info = ProfileInfo::new(None, 0, Some("alice@acme.com"), None);
+        assert!(collect(&info, true).is_none());
+    }
+
+    #[test]
+    fn test_default_profile_among_others() {
+        let info = ProfileInfo::new(None, 2, None, None);
+        let data = collect(&info, false).unwrap();
+        assert_eq!(data.primary, "default");
+        assert!(!data.metadata.contains_key("dynamic_color"));
+    }
+
+    #[test]
+    fn test_non_default_profile_is_colored() {
+        let info = ProfileInfo::new(Some("work".to_string()), 2, None, None);
+        let data = collect(&info, false).unwrap();
+        assert_eq!(data.primary, "work");
+        assert_eq!(
+            data.metadata.get("dynamic_color").map(String::as_str),
+            Some(NON_DEFAULT_COLOR)
+        );
+    }
+
+    #[test]
+    fn test_account_only_when_enabled() {
+        let info = ProfileInfo::new(
+            Some("work".to_string()),
+            1,
+            Some("alice@acme.example.com"),
+            Some("acct-1234567890"),
+        );
+        assert!(collect(&info, false).unwrap().secondary.is_empty());
+        assert_eq!(collect(&info, true).unwrap().secondary, "· alice@acme");
+    }
+
+    #[test]
+    fn test_account_id_fallback() {
+        let info = ProfileInfo::new(None, 1, None, Some("acct-1234567890"));
+        assert_eq!(info.account.as_deref(), Some("acct-123…"));
+        let info = ProfileInfo::new(None, 1, None, Some("short"));
+        assert_eq!(info.account.as_deref(), Some("short"));
+    }
+}
@@ -0,0 +1,161 @@
+// TranslationStatus Segment - 显示等待中的翻译 barrier、离线状态及即将耗尽的速率限额
+
+use std::time::Duration;
+use std::time::Instant;
+
+use chrono::DateTime;
+use chrono::Utc;
+
+use crate::statusline::StatusLineContext;
+use crate::statusline::segment::Segment;
+use crate::statusline::segment::SegmentData;
+use crate::statusline::segment::SegmentId;
+use crate::translation::BarrierInfo;
+use crate::translation::RateLimitState;
+
+/// 距离超时不足该时长时以红色显示
+const DEADLINE_WARNING: Duration = Duration::from_millis(500);
+
+/// 翻译离线时的文本颜色
+const OFFLINE_COLOR: &str = "yellow";
+
+/// 速率限额即将耗尽时的文本颜色
+const RATE_LIMIT_COLOR: &str = "yellow";
+
+pub struct TranslationStatusSegment;
+
+impl Segment for TranslationStatusSegment {
+    fn collect(&self, ctx: &StatusLineContext) -> Option<SegmentData> {
+        match ctx.translation_barrier_info.as_ref() {
+            Some(info) => Some(barrier_status(info, Instant::now())),
+            None if ctx.translation_offline => Some(
+                SegmentData::new("Translation offline")
+                    .with_metadata("dynamic_color", OFFLINE_COLOR),
+            ),
+            None => ctx
+                .translation_rate_limit
+                .filter(RateLimitState::is_low)
+                .map(|state| rate_limit_status(&state, Utc::now())),
+        }
+    }
+
+    fn id(&self) -> SegmentId {
+        SegmentId::TranslationStatus
+    }
+}
+
+/// 根据 barrier 信息生成显示数据，如 "Translating (2.3s)"
+fn barrier_status(info: &BarrierInfo, now: Instant) -> SegmentData {
+    let elapsed = now.saturating_duration_since(info.started_at);
+    let remaining = info.deadline.saturating_duration_since(now);
+
+    let mut data = SegmentData::new(format!("Translating ({:.1}s)", elapsed.as_secs_f64()))
+        .with_metadata("elapsed_ms", elapsed.as_millis().to_string())
+        .with_metadata("deferred", info.deferred_count.to_string());
+    if info.deferred_count > 0 {
+        data = data.with_secondary(format!("· {} waiting", info.deferred_count));
+    }
+    if remaining <= DEADLINE_WARNING {
+        data = data.with_metadata("dynamic_color", "red");
+    }
+    data
+}
+
+/// 生成速率限额提示，如 "Translation: 3 req left · resets in 42s"
+fn rate_limit_status(state: &RateLimitState, now: DateTime<Utc>) -> SegmentData {
+    let remaining = state.remaining_requests.unwrap_or_default();
+    let mut data = SegmentData::new(format!("Translation: {remaining} req left"))
+        .with_metadata("dynamic_color", RATE_LIMIT_COLOR);
+    if let Some(reset_at) = state.reset_requests_at
+        && reset_at > now
+    {
+        let secs = (reset_at - now).num_seconds().max(1);
+        data = data.with_secondary(format!("· resets in {secs}s"));
+    }
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::ThreadId;
+
+    fn info(started_at: Instant, max_wait: Duration, deferred_count: usize) -> BarrierInfo {
+        BarrierInfo {
+            started_at,
+            deadline: started_at + max_wait,
+            thread_id: ThreadId::new(),
+            deferred_count,
+        }
+    }
+
+    #[test]
+    fn test_hidden_without_barrier() {
+        let ctx = StatusLineContext::new("gpt-5", std::path::Path::new("/tmp"));
+        assert!(TranslationStatusSegment.collect(&ctx).is_none());
+    }
+
+    #[test]
+    fn test_shows_offline() {
+        let ctx = StatusLineContext::new("gpt-5", std::path::Path::new("/tmp"))
+            .with_translation_offline(true);
+        let data = TranslationStatusSegment.collect(&ctx).unwrap();
+        assert_eq!(data.primary, "Translation offline");
+        assert_eq!(
+            data.metadata.get("dynamic_color").map(String::as_str),
+            Some(OFFLINE_COLOR)
+        );
+    }
+
+    #[test]
+    fn test_shows_elapsed_time() {
+        let t0 = Instant::now();
+        let data = barrier_status(
+            &info(t0, Duration::from_secs(5), 0),
+            t0 + Duration::from_millis(2300),
+        );
+        assert_eq!(data.primary, "Translating (2.3s)");
+        assert!(data.secondary.is_empty());
+        assert!(!data.metadata.contains_key("dynamic_color"));
+    }
+
+    #[test]
+    fn test_red_near_deadline() {
+        let t0 = Instant::now();
+        let data = barrier_status(
+            &info(t0, Duration::from_secs(5), 2),
+            t0 + Duration::from_millis(4600),
+        );
+        assert_eq!(data.primary, "Translating (4.6s)");
+        assert_eq!(data.secondary, "· 2 waiting");
+        assert_eq!(
+            data.metadata.get("dynamic_color").map(String::as_str),
+            Some("red")
+        );
+    }
+
+    #[test]
+    fn test_shows_low_rate_limit() {
+        let now = Utc::now();
+        let state = RateLimitState {
+            remaining_requests: Some(3),
+            remaining_tokens: None,
+            reset_requests_at: Some(now + chrono::Duration::seconds(42)),
+        };
+        let data = rate_limit_status(&state, now);
+        assert_eq!(data.primary, "Translation: 3 req left");
+        assert_eq!(data.secondary, "· resets in 42s");
+        assert_eq!(
+            data.metadata.get("dynamic_color").map(String::as_str),
+            Some(RATE_LIMIT_COLOR)
+        );
+
+        let plenty = RateLimitState {
+            remaining_requests: Some(400),
+            ..state
+        };
+        let ctx = StatusLineContext::new("gpt-5", std::path::Path::new("/tmp"))
+            .with_translation_rate_limit(Some(plenty));
+        assert!(TranslationStatusSegment.collect(&ctx).is_none());
+    }
+}
@@ -154,7 +154,64 @@ impl ThemePresets {
                     styles: TextStyleConfig::default(),
                     options: HashMap::new(),
                 },
+                activity: SegmentItemConfig {
+                    id: super::segment::SegmentId::Activity,
+                    enabled: true,
+                    icon: IconConfig::new("⏳", "\u{f110}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                translation_status: SegmentItemConfig {
+                    id: super::segment::SegmentId::TranslationStatus,
+                    enabled: true,
+                    icon: IconConfig::new("🌐", "\u{f05ca}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                host: SegmentItemConfig {
+                    id: super::segment::SegmentId::Host,
+                    enabled: true,
+                    icon: IconConfig::new("🖥", "\u{f108}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                last_turn: SegmentItemConfig {
+                    id: super::segment::SegmentId::LastTurn,
+                    enabled: true,
+                    icon: IconConfig::new("⏱", "\u{f017}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                profile: SegmentItemConfig {
+                    id: super::segment::SegmentId::Profile,
+                    enabled: true,
+                    icon: IconConfig::new("👤", "\u{f007}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                diff_stats: SegmentItemConfig {
+                    id: super::segment::SegmentId::DiffStats,
+                    enabled: true,
+                    icon: IconConfig::new("±", "\u{f440}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                mcp: SegmentItemConfig {
+                    id: super::segment::SegmentId::Mcp,
+                    enabled: true,
+                    icon: IconConfig::new("🔌", "\u{f1e6}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
             },
+            order: Vec::new(),
         }
     }
 
@@ -206,7 +263,64 @@ impl ThemePresets {
                     styles: TextStyleConfig::default(),
                     options: HashMap::new(),
                 },
+                activity: SegmentItemConfig {
+                    id: super::segment::SegmentId::Activity,
+                    enabled: true,
+                    icon: IconConfig::new("⏳", "\u{f110}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                translation_status: SegmentItemConfig {
+                    id: super::segment::SegmentId::TranslationStatus,
+                    enabled: true,
+                    icon: IconConfig::new("🌐", "\u{f05ca}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                host: SegmentItemConfig {
+                    id: super::segment::SegmentId::Host,
+                    enabled: true,
+                    icon: IconConfig::new("🖥", "\u{f108}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                last_turn: SegmentItemConfig {
+                    id: super::segment::SegmentId::LastTurn,
+                    enabled: true,
+                    icon: IconConfig::new("⏱", "\u{f017}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                profile: SegmentItemConfig {
+                    id: super::segment::SegmentId::Profile,
+                    enabled: true,
+                    icon: IconConfig::new("👤", "\u{f007}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                diff_stats: SegmentItemConfig {
+                    id: super::segment::SegmentId::DiffStats,
+                    enabled: true,
+                    icon: IconConfig::new("±", "\u{f440}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                mcp: SegmentItemConfig {
+                    id: super::segment::SegmentId::Mcp,
+                    enabled: true,
+                    icon: IconConfig::new("🔌", "\u{f1e6}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
             },
+            order: Vec::new(),
         }
     }
 
@@ -258,7 +372,64 @@ impl ThemePresets {
                     styles: TextStyleConfig::default(),
                     options: HashMap::new(),
                 },
+                activity: SegmentItemConfig {
+                    id: super::segment::SegmentId::Activity,
+                    enabled: true,
+                    icon: IconConfig::new("⏳", "\u{f110}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                translation_status: SegmentItemConfig {
+                    id: super::segment::SegmentId::TranslationStatus,
+                    enabled: true,
+                    icon: IconConfig::new("🌐", "\u{f05ca}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                host: SegmentItemConfig {
+                    id: super::segment::SegmentId::Host,
+                    enabled: true,
+                    icon: IconConfig::new("🖥", "\u{f108}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                last_turn: SegmentItemConfig {
+                    id: super::segment::SegmentId::LastTurn,
+                    enabled: true,
+                    icon: IconConfig::new("⏱", "\u{f017}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                profile: SegmentItemConfig {
+                    id: super::segment::SegmentId::Profile,
+                    enabled: true,
+                    icon: IconConfig::new("👤", "\u{f007}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                diff_stats: SegmentItemConfig {
+                    id: super::segment::SegmentId::DiffStats,
+                    enabled: true,
+                    icon: IconConfig::new("±", "\u{f440}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                mcp: SegmentItemConfig {
+                    id: super::segment::SegmentId::Mcp,
+                    enabled: true,
+                    icon: IconConfig::new("🔌", "\u{f1e6}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
             },
+            order: Vec::new(),
         }
     }
 
@@ -314,7 +485,64 @@ impl ThemePresets {
                     styles: TextStyleConfig::default(),
                     options: HashMap::new(),
                 },
+                activity: SegmentItemConfig {
+                    id: super::segment::SegmentId::Activity,
+                    enabled: true,
+                    icon: IconConfig::new("⏳", "\u{f110}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                translation_status: SegmentItemConfig {
+                    id: super::segment::SegmentId::TranslationStatus,
+                    enabled: true,
+                    icon: IconConfig::new("🌐", "\u{f05ca}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                host: SegmentItemConfig {
+                    id: super::segment::SegmentId::Host,
+                    enabled: true,
+                    icon: IconConfig::new("🖥", "\u{f108}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                last_turn: SegmentItemConfig {
+                    id: super::segment::SegmentId::LastTurn,
+                    enabled: true,
+                    icon: IconConfig::new("⏱", "\u{f017}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                profile: SegmentItemConfig {
+                    id: super::segment::SegmentId::Profile,
+                    enabled: true,
+                    icon: IconConfig::new("👤", "\u{f007}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                diff_stats: SegmentItemConfig {
+                    id: super::segment::SegmentId::DiffStats,
+                    enabled: true,
+                    icon: IconConfig::new("±", "\u{f440}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                mcp: SegmentItemConfig {
+                    id: super::segment::SegmentId::Mcp,
+                    enabled: true,
+                    icon: IconConfig::new("🔌", "\u{f1e6}"),
+                    colors: ColorConfig::new(ansi16::BRIGHT_CYAN, ansi16::BRIGHT_CYAN),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
             },
+            order: Vec::new(),
         }
     }
 
@@ -373,7 +601,64 @@ impl ThemePresets {
                     styles: TextStyleConfig::default(),
                     options: HashMap::new(),
                 },
+                activity: SegmentItemConfig {
+                    id: super::segment::SegmentId::Activity,
+                    enabled: true,
+                    icon: IconConfig::new("⏳", "\u{f110}"),
+                    colors: ColorConfig::new(nord_polar, nord_polar).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                translation_status: SegmentItemConfig {
+                    id: super::segment::SegmentId::TranslationStatus,
+                    enabled: true,
+                    icon: IconConfig::new("🌐", "\u{f05ca}"),
+                    colors: ColorConfig::new(nord_polar, nord_polar).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                host: SegmentItemConfig {
+                    id: super::segment::SegmentId::Host,
+                    enabled: true,
+                    icon: IconConfig::new("🖥", "\u{f108}"),
+                    colors: ColorConfig::new(nord_polar, nord_polar).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                last_turn: SegmentItemConfig {
+                    id: super::segment::SegmentId::LastTurn,
+                    enabled: true,
+                    icon: IconConfig::new("⏱", "\u{f017}"),
+                    colors: ColorConfig::new(nord_polar, nord_polar).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                profile: SegmentItemConfig {
+                    id: super::segment::SegmentId::Profile,
+                    enabled: true,
+                    icon: IconConfig::new("👤", "\u{f007}"),
+                    colors: ColorConfig::new(nord_polar, nord_polar).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                diff_stats: SegmentItemConfig {
+                    id: super::segment::SegmentId::DiffStats,
+                    enabled: true,
+                    icon: IconConfig::new("±", "\u{f440}"),
+                    colors: ColorConfig::new(nord_polar, nord_polar).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                mcp: SegmentItemConfig {
+                    id: super::segment::SegmentId::Mcp,
+                    enabled: true,
+                    icon: IconConfig::new("🔌", "\u{f1e6}"),
+                    colors: ColorConfig::new(nord_polar, nord_polar).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
             },
+            order: Vec::new(),
         }
     }
 
@@ -434,7 +719,64 @@ impl ThemePresets {
                     styles: TextStyleConfig::default(),
                     options: HashMap::new(),
                 },
+                activity: SegmentItemConfig {
+                    id: super::segment::SegmentId::Activity,
+                    enabled: true,
+                    icon: IconConfig::new("⏳", "\u{f110}"),
+                    colors: ColorConfig::new(light_gray, light_gray).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                translation_status: SegmentItemConfig {
+                    id: super::segment::SegmentId::TranslationStatus,
+                    enabled: true,
+                    icon: IconConfig::new("🌐", "\u{f05ca}"),
+                    colors: ColorConfig::new(light_gray, light_gray).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                host: SegmentItemConfig {
+                    id: super::segment::SegmentId::Host,
+                    enabled: true,
+                    icon: IconConfig::new("🖥", "\u{f108}"),
+                    colors: ColorConfig::new(light_gray, light_gray).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                last_turn: SegmentItemConfig {
+                    id: super::segment::SegmentId::LastTurn,
+                    enabled: true,
+                    icon: IconConfig::new("⏱", "\u{f017}"),
+                    colors: ColorConfig::new(light_gray, light_gray).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                profile: SegmentItemConfig {
+                    id: super::segment::SegmentId::Profile,
+                    enabled: true,
+                    icon: IconConfig::new("👤", "\u{f007}"),
+                    colors: ColorConfig::new(light_gray, light_gray).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                diff_stats: SegmentItemConfig {
+                    id: super::segment::SegmentId::DiffStats,
+                    enabled: true,
+                    icon: IconConfig::new("±", "\u{f440}"),
+                    colors: ColorConfig::new(light_gray, light_gray).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                mcp: SegmentItemConfig {
+                    id: super::segment::SegmentId::Mcp,
+                    enabled: true,
+                    icon: IconConfig::new("🔌", "\u{f1e6}"),
+                    colors: ColorConfig::new(light_gray, light_gray).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
             },
+            order: Vec::new(),
         }
     }
 
@@ -495,7 +837,64 @@ impl ThemePresets {
                     styles: TextStyleConfig::default(),
                     options: HashMap::new(),
                 },
+                activity: SegmentItemConfig {
+                    id: super::segment::SegmentId::Activity,
+                    enabled: true,
+                    icon: IconConfig::new("⏳", "\u{f110}"),
+                    colors: ColorConfig::new(white, white).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                translation_status: SegmentItemConfig {
+                    id: super::segment::SegmentId::TranslationStatus,
+                    enabled: true,
+                    icon: IconConfig::new("🌐", "\u{f05ca}"),
+                    colors: ColorConfig::new(white, white).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                host: SegmentItemConfig {
+                    id: super::segment::SegmentId::Host,
+                    enabled: true,
+                    icon: IconConfig::new("🖥", "\u{f108}"),
+                    colors: ColorConfig::new(white, white).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                last_turn: SegmentItemConfig {
+                    id: super::segment::SegmentId::LastTurn,
+                    enabled: true,
+                    icon: IconConfig::new("⏱", "\u{f017}"),
+                    colors: ColorConfig::new(white, white).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                profile: SegmentItemConfig {
+                    id: super::segment::SegmentId::Profile,
+                    enabled: true,
+                    icon: IconConfig::new("👤", "\u{f007}"),
+                    colors: ColorConfig::new(white, white).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                diff_stats: SegmentItemConfig {
+                    id: super::segment::SegmentId::DiffStats,
+                    enabled: true,
+                    icon: IconConfig::new("±", "\u{f440}"),
+                    colors: ColorConfig::new(white, white).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                mcp: SegmentItemConfig {
+                    id: super::segment::SegmentId::Mcp,
+                    enabled: true,
+                    icon: IconConfig::new("🔌", "\u{f1e6}"),
+                    colors: ColorConfig::new(white, white).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
             },
+            order: Vec::new(),
         }
     }
 
@@ -559,7 +958,64 @@ impl ThemePresets {
                     styles: TextStyleConfig::default(),
                     options: HashMap::new(),
                 },
+                activity: SegmentItemConfig {
+                    id: super::segment::SegmentId::Activity,
+                    enabled: true,
+                    icon: IconConfig::new("⏳", "\u{f110}"),
+                    colors: ColorConfig::new(gold, gold).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                translation_status: SegmentItemConfig {
+                    id: super::segment::SegmentId::TranslationStatus,
+                    enabled: true,
+                    icon: IconConfig::new("🌐", "\u{f05ca}"),
+                    colors: ColorConfig::new(gold, gold).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                host: SegmentItemConfig {
+                    id: super::segment::SegmentId::Host,
+                    enabled: true,
+                    icon: IconConfig::new("🖥", "\u{f108}"),
+                    colors: ColorConfig::new(gold, gold).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                last_turn: SegmentItemConfig {
+                    id: super::segment::SegmentId::LastTurn,
+                    enabled: true,
+                    icon: IconConfig::new("⏱", "\u{f017}"),
+                    colors: ColorConfig::new(gold, gold).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                profile: SegmentItemConfig {
+                    id: super::segment::SegmentId::Profile,
+                    enabled: true,
+                    icon: IconConfig::new("👤", "\u{f007}"),
+                    colors: ColorConfig::new(gold, gold).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                diff_stats: SegmentItemConfig {
+                    id: super::segment::SegmentId::DiffStats,
+                    enabled: true,
+                    icon: IconConfig::new("±", "\u{f440}"),
+                    colors: ColorConfig::new(gold, gold).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                mcp: SegmentItemConfig {
+                    id: super::segment::SegmentId::Mcp,
+                    enabled: true,
+                    icon: IconConfig::new("🔌", "\u{f1e6}"),
+                    colors: ColorConfig::new(gold, gold).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
             },
+            order: Vec::new(),
         }
     }
 
@@ -623,7 +1079,64 @@ impl ThemePresets {
                     styles: TextStyleConfig::default(),
                     options: HashMap::new(),
                 },
+                activity: SegmentItemConfig {
+                    id: super::segment::SegmentId::Activity,
+                    enabled: true,
+                    icon: IconConfig::new("⏳", "\u{f110}"),
+                    colors: ColorConfig::new(orange, orange).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                translation_status: SegmentItemConfig {
+                    id: super::segment::SegmentId::TranslationStatus,
+                    enabled: true,
+                    icon: IconConfig::new("🌐", "\u{f05ca}"),
+                    colors: ColorConfig::new(orange, orange).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                host: SegmentItemConfig {
+                    id: super::segment::SegmentId::Host,
+                    enabled: true,
+                    icon: IconConfig::new("🖥", "\u{f108}"),
+                    colors: ColorConfig::new(orange, orange).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                last_turn: SegmentItemConfig {
+                    id: super::segment::SegmentId::LastTurn,
+                    enabled: true,
+                    icon: IconConfig::new("⏱", "\u{f017}"),
+                    colors: ColorConfig::new(orange, orange).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                profile: SegmentItemConfig {
+                    id: super::segment::SegmentId::Profile,
+                    enabled: true,
+                    icon: IconConfig::new("👤", "\u{f007}"),
+                    colors: ColorConfig::new(orange, orange).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                diff_stats: SegmentItemConfig {
+                    id: super::segment::SegmentId::DiffStats,
+                    enabled: true,
+                    icon: IconConfig::new("±", "\u{f440}"),
+                    colors: ColorConfig::new(orange, orange).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
+                mcp: SegmentItemConfig {
+                    id: super::segment::SegmentId::Mcp,
+                    enabled: true,
+                    icon: IconConfig::new("🔌", "\u{f1e6}"),
+                    colors: ColorConfig::new(orange, orange).with_background(bg_usage),
+                    styles: TextStyleConfig::default(),
+                    options: HashMap::new(),
+                },
             },
+            order: Vec::new(),
         }
     }
 }
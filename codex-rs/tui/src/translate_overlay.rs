@@ -2,8 +2,16 @@
 //!
 //! Provides a full-screen UI for configuring translation settings.
 
+use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::io::Result;
+use std::path::PathBuf;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
 
+use codex_core::config::find_codex_home;
+use codex_core::config::translation_config_path;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
@@ -22,97 +30,30 @@ use ratatui::widgets::Block;
 use ratatui::widgets::Borders;
 use ratatui::widgets::Paragraph;
 use ratatui::widgets::Widget;
-
+use ratatui::widgets::Wrap;
+use tokio::sync::oneshot;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthChar;
+use unicode_width::UnicodeWidthStr;
+
+use crate::color_mode::ColorMode;
+use crate::color_mode::strip_colors;
+use crate::hyperlink::osc8;
+use crate::hyperlink::supports_hyperlinks;
+use crate::live_wrap::take_prefix_by_width;
+
+use crate::translation::ApiKeyStatus;
+use crate::translation::DEFAULT_MAX_WAIT_MS;
 use crate::translation::ProviderId;
+use crate::translation::TargetLanguage;
 use crate::translation::TranslationConfig;
+use crate::translation::check_api_key;
+use crate::translation::max_wait_ms_warning;
+use crate::translation::translate_sample;
 use crate::tui;
+use crate::tui::FrameRequester;
 use crate::tui::TuiEvent;
 
-/// Supported target languages.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum TargetLanguage {
-    ChineseSimplified,
-    ChineseTraditional,
-    Japanese,
-    Korean,
-    English,
-    Spanish,
-    French,
-    German,
-    Russian,
-    Portuguese,
-    Italian,
-    Arabic,
-    Hindi,
-    Vietnamese,
-    Thai,
-}
-
-impl TargetLanguage {
-    const ALL: &'static [Self] = &[
-        Self::ChineseSimplified,
-        Self::ChineseTraditional,
-        Self::Japanese,
-        Self::Korean,
-        Self::English,
-        Self::Spanish,
-        Self::French,
-        Self::German,
-        Self::Russian,
-        Self::Portuguese,
-        Self::Italian,
-        Self::Arabic,
-        Self::Hindi,
-        Self::Vietnamese,
-        Self::Thai,
-    ];
-
-    fn code(self) -> &'static str {
-        match self {
-            Self::ChineseSimplified => "zh-CN",
-            Self::ChineseTraditional => "zh-TW",
-            Self::Japanese => "ja",
-            Self::Korean => "ko",
-            Self::English => "en",
-            Self::Spanish => "es",
-            Self::French => "fr",
-            Self::German => "de",
-            Self::Russian => "ru",
-            Self::Portuguese => "pt",
-            Self::Italian => "it",
-            Self::Arabic => "ar",
-            Self::Hindi => "hi",
-            Self::Vietnamese => "vi",
-            Self::Thai => "th",
-        }
-    }
-
-    fn name(self) -> &'static str {
-        match self {
-            Self::ChineseSimplified => "Chinese (Simplified)",
-            Self::ChineseTraditional => "Chinese (Traditional)",
-            Self::Japanese => "Japanese",
-            Self::Korean => "Korean",
-            Self::English => "English",
-            Self::Spanish => "Spanish",
-            Self::French => "French",
-            Self::German => "German",
-            Self::Russian => "Russian",
-            Self::Portuguese => "Portuguese",
-            Self::Italian => "Italian",
-            Self::Arabic => "Arabic",
-            Self::Hindi => "Hindi",
-            Self::Vietnamese => "Vietnamese",
-            Self::Thai => "Thai",
-        }
-    }
-
-    #[allow(dead_code)]
-    fn from_code(code: &str) -> Option<Self> {
-        Self::ALL.iter().find(|l| l.code() == code).copied()
-    }
-}
-
 /// Current selection in the overlay.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Selection {
@@ -121,7 +62,11 @@ enum Selection {
     ApiKey,
     Model,
     Language,
+    Proxy,
     BaseUrl,
+    ProviderBaseUrl,
+    MaxWaitMs,
+    AccountId,
 }
 
 impl Selection {
@@ -131,18 +76,75 @@ impl Selection {
         Self::ApiKey,
         Self::Model,
         Self::Language,
+        Self::Proxy,
         Self::BaseUrl,
+        Self::ProviderBaseUrl,
+        Self::MaxWaitMs,
+        Self::AccountId,
     ];
 
-    fn next(self) -> Self {
-        let idx = Self::ALL.iter().position(|s| *s == self).unwrap_or(0);
-        Self::ALL[(idx + 1) % Self::ALL.len()]
+    /// Whether this field is shown for the given provider.
+    fn is_visible(self, provider_id: ProviderId) -> bool {
+        match self {
+            Self::AccountId => provider_id == ProviderId::Cloudflare,
+            _ => true,
+        }
     }
 
-    fn prev(self) -> Self {
-        let idx = Self::ALL.iter().position(|s| *s == self).unwrap_or(0);
-        Self::ALL[(idx + Self::ALL.len() - 1) % Self::ALL.len()]
+    fn next(self, provider_id: ProviderId) -> Self {
+        self.step(provider_id, 1)
     }
+
+    fn prev(self, provider_id: ProviderId) -> Self {
+        self.step(provider_id, Self::ALL.len() - 1)
+    }
+
+    /// Move `offset` positions forward (wrapping), skipping hidden fields.
+    fn step(self, provider_id: ProviderId, offset: usize) -> Self {
+        let mut idx = Self::ALL.iter().position(|s| *s == self).unwrap_or(0);
+        loop {
+            idx = (idx + offset) % Self::ALL.len();
+            let candidate = Self::ALL[idx];
+            if candidate.is_visible(provider_id) {
+                return candidate;
+            }
+        }
+    }
+}
+
+/// Built-in text translated by the sample preview.
+const PREVIEW_SAMPLE: &str = "The build failed because the linker could not find the OpenSSL \
+symbols. I will add the missing dependency to Cargo.toml and run the tests again.";
+
+/// Rows reserved for the sample preview panel when it is shown.
+const PREVIEW_HEIGHT: u16 = 5;
+
+/// Form settings a sample preview was produced with; changing any of them makes it stale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PreviewKey {
+    provider_id: ProviderId,
+    model: String,
+    language: TargetLanguage,
+}
+
+/// Result of translating the sample.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PreviewOutcome {
+    result: std::result::Result<String, String>,
+    latency: Duration,
+}
+
+#[derive(Debug)]
+enum PreviewState {
+    /// Waiting for the translation task.
+    Running(oneshot::Receiver<PreviewOutcome>),
+    Done(PreviewOutcome),
+}
+
+#[derive(Debug)]
+struct SamplePreview {
+    key: PreviewKey,
+    state: PreviewState,
 }
 
 /// Input mode for text fields.
@@ -168,6 +170,14 @@ pub(crate) struct TranslateOverlay {
     model: String,
     /// Custom base URL.
     base_url: String,
+    /// Base URL overrides by provider id; only the selected provider's entry is shown.
+    base_url_per_provider: HashMap<String, String>,
+    /// Cloudflare account ID.
+    account_id: String,
+    /// HTTP(S) or SOCKS5 proxy URL.
+    proxy: String,
+    /// Translation wait in milliseconds; digits only.
+    max_wait_ms: String,
     /// Selected target language.
     language: TargetLanguage,
     /// Language selection index.
@@ -184,6 +194,25 @@ pub(crate) struct TranslateOverlay {
     status_message: Option<String>,
     /// Whether config was modified.
     modified: bool,
+    /// Loaded configuration, used to preserve settings not editable here.
+    base_config: TranslationConfig,
+    /// File to save to; the default location when unset.
+    save_path: Option<PathBuf>,
+    /// Base URL warning shown by the last save attempt; saving again with the same warning
+    /// goes ahead.
+    unconfirmed_save_warning: Option<String>,
+    /// Whether the sample preview panel is shown.
+    show_preview: bool,
+    /// Set by `p`; the preview starts once a frame requester is at hand.
+    preview_requested: bool,
+    /// Latest sample preview, possibly for earlier settings.
+    preview: Option<SamplePreview>,
+    /// Set by `t`; the API key check starts once a frame requester is at hand.
+    key_check_requested: bool,
+    /// API key check in flight; its result replaces the status message.
+    key_check: Option<oneshot::Receiver<std::result::Result<ApiKeyStatus, String>>>,
+    /// Whether colors are drawn or replaced with text attributes.
+    color_mode: ColorMode,
 }
 
 impl TranslateOverlay {
@@ -200,13 +229,20 @@ impl TranslateOverlay {
         // Find language index
         let language_index = TargetLanguage::ALL
             .iter()
-            .position(|l| l.code() == config.target_language)
+            .position(|l| *l == config.target_language)
             .unwrap_or(0);
         let language = TargetLanguage::ALL[language_index];
 
         let api_key = config.api_key.clone().unwrap_or_default();
         let model = config.model.clone().unwrap_or_default();
         let base_url = config.base_url.clone().unwrap_or_default();
+        let base_url_per_provider = config.base_url_per_provider.clone();
+        let account_id = config.cloudflare_account_id.clone().unwrap_or_default();
+        let proxy = config.proxy.clone().unwrap_or_default();
+        let max_wait_ms = config
+            .max_wait_ms
+            .map(|ms| ms.to_string())
+            .unwrap_or_default();
 
         Self {
             enabled,
@@ -215,39 +251,104 @@ impl TranslateOverlay {
             api_key,
             model,
             base_url,
+            base_url_per_provider,
+            account_id,
+            proxy,
+            max_wait_ms,
             language,
             language_index,
             selection: Selection::Enabled,
             input_mode: InputMode::Normal,
             cursor_position: 0,
             is_done: false,
-            status_message: None,
+            status_message: config.unsupported_target_language.as_ref().map(|raw| {
+                format!("Target language {raw:?} is not supported; choose one to replace it")
+            }),
             modified: false,
+            base_config: config.clone(),
+            save_path: None,
+            unconfirmed_save_warning: None,
+            show_preview: false,
+            preview_requested: false,
+            preview: None,
+            key_check_requested: false,
+            key_check: None,
+            color_mode: ColorMode::default(),
         }
     }
 
-    /// Get the configured translation settings.
+    /// Draw with `color_mode`, normally [`ColorMode::detect`].
+    pub fn with_color_mode(mut self, color_mode: ColorMode) -> Self {
+        self.color_mode = color_mode;
+        self
+    }
+
+    /// Save to `path` (normally the loaded `Config::translation_config_path`).
+    pub fn with_save_path(mut self, path: PathBuf) -> Self {
+        self.save_path = Some(path);
+        self
+    }
+
+    /// Start with `provider` selected and its API key field in edit mode, after the provider
+    /// rejected the configured key.
+    pub fn editing_api_key(mut self, provider: ProviderId) -> Self {
+        if provider != self.provider_id
+            && let Some(index) = ProviderId::ALL.iter().position(|p| *p == provider)
+        {
+            self.set_provider(index);
+        }
+        self.selection = Selection::ApiKey;
+        self.enter_edit_mode();
+        self.status_message = Some(format!(
+            "{} rejected the API key; enter a new one, press Enter, then s to save",
+            provider.definition().name
+        ));
+        self
+    }
+
+    /// Get the configured translation settings. Key, model and base URLs are normalized the
+    /// way [`Self::save_config`] stores them.
     pub fn config(&self) -> TranslationConfig {
+        let api_key = self.api_key.trim();
+        let model = self.model.trim();
+        let base_url = normalize_base_url(&self.base_url);
         TranslationConfig {
             enabled: self.enabled,
-            target_language: self.language.code().to_string(),
+            target_language: self.language,
             provider: self.provider_id.as_str().to_string(),
-            api_key: if self.api_key.is_empty() {
+            api_key: if api_key.is_empty() {
+                None
+            } else {
+                Some(api_key.to_string())
+            },
+            model: if model.is_empty() {
+                None
+            } else {
+                Some(model.to_string())
+            },
+            base_url: if base_url.is_empty() {
                 None
             } else {
-                Some(self.api_key.clone())
+                Some(base_url)
             },
-            model: if self.model.is_empty() {
+            base_url_per_provider: self
+                .base_url_per_provider
+                .iter()
+                .map(|(id, url)| (id.clone(), normalize_base_url(url)))
+                .filter(|(_, url)| !url.is_empty())
+                .collect(),
+            cloudflare_account_id: if self.account_id.is_empty() {
                 None
             } else {
-                Some(self.model.clone())
+                Some(self.account_id.clone())
             },
-            base_url: if self.base_url.is_empty() {
+            proxy: if self.proxy.is_empty() {
                 None
             } else {
-                Some(self.base_url.clone())
+                Some(self.proxy.clone())
             },
-            timeout_ms: None,
+            max_wait_ms: self.max_wait_ms.parse().ok(),
+            ..self.base_config.clone()
         }
     }
 
@@ -262,10 +363,31 @@ impl TranslateOverlay {
         self.modified
     }
 
-    /// Save configuration to file.
+    /// Save configuration to file. A config that can't translate (no API key for a provider
+    /// that needs one) is still saved, with a warning. A base URL that looks wrong for the
+    /// provider is only warned about; pressing `s` again saves it anyway.
     fn save_config(&mut self) {
+        self.normalize_fields();
         let config = self.config();
-        match config.save() {
+        let warning = config.base_url_warning();
+        if warning.is_some() && warning != self.unconfirmed_save_warning {
+            self.status_message = warning
+                .as_ref()
+                .map(|warning| format!("{warning}. Press s again to save anyway"));
+            self.unconfirmed_save_warning = warning;
+            return;
+        }
+        self.unconfirmed_save_warning = None;
+        let saved = match &self.save_path {
+            Some(path) => config.save_to_path(path),
+            None => find_codex_home()
+                .and_then(|codex_home| config.save_to_path(&translation_config_path(&codex_home))),
+        };
+        match saved {
+            Ok(()) if !config.is_valid() => {
+                self.status_message =
+                    Some("Saved, but translation won't work without a key".to_string());
+            }
             Ok(()) => {
                 self.status_message = Some("Configuration saved".to_string());
             }
@@ -275,20 +397,34 @@ impl TranslateOverlay {
         }
     }
 
+    /// Apply the normalization of [`Self::config`] to the fields themselves, so the form
+    /// shows what is saved.
+    fn normalize_fields(&mut self) {
+        self.api_key = self.api_key.trim().to_string();
+        self.model = self.model.trim().to_string();
+        self.base_url = normalize_base_url(&self.base_url);
+        for url in self.base_url_per_provider.values_mut() {
+            *url = normalize_base_url(url);
+        }
+    }
+
     pub fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match event {
             TuiEvent::Key(key_event) => {
                 self.handle_key_event(key_event)?;
+                self.start_requested_preview(tui.frame_requester());
+                self.start_requested_key_check(tui.frame_requester());
                 tui.frame_requester().schedule_frame();
             }
             TuiEvent::Paste(text) => {
                 // Handle paste in editing mode
-                if self.input_mode == InputMode::Editing {
-                    self.handle_paste(&text);
+                if self.handle_paste_event(&text) {
                     tui.frame_requester().schedule_frame();
                 }
             }
             TuiEvent::Draw => {
+                self.poll_preview();
+                self.poll_key_check();
                 tui.draw(u16::MAX, |frame| {
                     self.render(frame.area(), frame.buffer_mut());
                 })?;
@@ -297,6 +433,16 @@ impl TranslateOverlay {
         Ok(())
     }
 
+    /// Apply a paste if a text field is being edited. Returns true if the paste was applied.
+    fn handle_paste_event(&mut self, text: &str) -> bool {
+        if self.input_mode == InputMode::Editing {
+            self.handle_paste(text);
+            true
+        } else {
+            false
+        }
+    }
+
     fn handle_key_event(&mut self, key_event: KeyEvent) -> Result<()> {
         if key_event.kind != KeyEventKind::Press && key_event.kind != KeyEventKind::Repeat {
             return Ok(());
@@ -315,10 +461,10 @@ impl TranslateOverlay {
                 self.is_done = true;
             }
             KeyCode::Up | KeyCode::Char('k') => {
-                self.selection = self.selection.prev();
+                self.selection = self.selection.prev(self.provider_id);
             }
             KeyCode::Down | KeyCode::Char('j') => {
-                self.selection = self.selection.next();
+                self.selection = self.selection.next(self.provider_id);
             }
             KeyCode::Left | KeyCode::Char('h') => {
                 self.adjust_current(-1);
@@ -340,11 +486,130 @@ impl TranslateOverlay {
             KeyCode::Char('s') | KeyCode::Char('S') => {
                 self.save_config();
             }
+            KeyCode::Char('p') | KeyCode::Char('P') => {
+                // Show the panel and (re-)run the preview with the current form settings.
+                self.show_preview = true;
+                self.preview_requested = true;
+            }
+            KeyCode::Char('t') | KeyCode::Char('T') => {
+                self.key_check_requested = true;
+                self.status_message = Some("Testing API key…".to_string());
+            }
             _ => {}
         }
         Ok(())
     }
 
+    fn preview_key(&self) -> PreviewKey {
+        PreviewKey {
+            provider_id: self.provider_id,
+            model: self.model.clone(),
+            language: self.language,
+        }
+    }
+
+    /// The preview for the current provider, model and language, if any.
+    fn current_preview(&self) -> Option<&PreviewState> {
+        self.preview
+            .as_ref()
+            .filter(|preview| preview.key == self.preview_key())
+            .map(|preview| &preview.state)
+    }
+
+    /// Start the preview requested with `p`, redrawing through `frame_requester` when done.
+    fn start_requested_preview(&mut self, frame_requester: FrameRequester) {
+        if !std::mem::take(&mut self.preview_requested) {
+            return;
+        }
+        let key = self.preview_key();
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            self.preview = Some(SamplePreview {
+                key,
+                state: PreviewState::Done(PreviewOutcome {
+                    result: Err("no async runtime available".to_string()),
+                    latency: Duration::ZERO,
+                }),
+            });
+            return;
+        };
+
+        let config = self.config();
+        let (tx, rx) = oneshot::channel();
+        handle.spawn(async move {
+            let started = Instant::now();
+            let result = translate_sample(&config, PREVIEW_SAMPLE)
+                .await
+                .map_err(|e| e.to_string());
+            let _ = tx.send(PreviewOutcome {
+                result,
+                latency: started.elapsed(),
+            });
+            frame_requester.schedule_frame();
+        });
+        // Replacing an earlier preview drops its receiver, so a stale result is discarded.
+        self.preview = Some(SamplePreview {
+            key,
+            state: PreviewState::Running(rx),
+        });
+    }
+
+    /// Pick up a finished preview translation.
+    fn poll_preview(&mut self) {
+        let Some(preview) = &mut self.preview else {
+            return;
+        };
+        let PreviewState::Running(rx) = &mut preview.state else {
+            return;
+        };
+        let outcome = match rx.try_recv() {
+            Ok(outcome) => outcome,
+            Err(oneshot::error::TryRecvError::Empty) => return,
+            Err(oneshot::error::TryRecvError::Closed) => PreviewOutcome {
+                result: Err("preview task stopped".to_string()),
+                latency: Duration::ZERO,
+            },
+        };
+        preview.state = PreviewState::Done(outcome);
+    }
+
+    /// Start the API key check requested with `t`, redrawing through `frame_requester` when
+    /// done.
+    fn start_requested_key_check(&mut self, frame_requester: FrameRequester) {
+        if !std::mem::take(&mut self.key_check_requested) {
+            return;
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            self.status_message = Some("Key test failed: no async runtime available".to_string());
+            return;
+        };
+
+        let config = self.config();
+        let (tx, rx) = oneshot::channel();
+        handle.spawn(async move {
+            let result = check_api_key(&config).await.map_err(|e| e.to_string());
+            let _ = tx.send(result);
+            frame_requester.schedule_frame();
+        });
+        self.key_check = Some(rx);
+    }
+
+    /// Show the result of a finished API key check.
+    fn poll_key_check(&mut self) {
+        let Some(rx) = &mut self.key_check else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(oneshot::error::TryRecvError::Empty) => return,
+            Err(oneshot::error::TryRecvError::Closed) => Err("key test stopped".to_string()),
+        };
+        self.key_check = None;
+        self.status_message = Some(match result {
+            Ok(status) => key_status_message(self.provider_id.definition().name, &status),
+            Err(err) => format!("Key test failed: {err}"),
+        });
+    }
+
     fn handle_editing_mode(&mut self, key_event: KeyEvent) -> Result<()> {
         match key_event.code {
             KeyCode::Esc => {
@@ -353,6 +618,10 @@ impl TranslateOverlay {
             KeyCode::Enter => {
                 self.input_mode = InputMode::Normal;
                 self.modified = true;
+                if self.selection == Selection::MaxWaitMs {
+                    self.status_message =
+                        self.max_wait_ms.parse().ok().and_then(max_wait_ms_warning);
+                }
             }
             KeyCode::Char(c) => {
                 // Handle paste (Ctrl+V)
@@ -389,7 +658,13 @@ impl TranslateOverlay {
 
     fn enter_edit_mode(&mut self) {
         match self.selection {
-            Selection::ApiKey | Selection::Model | Selection::BaseUrl => {
+            Selection::ApiKey
+            | Selection::Model
+            | Selection::Proxy
+            | Selection::BaseUrl
+            | Selection::ProviderBaseUrl
+            | Selection::MaxWaitMs
+            | Selection::AccountId => {
                 self.input_mode = InputMode::Editing;
                 let text = self.current_text();
                 self.cursor_position = text.len();
@@ -406,7 +681,14 @@ impl TranslateOverlay {
         match self.selection {
             Selection::ApiKey => &self.api_key,
             Selection::Model => &self.model,
+            Selection::Proxy => &self.proxy,
             Selection::BaseUrl => &self.base_url,
+            Selection::ProviderBaseUrl => self
+                .base_url_per_provider
+                .get(self.provider_id.as_str())
+                .map_or("", String::as_str),
+            Selection::MaxWaitMs => &self.max_wait_ms,
+            Selection::AccountId => &self.account_id,
             _ => "",
         }
     }
@@ -415,17 +697,27 @@ impl TranslateOverlay {
         match self.selection {
             Selection::ApiKey => &mut self.api_key,
             Selection::Model => &mut self.model,
+            Selection::Proxy => &mut self.proxy,
             Selection::BaseUrl => &mut self.base_url,
+            Selection::ProviderBaseUrl => self
+                .base_url_per_provider
+                .entry(self.provider_id.as_str().to_string())
+                .or_default(),
+            Selection::MaxWaitMs => &mut self.max_wait_ms,
+            Selection::AccountId => &mut self.account_id,
             _ => unreachable!(),
         }
     }
 
     fn insert_char(&mut self, c: char) {
+        if self.selection == Selection::MaxWaitMs && !c.is_ascii_digit() {
+            return;
+        }
         let pos = self.cursor_position;
         let text = self.current_text_mut();
         if pos <= text.len() {
             text.insert(pos, c);
-            self.cursor_position += 1;
+            self.cursor_position += c.len_utf8();
         }
     }
 
@@ -437,6 +729,7 @@ impl TranslateOverlay {
             .replace('\n', "")
             .chars()
             .filter(|c| !c.is_control())
+            .filter(|c| self.selection != Selection::MaxWaitMs || c.is_ascii_digit())
             .collect();
 
         if clean.is_empty() {
@@ -453,11 +746,15 @@ impl TranslateOverlay {
     }
 
     fn delete_char_before_cursor(&mut self) {
-        if self.cursor_position > 0 {
-            let pos = self.cursor_position - 1;
-            let text = self.current_text_mut();
-            text.remove(pos);
-            self.cursor_position -= 1;
+        let pos = self.cursor_position;
+        let text = self.current_text_mut();
+        if let Some(prev) = text
+            .get(..pos)
+            .and_then(|before| before.chars().next_back())
+        {
+            let prev_pos = pos - prev.len_utf8();
+            text.remove(prev_pos);
+            self.cursor_position = prev_pos;
         }
     }
 
@@ -470,15 +767,24 @@ impl TranslateOverlay {
     }
 
     fn move_cursor_left(&mut self) {
-        if self.cursor_position > 0 {
-            self.cursor_position -= 1;
+        let pos = self.cursor_position;
+        if let Some(prev) = self
+            .current_text()
+            .get(..pos)
+            .and_then(|before| before.chars().next_back())
+        {
+            self.cursor_position = pos - prev.len_utf8();
         }
     }
 
     fn move_cursor_right(&mut self) {
-        let text = self.current_text();
-        if self.cursor_position < text.len() {
-            self.cursor_position += 1;
+        let pos = self.cursor_position;
+        if let Some(next) = self
+            .current_text()
+            .get(pos..)
+            .and_then(|after| after.chars().next())
+        {
+            self.cursor_position = pos + next.len_utf8();
         }
     }
 
@@ -490,13 +796,12 @@ impl TranslateOverlay {
             }
             Selection::Provider => {
                 let len = ProviderId::ALL.len();
-                self.provider_index = if delta > 0 {
+                let index = if delta > 0 {
                     (self.provider_index + 1) % len
                 } else {
                     (self.provider_index + len - 1) % len
                 };
-                self.provider_id = ProviderId::ALL[self.provider_index];
-                self.modified = true;
+                self.set_provider(index);
             }
             Selection::Language => {
                 let len = TargetLanguage::ALL.len();
@@ -506,12 +811,36 @@ impl TranslateOverlay {
                     (self.language_index + len - 1) % len
                 };
                 self.language = TargetLanguage::ALL[self.language_index];
+                // A language from the list replaces one this version couldn't load.
+                self.base_config.unsupported_target_language = None;
                 self.modified = true;
             }
             _ => {}
         }
     }
 
+    /// Switch to the provider at `index` in [`ProviderId::ALL`].
+    ///
+    /// A model equal to the previous provider's default is cleared so the new provider's default
+    /// applies; an explicit custom model is kept, with a warning that it may not exist there.
+    fn set_provider(&mut self, index: usize) {
+        let previous_default = self.provider_id.definition().default_model;
+        self.provider_index = index;
+        self.provider_id = ProviderId::ALL[index];
+        self.modified = true;
+
+        if self.model == previous_default {
+            self.model.clear();
+            self.status_message = None;
+        } else if !self.model.is_empty() {
+            self.status_message = Some(format!(
+                "Custom model \"{}\" kept; check that {} provides it",
+                self.model,
+                self.provider_id.definition().name
+            ));
+        }
+    }
+
     fn render(&self, area: Rect, buf: &mut Buffer) {
         // Clear background
         for y in area.top()..area.bottom() {
@@ -529,21 +858,31 @@ impl TranslateOverlay {
         block.render(area, buf);
 
         // Layout with spacing for all options
+        let preview_height = if self.show_preview { PREVIEW_HEIGHT } else { 0 };
         let chunks = Layout::vertical([
-            Constraint::Length(1), // Top padding
-            Constraint::Length(3), // Enabled toggle
-            Constraint::Length(1), // Spacing
-            Constraint::Length(3), // Provider
-            Constraint::Length(1), // Spacing
-            Constraint::Length(3), // API Key
-            Constraint::Length(1), // Spacing
-            Constraint::Length(3), // Model
-            Constraint::Length(1), // Spacing
-            Constraint::Length(3), // Language
-            Constraint::Length(1), // Spacing
-            Constraint::Length(3), // Base URL
-            Constraint::Length(2), // Status
-            Constraint::Min(1),    // Help (at bottom)
+            Constraint::Length(1),              // Top padding
+            Constraint::Length(3),              // Enabled toggle
+            Constraint::Length(1),              // Spacing
+            Constraint::Length(3),              // Provider
+            Constraint::Length(1),              // Spacing
+            Constraint::Length(3),              // API Key
+            Constraint::Length(1),              // Spacing
+            Constraint::Length(3),              // Model
+            Constraint::Length(1),              // Spacing
+            Constraint::Length(3),              // Language
+            Constraint::Length(1),              // Spacing
+            Constraint::Length(3),              // Proxy
+            Constraint::Length(1),              // Spacing
+            Constraint::Length(3),              // Base URL
+            Constraint::Length(1),              // Spacing
+            Constraint::Length(3),              // Base URL for the selected provider
+            Constraint::Length(1),              // Spacing
+            Constraint::Length(3),              // Max wait
+            Constraint::Length(1),              // Spacing
+            Constraint::Length(3),              // Account ID (Cloudflare only)
+            Constraint::Length(2),              // Status
+            Constraint::Length(preview_height), // Sample preview
+            Constraint::Min(1),                 // Help (at bottom)
         ])
         .split(inner);
 
@@ -572,6 +911,19 @@ impl TranslateOverlay {
             self.selection == Selection::Provider,
             self.api_key_status(),
         );
+        if self.selection == Selection::Provider
+            && !provider_def.pricing_url.is_empty()
+            && chunks[3].height > 2
+        {
+            let url = provider_def.pricing_url;
+            let mut line = pricing_line(url, supports_hyperlinks());
+            // The escape sequence counts as text when the line is laid out; show the bare URL
+            // rather than cut the sequence in half.
+            if line.width() > usize::from(chunks[3].width) {
+                line = pricing_line(url, false);
+            }
+            buf.set_line(chunks[3].x, chunks[3].y + 2, &line, chunks[3].width);
+        }
 
         // API Key input
         self.render_text_input(
@@ -607,10 +959,22 @@ impl TranslateOverlay {
             self.selection == Selection::Language,
         );
 
-        // Base URL input
+        // Proxy input
         self.render_text_input(
             chunks[11],
             buf,
+            "Proxy",
+            &self.proxy,
+            false,
+            self.selection == Selection::Proxy,
+            self.input_mode == InputMode::Editing && self.selection == Selection::Proxy,
+            "http(s):// or socks5:// URL (default: HTTPS_PROXY/HTTP_PROXY/ALL_PROXY)",
+        );
+
+        // Base URL input
+        self.render_text_input(
+            chunks[13],
+            buf,
             "Base URL",
             &self.base_url,
             false,
@@ -619,15 +983,61 @@ impl TranslateOverlay {
             &format!("Default: {}", provider_def.default_base_url),
         );
 
+        // Base URL for the selected provider only
+        self.render_text_input(
+            chunks[15],
+            buf,
+            &format!("{} Base URL", provider_def.name),
+            self.base_url_per_provider
+                .get(self.provider_id.as_str())
+                .map_or("", String::as_str),
+            false,
+            self.selection == Selection::ProviderBaseUrl,
+            self.input_mode == InputMode::Editing && self.selection == Selection::ProviderBaseUrl,
+            &format!("Overrides Base URL for {} only", provider_def.name),
+        );
+
+        // Max wait input
+        self.render_text_input(
+            chunks[17],
+            buf,
+            "Max Wait (ms)",
+            &self.max_wait_ms,
+            false,
+            self.selection == Selection::MaxWaitMs,
+            self.input_mode == InputMode::Editing && self.selection == Selection::MaxWaitMs,
+            &format!(
+                "Default: {DEFAULT_MAX_WAIT_MS}ms ({}s)",
+                DEFAULT_MAX_WAIT_MS / 1000
+            ),
+        );
+
+        // Account ID input (Cloudflare only)
+        if Selection::AccountId.is_visible(self.provider_id) {
+            self.render_text_input(
+                chunks[19],
+                buf,
+                "Account ID",
+                &self.account_id,
+                false,
+                self.selection == Selection::AccountId,
+                self.input_mode == InputMode::Editing && self.selection == Selection::AccountId,
+                "Required for Cloudflare Workers AI",
+            );
+        }
+
         // Status message
         if let Some(msg) = &self.status_message {
             let status = Paragraph::new(Line::from(vec![
                 Span::raw("  "),
                 Span::styled(msg, Style::default().fg(Color::Green)),
-            ]));
-            status.render(chunks[12], buf);
+            ]))
+            .wrap(Wrap { trim: false });
+            status.render(chunks[20], buf);
         }
 
+        self.render_preview(chunks[21], buf);
+
         // Help text at bottom
         let help = if self.input_mode == InputMode::Editing {
             Paragraph::new(vec![
@@ -654,13 +1064,69 @@ impl TranslateOverlay {
                     Span::raw(" Edit  "),
                     Span::styled("s", Style::default().bold()),
                     Span::raw(" Save  "),
+                    Span::styled("p", Style::default().bold()),
+                    Span::raw(" Preview  "),
+                    Span::styled("t", Style::default().bold()),
+                    Span::raw(" Test  "),
                     Span::styled("q", Style::default().bold()),
                     Span::raw(" Close"),
                 ])
                 .dim(),
             ])
         };
-        help.render(chunks[13], buf);
+        help.render(chunks[22], buf);
+
+        if self.color_mode == ColorMode::Monochrome {
+            strip_colors(buf, area);
+        }
+    }
+
+    fn render_preview(&self, area: Rect, buf: &mut Buffer) {
+        if !self.show_preview || area.is_empty() {
+            return;
+        }
+        let title = format!("  Preview ({})", self.language.name());
+        let lines = match self.current_preview() {
+            None => vec![Line::from(vec![
+                Span::styled(title, Style::default().bold()),
+                Span::styled(
+                    ": settings changed, press p to run again",
+                    Style::default().dim(),
+                ),
+            ])],
+            Some(PreviewState::Running(_)) => vec![Line::from(vec![
+                Span::styled(title, Style::default().bold()),
+                Span::styled(": translating…", Style::default().dim()),
+            ])],
+            Some(PreviewState::Done(outcome)) => {
+                let latency = format!(" · {}ms", outcome.latency.as_millis());
+                match &outcome.result {
+                    Ok(text) => vec![
+                        Line::from(vec![
+                            Span::styled(title, Style::default().bold()),
+                            Span::styled(latency, Style::default().dim()),
+                        ]),
+                        Line::from(vec![
+                            Span::raw("    "),
+                            Span::styled(text.clone(), Style::default().fg(Color::Yellow)),
+                        ]),
+                    ],
+                    Err(err) => vec![
+                        Line::from(vec![
+                            Span::styled(title, Style::default().bold()),
+                            Span::styled(latency, Style::default().dim()),
+                        ]),
+                        Line::from(vec![
+                            Span::raw("    "),
+                            Span::styled(format!("Failed: {err}"), Style::default().fg(Color::Red)),
+                        ]),
+                    ],
+                }
+            }
+        };
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
     }
 
     fn api_key_status(&self) -> Option<(&'static str, Color)> {
@@ -674,11 +1140,18 @@ impl TranslateOverlay {
         }
     }
 
-    fn mask_api_key(key: &str) -> String {
-        if key.len() <= 8 {
-            "*".repeat(key.len())
+    /// Mask an API key, keeping the first and last four graphemes of longer keys visible.
+    pub(crate) fn mask_api_key(key: &str) -> String {
+        let graphemes: Vec<&str> = key.graphemes(true).collect();
+        let len = graphemes.len();
+        if len <= 8 {
+            "*".repeat(len)
         } else {
-            format!("{}...{}", &key[..4], &key[key.len().saturating_sub(4)..])
+            format!(
+                "{}...{}",
+                graphemes[..4].concat(),
+                graphemes[len - 4..].concat()
+            )
         }
     }
 
@@ -808,42 +1281,51 @@ impl TranslateOverlay {
 
         let indicator = if selected { "▶ " } else { "  " };
 
-        let display_value = if editing {
-            // Show full value while editing
-            value.to_string()
-        } else if masked && !value.is_empty() {
-            Self::mask_api_key(value)
-        } else if value.is_empty() {
-            "(not set)".to_string()
-        } else {
-            value.to_string()
-        };
-
         let value_style = if value.is_empty() {
             Style::default().dim()
         } else {
             Style::default().fg(Color::Yellow)
         };
 
+        // Columns left for the value between "▶ Label: [" and "]" (plus the editing marker).
+        let prefix_width = indicator.width() + label.width() + ": [".width();
+        let suffix_width = "]".width() + if editing { EDITING_MARKER.width() } else { 0 };
+        let max_value_width = usize::from(area.width).saturating_sub(prefix_width + suffix_width);
+
         let mut spans = vec![
             Span::styled(indicator, style),
             Span::styled(format!("{label}: "), style),
             Span::raw("["),
-            Span::styled(&display_value, value_style),
         ];
 
-        // Show cursor if editing
         if editing {
-            // Add cursor indicator
-            spans.push(Span::styled("▏", Style::default().fg(Color::White)));
+            // Show the full value around the cursor, scrolled so the cursor stays visible.
+            let (before, after) = fit_editing_value(value, self.cursor_position, max_value_width);
+            spans.push(Span::styled(before, value_style));
+            spans.push(Span::styled(
+                CURSOR_GLYPH,
+                Style::default().fg(Color::White),
+            ));
+            spans.push(Span::styled(after, value_style));
+        } else {
+            let display_value = if masked && !value.is_empty() {
+                Self::mask_api_key(value)
+            } else if value.is_empty() {
+                "(not set)".to_string()
+            } else {
+                value.to_string()
+            };
+            spans.push(Span::styled(
+                truncate_to_width(&display_value, max_value_width),
+                value_style,
+            ));
         }
 
         spans.push(Span::raw("]"));
 
         if editing {
-            spans.push(Span::raw("  "));
             spans.push(Span::styled(
-                "(editing)",
+                EDITING_MARKER,
                 Style::default().fg(Color::Yellow),
             ));
         }
@@ -863,3 +1345,854 @@ impl TranslateOverlay {
         }
     }
 }
+
+/// Cursor glyph drawn inside a text field being edited.
+const CURSOR_GLYPH: &str = "▏";
+
+/// Marker shown after a text field being edited.
+const EDITING_MARKER: &str = "  (editing)";
+
+/// Ellipsis marking a value truncated to fit its field.
+const ELLIPSIS: &str = "…";
+
+/// Status line for an API key check, e.g. "API key valid · tier: 500 requests · 499 left".
+fn key_status_message(provider_name: &str, status: &ApiKeyStatus) -> String {
+    if !status.valid {
+        return format!("{provider_name} rejected the API key");
+    }
+    let mut message = "API key valid".to_string();
+    if let Some(tier) = &status.tier {
+        message.push_str(&format!(" · tier: {tier}"));
+    }
+    if let Some(remaining) = status.quota_remaining {
+        message.push_str(&format!(" · {remaining} left"));
+    }
+    message
+}
+
+/// A base URL as it should be stored: no surrounding whitespace or trailing `/`, and a
+/// doubled scheme from pasting over a prefilled `https://` (`https://https://host`) reduced
+/// to the pasted one.
+fn normalize_base_url(url: &str) -> String {
+    let mut url = url.trim();
+    while let Some(rest) = strip_scheme(url)
+        && strip_scheme(rest).is_some()
+    {
+        url = rest;
+    }
+    url.trim_end_matches('/').to_string()
+}
+
+/// `url` after its `http://` or `https://` scheme, matched ignoring case.
+fn strip_scheme(url: &str) -> Option<&str> {
+    ["https://", "http://"].iter().find_map(|scheme| {
+        url.get(..scheme.len())
+            .filter(|prefix| prefix.eq_ignore_ascii_case(scheme))
+            .map(|_| &url[scheme.len()..])
+    })
+}
+
+/// Hint under the provider row, e.g. "Pricing: https://openai.com/pricing". With `hyperlink`
+/// the URL is wrapped in OSC 8 so it can be clicked.
+fn pricing_line(url: &str, hyperlink: bool) -> Line<'static> {
+    let target = if hyperlink {
+        osc8(url, url)
+    } else {
+        url.to_string()
+    };
+    Line::from(vec![
+        Span::raw("    "),
+        Span::styled(
+            format!("Pricing: {target}"),
+            Style::default().dim().underlined(),
+        ),
+    ])
+}
+
+/// Truncate `text` to at most `max_width` display columns, ending with an ellipsis if cut.
+fn truncate_to_width(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+    let (prefix, _, _) = take_prefix_by_width(text, max_width - ELLIPSIS.width());
+    format!("{prefix}{ELLIPSIS}")
+}
+
+/// Longest suffix of `text` that fits in `max_width` display columns.
+fn take_suffix_by_width(text: &str, max_width: usize) -> &str {
+    let mut width = 0;
+    let mut start = text.len();
+    for (idx, ch) in text.char_indices().rev() {
+        let ch_width = ch.width().unwrap_or(0);
+        if width + ch_width > max_width {
+            break;
+        }
+        width += ch_width;
+        start = idx;
+    }
+    &text[start..]
+}
+
+/// Split `value` around the byte offset `cursor` so that the text before the cursor, the cursor
+/// glyph, and the text after it fit in `max_width` display columns with the cursor visible.
+fn fit_editing_value(value: &str, cursor: usize, max_width: usize) -> (String, String) {
+    let mut cursor = cursor.min(value.len());
+    while !value.is_char_boundary(cursor) {
+        cursor -= 1;
+    }
+    let (before, after) = value.split_at(cursor);
+    let room = max_width.saturating_sub(CURSOR_GLYPH.width());
+    let before_width = before.width();
+    if before_width <= room {
+        (
+            before.to_string(),
+            truncate_to_width(after, room - before_width),
+        )
+    } else {
+        let tail = take_suffix_by_width(before, room.saturating_sub(ELLIPSIS.width()));
+        (format!("{ELLIPSIS}{tail}"), String::new())
+    }
+}
+
+/// Owns a [`TranslateOverlay`] and consumes all input while it is open.
+///
+/// Key and paste events are always handled by the overlay and never handed back, so keys like
+/// `j`/`k` cannot reach the main view behind it. Other events (draw requests) are queued and
+/// surfaced through [`FocusTrap::poll_event`].
+pub(crate) struct FocusTrap {
+    overlay: TranslateOverlay,
+    pending: VecDeque<TuiEvent>,
+}
+
+impl FocusTrap {
+    pub(crate) fn new(overlay: TranslateOverlay) -> Self {
+        Self {
+            overlay,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// The trapped overlay.
+    pub(crate) fn overlay(&self) -> &TranslateOverlay {
+        &self.overlay
+    }
+
+    /// Check if the overlay has closed.
+    pub(crate) fn is_done(&self) -> bool {
+        self.overlay.is_done()
+    }
+
+    /// Feed an event into the trap.
+    ///
+    /// Returns true if the event was input consumed by the overlay.
+    pub(crate) fn intercept(&mut self, event: TuiEvent) -> Result<bool> {
+        match event {
+            TuiEvent::Key(key_event) => {
+                self.overlay.handle_key_event(key_event)?;
+                Ok(true)
+            }
+            TuiEvent::Paste(text) => {
+                self.overlay.handle_paste_event(&text);
+                Ok(true)
+            }
+            TuiEvent::Draw => {
+                self.pending.push_back(TuiEvent::Draw);
+                Ok(false)
+            }
+        }
+    }
+
+    /// Next non-input event for the caller to route.
+    ///
+    /// Returns `Poll::Ready(None)` once the overlay has closed and `Poll::Pending` when nothing
+    /// is queued.
+    pub(crate) fn poll_event(&mut self) -> Poll<Option<TuiEvent>> {
+        if self.overlay.is_done() {
+            self.pending.clear();
+            return Poll::Ready(None);
+        }
+        match self.pending.pop_front() {
+            Some(event) => Poll::Ready(Some(event)),
+            None => Poll::Pending,
+        }
+    }
+
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        if self.intercept(event)? {
+            self.overlay.start_requested_preview(tui.frame_requester());
+            self.overlay
+                .start_requested_key_check(tui.frame_requester());
+            tui.frame_requester().schedule_frame();
+        }
+        while let Poll::Ready(Some(event)) = self.poll_event() {
+            self.overlay.handle_event(tui, event)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn key(code: KeyCode) -> TuiEvent {
+        TuiEvent::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn focus_trap_consumes_navigation_keys() {
+        let mut trap = FocusTrap::new(TranslateOverlay::new(&TranslationConfig::default()));
+
+        assert!(trap.intercept(key(KeyCode::Char('j'))).unwrap());
+        assert!(trap.intercept(key(KeyCode::Char('j'))).unwrap());
+        assert!(trap.intercept(key(KeyCode::Char('k'))).unwrap());
+
+        // The keys moved the overlay selection and nothing was handed back to the caller.
+        assert_eq!(trap.overlay().selection, Selection::Provider);
+        assert!(trap.poll_event().is_pending());
+    }
+
+    #[test]
+    fn focus_trap_consumes_paste_outside_edit_mode() {
+        let mut trap = FocusTrap::new(TranslateOverlay::new(&TranslationConfig::default()));
+
+        assert!(trap.intercept(TuiEvent::Paste("jjj".to_string())).unwrap());
+
+        assert_eq!(trap.overlay().selection, Selection::Enabled);
+        assert!(trap.poll_event().is_pending());
+    }
+
+    #[test]
+    fn focus_trap_surfaces_draw_events() {
+        let mut trap = FocusTrap::new(TranslateOverlay::new(&TranslationConfig::default()));
+
+        assert!(!trap.intercept(TuiEvent::Draw).unwrap());
+
+        assert!(matches!(
+            trap.poll_event(),
+            Poll::Ready(Some(TuiEvent::Draw))
+        ));
+        assert!(trap.poll_event().is_pending());
+    }
+
+    #[test]
+    fn focus_trap_signals_close() {
+        let mut trap = FocusTrap::new(TranslateOverlay::new(&TranslationConfig::default()));
+        trap.intercept(TuiEvent::Draw).unwrap();
+
+        trap.intercept(key(KeyCode::Char('q'))).unwrap();
+
+        assert!(trap.is_done());
+        assert!(matches!(trap.poll_event(), Poll::Ready(None)));
+    }
+
+    #[test]
+    fn preview_key_requests_a_run() {
+        let mut trap = FocusTrap::new(TranslateOverlay::new(&TranslationConfig::default()));
+
+        trap.intercept(key(KeyCode::Char('p'))).unwrap();
+
+        assert!(trap.overlay().show_preview);
+        assert!(trap.overlay().preview_requested);
+    }
+
+    #[test]
+    fn preview_result_is_picked_up_and_invalidated_by_language_change() {
+        let mut overlay = TranslateOverlay::new(&TranslationConfig::default());
+        let (tx, rx) = oneshot::channel();
+        overlay.show_preview = true;
+        overlay.preview = Some(SamplePreview {
+            key: overlay.preview_key(),
+            state: PreviewState::Running(rx),
+        });
+
+        overlay.poll_preview();
+        assert!(matches!(
+            overlay.current_preview(),
+            Some(PreviewState::Running(_))
+        ));
+
+        let outcome = PreviewOutcome {
+            result: Ok("构建失败。".to_string()),
+            latency: Duration::from_millis(420),
+        };
+        tx.send(outcome.clone()).unwrap();
+        overlay.poll_preview();
+        assert!(matches!(
+            overlay.current_preview(),
+            Some(PreviewState::Done(done)) if *done == outcome
+        ));
+
+        overlay.selection = Selection::Language;
+        overlay.adjust_current(1);
+        assert!(overlay.current_preview().is_none());
+    }
+
+    #[test]
+    fn preview_without_runtime_reports_error() {
+        let mut overlay = TranslateOverlay::new(&TranslationConfig::default());
+        overlay.preview_requested = true;
+
+        overlay.start_requested_preview(FrameRequester::test_dummy());
+
+        assert!(!overlay.preview_requested);
+        assert!(matches!(
+            overlay.current_preview(),
+            Some(PreviewState::Done(PreviewOutcome { result: Err(_), .. }))
+        ));
+    }
+
+    #[test]
+    fn account_id_only_reachable_for_cloudflare() {
+        assert_eq!(
+            Selection::MaxWaitMs.next(ProviderId::OpenAI),
+            Selection::Enabled
+        );
+        assert_eq!(
+            Selection::Enabled.prev(ProviderId::OpenAI),
+            Selection::MaxWaitMs
+        );
+
+        assert_eq!(
+            Selection::MaxWaitMs.next(ProviderId::Cloudflare),
+            Selection::AccountId
+        );
+        assert_eq!(
+            Selection::Enabled.prev(ProviderId::Cloudflare),
+            Selection::AccountId
+        );
+    }
+
+    fn overlay_with_model(provider: &str, model: &str) -> TranslateOverlay {
+        TranslateOverlay::new(&TranslationConfig {
+            provider: provider.to_string(),
+            model: Some(model.to_string()),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn switching_provider_clears_previous_default_model() {
+        let mut overlay = overlay_with_model("openai", "gpt-4o-mini");
+        overlay.selection = Selection::Provider;
+
+        overlay.adjust_current(1);
+
+        assert_eq!(overlay.provider_id, ProviderId::Anthropic);
+        assert_eq!(overlay.model, "");
+        assert_eq!(overlay.config().model, None);
+        assert_eq!(overlay.status_message, None);
+    }
+
+    #[test]
+    fn switching_provider_keeps_custom_model_with_warning() {
+        let mut overlay = overlay_with_model("openai", "gpt-4.1");
+        overlay.selection = Selection::Provider;
+
+        overlay.adjust_current(1);
+
+        assert_eq!(overlay.provider_id, ProviderId::Anthropic);
+        assert_eq!(overlay.model, "gpt-4.1");
+        let warning = overlay.status_message.as_deref().unwrap_or_default();
+        assert!(warning.contains("gpt-4.1"), "{warning}");
+        assert!(warning.contains("Anthropic"), "{warning}");
+    }
+
+    #[test]
+    fn provider_base_url_edits_only_the_selected_provider() {
+        let mut overlay = TranslateOverlay::new(&TranslationConfig {
+            provider: "openai".to_string(),
+            base_url: Some("https://gateway.example.com/v1".to_string()),
+            ..Default::default()
+        });
+        overlay.selection = Selection::ProviderBaseUrl;
+        overlay.enter_edit_mode();
+        overlay.handle_paste("http://localhost:8080/v1");
+        overlay.input_mode = InputMode::Normal;
+
+        let config = overlay.config();
+        assert_eq!(
+            config.base_url.as_deref(),
+            Some("https://gateway.example.com/v1")
+        );
+        assert_eq!(
+            config.base_url_per_provider,
+            HashMap::from([("openai".to_string(), "http://localhost:8080/v1".to_string())])
+        );
+
+        // Another provider shows its own (empty) entry, and the OpenAI one is kept.
+        overlay.selection = Selection::Provider;
+        overlay.adjust_current(1);
+        overlay.selection = Selection::ProviderBaseUrl;
+        assert_eq!(overlay.provider_id, ProviderId::Anthropic);
+        assert_eq!(overlay.current_text(), "");
+        overlay.enter_edit_mode();
+        overlay.input_mode = InputMode::Normal;
+        assert_eq!(overlay.config().base_url_per_provider.len(), 1);
+    }
+
+    /// Visible text of row `y`, skipping the cells covered by wide characters.
+    fn row_text(buf: &Buffer, y: u16) -> String {
+        let mut text = String::new();
+        let mut x = buf.area.left();
+        while x < buf.area.right() {
+            let symbol = buf[(x, y)].symbol();
+            text.push_str(symbol);
+            x += u16::try_from(symbol.width().max(1)).unwrap_or(1);
+        }
+        text.trim_end().to_string()
+    }
+
+    fn render_model_field(overlay: &TranslateOverlay, width: u16, editing: bool) -> String {
+        let area = Rect::new(0, 0, width, 2);
+        let mut buf = Buffer::empty(area);
+        overlay.render_text_input(
+            area,
+            &mut buf,
+            "Model",
+            &overlay.model,
+            false,
+            false,
+            editing,
+            "",
+        );
+        row_text(&buf, 0)
+    }
+
+    #[test]
+    fn mask_api_key_handles_multibyte_keys() {
+        assert_eq!(
+            TranslateOverlay::mask_api_key("sk-1234567890"),
+            "sk-1...7890"
+        );
+        assert_eq!(TranslateOverlay::mask_api_key("密钥🔑"), "***");
+        assert_eq!(
+            TranslateOverlay::mask_api_key("密钥🔑👨‍👩‍👧甲乙丙丁戊"),
+            "密钥🔑👨‍👩‍👧...乙丙丁戊"
+        );
+    }
+
+    #[test]
+    fn long_cjk_value_is_truncated_with_ellipsis() {
+        let overlay = overlay_with_model("qwen", "通义千问-长文本模型-超大杯");
+
+        assert_eq!(
+            render_model_field(&overlay, 24, false),
+            "  Model: [通义千问-长…]"
+        );
+    }
+
+    #[test]
+    fn cursor_is_drawn_between_cjk_characters() {
+        let mut overlay = overlay_with_model("qwen", "模型AB");
+        overlay.cursor_position = "模型".len();
+
+        assert_eq!(
+            render_model_field(&overlay, 40, true),
+            "  Model: [模型▏AB]  (editing)"
+        );
+    }
+
+    #[test]
+    fn cursor_stays_visible_when_value_overflows() {
+        let mut overlay = overlay_with_model("qwen", "中文模型名称");
+        overlay.cursor_position = overlay.model.len();
+
+        assert_eq!(
+            render_model_field(&overlay, 30, true),
+            "  Model: […型名称▏]  (editing)"
+        );
+    }
+
+    #[test]
+    fn editing_moves_cursor_by_whole_characters() {
+        let mut overlay = overlay_with_model("qwen", "");
+        overlay.selection = Selection::Model;
+        overlay.enter_edit_mode();
+
+        for c in ['模', '型', 'x'] {
+            overlay.insert_char(c);
+        }
+        overlay.move_cursor_left();
+        overlay.move_cursor_left();
+        overlay.delete_char_before_cursor();
+        overlay.move_cursor_right();
+        overlay.insert_char('y');
+
+        assert_eq!(overlay.model, "型yx");
+        assert_eq!(overlay.cursor_position, "型y".len());
+    }
+
+    #[test]
+    fn proxy_round_trips_through_config() {
+        let config = TranslationConfig {
+            proxy: Some("socks5://127.0.0.1:1080".to_string()),
+            ..Default::default()
+        };
+        let mut overlay = TranslateOverlay::new(&config);
+        assert_eq!(
+            overlay.config().proxy.as_deref(),
+            Some("socks5://127.0.0.1:1080")
+        );
+
+        overlay.proxy.clear();
+        assert_eq!(overlay.config().proxy, None);
+    }
+
+    #[test]
+    fn unsupported_language_is_kept_until_another_is_chosen() {
+        let config = TranslationConfig {
+            unsupported_target_language: Some("pl".to_string()),
+            ..Default::default()
+        };
+        let mut overlay = TranslateOverlay::new(&config);
+        assert!(overlay.status_message.as_ref().unwrap().contains("\"pl\""));
+        assert_eq!(
+            overlay.config().unsupported_target_language.as_deref(),
+            Some("pl")
+        );
+
+        overlay.selection = Selection::Language;
+        overlay.adjust_current(1);
+        let saved = overlay.config();
+        assert_eq!(saved.target_language, TargetLanguage::ALL[1]);
+        assert_eq!(saved.unsupported_target_language, None);
+    }
+
+    #[test]
+    fn max_wait_accepts_digits_and_warns_outside_the_range() {
+        let mut overlay = TranslateOverlay::new(&TranslationConfig::default());
+        assert_eq!(overlay.config().max_wait_ms, None);
+
+        overlay.selection = Selection::MaxWaitMs;
+        overlay
+            .handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+            .unwrap();
+        overlay.handle_paste("8s00");
+        overlay
+            .handle_key_event(KeyEvent::new(KeyCode::Char('0'), KeyModifiers::NONE))
+            .unwrap();
+        overlay
+            .handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+            .unwrap();
+        assert_eq!(overlay.max_wait_ms, "8000");
+        assert_eq!(overlay.config().max_wait_ms, Some(8_000));
+        assert_eq!(overlay.status_message, None);
+
+        // Out of range is kept, with a warning.
+        overlay
+            .handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+            .unwrap();
+        overlay.handle_paste("0");
+        overlay
+            .handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE))
+            .unwrap();
+        assert_eq!(overlay.config().max_wait_ms, Some(80_000));
+        let warning = overlay.status_message.as_deref().unwrap_or_default();
+        assert!(warning.contains("500-60000"), "{warning}");
+    }
+
+    #[test]
+    fn account_id_round_trips_through_config() {
+        let config = TranslationConfig {
+            provider: "cloudflare".to_string(),
+            cloudflare_account_id: Some("abc123".to_string()),
+            ..Default::default()
+        };
+        let overlay = TranslateOverlay::new(&config);
+        assert_eq!(
+            overlay.config().cloudflare_account_id.as_deref(),
+            Some("abc123")
+        );
+    }
+
+    #[test]
+    fn editing_api_key_focuses_the_key_field_of_the_provider() {
+        let overlay = TranslateOverlay::new(&TranslationConfig {
+            api_key: Some("sk-old".to_string()),
+            ..Default::default()
+        })
+        .editing_api_key(ProviderId::DeepSeek);
+
+        assert_eq!(overlay.provider_id, ProviderId::DeepSeek);
+        assert_eq!(overlay.selection, Selection::ApiKey);
+        assert_eq!(overlay.input_mode, InputMode::Editing);
+        assert_eq!(overlay.cursor_position, "sk-old".len());
+        assert!(overlay.status_message.is_some());
+    }
+
+    #[test]
+    fn selected_provider_shows_its_pricing_page() {
+        let mut overlay = TranslateOverlay::new(&TranslationConfig::default());
+        let pricing_url = overlay.provider_id.definition().pricing_url;
+        let area = Rect::new(0, 0, 100, 60);
+        let rows = |overlay: &TranslateOverlay| {
+            let mut buf = Buffer::empty(area);
+            overlay.render(area, &mut buf);
+            (area.top()..area.bottom())
+                .map(|y| row_text(&buf, y))
+                .collect::<Vec<_>>()
+        };
+
+        assert!(!rows(&overlay).iter().any(|row| row.contains("Pricing:")));
+
+        overlay.selection = Selection::Provider;
+        assert!(
+            rows(&overlay)
+                .iter()
+                .any(|row| row.contains("Pricing: ") && row.contains(pricing_url))
+        );
+    }
+
+    #[test]
+    fn pricing_hint_links_the_url_when_supported() {
+        let url = "https://openai.com/pricing";
+        assert_eq!(
+            pricing_line(url, false).to_string(),
+            "    Pricing: https://openai.com/pricing"
+        );
+        assert_eq!(
+            pricing_line(url, true).to_string(),
+            format!("    Pricing: {}", osc8(url, url))
+        );
+    }
+
+    #[test]
+    fn key_check_result_is_shown_in_the_status_area() {
+        let mut overlay = TranslateOverlay::new(&TranslationConfig::default());
+        overlay
+            .handle_key_event(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::NONE))
+            .unwrap();
+        assert!(overlay.key_check_requested);
+
+        let (tx, rx) = oneshot::channel();
+        overlay.key_check = Some(rx);
+        overlay.poll_key_check();
+        assert_eq!(overlay.status_message.as_deref(), Some("Testing API key…"));
+
+        tx.send(Ok(ApiKeyStatus {
+            valid: true,
+            quota_remaining: Some(499),
+            expires_at: None,
+            tier: Some("500 requests".to_string()),
+        }))
+        .unwrap();
+        overlay.poll_key_check();
+        assert_eq!(
+            overlay.status_message.as_deref(),
+            Some("API key valid · tier: 500 requests · 499 left")
+        );
+        assert!(overlay.key_check.is_none());
+    }
+
+    #[test]
+    fn rejected_key_names_the_provider() {
+        assert_eq!(
+            key_status_message("OpenAI", &ApiKeyStatus::default()),
+            "OpenAI rejected the API key"
+        );
+        assert_eq!(
+            key_status_message(
+                "Gemini",
+                &ApiKeyStatus {
+                    valid: true,
+                    ..Default::default()
+                }
+            ),
+            "API key valid"
+        );
+    }
+
+    #[test]
+    fn translate_overlay_no_color_snapshot() {
+        let overlay = TranslateOverlay::new(&TranslationConfig::default())
+            .with_color_mode(ColorMode::Monochrome);
+        let mut terminal =
+            ratatui::Terminal::new(crate::test_backend::VT100Backend::new(80, 50)).unwrap();
+        terminal
+            .draw(|frame| overlay.render(frame.area(), frame.buffer_mut()))
+            .unwrap();
+        insta::assert_snapshot!("translate_overlay_no_color", terminal.backend());
+
+        let area = Rect::new(0, 0, 80, 50);
+        let mut buf = Buffer::empty(area);
+        overlay.render(area, &mut buf);
+        assert!(
+            buf.content()
+                .iter()
+                .all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset)
+        );
+        // The selected row and the missing key stand out without color.
+        assert!(buf[(1, 2)].modifier.contains(Modifier::BOLD));
+        let provider_row = row_text(&buf, 6);
+        let key_required = provider_row.find("[✗").unwrap();
+        let key_required = u16::try_from(provider_row[..key_required].width()).unwrap();
+        assert!(
+            buf[(key_required, 6)]
+                .modifier
+                .contains(Modifier::BOLD | Modifier::UNDERLINED)
+        );
+    }
+
+    #[test]
+    fn saving_without_a_required_key_warns_but_saves() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("translation.toml");
+        let mut overlay = TranslateOverlay::new(&TranslationConfig {
+            enabled: true,
+            provider: "openai".to_string(),
+            api_key: None,
+            ..Default::default()
+        })
+        .with_save_path(path.clone());
+
+        overlay.save_config();
+
+        assert_eq!(
+            overlay.status_message.as_deref(),
+            Some("Saved, but translation won't work without a key")
+        );
+        let saved = TranslationConfig::load_from_path(&path);
+        assert_eq!(saved.provider, "openai");
+
+        overlay.api_key = "sk-test".to_string();
+        overlay.save_config();
+        assert_eq!(
+            overlay.status_message.as_deref(),
+            Some("Configuration saved")
+        );
+    }
+
+    #[test]
+    fn mismatched_base_url_needs_a_second_save() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("translation.toml");
+        let mut overlay = TranslateOverlay::new(&TranslationConfig {
+            provider: "anthropic".to_string(),
+            api_key: Some("sk-ant".to_string()),
+            base_url: Some("https://gateway.example.com/v1/chat/completions".to_string()),
+            ..Default::default()
+        })
+        .with_save_path(path.clone());
+
+        overlay.save_config();
+        let warning = overlay.status_message.clone().unwrap_or_default();
+        assert!(warning.contains("OpenAI-compatible"), "{warning}");
+        assert!(
+            warning.ends_with("Press s again to save anyway"),
+            "{warning}"
+        );
+        assert!(!path.exists());
+
+        overlay.save_config();
+        assert_eq!(
+            overlay.status_message.as_deref(),
+            Some("Configuration saved")
+        );
+        assert_eq!(
+            TranslationConfig::load_from_path(&path).base_url.as_deref(),
+            Some("https://gateway.example.com/v1/chat/completions")
+        );
+
+        // A different mismatch asks again.
+        overlay.base_url = "https://openrouter.ai/api/v1".to_string();
+        overlay.save_config();
+        let warning = overlay.status_message.clone().unwrap_or_default();
+        assert!(warning.contains("openrouter.ai"), "{warning}");
+    }
+
+    #[test]
+    fn base_urls_are_normalized() {
+        let cases = [
+            ("https://api.openai.com/v1", "https://api.openai.com/v1"),
+            (
+                "  https://api.openai.com/v1 \t",
+                "https://api.openai.com/v1",
+            ),
+            ("https://api.openai.com/v1/", "https://api.openai.com/v1"),
+            ("https://api.openai.com/v1//", "https://api.openai.com/v1"),
+            (
+                "https://https://api.openai.com/v1",
+                "https://api.openai.com/v1",
+            ),
+            (
+                "HTTPS://https://api.openai.com/v1",
+                "https://api.openai.com/v1",
+            ),
+            ("https://http://localhost:11434", "http://localhost:11434"),
+            ("https://https://https://host/", "https://host"),
+            ("localhost:8080/", "localhost:8080"),
+            ("   ", ""),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(normalize_base_url(input), expected, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn saving_trims_and_normalizes_text_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("translation.toml");
+        let mut overlay = TranslateOverlay::new(&TranslationConfig {
+            provider: "openai".to_string(),
+            ..Default::default()
+        })
+        .with_save_path(path.clone());
+        overlay.api_key = "  sk-test\t".to_string();
+        overlay.model = " gpt-4o-mini ".to_string();
+        overlay.base_url = "https://https://api.openai.com/v1/ ".to_string();
+        overlay.base_url_per_provider.insert(
+            "deepseek".to_string(),
+            " https://api.deepseek.com/ ".to_string(),
+        );
+
+        overlay.save_config();
+
+        assert_eq!(
+            overlay.status_message.as_deref(),
+            Some("Configuration saved")
+        );
+        let saved = TranslationConfig::load_from_path(&path);
+        assert_eq!(saved.api_key.as_deref(), Some("sk-test"));
+        assert_eq!(saved.model.as_deref(), Some("gpt-4o-mini"));
+        assert_eq!(saved.base_url.as_deref(), Some("https://api.openai.com/v1"));
+        assert_eq!(
+            saved
+                .base_url_per_provider
+                .get("deepseek")
+                .map(String::as_str),
+            Some("https://api.deepseek.com")
+        );
+
+        // The form shows the saved values.
+        assert_eq!(overlay.api_key, "sk-test");
+        assert_eq!(overlay.model, "gpt-4o-mini");
+        assert_eq!(overlay.base_url, "https://api.openai.com/v1");
+        assert_eq!(
+            overlay
+                .base_url_per_provider
+                .get("deepseek")
+                .map(String::as_str),
+            Some("https://api.deepseek.com")
+        );
+    }
+
+    #[test]
+    fn whitespace_only_fields_are_saved_as_unset() {
+        let overlay = TranslateOverlay {
+            api_key: " \t".to_string(),
+            model: "  ".to_string(),
+            base_url: " / ".to_string(),
+            ..TranslateOverlay::new(&TranslationConfig::default())
+        };
+
+        let config = overlay.config();
+        assert_eq!(config.api_key, None);
+        assert_eq!(config.model, None);
+        assert_eq!(config.base_url, None);
+    }
+}
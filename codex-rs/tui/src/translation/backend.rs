@@ -0,0 +1,82 @@
+//! Where translation requests go.
+//!
+//! [`ProviderBackend`] sends each request to the configured provider. The orchestrator only
+//! sees the [`RequestBackend`] trait, so tests can answer requests without a network.
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use super::client::TranslationClient;
+use super::client::TranslationOutput;
+use super::config::TranslationConfig;
+use super::error::TranslationError;
+use super::limiter::ProviderLimiter;
+use super::metrics::TranslationMetrics;
+use super::rate_limit::RateLimitState;
+use super::retry::translate_with_retry;
+
+/// One translation request, with the state shared with the orchestrator.
+pub(super) struct BackendRequest {
+    pub(super) config: TranslationConfig,
+    pub(super) metrics: Arc<TranslationMetrics>,
+    pub(super) limiter: Arc<ProviderLimiter>,
+    /// Earlier translations sent as reference material (`rolling_context_chars`).
+    pub(super) context: Option<String>,
+    pub(super) short_prompt: bool,
+    pub(super) text: String,
+}
+
+/// The translation, and the rate limit quota the provider reported even when it failed.
+pub(super) type BackendResponse = (
+    Result<TranslationOutput, TranslationError>,
+    Option<RateLimitState>,
+);
+
+/// Answers translation requests.
+pub(super) trait RequestBackend: Debug + Send + Sync {
+    fn translate(
+        &self,
+        request: BackendRequest,
+    ) -> Pin<Box<dyn Future<Output = BackendResponse> + Send>>;
+}
+
+/// Sends requests to the configured provider, with retries.
+#[derive(Debug, Default)]
+pub(super) struct ProviderBackend;
+
+impl RequestBackend for ProviderBackend {
+    fn translate(
+        &self,
+        request: BackendRequest,
+    ) -> Pin<Box<dyn Future<Output = BackendResponse> + Send>> {
+        Box::pin(async move {
+            let mut rate_limit = None;
+            let result = Self::translate_with_provider(&request, &mut rate_limit).await;
+            (result, rate_limit)
+        })
+    }
+}
+
+impl ProviderBackend {
+    async fn translate_with_provider(
+        request: &BackendRequest,
+        rate_limit: &mut Option<RateLimitState>,
+    ) -> Result<TranslationOutput, TranslationError> {
+        let config = &request.config;
+        // Held until the request (including retries) finishes.
+        let _permit = request.limiter.acquire(config.effective_provider()).await;
+        let client = TranslationClient::from_config(config)?
+            .with_context(request.context.clone())
+            .with_short_prompt(request.short_prompt)
+            .with_connection_health(Arc::clone(request.metrics.connection_health()));
+        let result = translate_with_retry(&client, &request.text, config, &request.metrics).await;
+        *rate_limit = client.rate_limit_state();
+        let output = result?;
+        Ok(TranslationOutput {
+            text: config.output_encoding.apply(&output.text),
+            ..output
+        })
+    }
+}
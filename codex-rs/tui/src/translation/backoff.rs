@@ -0,0 +1,91 @@
+//! Shorter barrier waits while the provider keeps timing out.
+//!
+//! A provider that times out once is likely to time out on the next reasoning block too,
+//! and every attempt would hold the transcript for the full `max_wait`. [`TimeoutBackoff`]
+//! halves the wait after each consecutive timeout, down to a floor, and restores the full
+//! wait as soon as a translation arrives in time.
+
+use std::time::Duration;
+
+/// The wait never drops below this, so a provider that recovers still gets a chance.
+const MIN_WAIT: Duration = Duration::from_millis(500);
+
+/// Timeouts after which the wait stops shrinking (1/8 of the configured wait).
+const MAX_HALVINGS: u32 = 3;
+
+#[derive(Debug, Default)]
+pub(super) struct TimeoutBackoff {
+    consecutive_timeouts: u32,
+}
+
+impl TimeoutBackoff {
+    /// The wait to use for the next barrier, given the configured `max_wait`.
+    pub(super) fn max_wait(&self, configured: Duration) -> Duration {
+        if self.consecutive_timeouts == 0 {
+            return configured;
+        }
+        let halvings = self.consecutive_timeouts.min(MAX_HALVINGS);
+        (configured / 2u32.pow(halvings)).max(MIN_WAIT.min(configured))
+    }
+
+    pub(super) fn on_timeout(&mut self) {
+        self.consecutive_timeouts = self.consecutive_timeouts.saturating_add(1);
+    }
+
+    /// A translation arrived before its deadline: back to the full wait.
+    pub(super) fn on_success(&mut self) {
+        self.consecutive_timeouts = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn consecutive_timeouts_shorten_the_wait_down_to_a_floor() {
+        let configured = Duration::from_secs(8);
+        let mut backoff = TimeoutBackoff::default();
+        assert_eq!(backoff.max_wait(configured), configured);
+
+        let mut waits = Vec::new();
+        for _ in 0..5 {
+            backoff.on_timeout();
+            waits.push(backoff.max_wait(configured));
+        }
+        assert_eq!(
+            waits,
+            vec![
+                Duration::from_secs(4),
+                Duration::from_secs(2),
+                Duration::from_secs(1),
+                Duration::from_secs(1),
+                Duration::from_secs(1),
+            ]
+        );
+    }
+
+    #[test]
+    fn short_waits_stop_at_the_minimum() {
+        let mut backoff = TimeoutBackoff::default();
+        backoff.on_timeout();
+        backoff.on_timeout();
+        assert_eq!(backoff.max_wait(Duration::from_secs(1)), MIN_WAIT);
+        // A configured wait below the floor is never lengthened.
+        assert_eq!(
+            backoff.max_wait(Duration::from_millis(200)),
+            Duration::from_millis(200)
+        );
+    }
+
+    #[test]
+    fn success_restores_the_full_wait() {
+        let configured = Duration::from_secs(8);
+        let mut backoff = TimeoutBackoff::default();
+        backoff.on_timeout();
+        backoff.on_timeout();
+        backoff.on_success();
+        assert_eq!(backoff.max_wait(configured), configured);
+    }
+}
@@ -0,0 +1,319 @@
+//! Short reasoning blocks translated together (`translate_after_n_chars`).
+//!
+//! Agents sometimes emit a run of short reasoning blocks, and translating each one costs a
+//! request. [`BatchBuffer`] collects their bodies until the text is long enough, and the
+//! resulting [`Batch`] goes out as one request. Blocks are joined as paragraphs, so the
+//! translation normally comes back with the same paragraphs and [`Batch::split`] hands each
+//! block its own. When the model merges or splits paragraphs, the translation is cut at
+//! sentence boundaries instead, in proportion to the length of each block.
+
+/// Separates blocks, and paragraphs within a block.
+const PARAGRAPH_BREAK: &str = "\n\n";
+
+/// A reasoning block in a batch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BatchedBlock {
+    /// Bold title of the block (e.g. "Thinking"), shown above its part of the translation.
+    title: Option<String>,
+    /// Characters in the body.
+    chars: usize,
+    /// Paragraphs in the body.
+    paragraphs: usize,
+}
+
+/// Reasoning bodies waiting for enough text to be worth a request.
+#[derive(Debug, Default)]
+pub(super) struct BatchBuffer {
+    text: String,
+    blocks: Vec<BatchedBlock>,
+}
+
+impl BatchBuffer {
+    /// Add a block's body. Once the buffered text is longer than `threshold` characters the
+    /// buffer is emptied and its contents returned as a batch.
+    pub(super) fn push(
+        &mut self,
+        title: Option<String>,
+        body: &str,
+        threshold: usize,
+    ) -> Option<Batch> {
+        self.hold(title, body);
+        if self.text.chars().count() <= threshold {
+            return None;
+        }
+        self.take()
+    }
+
+    /// Add a block's body without checking the threshold, for blocks that arrive while
+    /// another translation is still pending.
+    pub(super) fn hold(&mut self, title: Option<String>, body: &str) {
+        let body = body.trim();
+        if !self.text.is_empty() {
+            self.text.push_str(PARAGRAPH_BREAK);
+        }
+        self.text.push_str(body);
+        self.blocks.push(BatchedBlock {
+            title,
+            chars: body.chars().count(),
+            paragraphs: paragraphs(body).count().max(1),
+        });
+    }
+
+    /// Empty the buffer, returning whatever it held as a batch however short it is.
+    pub(super) fn take(&mut self) -> Option<Batch> {
+        if self.blocks.is_empty() {
+            return None;
+        }
+        Some(Batch {
+            text: std::mem::take(&mut self.text),
+            blocks: std::mem::take(&mut self.blocks),
+        })
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.text.clear();
+        self.blocks.clear();
+    }
+}
+
+/// Blocks sent for translation in one request.
+#[derive(Debug)]
+pub(super) struct Batch {
+    text: String,
+    blocks: Vec<BatchedBlock>,
+}
+
+impl Batch {
+    /// The bodies of every block, as sent to the provider.
+    pub(super) fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub(super) fn block_count(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// The part of `translated` that belongs to each block, in order. A part is empty when
+    /// the translation was too short to give every block a sentence.
+    pub(super) fn split(&self, translated: &str) -> Vec<String> {
+        let translated_paragraphs: Vec<&str> = paragraphs(translated).collect();
+        let source_paragraphs: usize = self.blocks.iter().map(|block| block.paragraphs).sum();
+        if translated_paragraphs.len() == source_paragraphs {
+            let mut rest = translated_paragraphs.as_slice();
+            return self
+                .blocks
+                .iter()
+                .map(|block| {
+                    let (part, after) = rest.split_at(block.paragraphs);
+                    rest = after;
+                    part.join(PARAGRAPH_BREAK)
+                })
+                .collect();
+        }
+        let weights: Vec<usize> = self.blocks.iter().map(|block| block.chars).collect();
+        split_by_sentences(translated, &weights)
+    }
+
+    /// `translated` for display: each block's part under the block's title.
+    pub(super) fn render(&self, translated: &str) -> String {
+        self.blocks
+            .iter()
+            .zip(self.split(translated))
+            .filter(|(_, part)| !part.is_empty())
+            .map(|(block, part)| match &block.title {
+                Some(title) => format!("**{title}**{PARAGRAPH_BREAK}{part}"),
+                None => part,
+            })
+            .collect::<Vec<_>>()
+            .join(PARAGRAPH_BREAK)
+    }
+}
+
+/// Non-blank paragraphs of `text`, trimmed.
+fn paragraphs(text: &str) -> impl Iterator<Item = &str> {
+    text.split(PARAGRAPH_BREAK)
+        .map(str::trim)
+        .filter(|paragraph| !paragraph.is_empty())
+}
+
+/// Cut `text` into `weights.len()` parts at sentence boundaries, each sentence going to the
+/// block whose share of the source its midpoint falls in.
+fn split_by_sentences(text: &str, weights: &[usize]) -> Vec<String> {
+    let mut parts = vec![String::new(); weights.len()];
+    let Some(&first_weight) = weights.first() else {
+        return parts;
+    };
+    let sentences = sentences(text);
+    let total_chars: usize = sentences.iter().map(|s| s.chars().count()).sum();
+    let total_weight = weights.iter().sum::<usize>().max(1);
+
+    let mut block = 0;
+    let mut weight_so_far = first_weight;
+    let mut chars_so_far = 0;
+    for sentence in sentences {
+        let len = sentence.chars().count();
+        let midpoint = chars_so_far + len / 2;
+        // midpoint / total_chars >= weight_so_far / total_weight, without division.
+        while block + 1 < weights.len() && midpoint * total_weight >= weight_so_far * total_chars {
+            block += 1;
+            weight_so_far += weights[block];
+        }
+        parts[block].push_str(sentence);
+        chars_so_far += len;
+    }
+    parts
+        .into_iter()
+        .map(|part| part.trim().to_string())
+        .collect()
+}
+
+/// `text` cut after each sentence end, keeping the whitespace that follows it. Latin `.`,
+/// `!` and `?` only end a sentence before whitespace (so `3.5` and `foo.rs` stay whole);
+/// full-width `。！？` and line breaks always do.
+fn sentences(text: &str) -> Vec<&str> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let ends_sentence = match c {
+            '。' | '！' | '？' | '\n' => true,
+            '.' | '!' | '?' => chars.peek().is_none_or(|(_, next)| next.is_whitespace()),
+            _ => false,
+        };
+        if !ends_sentence {
+            continue;
+        }
+        let mut end = i + c.len_utf8();
+        while let Some(&(j, next)) = chars.peek()
+            && next.is_whitespace()
+        {
+            end = j + next.len_utf8();
+            chars.next();
+        }
+        sentences.push(&text[start..end]);
+        start = end;
+    }
+    if start < text.len() {
+        sentences.push(&text[start..]);
+    }
+    sentences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn batch_of(blocks: &[(&str, &str)]) -> Batch {
+        let mut buffer = BatchBuffer::default();
+        let (last, rest) = blocks.split_last().unwrap();
+        for (title, body) in rest {
+            assert!(
+                buffer
+                    .push(Some(title.to_string()), body, usize::MAX)
+                    .is_none()
+            );
+        }
+        buffer.push(Some(last.0.to_string()), last.1, 0).unwrap()
+    }
+
+    #[test]
+    fn blocks_accumulate_until_the_threshold_is_exceeded() {
+        let mut buffer = BatchBuffer::default();
+        assert!(buffer.push(None, "Checking the build.", 40).is_none());
+        assert!(buffer.push(None, "Running tests.", 40).is_none());
+        assert_eq!(buffer.blocks.len(), 2);
+
+        let batch = buffer.push(None, "  Fixing the lockfile.\n", 40).unwrap();
+        assert_eq!(batch.block_count(), 3);
+        assert_eq!(
+            batch.text(),
+            "Checking the build.\n\nRunning tests.\n\nFixing the lockfile."
+        );
+        assert!(buffer.text.is_empty() && buffer.blocks.is_empty());
+    }
+
+    #[test]
+    fn text_exactly_at_the_threshold_keeps_waiting() {
+        let mut buffer = BatchBuffer::default();
+        assert!(buffer.push(None, "12345", 5).is_none());
+        buffer.clear();
+        assert!(buffer.text.is_empty() && buffer.blocks.is_empty());
+        assert_eq!(buffer.push(None, "123456", 5).unwrap().block_count(), 1);
+    }
+
+    #[test]
+    fn take_returns_held_blocks_below_the_threshold() {
+        let mut buffer = BatchBuffer::default();
+        assert!(buffer.take().is_none());
+
+        assert!(buffer.push(None, "Checking the build.", 400).is_none());
+        buffer.hold(Some("Tests".to_string()), "Running tests.");
+        let batch = buffer.take().unwrap();
+        assert_eq!(batch.block_count(), 2);
+        assert_eq!(batch.text(), "Checking the build.\n\nRunning tests.");
+        assert!(buffer.take().is_none());
+    }
+
+    #[test]
+    fn matching_paragraphs_go_to_their_blocks() {
+        let batch = batch_of(&[
+            ("Planning", "Read the config.\n\nThen the tests."),
+            ("Fixing", "Patch the parser."),
+        ]);
+
+        assert_eq!(
+            batch.split("读取配置。\n\n然后是测试。\n\n修补解析器。"),
+            vec![
+                "读取配置。\n\n然后是测试。".to_string(),
+                "修补解析器。".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn merged_paragraphs_are_split_at_sentences_by_length() {
+        let batch = batch_of(&[
+            ("A", "Short one."),
+            ("B", "A much longer block with three times the text."),
+        ]);
+
+        assert_eq!(
+            batch.split("一。 二。 三。 四。"),
+            vec!["一。".to_string(), "二。 三。 四。".to_string()]
+        );
+    }
+
+    #[test]
+    fn sentence_ends_need_whitespace_after_latin_punctuation() {
+        assert_eq!(
+            sentences("Use v3.5 in foo.rs. Done!\nNext？好。"),
+            vec!["Use v3.5 in foo.rs. ", "Done!\n", "Next？", "好。"]
+        );
+        assert_eq!(sentences("no end"), vec!["no end"]);
+        assert_eq!(sentences(""), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn short_translations_leave_some_blocks_empty() {
+        // The only sentence is centered on the middle block's share.
+        assert_eq!(
+            split_by_sentences("Only one sentence.", &[10, 10, 10]),
+            vec![
+                String::new(),
+                "Only one sentence.".to_string(),
+                String::new()
+            ]
+        );
+    }
+
+    #[test]
+    fn render_puts_each_part_under_its_title() {
+        let batch = batch_of(&[("Planning", "Read the config."), ("Fixing", "Patch it.")]);
+
+        assert_eq!(
+            batch.render("读取配置。\n\n修补它。"),
+            "**Planning**\n\n读取配置。\n\n**Fixing**\n\n修补它。"
+        );
+    }
+}
@@ -0,0 +1,134 @@
+//! Per-session spend cap for translation requests.
+//!
+//! [`BudgetTracker`] counts requests and reported tokens against the configured
+//! [`SessionBudget`]. Once either limit is reached translation stays paused until
+//! [`BudgetTracker::reset`] is called.
+
+use super::config::SessionBudget;
+
+#[derive(Debug, Default)]
+pub(super) struct BudgetTracker {
+    limits: SessionBudget,
+    requests: u32,
+    tokens: u64,
+    exhausted: bool,
+}
+
+impl BudgetTracker {
+    pub(super) fn new(limits: SessionBudget) -> Self {
+        Self {
+            limits,
+            ..Default::default()
+        }
+    }
+
+    /// Whether translation is paused because a limit was reached.
+    pub(super) fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// Limits currently enforced.
+    pub(super) fn limits(&self) -> SessionBudget {
+        self.limits
+    }
+
+    /// Count a started request. Returns a notice when this request uses up the budget.
+    pub(super) fn record_request(&mut self) -> Option<String> {
+        self.requests = self.requests.saturating_add(1);
+        match self.limits.max_requests {
+            Some(max) if self.requests >= max => {
+                self.exhaust(format!("the session limit of {max} requests was reached"))
+            }
+            _ => None,
+        }
+    }
+
+    /// Count tokens reported by a completed request. Returns a notice when they use up the
+    /// budget.
+    pub(super) fn record_tokens(&mut self, tokens: u64) -> Option<String> {
+        self.tokens = self.tokens.saturating_add(tokens);
+        match self.limits.max_tokens {
+            Some(max) if self.tokens >= max => {
+                self.exhaust(format!("the session limit of {max} tokens was reached"))
+            }
+            _ => None,
+        }
+    }
+
+    /// Clear the counters and resume translation, optionally with new limits.
+    pub(super) fn reset(&mut self, limits: SessionBudget) {
+        *self = Self::new(limits);
+    }
+
+    fn exhaust(&mut self, reason: String) -> Option<String> {
+        if self.exhausted {
+            return None;
+        }
+        self.exhausted = true;
+        Some(format!(
+            "Translation paused: {reason}. Re-enable translation in /translate to resume."
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_limit_pauses_translation() {
+        let mut budget = BudgetTracker::new(SessionBudget {
+            max_requests: Some(2),
+            max_tokens: None,
+        });
+
+        assert_eq!(budget.record_request(), None);
+        assert!(!budget.is_exhausted());
+        let notice = budget.record_request().unwrap();
+        assert!(notice.contains("2 requests"), "{notice}");
+        assert!(budget.is_exhausted());
+
+        // The notice is only produced once.
+        assert_eq!(budget.record_request(), None);
+    }
+
+    #[test]
+    fn token_limit_pauses_translation() {
+        let mut budget = BudgetTracker::new(SessionBudget {
+            max_requests: None,
+            max_tokens: Some(1_000),
+        });
+
+        assert_eq!(budget.record_tokens(600), None);
+        let notice = budget.record_tokens(500).unwrap();
+        assert!(notice.contains("1000 tokens"), "{notice}");
+        assert!(budget.is_exhausted());
+    }
+
+    #[test]
+    fn reset_resumes_translation() {
+        let limits = SessionBudget {
+            max_requests: Some(1),
+            max_tokens: Some(10),
+        };
+        let mut budget = BudgetTracker::new(limits);
+        budget.record_request();
+        assert!(budget.is_exhausted());
+
+        budget.reset(limits);
+
+        assert!(!budget.is_exhausted());
+        assert!(budget.record_tokens(5).is_none());
+        assert!(budget.record_request().is_some());
+    }
+
+    #[test]
+    fn unlimited_budget_never_pauses() {
+        let mut budget = BudgetTracker::default();
+        for _ in 0..100 {
+            assert_eq!(budget.record_request(), None);
+            assert_eq!(budget.record_tokens(u64::MAX / 2), None);
+        }
+        assert!(!budget.is_exhausted());
+    }
+}
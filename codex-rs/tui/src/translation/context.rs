@@ -0,0 +1,129 @@
+//! Rolling context of recent translations.
+//!
+//! Reasoning blocks are translated one at a time, so pronouns and terminology can drift
+//! between blocks. With `rolling_context_chars` set, [`RollingContext`] keeps the most
+//! recent translated blocks and hands them to the next request as reference material.
+
+use std::collections::VecDeque;
+
+#[derive(Debug, Default)]
+pub(super) struct RollingContext {
+    /// Size bound in characters; `None` disables the context.
+    max_chars: Option<usize>,
+    /// Recent translations, oldest first.
+    blocks: VecDeque<String>,
+}
+
+impl RollingContext {
+    pub(super) fn new(max_chars: Option<usize>) -> Self {
+        Self {
+            max_chars,
+            blocks: VecDeque::new(),
+        }
+    }
+
+    pub(super) fn max_chars(&self) -> Option<usize> {
+        self.max_chars
+    }
+
+    /// Remember a translated block, dropping the oldest blocks beyond the size bound.
+    pub(super) fn push(&mut self, translated: &str) {
+        let Some(max_chars) = self.max_chars.filter(|&max| max > 0) else {
+            return;
+        };
+        let translated = translated.trim();
+        if translated.is_empty() {
+            return;
+        }
+        self.blocks
+            .push_back(keep_tail(translated, max_chars).to_string());
+        while self.len() > max_chars {
+            self.blocks.pop_front();
+        }
+    }
+
+    /// The context to send with the next request, if any.
+    pub(super) fn render(&self) -> Option<String> {
+        if self.blocks.is_empty() {
+            return None;
+        }
+        Some(
+            self.blocks
+                .iter()
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(BLOCK_SEPARATOR),
+        )
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.blocks.clear();
+    }
+
+    /// Rendered size in characters.
+    fn len(&self) -> usize {
+        let separators = self.blocks.len().saturating_sub(1) * BLOCK_SEPARATOR.chars().count();
+        self.blocks
+            .iter()
+            .map(|block| block.chars().count())
+            .sum::<usize>()
+            + separators
+    }
+}
+
+const BLOCK_SEPARATOR: &str = "\n\n";
+
+/// The last `max_chars` characters of `text`; the end of a block is closest to what follows.
+fn keep_tail(text: &str, max_chars: usize) -> &str {
+    let skip = text.chars().count().saturating_sub(max_chars);
+    match text.char_indices().nth(skip) {
+        Some((start, _)) => &text[start..],
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn disabled_context_stays_empty() {
+        let mut context = RollingContext::new(None);
+        context.push("第一段");
+        assert_eq!(context.render(), None);
+
+        let mut context = RollingContext::new(Some(0));
+        context.push("第一段");
+        assert_eq!(context.render(), None);
+    }
+
+    #[test]
+    fn recent_blocks_are_joined_oldest_first() {
+        let mut context = RollingContext::new(Some(100));
+        context.push("第一段");
+        context.push("  \n");
+        context.push("第二段\n");
+        assert_eq!(context.render().as_deref(), Some("第一段\n\n第二段"));
+    }
+
+    #[test]
+    fn oldest_blocks_are_dropped_to_fit_the_bound() {
+        let mut context = RollingContext::new(Some(10));
+        context.push("一二三四");
+        context.push("五六七八");
+        // 4 + 2 + 4 characters fit exactly.
+        assert_eq!(context.render().as_deref(), Some("一二三四\n\n五六七八"));
+
+        context.push("九十");
+        assert_eq!(context.render().as_deref(), Some("五六七八\n\n九十"));
+    }
+
+    #[test]
+    fn oversized_block_keeps_its_tail() {
+        let mut context = RollingContext::new(Some(4));
+        context.push("之前的内容");
+        context.push("这是很长的一段翻译");
+        assert_eq!(context.render().as_deref(), Some("一段翻译"));
+    }
+}
@@ -0,0 +1,229 @@
+//! Plausibility checks on translation output.
+//!
+//! Reasoning text is untrusted: it can quote instructions ("ignore the above and ...") that a
+//! model may follow instead of translating. [`check_output`] flags answers that do not look
+//! like a translation of their source, so the request can be retried with a stricter prompt.
+
+use super::client::SOURCE_END;
+use super::client::SOURCE_START;
+use super::client::TranslationOutput;
+
+/// Sources shorter than this (in characters) skip the length check; short phrases vary too
+/// much between languages.
+const MIN_CHARS_FOR_LENGTH_CHECK: usize = 40;
+/// Plausible output length relative to the source, in characters. CJK output is much
+/// shorter than English source; the reverse is much longer.
+const MIN_LENGTH_RATIO: f64 = 0.15;
+const MAX_LENGTH_RATIO: f64 = 5.0;
+
+/// Outputs with fewer letters outside code skip the script check.
+const MIN_LETTERS_FOR_SCRIPT_CHECK: usize = 20;
+/// Share of prose letters that must be in the target language's script.
+const MIN_SCRIPT_SHARE: f64 = 0.1;
+
+/// Openings of a model refusing or talking about the request rather than translating it.
+const REFUSAL_PREFIXES: [&str; 9] = [
+    "i'm sorry",
+    "i am sorry",
+    "sorry, i",
+    "i cannot",
+    "i can't",
+    "i can not",
+    "i'm unable",
+    "i am unable",
+    "as an ai",
+];
+
+/// Why an output does not look like a translation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Suspicion {
+    /// The output repeats the prompt's delimiters.
+    EchoedPrompt,
+    /// The output is an English refusal for a non-English target.
+    Refusal,
+    /// The output is far shorter or longer than a translation would be.
+    Length,
+    /// Almost none of the output is written in the target language's script.
+    WrongScript,
+}
+
+impl Suspicion {
+    pub(super) fn describe(self) -> &'static str {
+        match self {
+            Self::EchoedPrompt => "the output repeats the prompt",
+            Self::Refusal => "the model refused or answered instead of translating",
+            Self::Length => "the output length does not match the source",
+            Self::WrongScript => "the output is not in the target language",
+        }
+    }
+}
+
+/// Check that `output` plausibly translates `source` into `target_lang`.
+pub(super) fn check_output(
+    source: &str,
+    output: &TranslationOutput,
+    target_lang: &str,
+) -> Option<Suspicion> {
+    let text = output.text.trim();
+    if text.contains(SOURCE_START) || text.contains(SOURCE_END) {
+        return Some(Suspicion::EchoedPrompt);
+    }
+
+    let language = primary_subtag(target_lang);
+    if language != "en" {
+        let opening = text.to_lowercase();
+        let source = source.to_lowercase();
+        if REFUSAL_PREFIXES
+            .iter()
+            .any(|prefix| opening.starts_with(prefix) && !source.contains(prefix))
+        {
+            return Some(Suspicion::Refusal);
+        }
+    }
+
+    let source_chars = source.trim().chars().count();
+    // A truncated translation is legitimately short.
+    if source_chars >= MIN_CHARS_FOR_LENGTH_CHECK && !output.truncated {
+        let ratio = text.chars().count() as f64 / source_chars as f64;
+        if !(MIN_LENGTH_RATIO..=MAX_LENGTH_RATIO).contains(&ratio) {
+            return Some(Suspicion::Length);
+        }
+    }
+
+    if let Some(in_script) = script_of(&language) {
+        let prose = prose(text);
+        let letters = prose.chars().filter(|c| c.is_alphabetic()).count();
+        let in_target = prose.chars().filter(|&c| in_script(c)).count();
+        if letters >= MIN_LETTERS_FOR_SCRIPT_CHECK
+            && (in_target as f64) < letters as f64 * MIN_SCRIPT_SHARE
+        {
+            return Some(Suspicion::WrongScript);
+        }
+    }
+
+    None
+}
+
+/// Lowercase primary language subtag, e.g. "zh" for "zh-CN".
+fn primary_subtag(target_lang: &str) -> String {
+    target_lang
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+/// Character test for languages that are not written in Latin script.
+fn script_of(language: &str) -> Option<fn(char) -> bool> {
+    let in_script: fn(char) -> bool = match language {
+        "zh" => is_han,
+        "ja" => |c| is_han(c) || matches!(c, '\u{3040}'..='\u{30FF}'),
+        "ko" => is_hangul,
+        "ru" | "uk" | "bg" | "sr" => |c| matches!(c, '\u{0400}'..='\u{04FF}'),
+        "ar" | "fa" => |c| matches!(c, '\u{0600}'..='\u{06FF}'),
+        "hi" => |c| matches!(c, '\u{0900}'..='\u{097F}'),
+        "th" => |c| matches!(c, '\u{0E00}'..='\u{0E7F}'),
+        _ => return None,
+    };
+    Some(in_script)
+}
+
+fn is_han(c: char) -> bool {
+    matches!(c, '\u{4E00}'..='\u{9FFF}' | '\u{3400}'..='\u{4DBF}')
+}
+
+fn is_hangul(c: char) -> bool {
+    matches!(
+        c,
+        '\u{AC00}'..='\u{D7AF}' | '\u{1100}'..='\u{11FF}' | '\u{3130}'..='\u{318F}'
+    )
+}
+
+/// `text` without fenced or inline code, which stays untranslated.
+fn prose(text: &str) -> String {
+    text.split("```")
+        .step_by(2)
+        .flat_map(|outside_fences| outside_fences.split('`').step_by(2))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn output(text: &str) -> TranslationOutput {
+        TranslationOutput {
+            text: text.to_string(),
+            usage: None,
+            truncated: false,
+        }
+    }
+
+    const SOURCE: &str = "Running the full test suite to check whether the parser change broke \
+                          anything in the snapshot tests.";
+
+    #[test]
+    fn plausible_translations_pass() {
+        let translated = "运行完整的测试套件，检查解析器的改动是否破坏了快照测试。";
+        assert_eq!(check_output(SOURCE, &output(translated), "zh-CN"), None);
+
+        // Identifiers in code spans don't count against the target script.
+        let with_code = "运行 `cargo test -p codex-tui --all-features` 检查快照测试。";
+        assert_eq!(check_output(SOURCE, &output(with_code), "zh-CN"), None);
+
+        let spanish = "Ejecutando todas las pruebas para comprobar si el cambio rompió algo.";
+        assert_eq!(check_output(SOURCE, &output(spanish), "es"), None);
+    }
+
+    #[test]
+    fn answers_instead_of_translations_are_flagged() {
+        assert_eq!(
+            check_output(SOURCE, &output("OK"), "zh-CN"),
+            Some(Suspicion::Length)
+        );
+        assert_eq!(
+            check_output(
+                SOURCE,
+                &output("I'm sorry, but I can't help with running tests."),
+                "es"
+            ),
+            Some(Suspicion::Refusal)
+        );
+        assert_eq!(
+            check_output(
+                SOURCE,
+                &output("The snapshot tests all pass, so the parser change is fine."),
+                "zh-CN"
+            ),
+            Some(Suspicion::WrongScript)
+        );
+        assert_eq!(
+            check_output(
+                SOURCE,
+                &output(&format!(
+                    "{SOURCE_START}\n运行完整的测试套件。\n{SOURCE_END}"
+                )),
+                "zh-CN"
+            ),
+            Some(Suspicion::EchoedPrompt)
+        );
+    }
+
+    #[test]
+    fn english_targets_may_start_with_an_apology() {
+        let source = "抱歉，我无法运行这个命令，因为沙箱禁止了网络访问，需要换一种方式验证。";
+        let translated = "Sorry, I can't run this command because the sandbox blocks network \
+                          access, so I need another way to verify it.";
+        assert_eq!(check_output(source, &output(translated), "en"), None);
+    }
+
+    #[test]
+    fn truncated_output_skips_the_length_check() {
+        let truncated = TranslationOutput {
+            truncated: true,
+            ..output("运行完整的")
+        };
+        assert_eq!(check_output(SOURCE, &truncated, "zh-CN"), None);
+    }
+}
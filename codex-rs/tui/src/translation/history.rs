@@ -0,0 +1,150 @@
+//! Completed translations, optionally persisted between sessions.
+//!
+//! When `persist_between_sessions` is set the history is stored as JSON lines under codex home,
+//! written on shutdown and read back on startup.
+
+use std::collections::VecDeque;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use chrono::Utc;
+use codex_core::path_utils::write_atomically;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// File name of the persisted history inside codex home. Distinct from the `history_log`
+/// file, whose records have a different shape.
+const HISTORY_FILENAME: &str = "translation_store.jsonl";
+
+/// Maximum number of records kept; the oldest are dropped first.
+const MAX_HISTORY_RECORDS: usize = 1000;
+
+/// One completed translation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct TranslationHistoryEntry {
+    pub(crate) timestamp: DateTime<Utc>,
+    pub(crate) thread_id: String,
+    pub(crate) source: String,
+    pub(crate) translated: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(crate) struct TranslationHistory {
+    entries: VecDeque<TranslationHistoryEntry>,
+}
+
+impl TranslationHistory {
+    /// Path of the persisted history under codex home.
+    pub(super) fn default_path() -> io::Result<PathBuf> {
+        Ok(codex_core::config::find_codex_home()?.join(HISTORY_FILENAME))
+    }
+
+    /// Append `entry`, dropping the oldest record once the cap is reached.
+    pub(crate) fn push(&mut self, entry: TranslationHistoryEntry) {
+        if self.entries.len() == MAX_HISTORY_RECORDS {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// Records from oldest to newest.
+    pub(crate) fn entries(&self) -> impl Iterator<Item = &TranslationHistoryEntry> {
+        self.entries.iter()
+    }
+
+    /// Read a JSONL history file, skipping lines that fail to parse.
+    ///
+    /// Only the newest `MAX_HISTORY_RECORDS` records are kept.
+    pub(crate) fn load_from_file(path: &Path) -> io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let mut history = Self::default();
+        for (index, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str::<TranslationHistoryEntry>(line) {
+                Ok(entry) => history.push(entry),
+                Err(e) => {
+                    tracing::warn!(
+                        "Skipping malformed translation history line {} in {}: {e}",
+                        index + 1,
+                        path.display()
+                    );
+                }
+            }
+        }
+        Ok(history)
+    }
+
+    /// Atomically replace the file at `path` with this history as JSON lines.
+    pub(crate) fn save_to_file(&self, path: &Path) -> io::Result<()> {
+        let mut contents = String::new();
+        for entry in &self.entries {
+            contents.push_str(&serde_json::to_string(entry).map_err(io::Error::other)?);
+            contents.push('\n');
+        }
+        write_atomically(path, &contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn entry(n: usize) -> TranslationHistoryEntry {
+        TranslationHistoryEntry {
+            timestamp: DateTime::from_timestamp(1_700_000_000 + n as i64, 0).unwrap(),
+            thread_id: "thread-1".to_string(),
+            source: format!("source {n}"),
+            translated: format!("译文 {n}"),
+        }
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(HISTORY_FILENAME);
+        let mut history = TranslationHistory::default();
+        history.push(entry(1));
+        history.push(entry(2));
+
+        history.save_to_file(&path).unwrap();
+        let loaded = TranslationHistory::load_from_file(&path).unwrap();
+
+        assert_eq!(loaded, history);
+    }
+
+    #[test]
+    fn malformed_lines_are_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(HISTORY_FILENAME);
+        let valid = serde_json::to_string(&entry(1)).unwrap();
+        std::fs::write(
+            &path,
+            format!("{{not json\n{valid}\n\n{{\"source\":\"x\"}}\n"),
+        )
+        .unwrap();
+
+        let loaded = TranslationHistory::load_from_file(&path).unwrap();
+
+        assert_eq!(
+            loaded.entries().cloned().collect::<Vec<_>>(),
+            vec![entry(1)]
+        );
+    }
+
+    #[test]
+    fn oldest_records_are_dropped_at_cap() {
+        let mut history = TranslationHistory::default();
+        for n in 0..MAX_HISTORY_RECORDS + 5 {
+            history.push(entry(n));
+        }
+
+        assert_eq!(history.entries().count(), MAX_HISTORY_RECORDS);
+        assert_eq!(history.entries().next(), Some(&entry(5)));
+    }
+}
@@ -0,0 +1,170 @@
+//! JSON-lines history of completed translations.
+//!
+//! Each record describes one request (provider, model, latency, token usage) and identifies the
+//! source by its SHA-256, so usage can be analysed without storing reasoning content. Raw text is
+//! only written when `history_log_include_text` is set.
+
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+
+use super::client::TranslationOutput;
+use super::config::TranslationConfig;
+use super::error::TranslationError;
+use super::language::TargetLanguage;
+
+/// File name of the history log inside codex home.
+const HISTORY_LOG_FILENAME: &str = "translation_history.jsonl";
+
+/// One line of the history log.
+#[derive(Debug, Serialize)]
+pub(super) struct TranslationRecord {
+    timestamp: DateTime<Utc>,
+    source_sha256: String,
+    provider: String,
+    model: String,
+    target_language: TargetLanguage,
+    latency_ms: u64,
+    success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    input_tokens: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    output_tokens: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    translated_text: Option<String>,
+}
+
+impl TranslationRecord {
+    pub(super) fn new(
+        config: &TranslationConfig,
+        source: &str,
+        result: &Result<TranslationOutput, TranslationError>,
+        latency: Duration,
+    ) -> Self {
+        let provider_id = config.effective_provider();
+        let provider = provider_id.definition();
+        let output = result.as_ref().ok();
+        let usage = output.and_then(|o| o.usage);
+        let include_text = config.history_log_include_text;
+        Self {
+            timestamp: Utc::now(),
+            source_sha256: format!("{:x}", Sha256::digest(source.as_bytes())),
+            provider: provider_id.as_str().to_string(),
+            model: config.effective_model(provider).to_string(),
+            target_language: config.target_language,
+            latency_ms: u64::try_from(latency.as_millis()).unwrap_or(u64::MAX),
+            success: output.is_some(),
+            input_tokens: usage.map(|u| u.input_tokens),
+            output_tokens: usage.map(|u| u.output_tokens),
+            source_text: include_text.then(|| source.to_string()),
+            translated_text: output.filter(|_| include_text).map(|o| o.text.clone()),
+        }
+    }
+}
+
+/// Path of the history log under codex home.
+fn history_log_path() -> io::Result<PathBuf> {
+    Ok(codex_core::config::find_codex_home()?.join(HISTORY_LOG_FILENAME))
+}
+
+/// Append `record` as a single JSON line to the log at `path`.
+fn append_record(path: &Path, record: &TranslationRecord) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut line = serde_json::to_string(record).map_err(io::Error::other)?;
+    line.push('\n');
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.as_bytes())
+}
+
+/// Append a record for a completed translation to the history log.
+///
+/// Failures are logged and otherwise ignored; the history log never affects translation.
+pub(super) fn record_translation(record: &TranslationRecord) {
+    let result = history_log_path().and_then(|path| append_record(&path, record));
+    if let Err(e) = result {
+        tracing::warn!("Failed to append translation history record: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::translation::client::TokenUsage;
+    use pretty_assertions::assert_eq;
+
+    fn completed() -> Result<TranslationOutput, TranslationError> {
+        Ok(TranslationOutput {
+            text: "你好".to_string(),
+            usage: Some(TokenUsage {
+                input_tokens: 12,
+                output_tokens: 3,
+            }),
+            truncated: false,
+        })
+    }
+
+    #[test]
+    fn completed_translation_appends_jsonl_record() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(HISTORY_LOG_FILENAME);
+        let config = TranslationConfig::default();
+
+        let record =
+            TranslationRecord::new(&config, "hello", &completed(), Duration::from_millis(250));
+        append_record(&path, &record).unwrap();
+        let failed = Err(TranslationError::empty_response());
+        let record = TranslationRecord::new(&config, "hello", &failed, Duration::from_millis(80));
+        append_record(&path, &record).unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+
+        let first = &lines[0];
+        assert_eq!(
+            first["source_sha256"],
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert_eq!(first["provider"], config.effective_provider().as_str());
+        assert_eq!(first["target_language"], "zh-CN");
+        assert_eq!(first["latency_ms"], 250);
+        assert_eq!(first["success"], true);
+        assert_eq!(first["input_tokens"], 12);
+        assert_eq!(first["output_tokens"], 3);
+        assert!(first.get("source_text").is_none());
+        assert!(first.get("translated_text").is_none());
+
+        let second = &lines[1];
+        assert_eq!(second["success"], false);
+        assert!(second.get("input_tokens").is_none());
+    }
+
+    #[test]
+    fn text_is_recorded_only_when_enabled() {
+        let config = TranslationConfig {
+            history_log_include_text: true,
+            ..Default::default()
+        };
+
+        let record = TranslationRecord::new(&config, "hello", &completed(), Duration::ZERO);
+
+        assert_eq!(record.source_text.as_deref(), Some("hello"));
+        assert_eq!(record.translated_text.as_deref(), Some("你好"));
+    }
+}
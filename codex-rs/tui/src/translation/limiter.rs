@@ -0,0 +1,103 @@
+//! Per-provider cap on concurrent translation requests.
+//!
+//! Requests that outlive their barrier keep running in the background, so a burst of
+//! reasoning blocks can still put several requests in flight against one provider.
+//! [`ProviderLimiter`] hands out one semaphore per provider, sized to
+//! `max_concurrent_per_provider`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use tokio::sync::OwnedSemaphorePermit;
+use tokio::sync::Semaphore;
+
+use super::provider::ProviderId;
+
+#[derive(Debug, Default)]
+pub(super) struct ProviderLimiter {
+    /// Maximum concurrent requests per provider; `None` means unlimited.
+    limit: Option<u8>,
+    semaphores: Mutex<HashMap<ProviderId, Arc<Semaphore>>>,
+}
+
+impl ProviderLimiter {
+    pub(super) fn new(limit: Option<u8>) -> Self {
+        Self {
+            // A limit of zero would block every request forever.
+            limit: limit.map(|limit| limit.max(1)),
+            semaphores: Mutex::default(),
+        }
+    }
+
+    /// Wait for a free slot for `provider`. The slot is released when the permit drops.
+    pub(super) async fn acquire(&self, provider: ProviderId) -> Option<OwnedSemaphorePermit> {
+        let limit = self.limit?;
+        let semaphore = {
+            let mut semaphores = self
+                .semaphores
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            Arc::clone(
+                semaphores
+                    .entry(provider)
+                    .or_insert_with(|| Arc::new(Semaphore::new(usize::from(limit)))),
+            )
+        };
+        // The semaphore is never closed, so acquiring only fails if that invariant breaks.
+        semaphore.acquire_owned().await.ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+
+    async fn max_concurrency(limiter: Arc<ProviderLimiter>, providers: &[ProviderId]) -> usize {
+        let current = Arc::new(AtomicUsize::new(0));
+        let max = Arc::new(AtomicUsize::new(0));
+        let tasks: Vec<_> = providers
+            .iter()
+            .map(|&provider| {
+                let limiter = Arc::clone(&limiter);
+                let current = Arc::clone(&current);
+                let max = Arc::clone(&max);
+                tokio::spawn(async move {
+                    let _permit = limiter.acquire(provider).await;
+                    let running = current.fetch_add(1, Ordering::SeqCst) + 1;
+                    max.fetch_max(running, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    current.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+        max.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    async fn limit_of_one_serializes_requests_to_a_provider() {
+        let limiter = Arc::new(ProviderLimiter::new(Some(1)));
+        let max = max_concurrency(limiter, &[ProviderId::OpenAI; 4]).await;
+        assert_eq!(max, 1);
+    }
+
+    #[tokio::test]
+    async fn providers_are_limited_independently() {
+        let limiter = Arc::new(ProviderLimiter::new(Some(1)));
+        let max = max_concurrency(limiter, &[ProviderId::OpenAI, ProviderId::DeepSeek]).await;
+        assert_eq!(max, 2);
+    }
+
+    #[tokio::test]
+    async fn no_limit_hands_out_no_permits() {
+        let limiter = ProviderLimiter::new(None);
+        assert!(limiter.acquire(ProviderId::OpenAI).await.is_none());
+        assert_eq!(ProviderLimiter::new(Some(0)).limit, Some(1));
+    }
+}
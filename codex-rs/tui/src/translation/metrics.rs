@@ -0,0 +1,100 @@
+//! Translation metrics.
+//!
+//! Counters are shared between the orchestrator and its background translation
+//! tasks, so they use atomics rather than requiring `&mut` access. Each task builds its own
+//! [`TranslationClient`](super::client::TranslationClient); they all record into the one
+//! [`ConnectionHealth`] held here.
+
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use super::health::ConnectionHealth;
+use super::health::ConnectionMetrics;
+
+/// Counters describing translation activity for the current session.
+#[derive(Debug, Default)]
+pub(crate) struct TranslationMetrics {
+    /// Retries issued because the provider returned a blank response.
+    empty_response_retries_performed: AtomicU64,
+    /// Translations that arrived after their barrier had timed out.
+    late_results: AtomicU64,
+    /// Longest latency among those late translations, in milliseconds.
+    slowest_late_result_ms: AtomicU64,
+    /// Request counts and latencies from every client built this session.
+    connection_health: Arc<ConnectionHealth>,
+}
+
+/// Point-in-time copy of [`TranslationMetrics`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct TranslationStats {
+    pub(crate) empty_response_retries_performed: u64,
+    pub(crate) late_results: u64,
+    /// What `max_wait_ms` would have had to be for every late translation to make it.
+    pub(crate) slowest_late_result_ms: u64,
+    pub(crate) connections: ConnectionMetrics,
+}
+
+impl TranslationMetrics {
+    /// Record a retry caused by an empty provider response.
+    pub(crate) fn record_empty_response_retry(&self) {
+        self.empty_response_retries_performed
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Number of retries issued because of empty provider responses.
+    #[allow(dead_code)]
+    pub(crate) fn empty_response_retries_performed(&self) -> u64 {
+        self.empty_response_retries_performed
+            .load(Ordering::Relaxed)
+    }
+
+    /// Record a translation that arrived `latency` after its request was sent, too late for
+    /// its barrier.
+    pub(crate) fn record_late_result(&self, latency: Duration) {
+        self.late_results.fetch_add(1, Ordering::Relaxed);
+        let latency_ms = u64::try_from(latency.as_millis()).unwrap_or(u64::MAX);
+        self.slowest_late_result_ms
+            .fetch_max(latency_ms, Ordering::Relaxed);
+    }
+
+    /// Shared with the clients of translation tasks, which record their requests in it.
+    pub(crate) fn connection_health(&self) -> &Arc<ConnectionHealth> {
+        &self.connection_health
+    }
+
+    pub(crate) fn snapshot(&self) -> TranslationStats {
+        TranslationStats {
+            empty_response_retries_performed: self.empty_response_retries_performed(),
+            late_results: self.late_results.load(Ordering::Relaxed),
+            slowest_late_result_ms: self.slowest_late_result_ms.load(Ordering::Relaxed),
+            connections: self.connection_health.snapshot(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn snapshot_copies_every_counter() {
+        let metrics = TranslationMetrics::default();
+        metrics.record_empty_response_retry();
+        metrics.record_empty_response_retry();
+        metrics.record_late_result(Duration::from_millis(7_200));
+        metrics.record_late_result(Duration::from_millis(6_100));
+
+        assert_eq!(
+            metrics.snapshot(),
+            TranslationStats {
+                empty_response_retries_performed: 2,
+                late_results: 2,
+                slowest_late_result_ms: 7_200,
+                connections: ConnectionMetrics::default(),
+            }
+        );
+    }
+}
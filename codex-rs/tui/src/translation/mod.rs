@@ -1,18 +1,56 @@
 //! Translation module for agent reasoning content.
 //!
 //! This module provides:
-//! - `TranslationConfig` - Configuration for translation settings
 //! - `ReasoningTranslator` - Barrier mechanism to ensure
 //!   translation results appear immediately after original content
-//! - `TranslationClient` - HTTP client for translation APIs
-//! - `ProviderId` - Supported LLM provider identifiers
+//!
+//! The configuration, HTTP client and provider definitions live in the `codex-translation`
+//! crate, so other binaries can translate without the TUI; they are re-exported here.
+
+use codex_translation::client;
+use codex_translation::config;
+use codex_translation::error;
+use codex_translation::health;
+use codex_translation::language;
+use codex_translation::post_filter;
+use codex_translation::provider;
+use codex_translation::rate_limit;
 
-mod client;
-mod config;
-mod error;
+mod backend;
+mod backoff;
+mod batch;
+mod budget;
+mod context;
+mod guard;
+mod history;
+mod history_log;
+mod limiter;
+mod log_masking;
+mod metrics;
+mod offline;
 mod orchestrator;
-mod provider;
+mod plain_text;
+mod redraw;
+mod retry;
+mod spawner;
+mod throttle;
 
+pub(crate) use client::ApiKeyStatus;
+pub(crate) use client::check_api_key;
+pub(crate) use client::translate_sample;
+pub(crate) use config::DEFAULT_MAX_WAIT_MS;
 pub(crate) use config::TranslationConfig;
+pub(crate) use config::max_wait_ms_warning;
+pub(crate) use language::TargetLanguage;
+pub(crate) use log_masking::MaskingLayer;
+pub(crate) use log_masking::MaskingWriter;
+pub(crate) use metrics::TranslationStats;
+pub use orchestrator::BarrierInfo;
+pub use orchestrator::PendingCounts;
+pub(crate) use orchestrator::QueueSnapshot;
 pub(crate) use orchestrator::ReasoningTranslator;
+pub(crate) use orchestrator::SystemMessage;
+#[cfg(test)]
+pub(crate) use orchestrator::test_support::FakeBackend;
 pub(crate) use provider::ProviderId;
+pub use rate_limit::RateLimitState;
@@ -0,0 +1,181 @@
+//! Offline detection for translation requests.
+//!
+//! Without connectivity every reasoning block would hold its barrier for the full wait and
+//! then print a timeout. [`OfflineTracker`] switches translation off after consecutive
+//! network failures and lets a single probe through periodically (or at the next turn),
+//! resuming once a provider answers again.
+
+use std::time::Duration;
+use std::time::Instant;
+
+/// Consecutive network failures before translation is treated as offline.
+const OFFLINE_FAILURE_THRESHOLD: u32 = 3;
+
+/// How long to wait between probes while offline.
+const PROBE_INTERVAL: Duration = Duration::from_secs(120);
+
+/// Connectivity change to announce to the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Transition {
+    WentOffline,
+    BackOnline,
+}
+
+impl Transition {
+    pub(super) fn notice(self) -> &'static str {
+        match self {
+            Self::WentOffline => {
+                "Translation paused: the provider is unreachable. Retrying every 2 minutes \
+                 and at the start of each turn."
+            }
+            Self::BackOnline => "Translation resumed: the provider is reachable again.",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(super) struct OfflineTracker {
+    consecutive_failures: u32,
+    /// Set while offline: when the next probe may start.
+    next_probe_at: Option<Instant>,
+    /// A probe request is in flight.
+    probing: bool,
+}
+
+impl OfflineTracker {
+    pub(super) fn is_offline(&self) -> bool {
+        self.next_probe_at.is_some()
+    }
+
+    /// Time until the next probe, while offline.
+    pub(super) fn next_probe_in(&self, now: Instant) -> Option<Duration> {
+        self.next_probe_at
+            .map(|at| at.saturating_duration_since(now))
+    }
+
+    /// Whether a new translation request may go out. While offline only one probe is
+    /// allowed once it is due; everything else is skipped without opening a barrier.
+    pub(super) fn allows_request(&self, now: Instant) -> bool {
+        match self.next_probe_at {
+            None => true,
+            Some(at) => now >= at && !self.probing,
+        }
+    }
+
+    /// Note that a request allowed by [`Self::allows_request`] was sent.
+    pub(super) fn on_request_started(&mut self) {
+        self.probing = self.is_offline();
+    }
+
+    /// Forget an in-flight probe whose result will never be recorded (e.g. after a reset).
+    pub(super) fn on_requests_abandoned(&mut self) {
+        self.probing = false;
+    }
+
+    /// Allow a probe right away, e.g. when a new turn starts.
+    pub(super) fn probe_now(&mut self, now: Instant) {
+        if let Some(at) = self.next_probe_at.as_mut() {
+            *at = now;
+        }
+    }
+
+    /// Record a finished request. `network_failure` is true for connection errors and
+    /// timeouts; any other outcome shows the provider is reachable.
+    pub(super) fn record(&mut self, network_failure: bool, now: Instant) -> Option<Transition> {
+        self.probing = false;
+        if !network_failure {
+            self.consecutive_failures = 0;
+            return self.next_probe_at.take().map(|_| Transition::BackOnline);
+        }
+
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        if self.is_offline() {
+            self.next_probe_at = Some(now + PROBE_INTERVAL);
+            None
+        } else if self.consecutive_failures >= OFFLINE_FAILURE_THRESHOLD {
+            self.next_probe_at = Some(now + PROBE_INTERVAL);
+            Some(Transition::WentOffline)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn offline_tracker(now: Instant) -> OfflineTracker {
+        let mut tracker = OfflineTracker::default();
+        for _ in 1..OFFLINE_FAILURE_THRESHOLD {
+            assert_eq!(tracker.record(true, now), None);
+        }
+        assert_eq!(tracker.record(true, now), Some(Transition::WentOffline));
+        tracker
+    }
+
+    #[test]
+    fn consecutive_network_failures_go_offline() {
+        let now = Instant::now();
+        let tracker = offline_tracker(now);
+
+        assert!(tracker.is_offline());
+        assert!(!tracker.allows_request(now));
+        assert_eq!(tracker.next_probe_in(now), Some(PROBE_INTERVAL));
+    }
+
+    #[test]
+    fn other_outcomes_reset_the_failure_count() {
+        let now = Instant::now();
+        let mut tracker = OfflineTracker::default();
+        for _ in 0..OFFLINE_FAILURE_THRESHOLD * 2 {
+            assert_eq!(tracker.record(true, now), None);
+            assert_eq!(tracker.record(false, now), None);
+        }
+        assert!(!tracker.is_offline());
+        assert!(tracker.allows_request(now));
+    }
+
+    #[test]
+    fn one_probe_at_a_time_after_the_interval() {
+        let now = Instant::now();
+        let mut tracker = offline_tracker(now);
+        let later = now + PROBE_INTERVAL;
+
+        assert!(tracker.allows_request(later));
+        tracker.on_request_started();
+        assert!(!tracker.allows_request(later));
+
+        // A failed probe waits another interval.
+        assert_eq!(tracker.record(true, later), None);
+        assert!(!tracker.allows_request(later));
+        assert_eq!(tracker.next_probe_in(later), Some(PROBE_INTERVAL));
+    }
+
+    #[test]
+    fn abandoned_probe_is_not_stuck() {
+        let now = Instant::now();
+        let mut tracker = offline_tracker(now);
+
+        tracker.probe_now(now);
+        tracker.on_request_started();
+        assert!(!tracker.allows_request(now));
+        tracker.on_requests_abandoned();
+        assert!(tracker.allows_request(now));
+    }
+
+    #[test]
+    fn successful_probe_resumes() {
+        let now = Instant::now();
+        let mut tracker = offline_tracker(now);
+
+        tracker.probe_now(now);
+        assert!(tracker.allows_request(now));
+        tracker.on_request_started();
+        assert_eq!(tracker.record(false, now), Some(Transition::BackOnline));
+
+        assert!(!tracker.is_offline());
+        assert!(tracker.allows_request(now));
+    }
+}
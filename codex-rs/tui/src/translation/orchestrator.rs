@@ -3,34 +3,159 @@
 //! This module implements a barrier mechanism to ensure translation results
 //! appear immediately after their corresponding reasoning content in the UI.
 
+use std::collections::HashSet;
 use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 use std::time::Instant;
 
 use codex_protocol::ThreadId;
-
-use super::client::TranslationClient;
+use codex_protocol::openai_models::ReasoningEffort;
+use tokio_util::sync::CancellationToken;
+
+use super::backend::BackendRequest;
+use super::backend::ProviderBackend;
+use super::backend::RequestBackend;
+use super::backoff::TimeoutBackoff;
+use super::batch::Batch;
+use super::batch::BatchBuffer;
+use super::budget::BudgetTracker;
+use super::client::TokenUsage;
+use super::config::DEFAULT_MAX_WAIT_MS;
 use super::config::TranslationConfig;
+use super::config::TranslationDisplayMode;
+use super::context::RollingContext;
+use super::history::TranslationHistory;
+use super::history::TranslationHistoryEntry;
+use super::history_log;
+use super::history_log::TranslationRecord;
+use super::limiter::ProviderLimiter;
+use super::metrics::TranslationMetrics;
+use super::metrics::TranslationStats;
+use super::offline::OfflineTracker;
+use super::plain_text::strip_markdown;
+use super::post_filter::PostFilters;
+use super::provider::ProviderId;
+use super::rate_limit::RateLimitState;
+use super::redraw::RedrawScheduler;
+use super::spawner::Spawner;
+use super::spawner::TokioSpawner;
+use super::throttle::RequestThrottle;
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 use crate::history_cell;
 use crate::history_cell::HistoryCell;
 use crate::tui::FrameRequester;
 
-/// Default maximum wait time for translation (in milliseconds).
-const DEFAULT_TRANSLATION_MAX_WAIT_MS: u64 = 5000;
+#[cfg(test)]
+pub(crate) mod test_support;
 
-/// Environment variable to override the max wait time.
+/// Environment variable setting the max wait time when the config doesn't.
 const TRANSLATION_MAX_WAIT_ENV: &str = "CODEX_TUI_TRANSLATION_MAX_WAIT_MS";
 
+/// Footnote on the source text shown in place of a timed-out translation.
+const SOURCE_FALLBACK_NOTE: &str = "(translation unavailable, showing original)";
+
 #[derive(Debug)]
 struct TranslationBarrier {
     request_id: u64,
     thread_id: ThreadId,
     /// Original title for timeout error display.
     title: Option<String>,
+    /// Text sent for translation, shown instead of an error on timeout with
+    /// `fallback_to_source_on_timeout`.
+    source_text: String,
     max_wait: Duration,
+    started_at: Instant,
     deadline: Instant,
+    /// Reasoning cell held back until its translation arrives (`inline_annotation` and
+    /// `replace`).
+    held_cell: Option<Box<dyn HistoryCell>>,
+    /// Blocks translated together (`translate_after_n_chars`); their originals are already
+    /// shown.
+    batch: Option<Batch>,
+}
+
+/// Snapshot of the pending translation barrier, for status display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarrierInfo {
+    pub started_at: Instant,
+    pub deadline: Instant,
+    pub thread_id: ThreadId,
+    /// History cells held back until the barrier is released.
+    pub deferred_count: usize,
+}
+
+/// A finished agent message to translate (`translate_system_messages`), or the turn's final
+/// answer (`translate_final_answer`). Only its translation is inserted into the history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SystemMessage {
+    message: String,
+    /// The turn's final answer, queued when the turn completes.
+    final_answer: bool,
+}
+
+impl SystemMessage {
+    pub(crate) fn new(message: String) -> Self {
+        Self {
+            message,
+            final_answer: false,
+        }
+    }
+
+    pub(crate) fn final_answer(message: String) -> Self {
+        Self {
+            message,
+            final_answer: true,
+        }
+    }
+}
+
+/// Work held back while a barrier is pending.
+#[derive(Debug)]
+enum QueuedItem {
+    /// A history cell, inserted (and translated, for reasoning) once the barrier is released.
+    Cell(Box<dyn HistoryCell>),
+    /// An agent message whose translation starts once the barrier is released.
+    SystemMessage(SystemMessage),
+}
+
+#[derive(Debug)]
+struct DeferredCell {
+    item: QueuedItem,
+    deferred_at: Instant,
+}
+
+/// A reasoning block waiting on translation, as reported by `/translation queue`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct QueuedBlock {
+    /// Bold title of the block (e.g. "Thinking").
+    pub(crate) title: Option<String>,
+    /// Time since the block was translated or queued.
+    pub(crate) age: Duration,
+    /// The block holds the barrier and its translation is in flight; otherwise it waits
+    /// behind the barrier.
+    pub(crate) translating: bool,
+}
+
+/// Reasoning blocks and other cells held back by the translation barrier.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct QueueSnapshot {
+    /// Oldest first; the block holding the barrier, if any, comes first.
+    pub(crate) blocks: Vec<QueuedBlock>,
+    /// Deferred cells that are not reasoning blocks (tool output, messages, ...).
+    pub(crate) other_cells: usize,
+}
+
+/// Translation work not finished yet, for spinners and the statusline.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PendingCounts {
+    /// Requests sent whose result has not been drained yet, including ones whose barrier
+    /// already timed out.
+    pub in_flight: usize,
+    /// History cells held back by the barrier.
+    pub deferred: usize,
 }
 
 #[derive(Debug)]
@@ -39,8 +164,25 @@ pub(super) struct TranslationResult {
     thread_id: ThreadId,
     /// Original title (e.g., "Thinking") for error display.
     title: Option<String>,
+    /// Text sent for translation, kept for the translation history.
+    source: String,
     translated: Option<String>,
     error: Option<String>,
+    /// Token usage reported by the provider, counted against the session budget.
+    usage: Option<TokenUsage>,
+    /// The provider cut the translation off at its output token limit.
+    truncated: bool,
+    /// The request failed because the provider could not be reached.
+    network_failure: bool,
+    /// The provider rejected the configured API key (HTTP 401).
+    rejected_key_for: Option<ProviderId>,
+    /// Rate limit quota reported with the response.
+    rate_limit: Option<RateLimitState>,
+    /// How long the request took, retries included.
+    latency: Duration,
+    /// The task ended without a response (aborted or panicked), so the provider's
+    /// reachability is unknown.
+    abandoned: bool,
 }
 
 impl TranslationResult {
@@ -48,6 +190,7 @@ impl TranslationResult {
         request_id: u64,
         thread_id: ThreadId,
         title: Option<String>,
+        source: String,
         translated: Option<String>,
         error: Option<String>,
     ) -> Self {
@@ -55,8 +198,96 @@ impl TranslationResult {
             request_id,
             thread_id,
             title,
+            source,
             translated,
             error,
+            usage: None,
+            truncated: false,
+            network_failure: false,
+            rejected_key_for: None,
+            rate_limit: None,
+            latency: Duration::ZERO,
+            abandoned: false,
+        }
+    }
+
+    pub(super) fn with_usage(mut self, usage: Option<TokenUsage>) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    pub(super) fn with_truncated(mut self, truncated: bool) -> Self {
+        self.truncated = truncated;
+        self
+    }
+
+    pub(super) fn with_network_failure(mut self, network_failure: bool) -> Self {
+        self.network_failure = network_failure;
+        self
+    }
+
+    pub(super) fn with_rejected_key_for(mut self, provider: Option<ProviderId>) -> Self {
+        self.rejected_key_for = provider;
+        self
+    }
+
+    pub(super) fn with_rate_limit(mut self, rate_limit: Option<RateLimitState>) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    pub(super) fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    fn abandoned(
+        request_id: u64,
+        thread_id: ThreadId,
+        title: Option<String>,
+        source: String,
+    ) -> Self {
+        Self {
+            abandoned: true,
+            ..Self::new(
+                request_id,
+                thread_id,
+                title,
+                source,
+                None,
+                Some("Translation stopped before returning a result".to_string()),
+            )
+        }
+    }
+}
+
+/// Reports a translation task's result. A task dropped before sending one (aborted, or
+/// unwound by a panic) reports a failure instead, so its barrier is released rather than
+/// left to time out. Cancelled tasks report nothing: their barrier is already gone.
+struct ResultReporter {
+    tx: tokio::sync::mpsc::UnboundedSender<TranslationResult>,
+    cancel: CancellationToken,
+    redraw: RedrawScheduler,
+    frame_requester: FrameRequester,
+    /// Sent on drop unless [`ResultReporter::send`] ran first.
+    fallback: Option<TranslationResult>,
+}
+
+impl ResultReporter {
+    fn send(mut self, msg: TranslationResult) {
+        self.fallback = None;
+        let _ = self.tx.send(msg);
+        self.redraw.request(&self.frame_requester);
+    }
+}
+
+impl Drop for ResultReporter {
+    fn drop(&mut self) {
+        if let Some(msg) = self.fallback.take()
+            && !self.cancel.is_cancelled()
+        {
+            let _ = self.tx.send(msg);
+            self.redraw.request(&self.frame_requester);
         }
     }
 }
@@ -69,12 +300,60 @@ pub(crate) struct ReasoningTranslator {
     /// Barrier for aligning translation with original content.
     translation_barrier: Option<TranslationBarrier>,
     /// History cells deferred during barrier period.
-    deferred_history_cells: VecDeque<Box<dyn HistoryCell>>,
+    deferred_history_cells: VecDeque<DeferredCell>,
     /// Sequence number for binding async results to current barrier.
     translation_seq: u64,
     /// Channel for receiving translation results.
     results_tx: tokio::sync::mpsc::UnboundedSender<TranslationResult>,
     results_rx: tokio::sync::mpsc::UnboundedReceiver<TranslationResult>,
+    /// Coalesces redraw requests from results and draw ticks.
+    redraw: RedrawScheduler,
+    /// Counters shared with background translation tasks.
+    metrics: Arc<TranslationMetrics>,
+    /// Workspace of the active session, checked against the configured allowlist.
+    workspace: Option<PathBuf>,
+    /// Reasoning effort of the active session, checked against `min_effort_to_translate`.
+    reasoning_effort: Option<ReasoningEffort>,
+    /// Translation requests spawned whose results have not been drained yet.
+    in_flight: usize,
+    /// Completed translations, persisted when `persist_between_sessions` is set.
+    history: TranslationHistory,
+    /// Requests and tokens used against `session_budget`.
+    budget: BudgetTracker,
+    /// Notice to show once the budget runs out.
+    budget_notice: Option<String>,
+    /// Caps concurrent requests per provider, shared with background translation tasks.
+    limiter: Arc<ProviderLimiter>,
+    /// Spaces requests out to `requests_per_minute`, shared with background translation tasks.
+    throttle: Option<Arc<RequestThrottle>>,
+    /// Cancels translation tasks spawned before the last [`ReasoningTranslator::reset`].
+    cancel: CancellationToken,
+    /// Pauses translation while the provider is unreachable.
+    offline: OfflineTracker,
+    /// Notice to show when translation goes offline or comes back.
+    connectivity_notice: Option<&'static str>,
+    /// Shortens the barrier wait while the provider keeps timing out.
+    timeout_backoff: TimeoutBackoff,
+    /// Short reasoning blocks waiting to be translated together (`translate_after_n_chars`).
+    pending_text_buffer: BatchBuffer,
+    /// Set when the turn ends: whatever the buffer holds is translated as soon as no
+    /// translation is pending.
+    flush_pending_text: bool,
+    /// Requests whose barrier timed out. Their results are discarded when they arrive, but
+    /// their latency is recorded in the metrics.
+    timed_out_requests: HashSet<u64>,
+    /// Recent translations sent with the next request (`rolling_context_chars`).
+    rolling_context: RollingContext,
+    /// Body of the most recently displayed translation, for copying to the clipboard.
+    last_translation: Option<String>,
+    /// Latest rate limit quota reported by the provider.
+    rate_limit: Option<RateLimitState>,
+    /// Compiled `post_filters`, applied to every translation before it is displayed.
+    post_filters: PostFilters,
+    /// Answers translation requests; the configured provider outside tests.
+    backend: Arc<dyn RequestBackend>,
+    /// Runs translation requests in the background; the Tokio runtime outside tests.
+    spawner: Arc<dyn Spawner>,
 }
 
 pub(crate) struct OnTranslationResult {
@@ -99,31 +378,275 @@ impl ReasoningTranslator {
 
     /// Create from configuration.
     pub(crate) fn from_config(config: TranslationConfig) -> Self {
+        Self::with_backend(config, Arc::new(ProviderBackend), Arc::new(TokioSpawner))
+    }
+
+    /// Create from configuration, sending requests to `backend` and running them on `spawner`.
+    pub(super) fn with_backend(
+        config: TranslationConfig,
+        backend: Arc<dyn RequestBackend>,
+        spawner: Arc<dyn Spawner>,
+    ) -> Self {
         let (results_tx, results_rx) = tokio::sync::mpsc::unbounded_channel();
         let enabled = config.enabled;
+        let history = if config.persist_between_sessions {
+            load_persisted_history()
+        } else {
+            TranslationHistory::default()
+        };
         Self {
             enabled,
-            config,
             translation_barrier: None,
             deferred_history_cells: VecDeque::new(),
             translation_seq: 0,
             results_tx,
             results_rx,
+            redraw: RedrawScheduler::default(),
+            metrics: Arc::new(TranslationMetrics::default()),
+            workspace: None,
+            reasoning_effort: None,
+            in_flight: 0,
+            history,
+            budget: BudgetTracker::new(config.session_budget),
+            budget_notice: None,
+            limiter: Arc::new(ProviderLimiter::new(config.max_concurrent_per_provider)),
+            throttle: config
+                .requests_per_minute
+                .map(|rpm| Arc::new(RequestThrottle::new(rpm))),
+            cancel: CancellationToken::new(),
+            offline: OfflineTracker::default(),
+            connectivity_notice: None,
+            timeout_backoff: TimeoutBackoff::default(),
+            pending_text_buffer: BatchBuffer::default(),
+            flush_pending_text: false,
+            timed_out_requests: HashSet::new(),
+            rolling_context: RollingContext::new(config.rolling_context_chars),
+            last_translation: None,
+            rate_limit: None,
+            post_filters: PostFilters::compile(&config.post_filters),
+            backend,
+            spawner,
+            // Last, after the fields derived from it.
+            config,
         }
     }
 
+    /// Drop all per-session translation state before a new agent session starts.
+    ///
+    /// Pending translations are cancelled and deferred cells are discarded rather than
+    /// emitted, so nothing from the previous session lands in the new session's history.
+    pub(crate) fn reset(&mut self) {
+        self.cancel.cancel();
+        self.cancel = CancellationToken::new();
+        // Results already queued belong to the old session, and their request ids would
+        // collide with the restarted sequence.
+        let (results_tx, results_rx) = tokio::sync::mpsc::unbounded_channel();
+        self.results_tx = results_tx;
+        self.results_rx = results_rx;
+        self.in_flight = 0;
+        self.offline.on_requests_abandoned();
+        self.translation_barrier = None;
+        self.timed_out_requests.clear();
+        self.pending_text_buffer.clear();
+        self.flush_pending_text = false;
+        let dropped = self.deferred_history_cells.len();
+        self.deferred_history_cells.clear();
+        self.rolling_context.clear();
+        self.translation_seq = 0;
+        tracing::debug!("ReasoningTranslator: reset, dropped {dropped} deferred cells");
+    }
+
     /// Update configuration.
     pub(crate) fn update_config(&mut self, config: TranslationConfig) {
+        if config.persist_between_sessions && !self.config.persist_between_sessions {
+            // Restore earlier sessions ahead of anything recorded so far.
+            let mut history = load_persisted_history();
+            for entry in self.history.entries() {
+                history.push(entry.clone());
+            }
+            self.history = history;
+        }
+        // Changing the budget or re-enabling translation resumes a paused session.
+        if config.session_budget != self.budget.limits() || (config.enabled && !self.enabled) {
+            self.budget.reset(config.session_budget);
+        }
+        // Requests already in flight keep their permits from the previous limiter.
+        if config.max_concurrent_per_provider != self.config.max_concurrent_per_provider {
+            self.limiter = Arc::new(ProviderLimiter::new(config.max_concurrent_per_provider));
+        }
+        // Requests already waiting keep their slots in the previous throttle.
+        if config.requests_per_minute != self.config.requests_per_minute {
+            self.throttle = config
+                .requests_per_minute
+                .map(|rpm| Arc::new(RequestThrottle::new(rpm)));
+        }
+        if config.rolling_context_chars != self.rolling_context.max_chars() {
+            self.rolling_context = RollingContext::new(config.rolling_context_chars);
+        }
+        // A different provider may well be reachable; try it right away.
+        if config.effective_provider() != self.config.effective_provider() {
+            self.offline.probe_now(Instant::now());
+            // The quota and the timeouts belonged to the previous provider.
+            self.rate_limit = None;
+            self.timeout_backoff = TimeoutBackoff::default();
+        }
+        if config.post_filters != self.config.post_filters {
+            self.post_filters = PostFilters::compile(&config.post_filters);
+        }
         self.enabled = config.enabled;
         self.config = config;
     }
 
+    /// Save the translation history if `persist_between_sessions` is enabled.
+    ///
+    /// Called once when the TUI exits; failures are logged and otherwise ignored.
+    pub(crate) async fn shutdown(&self) {
+        if !self.config.persist_between_sessions {
+            return;
+        }
+        let history = self.history.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let path = TranslationHistory::default_path()?;
+            history.save_to_file(&path)
+        })
+        .await;
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => tracing::warn!("Failed to save translation history: {e}"),
+            Err(e) => tracing::warn!("Translation history save task failed: {e}"),
+        }
+    }
+
     /// Get current configuration.
-    #[allow(dead_code)]
     pub(crate) fn config(&self) -> &TranslationConfig {
         &self.config
     }
 
+    /// Set the workspace used to evaluate the translation allowlist.
+    pub(crate) fn set_workspace(&mut self, workspace: PathBuf) {
+        self.workspace = Some(workspace);
+    }
+
+    /// Set the reasoning effort the agent is running at.
+    pub(crate) fn set_reasoning_effort(&mut self, effort: Option<ReasoningEffort>) {
+        self.reasoning_effort = effort;
+    }
+
+    /// Get translation metrics for this session.
+    #[allow(dead_code)]
+    pub(crate) fn metrics(&self) -> &TranslationMetrics {
+        &self.metrics
+    }
+
+    /// Snapshot of this session's translation metrics, including connection health.
+    pub(crate) fn stats(&self) -> TranslationStats {
+        self.metrics.snapshot()
+    }
+
+    /// Number of translation requests still waiting for a result.
+    pub(crate) fn in_flight_count(&self) -> usize {
+        if self.enabled { self.in_flight } else { 0 }
+    }
+
+    /// Requests in flight and cells held back by the barrier.
+    pub(crate) fn pending_count(&self) -> PendingCounts {
+        PendingCounts {
+            in_flight: self.in_flight_count(),
+            deferred: self.deferred_history_cells.len(),
+        }
+    }
+
+    /// Metadata of the pending barrier, if any.
+    pub(crate) fn get_pending_barrier_info(&self) -> Option<BarrierInfo> {
+        self.translation_barrier
+            .as_ref()
+            .map(|barrier| BarrierInfo {
+                started_at: barrier.started_at,
+                deadline: barrier.deadline,
+                thread_id: barrier.thread_id,
+                deferred_count: self.deferred_history_cells.len(),
+            })
+    }
+
+    /// Latest rate limit quota reported by the provider.
+    pub(crate) fn rate_limit(&self) -> Option<RateLimitState> {
+        self.rate_limit
+    }
+
+    /// Body of the most recently displayed translation.
+    pub(crate) fn last_translation(&self) -> Option<&str> {
+        self.last_translation.as_deref()
+    }
+
+    /// While translation is paused because the provider is unreachable, the time until the
+    /// next connectivity probe.
+    pub(crate) fn offline_retry_in(&self) -> Option<Duration> {
+        if !self.enabled {
+            return None;
+        }
+        self.offline.next_probe_in(Instant::now())
+    }
+
+    /// Called when an agent turn starts; while offline, the next reasoning block probes the
+    /// provider instead of waiting for the retry interval.
+    pub(crate) fn on_turn_started(&mut self) {
+        self.offline.probe_now(Instant::now());
+    }
+
+    /// Reasoning blocks currently waiting on translation, for `/translation queue`.
+    pub(crate) fn queue_snapshot(&self) -> QueueSnapshot {
+        let now = Instant::now();
+        let mut snapshot = QueueSnapshot::default();
+        if let Some(barrier) = &self.translation_barrier {
+            snapshot.blocks.push(QueuedBlock {
+                title: barrier.title.clone(),
+                age: now.saturating_duration_since(barrier.started_at),
+                translating: true,
+            });
+        }
+        for deferred in &self.deferred_history_cells {
+            let QueuedItem::Cell(cell) = &deferred.item else {
+                continue;
+            };
+            match reasoning_markdown(cell.as_ref()) {
+                Some(markdown) => snapshot.blocks.push(QueuedBlock {
+                    title: extract_first_bold(&markdown),
+                    age: now.saturating_duration_since(deferred.deferred_at),
+                    translating: false,
+                }),
+                None => snapshot.other_cells += 1,
+            }
+        }
+        snapshot
+    }
+
+    /// Give up on pending translations and insert everything held back right away, in order
+    /// and untranslated. Returns the number of cells released.
+    pub(crate) fn clear_queue(&mut self, app_event_tx: &AppEventSender) -> usize {
+        // Requests still in flight would only produce results with no barrier to match.
+        self.cancel.cancel();
+        self.cancel = CancellationToken::new();
+        self.in_flight = 0;
+        self.offline.on_requests_abandoned();
+
+        let mut released = 0;
+        if let Some(cell) = self
+            .translation_barrier
+            .take()
+            .and_then(|barrier| barrier.held_cell)
+        {
+            app_event_tx.send(AppEvent::InsertHistoryCell(cell));
+            released += 1;
+        }
+        for deferred in self.deferred_history_cells.drain(..) {
+            if let QueuedItem::Cell(cell) = deferred.item {
+                app_event_tx.send(AppEvent::InsertHistoryCell(cell));
+                released += 1;
+            }
+        }
+        released
+    }
+
     /// Set whether translation is enabled.
     #[allow(dead_code)]
     pub(crate) fn set_enabled(&mut self, enabled: bool) {
@@ -145,10 +668,10 @@ impl ReasoningTranslator {
         full_reasoning: String,
         frame_requester: FrameRequester,
     ) -> bool {
-        if !self.enabled {
+        if !self.config.translate_reasoning {
             return false;
         }
-        let Some(thread_id) = thread_id else {
+        let Some(thread_id) = self.translatable_thread(thread_id) else {
             return false;
         };
 
@@ -162,48 +685,271 @@ impl ReasoningTranslator {
         if body.trim().is_empty() {
             return false;
         }
+        // Offline: show the reasoning right away instead of holding it for a request that
+        // would only time out.
+        if !self.offline.allows_request(Instant::now()) {
+            return false;
+        }
+
+        let threshold = self.config.translate_after_n_chars;
+        if threshold > 0 && self.translation_barrier.is_some() {
+            // Another translation is pending; the block goes out with the next batch.
+            self.pending_text_buffer.hold(title, &body);
+            return false;
+        }
+        if threshold > 0 {
+            // The block is shown now and translated once enough text has followed it.
+            let Some(batch) = self
+                .pending_text_buffer
+                .push(title.clone(), &body, threshold)
+            else {
+                return false;
+            };
+            if batch.block_count() > 1 {
+                return self.start_batch_translation(thread_id, batch, frame_requester);
+            }
+        }
+
+        // The `**title**` line is only sent when it should be translated as well.
+        let source = if self.config.header_translation {
+            full_reasoning
+        } else {
+            body.clone()
+        };
+        self.start_translation(thread_id, title, body, source, false, frame_requester)
+    }
+
+    /// Translate the blocks of `batch` in one request, shown as one translation block after
+    /// the last of them. Returns true if translation was started.
+    fn start_batch_translation(
+        &mut self,
+        thread_id: ThreadId,
+        batch: Batch,
+        frame_requester: FrameRequester,
+    ) -> bool {
+        let text = batch.text().to_string();
+        if !self.start_translation(thread_id, None, text.clone(), text, false, frame_requester) {
+            return false;
+        }
+        if let Some(barrier) = self.translation_barrier.as_mut() {
+            barrier.batch = Some(batch);
+        }
+        true
+    }
+
+    /// Translate the reasoning blocks still waiting in the batch buffer, called when the turn
+    /// ends. While another translation is pending they are sent once it finishes, ahead of
+    /// the cells deferred behind it. Returns true if translation was started.
+    pub(crate) fn flush_pending_text(
+        &mut self,
+        thread_id: Option<ThreadId>,
+        frame_requester: FrameRequester,
+    ) -> bool {
+        self.flush_pending_text = true;
+        self.try_flush_pending_text(thread_id, frame_requester)
+    }
+
+    fn try_flush_pending_text(
+        &mut self,
+        thread_id: Option<ThreadId>,
+        frame_requester: FrameRequester,
+    ) -> bool {
+        if !self.flush_pending_text || self.translation_barrier.is_some() {
+            return false;
+        }
+        self.flush_pending_text = false;
+        let Some(batch) = self.pending_text_buffer.take() else {
+            return false;
+        };
+        let Some(thread_id) = self.translatable_thread(thread_id) else {
+            return false;
+        };
+        if !self.offline.allows_request(Instant::now()) {
+            return false;
+        }
+        self.start_batch_translation(thread_id, batch, frame_requester)
+    }
+
+    /// Start translating a finished agent message (`translate_system_messages`) or the
+    /// turn's final answer (`translate_final_answer`). Returns true if translation was started.
+    fn maybe_translate_system_message(
+        &mut self,
+        thread_id: Option<ThreadId>,
+        system_message: &SystemMessage,
+        frame_requester: FrameRequester,
+    ) -> bool {
+        let wanted = if system_message.final_answer {
+            // With `translate_system_messages` the answer was translated as it finished.
+            self.config.translate_final_answer && !self.config.translate_system_messages
+        } else {
+            self.config.translate_system_messages
+        };
+        if !wanted {
+            return false;
+        }
+        let Some(thread_id) = self.translatable_thread(thread_id) else {
+            return false;
+        };
+        let message = system_message.message.trim();
+        if message.chars().count() < self.config.min_system_message_length {
+            return false;
+        }
+        if !self.offline.allows_request(Instant::now()) {
+            return false;
+        }
+        self.start_translation(
+            thread_id,
+            None,
+            message.to_string(),
+            message.to_string(),
+            true,
+            frame_requester,
+        )
+    }
+
+    /// `thread_id` if translation is on and allowed for that thread at the current reasoning
+    /// effort and budget.
+    fn translatable_thread(&self, thread_id: Option<ThreadId>) -> Option<ThreadId> {
+        if !self.enabled {
+            return None;
+        }
+        let thread_id = thread_id?;
+        if !self
+            .config
+            .allows_context(&thread_id.to_string(), self.workspace.as_deref())
+        {
+            return None;
+        }
+        if !self.config.allows_effort(self.reasoning_effort) {
+            return None;
+        }
+        if self.budget.is_exhausted() {
+            return None;
+        }
+        Some(thread_id)
+    }
 
+    /// Open a barrier for `body` and send `source` to the provider; `short_prompt` selects
+    /// the system message prompt. Returns true if translation was started.
+    fn start_translation(
+        &mut self,
+        thread_id: ThreadId,
+        title: Option<String>,
+        body: String,
+        source: String,
+        short_prompt: bool,
+        frame_requester: FrameRequester,
+    ) -> bool {
         // Begin barrier to ensure translation follows original content
         let Some(request_id) =
-            self.begin_barrier(thread_id, title.clone(), frame_requester.clone())
+            self.begin_barrier(thread_id, title.clone(), body, frame_requester.clone())
         else {
             return false;
         };
 
-        let result_tx = self.results_tx.clone();
+        if let Some(remaining) = self
+            .rate_limit
+            .filter(RateLimitState::is_low)
+            .and_then(|rate_limit| rate_limit.remaining_requests)
+        {
+            tracing::warn!(
+                "Translation provider rate limit nearly exhausted: {remaining} requests left"
+            );
+        }
+
+        let reporter = ResultReporter {
+            tx: self.results_tx.clone(),
+            cancel: self.cancel.clone(),
+            redraw: self.redraw.clone(),
+            frame_requester,
+            fallback: Some(TranslationResult::abandoned(
+                request_id,
+                thread_id,
+                title.clone(),
+                source.clone(),
+            )),
+        };
+        let throttle = self.throttle.clone();
+        let cancel = self.cancel.clone();
         let config = self.config.clone();
-        // Translate the full reasoning (header + body) so translator can produce bilingual output
-        let full_reasoning_owned = full_reasoning;
+        let backend = Arc::clone(&self.backend);
+        let request = BackendRequest {
+            config: self.config.clone(),
+            metrics: Arc::clone(&self.metrics),
+            limiter: Arc::clone(&self.limiter),
+            context: self.rolling_context.render(),
+            short_prompt,
+            // The original text is still what gets recorded and shown; only the request is
+            // plain.
+            text: if self.config.strip_markdown {
+                strip_markdown(&source)
+            } else {
+                source.clone()
+            },
+        };
 
         // Spawn async translation task
-        tokio::spawn(async move {
-            let result = Self::do_translate(&config, &full_reasoning_owned).await;
-
-            let msg = match result {
-                Ok(translated) => {
-                    TranslationResult::new(request_id, thread_id, title, Some(translated), None)
-                }
-                Err(e) => {
-                    TranslationResult::new(request_id, thread_id, title, None, Some(e.to_string()))
+        self.spawner.spawn(Box::pin(async move {
+            if let Some(throttle) = &throttle {
+                tokio::select! {
+                    _ = cancel.cancelled() => return,
+                    _ = throttle.wait() => {}
                 }
+            }
+            let started = Instant::now();
+            // A reset abandons the request; its result would belong to the previous session.
+            let (result, rate_limit) = tokio::select! {
+                _ = cancel.cancelled() => return,
+                response = backend.translate(request) => response,
             };
+            if config.history_log {
+                history_log::record_translation(&TranslationRecord::new(
+                    &config,
+                    &source,
+                    &result,
+                    started.elapsed(),
+                ));
+            }
 
-            let _ = result_tx.send(msg);
-            frame_requester.schedule_frame();
-        });
+            let msg = match result {
+                Ok(output) => TranslationResult::new(
+                    request_id,
+                    thread_id,
+                    title,
+                    source,
+                    Some(output.text),
+                    None,
+                )
+                .with_usage(output.usage)
+                .with_truncated(output.truncated),
+                Err(e) => TranslationResult::new(
+                    request_id,
+                    thread_id,
+                    title,
+                    source,
+                    None,
+                    Some(match e.explanation() {
+                        Some(explanation) => format!("{e}\n\n{explanation}"),
+                        None => e.to_string(),
+                    }),
+                )
+                .with_network_failure(e.is_network())
+                .with_rejected_key_for(e.is_unauthorized().then(|| config.effective_provider())),
+            }
+            .with_rate_limit(rate_limit)
+            .with_latency(started.elapsed());
+
+            reporter.send(msg);
+        }));
+        self.in_flight += 1;
+        self.offline.on_request_started();
+        if let Some(notice) = self.budget.record_request() {
+            self.budget_notice = Some(notice);
+        }
 
         true
     }
 
-    /// Perform the actual translation.
-    async fn do_translate(
-        config: &TranslationConfig,
-        text: &str,
-    ) -> Result<String, super::error::TranslationError> {
-        let client = TranslationClient::from_config(config)?;
-        client.translate(text, &config.target_language).await
-    }
-
     /// Drain pending translation results.
     pub(crate) fn drain_results(
         &mut self,
@@ -221,19 +967,51 @@ impl ReasoningTranslator {
             needs_redraw: false,
         };
 
-        loop {
-            match self.results_rx.try_recv() {
-                Ok(msg) => {
-                    let result = self.on_translation_completed(
-                        msg,
-                        active_thread_id,
-                        app_event_tx,
-                        frame_requester.clone(),
-                    );
-                    out.needs_redraw |= result.needs_redraw;
-                }
-                Err(tokio::sync::mpsc::error::TryRecvError::Empty) => break,
-                Err(tokio::sync::mpsc::error::TryRecvError::Disconnected) => break,
+        // The translator keeps a sender of its own, so the channel never disconnects;
+        // a task that ends without a result reports a failure instead.
+        while let Ok(msg) = self.results_rx.try_recv() {
+            self.in_flight = self.in_flight.saturating_sub(1);
+            if self.timed_out_requests.remove(&msg.request_id) {
+                self.metrics.record_late_result(msg.latency);
+            }
+            if msg.abandoned {
+                self.offline.on_requests_abandoned();
+            } else if let Some(transition) =
+                self.offline.record(msg.network_failure, Instant::now())
+            {
+                self.connectivity_notice = Some(transition.notice());
+            }
+            if let Some(usage) = msg.usage
+                && let Some(notice) = self
+                    .budget
+                    .record_tokens(usage.input_tokens + usage.output_tokens)
+            {
+                self.budget_notice = Some(notice);
+            }
+            if let Some(translated) = &msg.translated {
+                self.rolling_context.push(translated);
+                self.history.push(TranslationHistoryEntry {
+                    timestamp: chrono::Utc::now(),
+                    thread_id: msg.thread_id.to_string(),
+                    source: msg.source.clone(),
+                    translated: translated.clone(),
+                });
+            }
+            if let Some(rate_limit) = msg.rate_limit {
+                self.rate_limit = Some(rate_limit);
+                app_event_tx.send(AppEvent::UpdateRateLimitState(rate_limit));
+            }
+            let rejected_key_for = msg.rejected_key_for;
+            let result = self.on_translation_completed(
+                msg,
+                active_thread_id,
+                app_event_tx,
+                frame_requester.clone(),
+            );
+            out.needs_redraw |= result.needs_redraw;
+            // After the error block, so the key prompt follows it.
+            if let Some(provider) = rejected_key_for {
+                app_event_tx.send(AppEvent::TranslationAuthFailed(provider));
             }
         }
 
@@ -251,8 +1029,16 @@ impl ReasoningTranslator {
             request_id,
             thread_id,
             title,
+            source: _,
             translated,
             error,
+            usage: _,
+            truncated,
+            network_failure: _,
+            rejected_key_for: _,
+            rate_limit: _,
+            latency: _,
+            abandoned: _,
         } = msg;
 
         // Validate barrier is still active and matches
@@ -273,28 +1059,73 @@ impl ReasoningTranslator {
         }
 
         // Release barrier before inserting content
-        self.translation_barrier = None;
+        let (held_cell, batch) = match self.translation_barrier.take() {
+            Some(barrier) => (barrier.held_cell, barrier.batch),
+            None => (None, None),
+        };
 
         if let Some(translated) = translated {
-            // Extract body for display; translated content already contains the title
-            // (e.g., "**思考中**\n内容...")
-            let translated_body = extract_reasoning_body(&translated)
-                .unwrap_or_else(|| translated.clone())
-                .trim()
-                .to_string();
-
-            self.emit_history_cell(
-                app_event_tx,
-                history_cell::new_agent_reasoning_translation_block(
-                    None, // title not needed for success; content already has it
-                    if translated_body.is_empty() {
-                        translated
-                    } else {
-                        translated_body
-                    },
-                ),
-            );
+            self.timeout_backoff.on_success();
+            let translated = self.post_filters.apply(translated.trim().to_string());
+            // Batches carry their block titles inline and were sent without headers.
+            let (translated_title, translated) = match &batch {
+                Some(batch) => (None, batch.render(&translated)),
+                None => split_translated_title(translated, self.config.header_translation),
+            };
+            self.last_translation = Some(translated.clone());
+
+            let display_mode = self.config.display_mode;
+            match held_cell {
+                Some(mut cell) if display_mode == TranslationDisplayMode::InlineAnnotation => {
+                    if let Some(reasoning) = cell
+                        .as_any_mut()
+                        .downcast_mut::<history_cell::ReasoningSummaryCell>()
+                    {
+                        let annotation = match translated_title {
+                            Some(title) => format!("**{title}**\n\n{translated}"),
+                            None => translated,
+                        };
+                        reasoning.set_translation(annotation);
+                    }
+                    self.emit_history_cell(app_event_tx, cell);
+                }
+                held_cell => {
+                    if let Some(mut cell) = held_cell {
+                        // `replace`: the original goes to the transcript only, so the
+                        // translation takes its place in the main view.
+                        if display_mode == TranslationDisplayMode::Replace
+                            && let Some(reasoning) =
+                                cell.as_any_mut()
+                                    .downcast_mut::<history_cell::ReasoningSummaryCell>()
+                        {
+                            reasoning.set_transcript_only();
+                        }
+                        self.emit_history_cell(app_event_tx, cell);
+                    }
+                    self.emit_history_cell(
+                        app_event_tx,
+                        history_cell::new_agent_reasoning_translation_block(
+                            translated_title,
+                            translated,
+                            self.config.max_display_lines,
+                        ),
+                    );
+                }
+            }
+            if truncated {
+                self.emit_history_cell(
+                    app_event_tx,
+                    Box::new(history_cell::new_warning_event(
+                        "Translation was cut off at the output token limit; raise \
+                         max_output_tokens in the translation config to translate it in full."
+                            .to_string(),
+                    )),
+                );
+            }
         } else {
+            if let Some(cell) = held_cell {
+                self.emit_history_cell(app_event_tx, cell);
+            }
             let reason = error.unwrap_or_else(|| "unknown error".to_string());
             self.emit_history_cell(
                 app_event_tx,
@@ -317,27 +1148,36 @@ impl ReasoningTranslator {
         if !self.enabled {
             return false;
         }
-        let Some(barrier) = self.translation_barrier.as_ref() else {
-            return false;
-        };
-        if Instant::now() < barrier.deadline {
+        if self
+            .translation_barrier
+            .as_ref()
+            .is_none_or(|barrier| Instant::now() < barrier.deadline)
+        {
             return false;
         }
+        let Some(barrier) = self.translation_barrier.take() else {
+            return false;
+        };
 
-        let title = barrier.title.clone();
-        let max_wait_ms = barrier.max_wait.as_millis();
+        let message = self.timeout_message(&barrier);
+        self.timed_out_requests.insert(barrier.request_id);
+        self.timeout_backoff.on_timeout();
 
-        // Release barrier
-        self.translation_barrier = None;
+        // Release barrier, showing any held reasoning cell without its translation
+        if let Some(cell) = barrier.held_cell {
+            self.emit_history_cell(app_event_tx, cell);
+        }
 
-        // Insert error block with title
-        self.emit_history_cell(
-            app_event_tx,
-            history_cell::new_agent_reasoning_translation_error_block(
-                title,
-                format!("Translation timeout ({max_wait_ms}ms)"),
-            ),
-        );
+        let cell = if self.config.fallback_to_source_on_timeout {
+            history_cell::new_agent_reasoning_translation_block(
+                None,
+                format!("{}\n\n*{SOURCE_FALLBACK_NOTE}*", barrier.source_text),
+                self.config.max_display_lines,
+            )
+        } else {
+            history_cell::new_agent_reasoning_translation_error_block(barrier.title, message)
+        };
+        self.emit_history_cell(app_event_tx, cell);
 
         self.flush_deferred_cells(active_thread_id, app_event_tx, frame_requester);
         true
@@ -350,12 +1190,34 @@ impl ReasoningTranslator {
         cell: Box<dyn HistoryCell>,
     ) {
         if self.translation_barrier.is_some() {
-            self.deferred_history_cells.push_back(cell);
+            self.defer(QueuedItem::Cell(cell));
         } else {
             app_event_tx.send(AppEvent::InsertHistoryCell(cell));
         }
     }
 
+    fn defer(&mut self, item: QueuedItem) {
+        self.deferred_history_cells.push_back(DeferredCell {
+            item,
+            deferred_at: Instant::now(),
+        });
+    }
+
+    /// Translate a finished agent message once the cells queued before it are inserted, so
+    /// the translation follows the message.
+    pub(crate) fn queue_system_message(
+        &mut self,
+        active_thread_id: Option<ThreadId>,
+        frame_requester: FrameRequester,
+        system_message: SystemMessage,
+    ) {
+        if self.translation_barrier.is_some() {
+            self.defer(QueuedItem::SystemMessage(system_message));
+            return;
+        }
+        self.maybe_translate_system_message(active_thread_id, &system_message, frame_requester);
+    }
+
     /// Emit a history cell and potentially start translation.
     pub(crate) fn emit_history_cell_with_translation_hook(
         &mut self,
@@ -365,24 +1227,52 @@ impl ReasoningTranslator {
         cell: Box<dyn HistoryCell>,
     ) {
         if self.translation_barrier.is_some() {
-            self.deferred_history_cells.push_back(cell);
+            self.defer(QueuedItem::Cell(cell));
             return;
         }
 
-        // Check if this is a reasoning cell that needs translation
-        let maybe_reasoning = cell
-            .as_any()
-            .downcast_ref::<history_cell::ReasoningSummaryCell>()
-            .and_then(history_cell::ReasoningSummaryCell::full_markdown_for_translation);
+        self.send_with_translation(app_event_tx, active_thread_id, frame_requester, cell);
+    }
 
-        app_event_tx.send(AppEvent::InsertHistoryCell(cell));
+    /// Insert `cell` and start translating it if it is a reasoning cell.
+    ///
+    /// In `inline_annotation` and `replace` modes a reasoning cell whose translation started
+    /// is held in the barrier instead, so it can be inserted once the translation arrives:
+    /// with the translation attached, or hidden behind it. Until then it is withheld like any
+    /// deferred cell, and a failed or timed-out translation releases it unchanged.
+    fn send_with_translation(
+        &mut self,
+        app_event_tx: &AppEventSender,
+        active_thread_id: Option<ThreadId>,
+        frame_requester: FrameRequester,
+        cell: Box<dyn HistoryCell>,
+    ) {
+        // Check if this is a reasoning cell that needs translation
+        let Some(full_reasoning) = reasoning_markdown(cell.as_ref()) else {
+            app_event_tx.send(AppEvent::InsertHistoryCell(cell));
+            return;
+        };
 
-        if let Some(full_reasoning) = maybe_reasoning {
-            self.maybe_translate_reasoning(active_thread_id, full_reasoning, frame_requester);
+        if self.config.display_mode != TranslationDisplayMode::Block {
+            let started =
+                self.maybe_translate_reasoning(active_thread_id, full_reasoning, frame_requester);
+            match self.translation_barrier.as_mut() {
+                // A batch's earlier blocks are already shown, so the last one is too.
+                Some(barrier) if started && barrier.batch.is_none() => {
+                    barrier.held_cell = Some(cell);
+                }
+                _ => app_event_tx.send(AppEvent::InsertHistoryCell(cell)),
+            }
+            return;
         }
+
+        app_event_tx.send(AppEvent::InsertHistoryCell(cell));
+        self.maybe_translate_reasoning(active_thread_id, full_reasoning, frame_requester);
     }
 
     /// Called on each draw tick to process results and timeouts.
+    ///
+    /// When the tick produced visible changes, a coalesced redraw is requested.
     pub(crate) fn on_draw_tick(
         &mut self,
         active_thread_id: Option<ThreadId>,
@@ -398,10 +1288,30 @@ impl ReasoningTranslator {
         let mut result =
             self.drain_results(active_thread_id, app_event_tx, frame_requester.clone());
 
-        if self.maybe_flush_timeout(active_thread_id, app_event_tx, frame_requester) {
+        if self.maybe_flush_timeout(active_thread_id, app_event_tx, frame_requester.clone()) {
             result.needs_redraw = true;
         }
 
+        if let Some(notice) = self.budget_notice.take() {
+            self.emit_history_cell(
+                app_event_tx,
+                Box::new(history_cell::new_warning_event(notice)),
+            );
+            result.needs_redraw = true;
+        }
+
+        if let Some(notice) = self.connectivity_notice.take() {
+            self.emit_history_cell(
+                app_event_tx,
+                Box::new(history_cell::new_info_event(notice.to_string(), None)),
+            );
+            result.needs_redraw = true;
+        }
+
+        if result.needs_redraw {
+            self.redraw.request(&frame_requester);
+        }
+
         result
     }
 
@@ -411,31 +1321,32 @@ impl ReasoningTranslator {
         app_event_tx: &AppEventSender,
         frame_requester: FrameRequester,
     ) {
-        while let Some(cell) = self.deferred_history_cells.pop_front() {
-            // Check if this deferred cell is also a reasoning cell
-            let maybe_reasoning = cell
-                .as_any()
-                .downcast_ref::<history_cell::ReasoningSummaryCell>()
-                .and_then(history_cell::ReasoningSummaryCell::full_markdown_for_translation);
-
-            app_event_tx.send(AppEvent::InsertHistoryCell(cell));
-
-            // If we encounter another reasoning cell during flush, start its translation
-            // and stop flushing to maintain order
-            if let Some(full_reasoning) = maybe_reasoning
-                && self.translation_barrier.is_none()
-            {
-                // Use current active_thread_id for translation
-                self.maybe_translate_reasoning(
+        // Blocks held for the end of the turn are translated before the cells that followed.
+        if self.try_flush_pending_text(active_thread_id, frame_requester.clone()) {
+            return;
+        }
+        while let Some(DeferredCell { item, .. }) = self.deferred_history_cells.pop_front() {
+            // If this deferred cell is another reasoning cell, start its translation
+            // (using the current active_thread_id)
+            match item {
+                QueuedItem::Cell(cell) => self.send_with_translation(
+                    app_event_tx,
                     active_thread_id,
-                    full_reasoning,
                     frame_requester.clone(),
-                );
-                if self.translation_barrier.is_some() {
-                    // New barrier started, stop flushing to maintain order
-                    break;
+                    cell,
+                ),
+                QueuedItem::SystemMessage(system_message) => {
+                    self.maybe_translate_system_message(
+                        active_thread_id,
+                        &system_message,
+                        frame_requester.clone(),
+                    );
                 }
             }
+            if self.translation_barrier.is_some() {
+                // New barrier started, stop flushing to maintain order
+                break;
+            }
         }
     }
 
@@ -443,6 +1354,7 @@ impl ReasoningTranslator {
         &mut self,
         thread_id: ThreadId,
         title: Option<String>,
+        source_text: String,
         frame_requester: FrameRequester,
     ) -> Option<u64> {
         if self.translation_barrier.is_some() {
@@ -453,17 +1365,20 @@ impl ReasoningTranslator {
         let request_id = self.translation_seq;
         self.translation_seq = self.translation_seq.saturating_add(1);
 
-        let max_wait = self.max_wait_from_env();
-        let deadline = Instant::now()
-            .checked_add(max_wait)
-            .unwrap_or_else(Instant::now);
+        let max_wait = self.max_wait();
+        let started_at = Instant::now();
+        let deadline = started_at.checked_add(max_wait).unwrap_or(started_at);
 
         self.translation_barrier = Some(TranslationBarrier {
             request_id,
             thread_id,
             title,
+            source_text,
             max_wait,
+            started_at,
             deadline,
+            held_cell: None,
+            batch: None,
         });
 
         // Schedule a frame for timeout handling
@@ -471,15 +1386,69 @@ impl ReasoningTranslator {
         Some(request_id)
     }
 
-    fn max_wait_from_env(&self) -> Duration {
-        match std::env::var(TRANSLATION_MAX_WAIT_ENV) {
-            Ok(raw) => match raw.trim().parse::<u64>() {
-                Ok(ms) => Duration::from_millis(ms),
-                Err(_) => Duration::from_millis(DEFAULT_TRANSLATION_MAX_WAIT_MS),
-            },
-            Err(_) => Duration::from_millis(DEFAULT_TRANSLATION_MAX_WAIT_MS),
-        }
+    /// How long a barrier waits: the configured wait, shortened after consecutive timeouts.
+    fn max_wait(&self) -> Duration {
+        self.timeout_backoff.max_wait(self.configured_max_wait())
+    }
+
+    /// `max_wait_ms` from the config, then the environment, then the default.
+    pub(crate) fn configured_max_wait(&self) -> Duration {
+        let ms = self.config.max_wait_ms.unwrap_or_else(|| {
+            std::env::var(TRANSLATION_MAX_WAIT_ENV)
+                .ok()
+                .and_then(|raw| raw.trim().parse().ok())
+                .unwrap_or(DEFAULT_MAX_WAIT_MS)
+        });
+        Duration::from_millis(ms)
     }
+
+    /// Error shown when `barrier` times out: which provider and model were slow, how long the
+    /// request had been running, and how to wait longer.
+    fn timeout_message(&self, barrier: &TranslationBarrier) -> String {
+        let provider = self.config.effective_provider().definition();
+        let model = self.config.effective_model(provider);
+        let configured = self.configured_max_wait();
+        let limit = if barrier.max_wait < configured {
+            format!(
+                "{}ms, shortened from {}ms after consecutive timeouts",
+                barrier.max_wait.as_millis(),
+                configured.as_millis()
+            )
+        } else {
+            format!("{}ms", barrier.max_wait.as_millis())
+        };
+        format!(
+            "Translation timeout ({limit}): {} ({model}) had not answered after {}ms.\n\n\
+             To wait longer, raise max_wait_ms in the translation config, or set \
+             {TRANSLATION_MAX_WAIT_ENV} when max_wait_ms is unset.",
+            provider.name,
+            barrier.started_at.elapsed().as_millis()
+        )
+    }
+}
+
+/// Markdown to translate if `cell` is a reasoning cell with a title.
+fn reasoning_markdown(cell: &dyn HistoryCell) -> Option<String> {
+    cell.as_any()
+        .downcast_ref::<history_cell::ReasoningSummaryCell>()
+        .and_then(history_cell::ReasoningSummaryCell::full_markdown_for_translation)
+}
+
+/// Load the persisted translation history, starting empty if it is missing or unreadable.
+fn load_persisted_history() -> TranslationHistory {
+    let result =
+        TranslationHistory::default_path().and_then(
+            |path| match TranslationHistory::load_from_file(&path) {
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    Ok(TranslationHistory::default())
+                }
+                result => result,
+            },
+        );
+    result.unwrap_or_else(|e| {
+        tracing::warn!("Failed to load translation history: {e}");
+        TranslationHistory::default()
+    })
 }
 
 /// Extract the first bold text (e.g., "Thinking" from "**Thinking**").
@@ -510,6 +1479,22 @@ fn extract_first_bold(s: &str) -> Option<String> {
     None
 }
 
+/// Split a translation into its translated `**title**` (with `header_translation`) and the
+/// body to display. Without `header_translation` only the body was sent, so any bold text in
+/// the response belongs to the body.
+fn split_translated_title(
+    translated: String,
+    header_translation: bool,
+) -> (Option<String>, String) {
+    if !header_translation {
+        return (None, translated);
+    }
+    match extract_reasoning_body(&translated) {
+        Some(body) => (extract_first_bold(&translated), body.trim().to_string()),
+        None => (None, translated),
+    }
+}
+
 /// Extract reasoning body (content after `**title**`).
 fn extract_reasoning_body(full_reasoning: &str) -> Option<String> {
     let full_reasoning = full_reasoning.trim();
@@ -528,3 +1513,1581 @@ fn extract_reasoning_body(full_reasoning: &str) -> Option<String> {
         Some(body.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::FakeBackend;
+    use super::test_support::ManualSpawner;
+    use super::*;
+    use crate::translation::config::PostFilter;
+    use crate::translation::config::SessionBudget;
+    use std::path::Path;
+
+    fn translator_for(allowed_workspace: &str) -> ReasoningTranslator {
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            allowed_workspaces: vec![PathBuf::from(allowed_workspace)],
+            ..Default::default()
+        });
+        translator.set_workspace(Path::new("/repo/frontend").to_path_buf());
+        translator
+    }
+
+    #[tokio::test]
+    async fn thread_outside_allowlist_is_not_translated() {
+        let mut translator = translator_for("/repo/backend");
+
+        let started = translator.maybe_translate_reasoning(
+            Some(ThreadId::new()),
+            "**Thinking**\n\nChecking the build.".to_string(),
+            FrameRequester::test_dummy(),
+        );
+
+        assert!(!started);
+        assert!(translator.translation_barrier.is_none());
+    }
+
+    #[tokio::test]
+    async fn low_effort_reasoning_is_not_translated() {
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            min_effort_to_translate: Some(ReasoningEffort::Low),
+            ..Default::default()
+        });
+        let reasoning = || "**Thinking**\n\nChecking the build.".to_string();
+
+        translator.set_reasoning_effort(Some(ReasoningEffort::Minimal));
+        assert!(!translator.maybe_translate_reasoning(
+            Some(ThreadId::new()),
+            reasoning(),
+            FrameRequester::test_dummy(),
+        ));
+        assert!(translator.translation_barrier.is_none());
+
+        translator.set_reasoning_effort(Some(ReasoningEffort::Medium));
+        assert!(translator.maybe_translate_reasoning(
+            Some(ThreadId::new()),
+            reasoning(),
+            FrameRequester::test_dummy(),
+        ));
+    }
+
+    #[tokio::test]
+    async fn thread_inside_allowlist_is_translated() {
+        let mut translator = translator_for("/repo");
+
+        let started = translator.maybe_translate_reasoning(
+            Some(ThreadId::new()),
+            "**Thinking**\n\nChecking the build.".to_string(),
+            FrameRequester::test_dummy(),
+        );
+
+        assert!(started);
+        assert!(translator.translation_barrier.is_some());
+        assert_eq!(translator.in_flight_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn pending_barrier_info_reflects_barrier() {
+        let mut translator = translator_for("/repo");
+        assert_eq!(translator.get_pending_barrier_info(), None);
+
+        let thread_id = ThreadId::new();
+        translator.maybe_translate_reasoning(
+            Some(thread_id),
+            "**Thinking**\n\nChecking the build.".to_string(),
+            FrameRequester::test_dummy(),
+        );
+        translator.defer(QueuedItem::Cell(Box::new(history_cell::new_info_event(
+            "later".to_string(),
+            None,
+        ))));
+
+        let info = translator.get_pending_barrier_info().unwrap();
+        assert_eq!(info.thread_id, thread_id);
+        assert_eq!(info.deferred_count, 1);
+        assert!(info.deadline > info.started_at);
+    }
+
+    #[tokio::test]
+    async fn pending_count_follows_submit_and_completion() {
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            ..Default::default()
+        })
+        .with_fake_backend(FakeBackend::translating("正在检查构建。"));
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+        assert_eq!(translator.pending_count(), PendingCounts::default());
+
+        translator.maybe_translate_reasoning(
+            Some(thread_id),
+            "**Thinking**\n\nChecking the build.".to_string(),
+            FrameRequester::test_dummy(),
+        );
+        translator.defer(QueuedItem::Cell(Box::new(history_cell::new_info_event(
+            "later".to_string(),
+            None,
+        ))));
+        assert_eq!(
+            translator.pending_count(),
+            PendingCounts {
+                in_flight: 1,
+                deferred: 1,
+            }
+        );
+
+        for _ in 0..100 {
+            tokio::task::yield_now().await;
+            translator.drain_results(Some(thread_id), &app_event_tx, FrameRequester::test_dummy());
+            if translator.pending_count().in_flight == 0 {
+                break;
+            }
+        }
+        assert_eq!(translator.pending_count(), PendingCounts::default());
+    }
+
+    #[tokio::test]
+    async fn timed_out_requests_stay_in_flight() {
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            ..Default::default()
+        })
+        .with_fake_backend(FakeBackend::unresponsive());
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+        translator.maybe_translate_reasoning(
+            Some(thread_id),
+            "**Thinking**\n\nChecking the build.".to_string(),
+            FrameRequester::test_dummy(),
+        );
+        translator.defer(QueuedItem::Cell(Box::new(history_cell::new_info_event(
+            "later".to_string(),
+            None,
+        ))));
+
+        translator.expire_barrier();
+        translator.maybe_flush_timeout(
+            Some(thread_id),
+            &app_event_tx,
+            FrameRequester::test_dummy(),
+        );
+
+        // The deferred cell is released, but the request has not answered yet.
+        assert_eq!(
+            translator.pending_count(),
+            PendingCounts {
+                in_flight: 1,
+                deferred: 0,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn configured_max_wait_sets_the_deadline() {
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            max_wait_ms: Some(12_000),
+            ..Default::default()
+        })
+        .with_fake_backend(FakeBackend::unresponsive());
+        translator.maybe_translate_reasoning(
+            Some(ThreadId::new()),
+            "**Thinking**\n\nChecking the build.".to_string(),
+            FrameRequester::test_dummy(),
+        );
+
+        let info = translator.get_pending_barrier_info().unwrap();
+        assert_eq!(
+            info.deadline.duration_since(info.started_at),
+            Duration::from_millis(12_000)
+        );
+    }
+
+    #[tokio::test]
+    async fn exhausted_budget_pauses_until_reenabled() {
+        let config = TranslationConfig {
+            enabled: true,
+            session_budget: SessionBudget {
+                max_requests: Some(1),
+                max_tokens: None,
+            },
+            ..Default::default()
+        };
+        let mut translator = ReasoningTranslator::from_config(config.clone());
+        let start = |translator: &mut ReasoningTranslator| {
+            let started = translator.maybe_translate_reasoning(
+                Some(ThreadId::new()),
+                "**Thinking**\n\nChecking the build.".to_string(),
+                FrameRequester::test_dummy(),
+            );
+            translator.translation_barrier = None;
+            started
+        };
+
+        assert!(start(&mut translator));
+        assert!(translator.budget_notice.is_some());
+        assert!(!start(&mut translator));
+
+        translator.update_config(TranslationConfig {
+            enabled: false,
+            ..config.clone()
+        });
+        translator.update_config(config);
+        assert!(start(&mut translator));
+    }
+
+    #[tokio::test]
+    async fn inline_annotation_attaches_translation_to_reasoning_cell() {
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            display_mode: TranslationDisplayMode::InlineAnnotation,
+            ..Default::default()
+        });
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+        let source = "**Thinking**\n\nChecking the build.".to_string();
+
+        translator.emit_history_cell_with_translation_hook(
+            &app_event_tx,
+            Some(thread_id),
+            FrameRequester::test_dummy(),
+            history_cell::new_reasoning_summary_block(source.clone()),
+        );
+        // The reasoning cell is held until its translation arrives.
+        assert!(rx.try_recv().is_err());
+
+        let request_id = translator.translation_barrier.as_ref().unwrap().request_id;
+        translator.on_translation_completed(
+            TranslationResult::new(
+                request_id,
+                thread_id,
+                Some("Thinking".to_string()),
+                source,
+                Some("正在检查构建。".to_string()),
+                None,
+            ),
+            Some(thread_id),
+            &app_event_tx,
+            FrameRequester::test_dummy(),
+        );
+
+        let mut cells = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let AppEvent::InsertHistoryCell(cell) = event {
+                cells.push(cell);
+            }
+        }
+        assert_eq!(cells.len(), 1);
+        assert!(
+            cells[0]
+                .as_any()
+                .downcast_ref::<history_cell::ReasoningSummaryCell>()
+                .is_some()
+        );
+        let rendered = cells[0]
+            .display_lines(80)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        assert!(
+            rendered.contains(&"  └ 正在检查构建。".to_string()),
+            "{rendered:?}"
+        );
+    }
+
+    #[test]
+    fn aborted_translation_task_releases_barrier() {
+        let spawner = ManualSpawner::default();
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            ..Default::default()
+        })
+        .with_fake_backend(FakeBackend::unresponsive())
+        .with_spawner(spawner.clone());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+
+        translator.emit_history_cell_with_translation_hook(
+            &app_event_tx,
+            Some(thread_id),
+            FrameRequester::test_dummy(),
+            history_cell::new_reasoning_summary_block("**Thinking**\n\nChecking.".to_string()),
+        );
+        assert!(translator.translation_barrier.is_some());
+        translator.emit_history_cell(
+            &app_event_tx,
+            Box::new(history_cell::new_warning_event("deferred".to_string())),
+        );
+        assert_eq!(translator.deferred_history_cells.len(), 1);
+        while rx.try_recv().is_ok() {}
+
+        // The request is waiting on the provider when its task goes away.
+        assert_eq!(spawner.queued(), 1);
+        spawner.abort_all();
+
+        let result =
+            translator.drain_results(Some(thread_id), &app_event_tx, FrameRequester::test_dummy());
+
+        assert!(result.needs_redraw);
+        assert!(translator.translation_barrier.is_none());
+        assert!(translator.deferred_history_cells.is_empty());
+        assert_eq!(translator.in_flight, 0);
+        let inserted = std::iter::from_fn(|| rx.try_recv().ok())
+            .filter(|event| matches!(event, AppEvent::InsertHistoryCell(_)))
+            .count();
+        // The error block, then the deferred cell.
+        assert_eq!(inserted, 2);
+    }
+
+    #[tokio::test]
+    async fn timeout_falls_back_to_source_text() {
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            fallback_to_source_on_timeout: true,
+            ..Default::default()
+        })
+        .with_fake_backend(FakeBackend::unresponsive());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+
+        translator.emit_history_cell_with_translation_hook(
+            &app_event_tx,
+            Some(thread_id),
+            FrameRequester::test_dummy(),
+            history_cell::new_reasoning_summary_block(
+                "**Thinking**\n\nChecking the build.".to_string(),
+            ),
+        );
+        while rx.try_recv().is_ok() {}
+        translator.expire_barrier();
+
+        assert!(translator.maybe_flush_timeout(
+            Some(thread_id),
+            &app_event_tx,
+            FrameRequester::test_dummy(),
+        ));
+
+        let rendered: Vec<String> = std::iter::from_fn(|| rx.try_recv().ok())
+            .filter_map(|event| match event {
+                AppEvent::InsertHistoryCell(cell) => Some(cell),
+                _ => None,
+            })
+            .flat_map(|cell| cell.display_lines(80))
+            .map(|line| line.to_string())
+            .collect();
+        assert_eq!(
+            rendered.first().map(String::as_str),
+            Some("  └ Checking the build.")
+        );
+        assert!(
+            rendered
+                .iter()
+                .any(|line| line.contains(SOURCE_FALLBACK_NOTE)),
+            "{rendered:?}"
+        );
+        assert!(
+            !rendered
+                .iter()
+                .any(|line| line.contains("Translation failed")),
+            "{rendered:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn reset_discards_previous_session_history() {
+        let mut translator = ReasoningTranslator::new(true);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+
+        translator.emit_history_cell_with_translation_hook(
+            &app_event_tx,
+            Some(thread_id),
+            FrameRequester::test_dummy(),
+            history_cell::new_reasoning_summary_block("**Thinking**\n\nOld session.".to_string()),
+        );
+        translator.emit_history_cell(
+            &app_event_tx,
+            Box::new(history_cell::new_warning_event("old session".to_string())),
+        );
+        while rx.try_recv().is_ok() {}
+
+        translator.reset();
+
+        assert!(translator.translation_barrier.is_none());
+        assert!(translator.deferred_history_cells.is_empty());
+        assert_eq!(translator.translation_seq, 0);
+        assert_eq!(translator.in_flight_count(), 0);
+
+        // Neither draining nor timing out releases the old session's cells.
+        let next_thread = ThreadId::new();
+        translator.drain_results(
+            Some(next_thread),
+            &app_event_tx,
+            FrameRequester::test_dummy(),
+        );
+        translator.maybe_flush_timeout(
+            Some(next_thread),
+            &app_event_tx,
+            FrameRequester::test_dummy(),
+        );
+        assert!(rx.try_recv().is_err());
+
+        // New history goes straight through.
+        translator.emit_history_cell(
+            &app_event_tx,
+            Box::new(history_cell::new_warning_event("new session".to_string())),
+        );
+        let inserted = std::iter::from_fn(|| rx.try_recv().ok())
+            .filter(|event| matches!(event, AppEvent::InsertHistoryCell(_)))
+            .count();
+        assert_eq!(inserted, 1);
+    }
+
+    #[tokio::test]
+    async fn unreachable_provider_pauses_translation_until_probe_succeeds() {
+        let mut translator = ReasoningTranslator::new(true);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+        let result = |network_failure: bool| {
+            TranslationResult::new(0, thread_id, None, String::new(), None, None)
+                .with_network_failure(network_failure)
+        };
+        let start = |translator: &mut ReasoningTranslator| {
+            translator.maybe_translate_reasoning(
+                Some(thread_id),
+                "**Thinking**\n\nChecking the build.".to_string(),
+                FrameRequester::test_dummy(),
+            )
+        };
+        let notices = |rx: &mut tokio::sync::mpsc::UnboundedReceiver<AppEvent>| {
+            std::iter::from_fn(|| rx.try_recv().ok())
+                .filter(|event| matches!(event, AppEvent::InsertHistoryCell(_)))
+                .count()
+        };
+
+        for _ in 0..3 {
+            translator.results_tx.send(result(true)).unwrap();
+        }
+        translator.on_draw_tick(Some(thread_id), &app_event_tx, FrameRequester::test_dummy());
+        assert!(translator.offline_retry_in().is_some());
+        assert_eq!(notices(&mut rx), 1);
+
+        // Skipped without holding anything back.
+        assert!(!start(&mut translator));
+        assert!(translator.translation_barrier.is_none());
+
+        // The next turn probes once.
+        translator.on_turn_started();
+        assert!(start(&mut translator));
+        translator.translation_barrier = None;
+        assert!(!start(&mut translator));
+
+        translator.results_tx.send(result(false)).unwrap();
+        translator.on_draw_tick(Some(thread_id), &app_event_tx, FrameRequester::test_dummy());
+        assert_eq!(translator.offline_retry_in(), None);
+        assert_eq!(notices(&mut rx), 1);
+    }
+
+    #[tokio::test]
+    async fn translations_feed_the_rolling_context_when_enabled() {
+        let thread_id = ThreadId::new();
+        let drain = |translator: &mut ReasoningTranslator, translated: &str| {
+            let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+            translator
+                .results_tx
+                .send(TranslationResult::new(
+                    0,
+                    thread_id,
+                    None,
+                    String::new(),
+                    Some(translated.to_string()),
+                    None,
+                ))
+                .unwrap();
+            translator.drain_results(
+                Some(thread_id),
+                &AppEventSender::new(tx),
+                FrameRequester::test_dummy(),
+            );
+        };
+
+        let mut translator = ReasoningTranslator::new(true);
+        drain(&mut translator, "我们先检查构建。");
+        assert_eq!(translator.rolling_context.render(), None);
+
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            rolling_context_chars: Some(12),
+            ..Default::default()
+        });
+        drain(&mut translator, "我们先检查构建。");
+        drain(&mut translator, "然后运行测试。");
+        assert_eq!(
+            translator.rolling_context.render().as_deref(),
+            Some("然后运行测试。")
+        );
+
+        translator.reset();
+        assert_eq!(translator.rolling_context.render(), None);
+    }
+
+    #[tokio::test]
+    async fn header_translation_sends_the_title() {
+        let source_sent = |header_translation: bool| async move {
+            let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+                enabled: true,
+                header_translation,
+                api_key: Some("test-key".to_string()),
+                // Nothing listens here, so the request fails fast and reports its source.
+                base_url: Some("http://127.0.0.1:9".to_string()),
+                timeout_ms: Some(1_000),
+                ..Default::default()
+            });
+            assert!(translator.maybe_translate_reasoning(
+                Some(ThreadId::new()),
+                "**Thinking**\n\nChecking the build.".to_string(),
+                FrameRequester::test_dummy(),
+            ));
+            translator.results_rx.recv().await.unwrap().source
+        };
+
+        assert_eq!(source_sent(false).await, "Checking the build.");
+        assert_eq!(
+            source_sent(true).await,
+            "**Thinking**\n\nChecking the build."
+        );
+    }
+
+    #[test]
+    fn translated_title_is_split_only_with_header_translation() {
+        let translated = "**思考中**\n\n正在**检查**构建。".to_string();
+        assert_eq!(
+            split_translated_title(translated.clone(), true),
+            (Some("思考中".to_string()), "正在**检查**构建。".to_string())
+        );
+        // Body-only translations keep their bold text.
+        assert_eq!(
+            split_translated_title(translated.clone(), false),
+            (None, translated)
+        );
+    }
+
+    #[tokio::test]
+    async fn last_translation_matches_the_emitted_cell() {
+        let mut translator = ReasoningTranslator::new(true);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+        assert_eq!(translator.last_translation(), None);
+
+        translator.maybe_translate_reasoning(
+            Some(thread_id),
+            "**Thinking**\n\nChecking the build.".to_string(),
+            FrameRequester::test_dummy(),
+        );
+        let request_id = translator.translation_barrier.as_ref().unwrap().request_id;
+        translator.on_translation_completed(
+            TranslationResult::new(
+                request_id,
+                thread_id,
+                Some("Thinking".to_string()),
+                "Checking the build.".to_string(),
+                Some("  正在检查构建。\n".to_string()),
+                None,
+            ),
+            Some(thread_id),
+            &app_event_tx,
+            FrameRequester::test_dummy(),
+        );
+
+        let Some(AppEvent::InsertHistoryCell(cell)) = rx.try_recv().ok() else {
+            panic!("expected the translation cell");
+        };
+        let rendered: String = cell.display_lines(80)[0]
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(rendered, "  └ 正在检查构建。");
+        assert_eq!(translator.last_translation(), Some("正在检查构建。"));
+    }
+
+    #[tokio::test]
+    async fn post_filters_clean_the_displayed_translation() {
+        let mut translator = ReasoningTranslator::new(true);
+        translator.update_config(TranslationConfig {
+            enabled: true,
+            post_filters: vec![
+                PostFilter {
+                    pattern: r"\s*（翻译）$".to_string(),
+                    replacement: String::new(),
+                },
+                PostFilter {
+                    pattern: "构建".to_string(),
+                    replacement: "build".to_string(),
+                },
+            ],
+            ..Default::default()
+        });
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+
+        translator.maybe_translate_reasoning(
+            Some(thread_id),
+            "**Thinking**\n\nChecking the build.".to_string(),
+            FrameRequester::test_dummy(),
+        );
+        let request_id = translator.translation_barrier.as_ref().unwrap().request_id;
+        translator.on_translation_completed(
+            TranslationResult::new(
+                request_id,
+                thread_id,
+                Some("Thinking".to_string()),
+                "Checking the build.".to_string(),
+                Some("正在检查构建。 （翻译）".to_string()),
+                None,
+            ),
+            Some(thread_id),
+            &app_event_tx,
+            FrameRequester::test_dummy(),
+        );
+
+        assert_eq!(translator.last_translation(), Some("正在检查build。"));
+    }
+
+    #[tokio::test]
+    async fn clearing_the_queue_mid_barrier_releases_cells_in_order() {
+        let mut translator = ReasoningTranslator::new(true);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+        let reasoning = |title: &str| {
+            history_cell::new_reasoning_summary_block(format!("**{title}**\n\nBody."))
+        };
+
+        translator.emit_history_cell_with_translation_hook(
+            &app_event_tx,
+            Some(thread_id),
+            FrameRequester::test_dummy(),
+            reasoning("Thinking"),
+        );
+        // The first reasoning cell is inserted; its translation holds the barrier.
+        assert_eq!(std::iter::from_fn(|| rx.try_recv().ok()).count(), 1);
+        translator.emit_history_cell(
+            &app_event_tx,
+            Box::new(history_cell::new_info_event(
+                "tool output".to_string(),
+                None,
+            )),
+        );
+        translator.emit_history_cell_with_translation_hook(
+            &app_event_tx,
+            Some(thread_id),
+            FrameRequester::test_dummy(),
+            reasoning("Planning"),
+        );
+
+        let snapshot = translator.queue_snapshot();
+        let titles: Vec<_> = snapshot
+            .blocks
+            .iter()
+            .map(|block| (block.title.as_deref(), block.translating))
+            .collect();
+        assert_eq!(
+            titles,
+            vec![(Some("Thinking"), true), (Some("Planning"), false)]
+        );
+        assert_eq!(snapshot.other_cells, 1);
+
+        assert_eq!(translator.clear_queue(&app_event_tx), 2);
+        assert_eq!(translator.queue_snapshot(), QueueSnapshot::default());
+        assert_eq!(translator.in_flight_count(), 0);
+        let released: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok())
+            .map(|event| match event {
+                AppEvent::InsertHistoryCell(cell) => cell
+                    .as_any()
+                    .downcast_ref::<history_cell::ReasoningSummaryCell>()
+                    .is_some(),
+                _ => panic!("expected history cells"),
+            })
+            .collect();
+        assert_eq!(released, vec![false, true]);
+
+        // Later cells render immediately and reasoning translates again.
+        translator.emit_history_cell_with_translation_hook(
+            &app_event_tx,
+            Some(thread_id),
+            FrameRequester::test_dummy(),
+            reasoning("Checking"),
+        );
+        assert!(rx.try_recv().is_ok());
+        assert!(translator.translation_barrier.is_some());
+    }
+
+    #[tokio::test]
+    async fn rejected_api_key_requests_key_reentry_for_the_provider() {
+        let mut translator = ReasoningTranslator::new(true);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+
+        translator.maybe_translate_reasoning(
+            Some(thread_id),
+            "**Thinking**\n\nChecking the build.".to_string(),
+            FrameRequester::test_dummy(),
+        );
+        let request_id = translator.translation_barrier.as_ref().unwrap().request_id;
+        translator
+            .results_tx
+            .send(
+                TranslationResult::new(
+                    request_id,
+                    thread_id,
+                    Some("Thinking".to_string()),
+                    "Checking the build.".to_string(),
+                    None,
+                    Some("API error (401): Unauthorized".to_string()),
+                )
+                .with_rejected_key_for(Some(ProviderId::DeepSeek)),
+            )
+            .unwrap();
+        translator.drain_results(Some(thread_id), &app_event_tx, FrameRequester::test_dummy());
+
+        let events: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok()).collect();
+        assert!(matches!(events[0], AppEvent::InsertHistoryCell(_)));
+        assert!(matches!(
+            events.last(),
+            Some(AppEvent::TranslationAuthFailed(ProviderId::DeepSeek))
+        ));
+    }
+
+    #[tokio::test]
+    async fn reported_rate_limit_is_kept_and_sent_to_the_ui() {
+        let mut translator = ReasoningTranslator::new(true);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+        let rate_limit = RateLimitState {
+            remaining_requests: Some(3),
+            remaining_tokens: Some(12_000),
+            reset_requests_at: None,
+        };
+
+        translator.maybe_translate_reasoning(
+            Some(thread_id),
+            "**Thinking**\n\nChecking the build.".to_string(),
+            FrameRequester::test_dummy(),
+        );
+        let request_id = translator.translation_barrier.as_ref().unwrap().request_id;
+        translator
+            .results_tx
+            .send(
+                TranslationResult::new(
+                    request_id,
+                    thread_id,
+                    Some("Thinking".to_string()),
+                    "Checking the build.".to_string(),
+                    Some("正在检查构建。".to_string()),
+                    None,
+                )
+                .with_rate_limit(Some(rate_limit)),
+            )
+            .unwrap();
+        translator.drain_results(Some(thread_id), &app_event_tx, FrameRequester::test_dummy());
+
+        assert_eq!(translator.rate_limit(), Some(rate_limit));
+        let events: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok()).collect();
+        assert!(events.iter().any(
+            |event| matches!(event, AppEvent::UpdateRateLimitState(state) if *state == rate_limit)
+        ));
+
+        // Switching providers forgets the old provider's quota.
+        translator.update_config(TranslationConfig {
+            enabled: true,
+            provider: "anthropic".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(translator.rate_limit(), None);
+    }
+
+    #[tokio::test]
+    async fn long_system_messages_are_translated_after_the_message() {
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            translate_system_messages: true,
+            min_system_message_length: 20,
+            ..Default::default()
+        })
+        .with_fake_backend(FakeBackend::translating("我来帮你处理这个问题。"));
+        let thread_id = ThreadId::new();
+        let emit = |translator: &mut ReasoningTranslator, message: &str| {
+            translator.queue_system_message(
+                Some(thread_id),
+                FrameRequester::test_dummy(),
+                SystemMessage::new(message.to_string()),
+            );
+        };
+
+        emit(&mut translator, "On it.");
+        assert!(translator.translation_barrier.is_none());
+
+        let message = "I'll help you with that. First, let me look at the build.";
+        emit(&mut translator, message);
+        assert!(translator.translation_barrier.is_some());
+        assert_eq!(translator.results_rx.recv().await.unwrap().source, message);
+    }
+
+    #[tokio::test]
+    async fn system_messages_are_ignored_unless_enabled() {
+        let mut translator = ReasoningTranslator::new(true);
+
+        translator.queue_system_message(
+            Some(ThreadId::new()),
+            FrameRequester::test_dummy(),
+            SystemMessage::new("x".repeat(500)),
+        );
+
+        assert!(translator.translation_barrier.is_none());
+    }
+
+    #[test]
+    fn system_message_waits_behind_the_barrier() {
+        let spawner = ManualSpawner::default();
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            translate_system_messages: true,
+            min_system_message_length: 20,
+            ..Default::default()
+        })
+        .with_fake_backend(FakeBackend::translating("检查构建。"))
+        .with_spawner(spawner.clone());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+        let message = "I'll help you with that. First, let me look at the build.";
+
+        translator.emit_history_cell_with_translation_hook(
+            &app_event_tx,
+            Some(thread_id),
+            FrameRequester::test_dummy(),
+            history_cell::new_reasoning_summary_block(
+                "**Thinking**\n\nChecking the build.".to_string(),
+            ),
+        );
+        translator.queue_system_message(
+            Some(thread_id),
+            FrameRequester::test_dummy(),
+            SystemMessage::new(message.to_string()),
+        );
+        // Queued, but not shown as a block or a cell.
+        assert_eq!(spawner.queued(), 1);
+        assert_eq!(translator.queue_snapshot().blocks.len(), 1);
+        assert_eq!(translator.queue_snapshot().other_cells, 0);
+        while rx.try_recv().is_ok() {}
+
+        spawner.run_all();
+        translator.drain_results(Some(thread_id), &app_event_tx, FrameRequester::test_dummy());
+
+        // The reasoning translation is inserted, then the message's translation starts.
+        assert_eq!(inserted_cells(&mut rx).len(), 1);
+        assert!(translator.translation_barrier.is_some());
+        assert_eq!(spawner.queued(), 1);
+        assert!(translator.deferred_history_cells.is_empty());
+    }
+
+    // A plain `#[test]`: there is no runtime until the spawner runs the queued request.
+    #[test]
+    fn reasoning_is_translated_without_a_live_runtime() {
+        let spawner = ManualSpawner::default();
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            ..Default::default()
+        })
+        .with_fake_backend(FakeBackend::translating("检查构建。"))
+        .with_spawner(spawner.clone());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+
+        assert!(translator.maybe_translate_reasoning(
+            Some(thread_id),
+            "**Thinking**\n\nChecking the build.".to_string(),
+            FrameRequester::test_dummy(),
+        ));
+        assert_eq!(spawner.queued(), 1);
+        assert!(translator.results_rx.try_recv().is_err());
+
+        spawner.run_all();
+        assert_eq!(spawner.queued(), 0);
+        translator.drain_results(Some(thread_id), &app_event_tx, FrameRequester::test_dummy());
+
+        assert!(translator.translation_barrier.is_none());
+        let translations: Vec<String> = inserted_cells(&mut rx)
+            .iter()
+            .filter_map(|(_, cell)| {
+                cell.translation_content()?
+                    .translated_text()
+                    .map(str::to_string)
+            })
+            .collect();
+        assert_eq!(translations, vec!["检查构建。".to_string()]);
+    }
+
+    const FINAL_ANSWER: &str =
+        "The build failed because the lockfile was out of date; I regenerated it.";
+
+    fn emit_final_answer(translator: &mut ReasoningTranslator) {
+        translator.queue_system_message(
+            Some(ThreadId::new()),
+            FrameRequester::test_dummy(),
+            SystemMessage::final_answer(FINAL_ANSWER.to_string()),
+        );
+    }
+
+    #[tokio::test]
+    async fn final_answer_is_translated_without_reasoning() {
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            translate_reasoning: false,
+            translate_final_answer: true,
+            min_system_message_length: 20,
+            ..Default::default()
+        })
+        .with_fake_backend(FakeBackend::translating("构建失败是因为锁文件过期了。"));
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+
+        translator.emit_history_cell_with_translation_hook(
+            &app_event_tx,
+            Some(ThreadId::new()),
+            FrameRequester::test_dummy(),
+            history_cell::new_reasoning_summary_block(
+                "**Thinking**\n\nChecking the build.".to_string(),
+            ),
+        );
+        assert!(translator.translation_barrier.is_none());
+        assert_eq!(inserted_cells(&mut rx).len(), 1);
+
+        emit_final_answer(&mut translator);
+        assert!(translator.translation_barrier.is_some());
+        assert!(rx.try_recv().is_err());
+        assert_eq!(
+            translator.results_rx.recv().await.unwrap().source,
+            FINAL_ANSWER
+        );
+    }
+
+    #[tokio::test]
+    async fn reasoning_is_translated_without_the_final_answer() {
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            min_system_message_length: 20,
+            ..Default::default()
+        })
+        .with_fake_backend(FakeBackend::unresponsive());
+
+        emit_final_answer(&mut translator);
+        assert!(translator.translation_barrier.is_none());
+
+        assert!(translator.maybe_translate_reasoning(
+            Some(ThreadId::new()),
+            "**Thinking**\n\nChecking the build.".to_string(),
+            FrameRequester::test_dummy(),
+        ));
+    }
+
+    #[tokio::test]
+    async fn final_answer_is_not_translated_twice_with_system_messages() {
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            translate_system_messages: true,
+            translate_final_answer: true,
+            min_system_message_length: 20,
+            ..Default::default()
+        })
+        .with_fake_backend(FakeBackend::unresponsive());
+
+        // `translate_system_messages` already queued the answer when it finished.
+        emit_final_answer(&mut translator);
+        assert!(translator.translation_barrier.is_none());
+    }
+
+    #[tokio::test]
+    async fn consecutive_timeouts_shorten_the_wait_until_a_translation_arrives() {
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            max_wait_ms: Some(8_000),
+            ..Default::default()
+        })
+        .with_fake_backend(FakeBackend::unresponsive());
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+        let next_wait = |translator: &mut ReasoningTranslator| {
+            assert!(translator.maybe_translate_reasoning(
+                Some(thread_id),
+                "**Thinking**\n\nChecking the build.".to_string(),
+                FrameRequester::test_dummy(),
+            ));
+            let info = translator.get_pending_barrier_info().unwrap();
+            info.deadline.duration_since(info.started_at)
+        };
+        let time_out = |translator: &mut ReasoningTranslator| {
+            translator.expire_barrier();
+            assert!(translator.maybe_flush_timeout(
+                Some(thread_id),
+                &app_event_tx,
+                FrameRequester::test_dummy(),
+            ));
+        };
+
+        assert_eq!(next_wait(&mut translator), Duration::from_secs(8));
+        time_out(&mut translator);
+        assert_eq!(next_wait(&mut translator), Duration::from_secs(4));
+        time_out(&mut translator);
+        assert_eq!(next_wait(&mut translator), Duration::from_secs(2));
+
+        let request_id = translator.translation_barrier.as_ref().unwrap().request_id;
+        translator.on_translation_completed(
+            TranslationResult::new(
+                request_id,
+                thread_id,
+                Some("Thinking".to_string()),
+                "Checking the build.".to_string(),
+                Some("正在检查构建。".to_string()),
+                None,
+            ),
+            Some(thread_id),
+            &app_event_tx,
+            FrameRequester::test_dummy(),
+        );
+        assert_eq!(next_wait(&mut translator), Duration::from_secs(8));
+    }
+
+    #[tokio::test]
+    async fn timeout_error_names_the_provider_and_how_to_wait_longer() {
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            provider: "deepseek".to_string(),
+            model: Some("deepseek-chat".to_string()),
+            max_wait_ms: Some(8_000),
+            ..Default::default()
+        })
+        .with_fake_backend(FakeBackend::unresponsive());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+        let mut time_out = |translator: &mut ReasoningTranslator| {
+            assert!(translator.maybe_translate_reasoning(
+                Some(thread_id),
+                "**Thinking**\n\nChecking the build.".to_string(),
+                FrameRequester::test_dummy(),
+            ));
+            translator.expire_barrier();
+            assert!(translator.maybe_flush_timeout(
+                Some(thread_id),
+                &app_event_tx,
+                FrameRequester::test_dummy(),
+            ));
+            let errors: Vec<String> = inserted_cells(&mut rx)
+                .iter()
+                .filter_map(|(_, cell)| {
+                    cell.translation_content()?.error_text().map(str::to_string)
+                })
+                .collect();
+            assert_eq!(errors.len(), 1, "{errors:?}");
+            errors[0].clone()
+        };
+
+        let error = time_out(&mut translator);
+        assert!(
+            error.starts_with(
+                "Translation timeout (8000ms): DeepSeek (deepseek-chat) had not answered after "
+            ),
+            "{error}"
+        );
+        assert!(error.contains("raise max_wait_ms"), "{error}");
+        assert!(error.contains(TRANSLATION_MAX_WAIT_ENV), "{error}");
+
+        let error = time_out(&mut translator);
+        assert!(
+            error.starts_with(
+                "Translation timeout (4000ms, shortened from 8000ms after consecutive timeouts)"
+            ),
+            "{error}"
+        );
+    }
+
+    #[tokio::test]
+    async fn late_results_are_discarded_but_their_latency_is_recorded() {
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            ..Default::default()
+        })
+        .with_fake_backend(FakeBackend::unresponsive());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+
+        assert!(translator.maybe_translate_reasoning(
+            Some(thread_id),
+            "**Thinking**\n\nChecking the build.".to_string(),
+            FrameRequester::test_dummy(),
+        ));
+        let request_id = translator.translation_barrier.as_ref().unwrap().request_id;
+        translator.expire_barrier();
+        assert!(translator.maybe_flush_timeout(
+            Some(thread_id),
+            &app_event_tx,
+            FrameRequester::test_dummy(),
+        ));
+        while rx.try_recv().is_ok() {}
+
+        translator
+            .results_tx
+            .send(
+                TranslationResult::new(
+                    request_id,
+                    thread_id,
+                    Some("Thinking".to_string()),
+                    "Checking the build.".to_string(),
+                    Some("正在检查构建。".to_string()),
+                    None,
+                )
+                .with_latency(Duration::from_millis(9_500)),
+            )
+            .unwrap();
+        translator.drain_results(Some(thread_id), &app_event_tx, FrameRequester::test_dummy());
+
+        assert!(inserted_cells(&mut rx).is_empty());
+        let stats = translator.stats();
+        assert_eq!(stats.late_results, 1);
+        assert_eq!(stats.slowest_late_result_ms, 9_500);
+    }
+
+    #[tokio::test]
+    async fn short_blocks_are_translated_together_after_the_last_one() {
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            translate_after_n_chars: 30,
+            ..Default::default()
+        })
+        .with_fake_backend(FakeBackend::unresponsive());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+        let emit = |translator: &mut ReasoningTranslator, markdown: &str| {
+            translator.emit_history_cell_with_translation_hook(
+                &app_event_tx,
+                Some(thread_id),
+                FrameRequester::test_dummy(),
+                history_cell::new_reasoning_summary_block(markdown.to_string()),
+            );
+        };
+
+        emit(&mut translator, "**Planning**\n\nRead the config.");
+        assert!(translator.translation_barrier.is_none());
+        emit(&mut translator, "**Fixing**\n\nPatch the parser.");
+
+        let barrier = translator.translation_barrier.as_ref().unwrap();
+        assert_eq!(barrier.source_text, "Read the config.\n\nPatch the parser.");
+        assert_eq!(barrier.batch.as_ref().map(Batch::block_count), Some(2));
+
+        complete_barrier(
+            &mut translator,
+            thread_id,
+            &app_event_tx,
+            Some("读取配置。\n\n修补解析器。"),
+        );
+        let cells = inserted_cells(&mut rx);
+        let labels: Vec<&str> = cells.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels.len(), 3, "{labels:?}");
+        assert_eq!(labels[..2], ["reasoning", "reasoning"]);
+        let translated = cells[2]
+            .1
+            .translation_content()
+            .and_then(|content| content.translated_text())
+            .map(str::to_string);
+        assert_eq!(
+            translated.as_deref(),
+            Some("**Planning**\n\n读取配置。\n\n**Fixing**\n\n修补解析器。")
+        );
+    }
+
+    #[tokio::test]
+    async fn reset_drops_buffered_blocks() {
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            translate_after_n_chars: 30,
+            ..Default::default()
+        })
+        .with_fake_backend(FakeBackend::unresponsive());
+        let thread_id = ThreadId::new();
+
+        assert!(!translator.maybe_translate_reasoning(
+            Some(thread_id),
+            "**Planning**\n\nRead the config.".to_string(),
+            FrameRequester::test_dummy(),
+        ));
+        translator.reset();
+
+        // The old block no longer counts towards the threshold.
+        assert!(!translator.maybe_translate_reasoning(
+            Some(thread_id),
+            "**Fixing**\n\nPatch the parser.".to_string(),
+            FrameRequester::test_dummy(),
+        ));
+        assert!(translator.translation_barrier.is_none());
+    }
+
+    #[tokio::test]
+    async fn buffered_blocks_are_translated_when_the_turn_ends() {
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            translate_after_n_chars: 30,
+            ..Default::default()
+        })
+        .with_fake_backend(FakeBackend::unresponsive());
+        let thread_id = ThreadId::new();
+
+        assert!(!translator.maybe_translate_reasoning(
+            Some(thread_id),
+            "**Planning**\n\nRead the config.".to_string(),
+            FrameRequester::test_dummy(),
+        ));
+        assert!(translator.flush_pending_text(Some(thread_id), FrameRequester::test_dummy()));
+
+        let barrier = translator.translation_barrier.as_ref().unwrap();
+        assert_eq!(barrier.source_text, "Read the config.");
+        assert_eq!(barrier.batch.as_ref().map(Batch::block_count), Some(1));
+        // Nothing is left for the next turn.
+        assert!(translator.pending_text_buffer.take().is_none());
+    }
+
+    #[tokio::test]
+    async fn blocks_arriving_during_a_translation_wait_in_the_buffer() {
+        let mut translator = ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            translate_after_n_chars: 10,
+            ..Default::default()
+        })
+        .with_fake_backend(FakeBackend::unresponsive());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+
+        assert!(translator.maybe_translate_reasoning(
+            Some(thread_id),
+            "**Planning**\n\nRead the config.".to_string(),
+            FrameRequester::test_dummy(),
+        ));
+        assert!(!translator.maybe_translate_reasoning(
+            Some(thread_id),
+            "**Fixing**\n\nPatch the parser.".to_string(),
+            FrameRequester::test_dummy(),
+        ));
+        // The turn ends while the first translation is still pending.
+        assert!(!translator.flush_pending_text(Some(thread_id), FrameRequester::test_dummy()));
+        translator.emit_history_cell(
+            &app_event_tx,
+            Box::new(history_cell::new_info_event(
+                "Turn finished".to_string(),
+                None,
+            )),
+        );
+
+        complete_barrier(
+            &mut translator,
+            thread_id,
+            &app_event_tx,
+            Some("读取配置。"),
+        );
+
+        let barrier = translator.translation_barrier.as_ref().unwrap();
+        assert_eq!(barrier.source_text, "Patch the parser.");
+        assert_eq!(barrier.batch.as_ref().map(Batch::block_count), Some(1));
+        // The cell that followed the end of the turn waits behind the held block.
+        assert_eq!(translator.deferred_history_cells.len(), 1);
+        assert_eq!(inserted_cells(&mut rx).len(), 1);
+    }
+
+    fn replacing_translator() -> ReasoningTranslator {
+        ReasoningTranslator::from_config(TranslationConfig {
+            enabled: true,
+            display_mode: TranslationDisplayMode::Replace,
+            ..Default::default()
+        })
+        .with_fake_backend(FakeBackend::unresponsive())
+    }
+
+    /// Inserted cells in order: reasoning cells as "reasoning" (or "reasoning (transcript
+    /// only)" when hidden from the main view), anything else as its rendered text.
+    fn inserted_cells(
+        rx: &mut tokio::sync::mpsc::UnboundedReceiver<AppEvent>,
+    ) -> Vec<(String, Box<dyn HistoryCell>)> {
+        std::iter::from_fn(|| rx.try_recv().ok())
+            .filter_map(|event| match event {
+                AppEvent::InsertHistoryCell(cell) => Some(cell),
+                _ => None,
+            })
+            .map(|cell| {
+                let label = if cell
+                    .as_any()
+                    .downcast_ref::<history_cell::ReasoningSummaryCell>()
+                    .is_some()
+                {
+                    if cell.display_lines(80).is_empty() {
+                        "reasoning (transcript only)".to_string()
+                    } else {
+                        "reasoning".to_string()
+                    }
+                } else {
+                    cell.display_lines(80)
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                };
+                (label, cell)
+            })
+            .collect()
+    }
+
+    fn complete_barrier(
+        translator: &mut ReasoningTranslator,
+        thread_id: ThreadId,
+        app_event_tx: &AppEventSender,
+        translated: Option<&str>,
+    ) {
+        let barrier = translator.translation_barrier.as_ref().unwrap();
+        let request_id = barrier.request_id;
+        let title = barrier.title.clone();
+        let source = barrier.source_text.clone();
+        let error = translated.is_none().then(|| "provider error".to_string());
+        translator.on_translation_completed(
+            TranslationResult::new(
+                request_id,
+                thread_id,
+                title,
+                source,
+                translated.map(str::to_string),
+                error,
+            ),
+            Some(thread_id),
+            app_event_tx,
+            FrameRequester::test_dummy(),
+        );
+    }
+
+    #[tokio::test]
+    async fn replace_mode_shows_the_translation_in_place_of_the_original() {
+        let mut translator = replacing_translator();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+
+        translator.emit_history_cell_with_translation_hook(
+            &app_event_tx,
+            Some(thread_id),
+            FrameRequester::test_dummy(),
+            history_cell::new_reasoning_summary_block(
+                "**Thinking**\n\nChecking the build.".to_string(),
+            ),
+        );
+        translator.emit_history_cell(
+            &app_event_tx,
+            Box::new(history_cell::new_info_event(
+                "tool output".to_string(),
+                None,
+            )),
+        );
+        // Both the reasoning cell and the cell after it are withheld.
+        assert!(rx.try_recv().is_err());
+        assert_eq!(translator.pending_count().deferred, 1);
+
+        complete_barrier(
+            &mut translator,
+            thread_id,
+            &app_event_tx,
+            Some("正在检查构建。"),
+        );
+
+        let cells = inserted_cells(&mut rx);
+        assert_eq!(
+            cells.len(),
+            3,
+            "{:?}",
+            cells.iter().map(|c| &c.0).collect::<Vec<_>>()
+        );
+        assert_eq!(cells[0].0, "reasoning (transcript only)");
+        assert!(cells[1].0.contains("正在检查构建。"), "{}", cells[1].0);
+        assert!(cells[2].0.contains("tool output"), "{}", cells[2].0);
+
+        // The original can still be read in the transcript.
+        let transcript: Vec<String> = cells[0]
+            .1
+            .transcript_lines(80)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert!(
+            transcript
+                .iter()
+                .any(|line| line.contains("Checking the build.")),
+            "{transcript:?}"
+        );
+        assert_eq!(translator.last_translation(), Some("正在检查构建。"));
+    }
+
+    #[tokio::test]
+    async fn replace_mode_shows_the_original_when_translation_fails() {
+        let mut translator = replacing_translator();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+
+        translator.emit_history_cell_with_translation_hook(
+            &app_event_tx,
+            Some(thread_id),
+            FrameRequester::test_dummy(),
+            history_cell::new_reasoning_summary_block("**Thinking**\n\nChecking.".to_string()),
+        );
+        assert!(rx.try_recv().is_err());
+
+        complete_barrier(&mut translator, thread_id, &app_event_tx, None);
+
+        let cells = inserted_cells(&mut rx);
+        let labels: Vec<&str> = cells.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels.len(), 2, "{labels:?}");
+        assert_eq!(labels[0], "reasoning");
+        assert!(labels[1].contains("provider error"), "{labels:?}");
+    }
+
+    #[tokio::test]
+    async fn replace_mode_shows_the_original_on_timeout() {
+        let mut translator = replacing_translator();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+
+        translator.emit_history_cell_with_translation_hook(
+            &app_event_tx,
+            Some(thread_id),
+            FrameRequester::test_dummy(),
+            history_cell::new_reasoning_summary_block("**Thinking**\n\nChecking.".to_string()),
+        );
+        translator.emit_history_cell(
+            &app_event_tx,
+            Box::new(history_cell::new_info_event(
+                "tool output".to_string(),
+                None,
+            )),
+        );
+        assert!(rx.try_recv().is_err());
+
+        translator.expire_barrier();
+        assert!(translator.maybe_flush_timeout(
+            Some(thread_id),
+            &app_event_tx,
+            FrameRequester::test_dummy(),
+        ));
+
+        let cells = inserted_cells(&mut rx);
+        let labels: Vec<&str> = cells.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels.len(), 3, "{labels:?}");
+        assert_eq!(labels[0], "reasoning");
+        assert!(labels[1].contains("Translation timeout"), "{labels:?}");
+        assert!(labels[2].contains("tool output"), "{labels:?}");
+    }
+
+    #[tokio::test]
+    async fn replace_mode_keeps_consecutive_blocks_in_order() {
+        let mut translator = replacing_translator();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+        let thread_id = ThreadId::new();
+        let reasoning = |title: &str| {
+            history_cell::new_reasoning_summary_block(format!("**{title}**\n\nBody."))
+        };
+        let info = |text: &str| -> Box<dyn HistoryCell> {
+            Box::new(history_cell::new_info_event(text.to_string(), None))
+        };
+
+        translator.emit_history_cell_with_translation_hook(
+            &app_event_tx,
+            Some(thread_id),
+            FrameRequester::test_dummy(),
+            reasoning("Thinking"),
+        );
+        translator.emit_history_cell(&app_event_tx, info("first tool"));
+        translator.emit_history_cell_with_translation_hook(
+            &app_event_tx,
+            Some(thread_id),
+            FrameRequester::test_dummy(),
+            reasoning("Planning"),
+        );
+        translator.emit_history_cell(&app_event_tx, info("second tool"));
+        assert!(rx.try_recv().is_err());
+
+        // The first translation releases everything up to the next reasoning block, which
+        // is then held by its own barrier.
+        complete_barrier(&mut translator, thread_id, &app_event_tx, Some("思考中"));
+        let cells = inserted_cells(&mut rx);
+        let labels: Vec<&str> = cells.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels.len(), 3, "{labels:?}");
+        assert_eq!(labels[0], "reasoning (transcript only)");
+        assert!(labels[1].contains("思考中"), "{labels:?}");
+        assert!(labels[2].contains("first tool"), "{labels:?}");
+        assert_eq!(
+            translator
+                .translation_barrier
+                .as_ref()
+                .and_then(|barrier| barrier.title.as_deref()),
+            Some("Planning")
+        );
+        assert_eq!(translator.pending_count().deferred, 1);
+
+        complete_barrier(&mut translator, thread_id, &app_event_tx, Some("计划中"));
+        let cells = inserted_cells(&mut rx);
+        let labels: Vec<&str> = cells.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels.len(), 3, "{labels:?}");
+        assert_eq!(labels[0], "reasoning (transcript only)");
+        assert!(labels[1].contains("计划中"), "{labels:?}");
+        assert!(labels[2].contains("second tool"), "{labels:?}");
+        assert!(translator.translation_barrier.is_none());
+    }
+
+    #[tokio::test]
+    async fn replace_mode_inserts_untranslated_reasoning_directly() {
+        let mut translator = replacing_translator();
+        translator.set_enabled(false);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let app_event_tx = AppEventSender::new(tx);
+
+        translator.emit_history_cell_with_translation_hook(
+            &app_event_tx,
+            Some(ThreadId::new()),
+            FrameRequester::test_dummy(),
+            history_cell::new_reasoning_summary_block("**Thinking**\n\nChecking.".to_string()),
+        );
+
+        let cells = inserted_cells(&mut rx);
+        let labels: Vec<&str> = cells.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(labels, vec!["reasoning"]);
+        assert!(translator.translation_barrier.is_none());
+    }
+}
@@ -0,0 +1,149 @@
+//! Stand-ins for the provider and the Tokio runtime, so tests can drive the orchestrator
+//! deterministically.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use super::ReasoningTranslator;
+use crate::translation::backend::BackendRequest;
+use crate::translation::backend::BackendResponse;
+use crate::translation::backend::RequestBackend;
+use crate::translation::client::TranslationBackend;
+use crate::translation::client::TranslationOutput;
+use crate::translation::error::TranslationError;
+use crate::translation::retry::translate_with_retry;
+use crate::translation::spawner::Spawner;
+use crate::translation::spawner::Task;
+
+impl ReasoningTranslator {
+    /// Send translation requests to `backend` instead of the configured provider.
+    pub(crate) fn with_fake_backend(mut self, backend: FakeBackend) -> Self {
+        self.backend = Arc::new(backend);
+        self
+    }
+
+    /// Queue translation tasks on `spawner` instead of the Tokio runtime, so no runtime is
+    /// needed until the test runs them.
+    pub(crate) fn with_spawner(mut self, spawner: ManualSpawner) -> Self {
+        self.spawner = Arc::new(spawner);
+        self
+    }
+
+    /// Make the pending barrier time out on the next check.
+    pub(crate) fn expire_barrier(&mut self) {
+        if let Some(barrier) = self.translation_barrier.as_mut() {
+            barrier.deadline = Instant::now();
+        }
+    }
+}
+
+/// Stand-in for the provider: answers every request with a canned translation, or never
+/// answers. Requests still go through the retry policy.
+#[derive(Debug, Clone)]
+pub(crate) struct FakeBackend {
+    translation: Option<String>,
+}
+
+impl FakeBackend {
+    pub(crate) fn translating(translation: &str) -> Self {
+        Self {
+            translation: Some(translation.to_string()),
+        }
+    }
+
+    pub(crate) fn unresponsive() -> Self {
+        Self { translation: None }
+    }
+}
+
+impl TranslationBackend for FakeBackend {
+    async fn translate(
+        &self,
+        _text: &str,
+        _target_lang: &str,
+        _strict: bool,
+    ) -> Result<TranslationOutput, TranslationError> {
+        match &self.translation {
+            Some(text) => Ok(TranslationOutput {
+                text: text.clone(),
+                usage: None,
+                truncated: false,
+            }),
+            None => std::future::pending().await,
+        }
+    }
+}
+
+impl RequestBackend for FakeBackend {
+    fn translate(
+        &self,
+        request: BackendRequest,
+    ) -> Pin<Box<dyn Future<Output = BackendResponse> + Send>> {
+        let backend = self.clone();
+        Box::pin(async move {
+            let result =
+                translate_with_retry(&backend, &request.text, &request.config, &request.metrics)
+                    .await;
+            (result, None)
+        })
+    }
+}
+
+/// Stand-in for the Tokio runtime: holds translation tasks until [`ManualSpawner::run_all`],
+/// so the orchestrator can be driven from a plain `#[test]`.
+#[derive(Clone, Default)]
+pub(crate) struct ManualSpawner {
+    tasks: Arc<Mutex<Vec<Task>>>,
+}
+
+impl std::fmt::Debug for ManualSpawner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ManualSpawner")
+            .field("queued", &self.queued())
+            .finish()
+    }
+}
+
+impl Spawner for ManualSpawner {
+    fn spawn(&self, task: Task) {
+        self.tasks.lock().unwrap().push(task);
+    }
+}
+
+impl ManualSpawner {
+    pub(crate) fn queued(&self) -> usize {
+        self.tasks.lock().unwrap().len()
+    }
+
+    /// Run the queued tasks to completion, one after another, on a runtime of their own.
+    pub(crate) fn run_all(&self) {
+        let tasks = std::mem::take(&mut *self.tasks.lock().unwrap());
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        for task in tasks {
+            runtime.block_on(task);
+        }
+    }
+
+    /// Poll each queued task once, so its request is in flight, then drop it the way an
+    /// aborted task is dropped.
+    pub(crate) fn abort_all(&self) {
+        let tasks = std::mem::take(&mut *self.tasks.lock().unwrap());
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        for task in tasks {
+            // `timeout` polls the task before checking the deadline.
+            runtime.block_on(async {
+                let _ = tokio::time::timeout(Duration::ZERO, task).await;
+            });
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! Markdown removal for `strip_markdown`.
+//!
+//! Some translation models mangle Markdown: they translate inside `**` pairs, drop or move
+//! markers, or render bullets as literal dashes in the target language. With
+//! `strip_markdown` enabled the source is reduced to plain text before it is sent. Code is
+//! left alone: fenced blocks and inline code spans pass through unchanged. The translation is
+//! shown as returned; no formatting is added back.
+
+/// `text` without emphasis markers, heading markers, blockquote markers and list bullets.
+/// Lines inside ```` ``` ```` fences, including the fences, and `` `inline code` `` are kept
+/// as they are.
+pub(crate) fn strip_markdown(text: &str) -> String {
+    let mut in_fence = false;
+    let mut lines = Vec::new();
+    for line in text.split('\n') {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            lines.push(line.to_string());
+        } else if in_fence {
+            lines.push(line.to_string());
+        } else {
+            lines.push(strip_line(line));
+        }
+    }
+    lines.join("\n")
+}
+
+/// One line outside a code fence: block markers first, then inline emphasis.
+fn strip_line(line: &str) -> String {
+    let content = line.trim_start();
+    let indent = &line[..line.len() - content.len()];
+    let content = strip_blockquote(content);
+    let content = strip_heading(content);
+    let content = strip_bullet(content);
+    format!("{indent}{}", strip_emphasis(content))
+}
+
+/// Drop any number of leading `>` markers, nested or not.
+fn strip_blockquote(mut content: &str) -> &str {
+    while let Some(rest) = content.strip_prefix('>') {
+        content = rest.trim_start();
+    }
+    content
+}
+
+/// Drop an ATX heading marker (`#` to `######` followed by a space) and the closing `#`s
+/// some writers add.
+fn strip_heading(content: &str) -> &str {
+    let level = content.chars().take_while(|&c| c == '#').count();
+    if !(1..=6).contains(&level) {
+        return content;
+    }
+    let rest = &content[level..];
+    if !rest.is_empty() && !rest.starts_with(' ') {
+        // `#hashtag` or `#123` is not a heading.
+        return content;
+    }
+    let rest = rest.trim();
+    let without_closing = rest.trim_end_matches('#');
+    if without_closing.is_empty() || without_closing.ends_with(' ') {
+        without_closing.trim_end()
+    } else {
+        rest
+    }
+}
+
+/// Drop a list bullet (`- `, `* ` or `+ `). Numbered lists keep their numbers, which
+/// translate fine.
+fn strip_bullet(content: &str) -> &str {
+    ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|bullet| content.strip_prefix(bullet))
+        .map_or(content, str::trim_start)
+}
+
+/// Remove `*` and `_` delimiter runs outside inline code spans. A run with whitespace on
+/// both sides (`2 * 3`) is not emphasis and stays, as does a `_` between two letters or
+/// digits (`snake_case`).
+fn strip_emphasis(content: &str) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut out = String::with_capacity(content.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '`' {
+            let run = run_length(&chars, i);
+            if let Some(close) = closing_backticks(&chars, i + run, run) {
+                out.extend(&chars[i..close + run]);
+                i = close + run;
+            } else {
+                // An unmatched backtick run is literal text.
+                out.extend(&chars[i..i + run]);
+                i += run;
+            }
+            continue;
+        }
+        if c == '*' || c == '_' {
+            let run = run_length(&chars, i);
+            let before = i.checked_sub(1).map(|j| chars[j]);
+            let after = chars.get(i + run).copied();
+            let is_space = |c: Option<char>| c.is_none_or(char::is_whitespace);
+            let is_word = |c: Option<char>| c.is_some_and(char::is_alphanumeric);
+            let literal = (is_space(before) && is_space(after))
+                || (c == '_' && is_word(before) && is_word(after));
+            if literal {
+                out.extend(&chars[i..i + run]);
+            }
+            i += run;
+            continue;
+        }
+        out.push(c);
+        i += 1;
+    }
+    out
+}
+
+/// Length of the run of `chars[start]` starting at `start`.
+fn run_length(chars: &[char], start: usize) -> usize {
+    chars[start..]
+        .iter()
+        .take_while(|&&c| c == chars[start])
+        .count()
+}
+
+/// Start of the first backtick run of exactly `len` at or after `from`.
+fn closing_backticks(chars: &[char], from: usize, len: usize) -> Option<usize> {
+    let mut i = from;
+    while i < chars.len() {
+        if chars[i] == '`' {
+            let run = run_length(chars, i);
+            if run == len {
+                return Some(i);
+            }
+            i += run;
+        } else {
+            i += 1;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn emphasis_markers_are_removed() {
+        assert_eq!(
+            strip_markdown("This is **bold**, *italic* and __also bold__."),
+            "This is bold, italic and also bold."
+        );
+        assert_eq!(
+            strip_markdown("_leading_ and ***both***"),
+            "leading and both"
+        );
+    }
+
+    #[test]
+    fn nested_emphasis_is_removed() {
+        assert_eq!(
+            strip_markdown("**bold with *italic* inside**"),
+            "bold with italic inside"
+        );
+        assert_eq!(
+            strip_markdown("*italic with __bold__ inside*"),
+            "italic with bold inside"
+        );
+        assert_eq!(strip_markdown("**_both at once_**"), "both at once");
+    }
+
+    #[test]
+    fn literal_asterisks_and_underscores_stay() {
+        assert_eq!(strip_markdown("2 * 3 = 6"), "2 * 3 = 6");
+        assert_eq!(
+            strip_markdown("rename max_wait_ms to timeout_ms"),
+            "rename max_wait_ms to timeout_ms"
+        );
+        assert_eq!(strip_markdown("a _ b"), "a _ b");
+    }
+
+    #[test]
+    fn headings_lose_their_markers() {
+        assert_eq!(
+            strip_markdown("# Title\n## Section ##\n###### Deep"),
+            "Title\nSection\nDeep"
+        );
+        assert_eq!(strip_markdown("# **Bold** title"), "Bold title");
+        assert_eq!(strip_markdown("## C#"), "C#");
+    }
+
+    #[test]
+    fn non_headings_keep_their_hashes() {
+        assert_eq!(strip_markdown("#hashtag"), "#hashtag");
+        assert_eq!(strip_markdown("####### seven"), "####### seven");
+        assert_eq!(strip_markdown("see issue #42"), "see issue #42");
+    }
+
+    #[test]
+    fn blockquotes_lose_their_markers() {
+        assert_eq!(
+            strip_markdown("> quoted\n>> nested\n> > spaced"),
+            "quoted\nnested\nspaced"
+        );
+        assert_eq!(strip_markdown("> - quoted item"), "quoted item");
+    }
+
+    #[test]
+    fn list_bullets_are_removed() {
+        assert_eq!(
+            strip_markdown("- first\n* second\n+ third\n  - nested *item*"),
+            "first\nsecond\nthird\n  nested item"
+        );
+        assert_eq!(strip_markdown("1. numbered"), "1. numbered");
+        assert_eq!(strip_markdown("-5 degrees"), "-5 degrees");
+    }
+
+    #[test]
+    fn code_fences_are_kept_intact() {
+        let text = "Run **this**:\n```rust\n// # not a heading\nlet x = *y * 2;\n- not a bullet\n```\n- done";
+        assert_eq!(
+            strip_markdown(text),
+            "Run this:\n```rust\n// # not a heading\nlet x = *y * 2;\n- not a bullet\n```\ndone"
+        );
+    }
+
+    #[test]
+    fn unterminated_fence_keeps_the_rest() {
+        assert_eq!(strip_markdown("**a**\n```\n**b**"), "a\n```\n**b**");
+    }
+
+    #[test]
+    fn inline_code_is_kept_intact() {
+        assert_eq!(
+            strip_markdown("Call `foo(*args, **kwargs)` with **care**"),
+            "Call `foo(*args, **kwargs)` with care"
+        );
+        assert_eq!(
+            strip_markdown("``code with ` tick and _x_`` then _y_"),
+            "``code with ` tick and _x_`` then y"
+        );
+    }
+
+    #[test]
+    fn unmatched_backtick_is_literal() {
+        assert_eq!(strip_markdown("a ` b **c**"), "a ` b c");
+    }
+
+    #[test]
+    fn plain_text_is_unchanged() {
+        let text = "Nothing to strip here.\n\n  Indented line, with punctuation!";
+        assert_eq!(strip_markdown(text), text);
+    }
+}
@@ -0,0 +1,159 @@
+//! Coalesces redraw requests triggered by translation activity.
+//!
+//! Every translation result and draw tick may ask for a redraw. With many barriers (or fast
+//! providers) this can turn into a redraw storm, so requests that land inside a short window
+//! collapse into a single scheduled frame.
+//!
+//! The decision logic lives in [`RedrawCoalescer`], a small pure helper that can be unit-tested
+//! without a running TUI. [`RedrawScheduler`] is the shared handle used by the orchestrator and
+//! its background translation tasks.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use crate::tui::FrameRequester;
+
+/// Minimum spacing between translation-triggered frames.
+pub(super) const REDRAW_COALESCE_WINDOW: Duration = Duration::from_millis(50);
+
+/// Decides when (if at all) a redraw request should schedule a frame.
+#[derive(Debug)]
+pub(super) struct RedrawCoalescer {
+    window: Duration,
+    /// Time of the most recently scheduled frame; may be in the future for a deferred frame.
+    last_frame_at: Option<Instant>,
+}
+
+impl RedrawCoalescer {
+    pub(super) fn new(window: Duration) -> Self {
+        Self {
+            window,
+            last_frame_at: None,
+        }
+    }
+
+    /// Returns the delay after which a frame should be scheduled for a request made at `now`,
+    /// or `None` if an already-scheduled frame will cover it.
+    ///
+    /// At most one frame is scheduled per coalescing window. A request inside the window of the
+    /// previous frame is deferred to the end of that window rather than dropped, so the final
+    /// state is always drawn.
+    pub(super) fn schedule_delay(&mut self, now: Instant) -> Option<Duration> {
+        let Some(last) = self.last_frame_at else {
+            self.last_frame_at = Some(now);
+            return Some(Duration::ZERO);
+        };
+
+        if last > now {
+            // A deferred frame is already pending and will pick up this request.
+            return None;
+        }
+
+        let window_end = last.checked_add(self.window).unwrap_or(last);
+        if now >= window_end {
+            self.last_frame_at = Some(now);
+            Some(Duration::ZERO)
+        } else {
+            self.last_frame_at = Some(window_end);
+            Some(window_end.duration_since(now))
+        }
+    }
+}
+
+/// Shared, cloneable handle that routes redraw requests through a [`RedrawCoalescer`].
+#[derive(Clone, Debug)]
+pub(super) struct RedrawScheduler {
+    inner: Arc<Mutex<RedrawCoalescer>>,
+}
+
+impl Default for RedrawScheduler {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(RedrawCoalescer::new(REDRAW_COALESCE_WINDOW))),
+        }
+    }
+}
+
+impl RedrawScheduler {
+    /// Request a redraw, scheduling at most one frame per coalescing window.
+    pub(super) fn request(&self, frame_requester: &FrameRequester) {
+        let delay = match self.inner.lock() {
+            Ok(mut coalescer) => coalescer.schedule_delay(Instant::now()),
+            // A poisoned lock should never suppress redraws.
+            Err(_) => Some(Duration::ZERO),
+        };
+        match delay {
+            Some(delay) if delay.is_zero() => frame_requester.schedule_frame(),
+            Some(delay) => frame_requester.schedule_frame_in(delay),
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const WINDOW: Duration = Duration::from_millis(50);
+
+    #[test]
+    fn first_request_schedules_immediately() {
+        let t0 = Instant::now();
+        let mut coalescer = RedrawCoalescer::new(WINDOW);
+        assert_eq!(coalescer.schedule_delay(t0), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn burst_inside_window_schedules_single_deferred_frame() {
+        let t0 = Instant::now();
+        let mut coalescer = RedrawCoalescer::new(WINDOW);
+        assert_eq!(coalescer.schedule_delay(t0), Some(Duration::ZERO));
+
+        // The first request inside the window defers to the end of the window.
+        let t1 = t0 + Duration::from_millis(10);
+        assert_eq!(
+            coalescer.schedule_delay(t1),
+            Some(Duration::from_millis(40))
+        );
+
+        // Further requests before the deferred frame fires are coalesced away.
+        for ms in [11, 20, 49] {
+            assert_eq!(
+                coalescer.schedule_delay(t0 + Duration::from_millis(ms)),
+                None
+            );
+        }
+    }
+
+    #[test]
+    fn at_most_one_frame_per_window_under_sustained_load() {
+        let t0 = Instant::now();
+        let mut coalescer = RedrawCoalescer::new(WINDOW);
+
+        // Request a redraw every millisecond for 500ms and record when frames would fire.
+        let mut frames: Vec<Instant> = Vec::new();
+        for ms in 0..500 {
+            let now = t0 + Duration::from_millis(ms);
+            if let Some(delay) = coalescer.schedule_delay(now) {
+                frames.push(now + delay);
+            }
+        }
+
+        // Frames at 0, 50, ..., 500ms: one per window, with the last request deferred.
+        assert_eq!(frames.len(), 11);
+        for pair in frames.windows(2) {
+            assert!(pair[1].duration_since(pair[0]) >= WINDOW);
+        }
+    }
+
+    #[test]
+    fn request_after_window_schedules_immediately() {
+        let t0 = Instant::now();
+        let mut coalescer = RedrawCoalescer::new(WINDOW);
+        assert_eq!(coalescer.schedule_delay(t0), Some(Duration::ZERO));
+        assert_eq!(coalescer.schedule_delay(t0 + WINDOW), Some(Duration::ZERO));
+    }
+}
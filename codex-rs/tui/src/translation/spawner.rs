@@ -0,0 +1,26 @@
+//! Where background translation tasks run.
+//!
+//! The orchestrator hands every request to a [`Spawner`] rather than calling `tokio::spawn`
+//! directly, so tests can hold the tasks and run them when they choose.
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::pin::Pin;
+
+/// A background translation task.
+pub(super) type Task = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Runs background translation tasks.
+pub(super) trait Spawner: Debug + Send + Sync {
+    fn spawn(&self, task: Task);
+}
+
+/// Runs tasks on the current Tokio runtime.
+#[derive(Debug, Default)]
+pub(super) struct TokioSpawner;
+
+impl Spawner for TokioSpawner {
+    fn spawn(&self, task: Task) {
+        tokio::spawn(task);
+    }
+}
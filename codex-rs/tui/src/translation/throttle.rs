@@ -0,0 +1,127 @@
+//! Requests-per-minute cap shared by every provider (`requests_per_minute`).
+//!
+//! [`ProviderLimiter`](super::limiter::ProviderLimiter) only bounds concurrency, and a 429
+//! is handled after the quota is already spent. [`RequestThrottle`] spaces requests out
+//! before they are sent, so a run of quick turns stays within a free tier's per-minute
+//! quota. It is a token bucket holding a single token: a request that finds it empty waits
+//! for the next one rather than being dropped, and idle time never builds up a burst.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+#[derive(Debug)]
+pub(super) struct RequestThrottle {
+    /// Time between two requests.
+    interval: Duration,
+    /// When the next request may be sent; requests already waiting have moved it forward.
+    next_slot: Mutex<Option<Instant>>,
+}
+
+impl RequestThrottle {
+    pub(super) fn new(requests_per_minute: u32) -> Self {
+        Self {
+            // Zero would stop translation altogether.
+            interval: Duration::from_secs(60) / requests_per_minute.max(1),
+            next_slot: Mutex::new(None),
+        }
+    }
+
+    /// Claim the next free slot for a request made at `now` and return how long it has to
+    /// wait for it. Each call claims its own slot, so concurrent requests queue up.
+    pub(super) fn reserve(&self, now: Instant) -> Duration {
+        let mut next_slot = self
+            .next_slot
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let slot = next_slot.map_or(now, |next| next.max(now));
+        *next_slot = Some(slot + self.interval);
+        slot - now
+    }
+
+    /// Wait until a request may be sent.
+    pub(super) async fn wait(&self) {
+        let delay = self.reserve(Instant::now());
+        if !delay.is_zero() {
+            tracing::debug!("Translation request delayed {delay:?} by requests_per_minute");
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::Arc;
+
+    #[test]
+    fn requests_beyond_the_rate_wait_for_their_slot() {
+        let throttle = RequestThrottle::new(60);
+        let start = Instant::now();
+
+        let delays: Vec<Duration> = (0..3).map(|_| throttle.reserve(start)).collect();
+        assert_eq!(
+            delays,
+            vec![
+                Duration::ZERO,
+                Duration::from_secs(1),
+                Duration::from_secs(2)
+            ]
+        );
+
+        // Half a second later the queue has moved up by half a second.
+        assert_eq!(
+            throttle.reserve(start + Duration::from_millis(500)),
+            Duration::from_millis(2500)
+        );
+    }
+
+    #[test]
+    fn idle_time_does_not_allow_a_burst() {
+        let throttle = RequestThrottle::new(30);
+        let start = Instant::now();
+        assert_eq!(throttle.reserve(start), Duration::ZERO);
+
+        let later = start + Duration::from_secs(60);
+        assert_eq!(throttle.reserve(later), Duration::ZERO);
+        assert_eq!(throttle.reserve(later), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn zero_is_treated_as_one_per_minute() {
+        let throttle = RequestThrottle::new(0);
+        let start = Instant::now();
+        throttle.reserve(start);
+        assert_eq!(throttle.reserve(start), Duration::from_secs(60));
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn concurrent_requests_are_spaced_out() {
+        let throttle = Arc::new(RequestThrottle::new(120));
+        let start = Instant::now();
+        let tasks: Vec<_> = (0..3)
+            .map(|_| {
+                let throttle = Arc::clone(&throttle);
+                tokio::spawn(async move {
+                    throttle.wait().await;
+                    Instant::now() - start
+                })
+            })
+            .collect();
+
+        let mut sent_at = Vec::new();
+        for task in tasks {
+            sent_at.push(task.await.unwrap());
+        }
+        assert_eq!(
+            sent_at,
+            vec![
+                Duration::ZERO,
+                Duration::from_millis(500),
+                Duration::from_secs(1)
+            ]
+        );
+    }
+}
@@ -1,4 +1,14 @@
-/// Update action the CLI should perform after the TUI exits.
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_core::config::types::UpdateManager;
+
+/// npm package installed by every [`UpdateAction`].
+pub(crate) const PACKAGE_NAME: &str = "@echoflux537/codex";
+
+/// Update action the CLI should perform after the TUI exits, or that the update prompt
+/// performs itself ([`UpdateAction::SelfReplace`]).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UpdateAction {
     /// Update via `npm install -g @echoflux537/codex`.
@@ -7,26 +17,208 @@ pub enum UpdateAction {
     PnpmGlobalLatest,
     /// Update via `bun install -g @echoflux537/codex`.
     BunGlobalLatest,
+    /// Update via `yarn global add @echoflux537/codex`.
+    YarnGlobalLatest,
+    /// Update via `volta install @echoflux537/codex`.
+    VoltaInstallLatest,
+    /// Download the release binary and replace the running executable with it, for
+    /// installs no package manager owns. See [`crate::self_update`].
+    SelfReplace,
 }
 
 impl UpdateAction {
-    /// Returns the list of command-line arguments for invoking the update.
-    pub fn command_args(self) -> (&'static str, &'static [&'static str]) {
+    /// Returns the list of command-line arguments for invoking the update, or `None` for
+    /// [`UpdateAction::SelfReplace`], which runs no command.
+    pub fn command_args(self) -> Option<(&'static str, &'static [&'static str])> {
+        match self {
+            UpdateAction::NpmGlobalLatest => Some(("npm", &["install", "-g", PACKAGE_NAME])),
+            UpdateAction::PnpmGlobalLatest => Some(("pnpm", &["add", "-g", PACKAGE_NAME])),
+            UpdateAction::BunGlobalLatest => Some(("bun", &["install", "-g", PACKAGE_NAME])),
+            UpdateAction::YarnGlobalLatest => Some(("yarn", &["global", "add", PACKAGE_NAME])),
+            UpdateAction::VoltaInstallLatest => Some(("volta", &["install", PACKAGE_NAME])),
+            UpdateAction::SelfReplace => None,
+        }
+    }
+
+    /// Launcher family this action belongs to. pnpm, yarn and volta installs run through the
+    /// npm launcher; a self-replace has none.
+    fn package_manager(self) -> Option<PackageManager> {
         match self {
-            UpdateAction::NpmGlobalLatest => ("npm", &["install", "-g", "@echoflux537/codex"]),
-            UpdateAction::PnpmGlobalLatest => ("pnpm", &["add", "-g", "@echoflux537/codex"]),
-            UpdateAction::BunGlobalLatest => ("bun", &["install", "-g", "@echoflux537/codex"]),
+            UpdateAction::NpmGlobalLatest
+            | UpdateAction::PnpmGlobalLatest
+            | UpdateAction::YarnGlobalLatest
+            | UpdateAction::VoltaInstallLatest => Some(PackageManager::Npm),
+            UpdateAction::BunGlobalLatest => Some(PackageManager::Bun),
+            UpdateAction::SelfReplace => None,
         }
     }
 
     /// Returns string representation of the command-line arguments for invoking the update.
-    pub fn command_str(self) -> String {
-        let (command, args) = self.command_args();
-        shlex::try_join(std::iter::once(command).chain(args.iter().copied()))
-            .unwrap_or_else(|_| format!("{command} {}", args.join(" ")))
+    pub fn command_str(self) -> Option<String> {
+        let (command, args) = self.command_args()?;
+        Some(
+            shlex::try_join(std::iter::once(command).chain(args.iter().copied()))
+                .unwrap_or_else(|_| format!("{command} {}", args.join(" "))),
+        )
+    }
+
+    /// What the update does, for the update prompt: the command, or a description of the
+    /// self-replace.
+    pub(crate) fn description(self) -> String {
+        match self.command_str() {
+            Some(command) => format!("runs `{command}`"),
+            None => "downloads the release binary and replaces this one".to_string(),
+        }
+    }
+}
+
+impl From<UpdateManager> for UpdateAction {
+    fn from(manager: UpdateManager) -> Self {
+        match manager {
+            UpdateManager::Npm => UpdateAction::NpmGlobalLatest,
+            UpdateManager::Pnpm => UpdateAction::PnpmGlobalLatest,
+            UpdateManager::Bun => UpdateAction::BunGlobalLatest,
+            UpdateManager::Yarn => UpdateAction::YarnGlobalLatest,
+            UpdateManager::Volta => UpdateAction::VoltaInstallLatest,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PackageManager {
+    Npm,
+    Bun,
+}
+
+impl PackageManager {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npm",
+            PackageManager::Bun => "bun",
+        }
     }
 }
 
+/// Where the running binary appears to have been installed from.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct InstallSource {
+    /// Launcher that started codex, from `CODEX_MANAGED_BY_NPM` / `CODEX_MANAGED_BY_BUN`.
+    pub(crate) manager: Option<PackageManager>,
+    /// npm package containing the running binary, when it lives under `node_modules`.
+    pub(crate) package: Option<String>,
+}
+
+impl InstallSource {
+    pub(crate) fn detect() -> Self {
+        let managed_by_npm = std::env::var_os("CODEX_MANAGED_BY_NPM").is_some();
+        let managed_by_bun = std::env::var_os("CODEX_MANAGED_BY_BUN").is_some();
+        let exe = std::env::current_exe().ok();
+        Self::from_parts(managed_by_npm, managed_by_bun, exe.as_deref())
+    }
+
+    fn from_parts(managed_by_npm: bool, managed_by_bun: bool, exe: Option<&Path>) -> Self {
+        let manager = if managed_by_npm {
+            Some(PackageManager::Npm)
+        } else if managed_by_bun {
+            Some(PackageManager::Bun)
+        } else {
+            None
+        };
+        Self {
+            manager,
+            package: exe.and_then(package_containing),
+        }
+    }
+}
+
+/// Tool other than npm that owns the global install the running binary lives in.
+///
+/// Both run the npm launcher, so `CODEX_MANAGED_BY_NPM` is set for them too; updating with
+/// npm would leave a second, parallel install.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GlobalInstall {
+    /// `yarn global add`, e.g. `~/.config/yarn/global/node_modules/...`.
+    Yarn,
+    /// A volta-managed package, e.g. `~/.volta/tools/image/packages/...`.
+    Volta,
+}
+
+impl GlobalInstall {
+    /// Recognize the install layout of `exe`. `volta_home` is `VOLTA_HOME`, for volta
+    /// installs outside the default `.volta` directory.
+    fn detect(exe: &Path, volta_home: Option<&Path>) -> Option<Self> {
+        let components = normal_components(exe);
+        if volta_home.is_some_and(|home| exe.starts_with(home)) || components.contains(&".volta") {
+            return Some(GlobalInstall::Volta);
+        }
+        let global_modules = components
+            .windows(2)
+            .position(|pair| pair == ["global", "node_modules"])?;
+        components[..global_modules]
+            .iter()
+            .any(|part| part.eq_ignore_ascii_case("yarn") || part.eq_ignore_ascii_case(".yarn"))
+            .then_some(GlobalInstall::Yarn)
+    }
+}
+
+/// The named components of `path`, skipping roots and `.`/`..`.
+fn normal_components(path: &Path) -> Vec<&str> {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => part.to_str(),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Name of the package under the last `node_modules` directory in `path`, e.g.
+/// `@scope/name` for `.../node_modules/@scope/name/vendor/codex`.
+fn package_containing(path: &Path) -> Option<String> {
+    let components = normal_components(path);
+    let idx = components
+        .iter()
+        .rposition(|part| *part == "node_modules")?;
+    let name = *components.get(idx + 1)?;
+    if name.starts_with('@') {
+        let rest = components.get(idx + 2)?;
+        Some(format!("{name}/{rest}"))
+    } else {
+        Some(name.to_string())
+    }
+}
+
+/// Problems that make running `action` risky for a binary installed from `source`.
+///
+/// An empty result means the action matches the detected install.
+pub(crate) fn verify_update_action(action: UpdateAction, source: &InstallSource) -> Vec<String> {
+    // Replaces exactly the running binary.
+    let (Some(action_manager), Some((command, _))) =
+        (action.package_manager(), action.command_args())
+    else {
+        return Vec::new();
+    };
+    let mut warnings = Vec::new();
+    if let Some(package) = source.package.as_deref()
+        && package != PACKAGE_NAME
+    {
+        warnings.push(format!(
+            "This codex binary belongs to `{package}`, but the update installs `{PACKAGE_NAME}`."
+        ));
+    }
+    match source.manager {
+        Some(manager) if manager != action_manager => warnings.push(format!(
+            "codex was launched by {}, but the update runs {command}.",
+            manager.as_str(),
+        )),
+        Some(_) => {}
+        None => warnings.push(
+            "Could not detect how codex was installed; the update may not replace this binary."
+                .to_string(),
+        ),
+    }
+    warnings
+}
+
 fn command_is_available(command: &str) -> bool {
     let Some(path_var) = std::env::var_os("PATH") else {
         return false;
@@ -77,21 +269,65 @@ fn command_is_available(command: &str) -> bool {
     false
 }
 
-#[cfg(not(debug_assertions))]
-pub(crate) fn get_update_actions() -> Vec<UpdateAction> {
+/// Update actions to offer, with `preferred` (`tui.preferred_update_manager`) first when its
+/// command is installed.
+pub(crate) fn get_update_actions(preferred: Option<UpdateManager>) -> Vec<UpdateAction> {
     let managed_by_npm = std::env::var_os("CODEX_MANAGED_BY_NPM").is_some();
     let managed_by_bun = std::env::var_os("CODEX_MANAGED_BY_BUN").is_some();
 
     let pnpm_available = command_is_available("pnpm");
-    detect_update_actions(managed_by_npm, managed_by_bun, pnpm_available)
+    let volta_home = std::env::var_os("VOLTA_HOME").map(PathBuf::from);
+    let exe = std::env::current_exe().ok();
+    let global_install = exe
+        .as_deref()
+        .and_then(|exe| GlobalInstall::detect(exe, volta_home.as_deref()));
+    let self_replace_available = exe
+        .as_deref()
+        .is_some_and(crate::self_update::can_self_replace);
+    let actions = detect_update_actions(
+        managed_by_npm,
+        managed_by_bun,
+        pnpm_available,
+        global_install,
+        self_replace_available,
+    );
+    prefer_update_action(actions, preferred, command_is_available)
+}
+
+/// Move the action for `preferred` to the front of `actions`. A manager whose command is
+/// not installed is ignored, leaving the detected order.
+fn prefer_update_action(
+    mut actions: Vec<UpdateAction>,
+    preferred: Option<UpdateManager>,
+    is_available: impl Fn(&str) -> bool,
+) -> Vec<UpdateAction> {
+    let Some(preferred) = preferred.map(UpdateAction::from) else {
+        return actions;
+    };
+    if !preferred
+        .command_args()
+        .is_some_and(|(command, _)| is_available(command))
+    {
+        return actions;
+    }
+    actions.retain(|action| *action != preferred);
+    actions.insert(0, preferred);
+    actions
 }
 
-#[cfg(any(not(debug_assertions), test))]
 fn detect_update_actions(
     managed_by_npm: bool,
     managed_by_bun: bool,
     pnpm_available: bool,
+    global_install: Option<GlobalInstall>,
+    self_replace_available: bool,
 ) -> Vec<UpdateAction> {
+    // The install layout is more specific than the launcher variables.
+    match global_install {
+        Some(GlobalInstall::Volta) => return vec![UpdateAction::VoltaInstallLatest],
+        Some(GlobalInstall::Yarn) => return vec![UpdateAction::YarnGlobalLatest],
+        None => {}
+    }
     if managed_by_npm {
         let mut actions = vec![UpdateAction::NpmGlobalLatest];
         if pnpm_available {
@@ -100,6 +336,9 @@ fn detect_update_actions(
         actions
     } else if managed_by_bun {
         vec![UpdateAction::BunGlobalLatest]
+    } else if self_replace_available {
+        // No package manager owns this binary; replacing it in place avoids a second install.
+        vec![UpdateAction::SelfReplace, UpdateAction::NpmGlobalLatest]
     } else {
         // Default to npm if no specific manager is detected
         vec![UpdateAction::NpmGlobalLatest]
@@ -109,22 +348,23 @@ fn detect_update_actions(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use pretty_assertions::assert_eq;
 
     #[test]
     fn detects_update_action_without_env_mutation() {
         // Default to npm when no manager is detected
         assert_eq!(
-            detect_update_actions(false, false, false),
+            detect_update_actions(false, false, false, None, false),
             vec![UpdateAction::NpmGlobalLatest]
         );
         // npm managed
         assert_eq!(
-            detect_update_actions(true, false, false),
+            detect_update_actions(true, false, false, None, false),
             vec![UpdateAction::NpmGlobalLatest]
         );
         // When CODEX_MANAGED_BY_NPM is set, enable pnpm if available.
         assert_eq!(
-            detect_update_actions(true, false, true),
+            detect_update_actions(true, false, true, None, false),
             vec![
                 UpdateAction::NpmGlobalLatest,
                 UpdateAction::PnpmGlobalLatest
@@ -132,16 +372,214 @@ mod tests {
         );
         // bun managed
         assert_eq!(
-            detect_update_actions(false, true, false),
+            detect_update_actions(false, true, false, None, false),
             vec![UpdateAction::BunGlobalLatest]
         );
         // npm takes precedence over bun
         assert_eq!(
-            detect_update_actions(true, true, true),
+            detect_update_actions(true, true, true, None, false),
             vec![
                 UpdateAction::NpmGlobalLatest,
                 UpdateAction::PnpmGlobalLatest
             ]
         );
     }
+
+    #[test]
+    fn yarn_and_volta_installs_are_updated_with_their_own_tool() {
+        assert_eq!(
+            GlobalInstall::detect(
+                Path::new(
+                    "/home/dev/.config/yarn/global/node_modules/@echoflux537/codex/bin/codex"
+                ),
+                None
+            ),
+            Some(GlobalInstall::Yarn)
+        );
+        assert_eq!(
+            GlobalInstall::detect(
+                Path::new(
+                    "/home/dev/.volta/tools/image/packages/@echoflux537/codex/lib/node_modules/@echoflux537/codex/bin/codex"
+                ),
+                None
+            ),
+            Some(GlobalInstall::Volta)
+        );
+        assert_eq!(
+            GlobalInstall::detect(
+                Path::new("/opt/volta/tools/image/packages/@echoflux537/codex/bin/codex"),
+                Some(Path::new("/opt/volta"))
+            ),
+            Some(GlobalInstall::Volta)
+        );
+        assert_eq!(
+            GlobalInstall::detect(
+                Path::new("/usr/lib/node_modules/@echoflux537/codex/bin/codex"),
+                None
+            ),
+            None
+        );
+
+        // The npm launcher sets CODEX_MANAGED_BY_NPM for both, so the layout wins.
+        assert_eq!(
+            detect_update_actions(true, false, true, Some(GlobalInstall::Yarn), false),
+            vec![UpdateAction::YarnGlobalLatest]
+        );
+        assert_eq!(
+            detect_update_actions(true, false, true, Some(GlobalInstall::Volta), false),
+            vec![UpdateAction::VoltaInstallLatest]
+        );
+    }
+
+    #[test]
+    fn update_commands_render_per_tool() {
+        assert_eq!(
+            UpdateAction::YarnGlobalLatest.command_str().as_deref(),
+            Some("yarn global add @echoflux537/codex")
+        );
+        assert_eq!(
+            UpdateAction::VoltaInstallLatest.command_str().as_deref(),
+            Some("volta install @echoflux537/codex")
+        );
+        assert_eq!(
+            UpdateAction::NpmGlobalLatest.command_str().as_deref(),
+            Some("npm install -g @echoflux537/codex")
+        );
+        assert_eq!(UpdateAction::SelfReplace.command_str(), None);
+    }
+
+    #[test]
+    fn self_replace_is_offered_only_without_a_package_manager() {
+        assert_eq!(
+            detect_update_actions(false, false, false, None, true),
+            vec![UpdateAction::SelfReplace, UpdateAction::NpmGlobalLatest]
+        );
+        assert_eq!(
+            detect_update_actions(true, false, false, None, true),
+            vec![UpdateAction::NpmGlobalLatest]
+        );
+        assert_eq!(
+            detect_update_actions(false, true, false, None, true),
+            vec![UpdateAction::BunGlobalLatest]
+        );
+        assert_eq!(
+            detect_update_actions(false, false, false, Some(GlobalInstall::Yarn), true),
+            vec![UpdateAction::YarnGlobalLatest]
+        );
+
+        // It replaces exactly the running binary, so there is nothing to confirm.
+        let unknown = InstallSource::from_parts(false, false, None);
+        assert!(verify_update_action(UpdateAction::SelfReplace, &unknown).is_empty());
+    }
+
+    #[test]
+    fn preferred_manager_is_offered_first() {
+        let detected = detect_update_actions(true, false, true, None, false);
+        assert_eq!(
+            prefer_update_action(detected.clone(), Some(UpdateManager::Pnpm), |_| true),
+            vec![
+                UpdateAction::PnpmGlobalLatest,
+                UpdateAction::NpmGlobalLatest
+            ]
+        );
+        // A preferred manager that detection did not offer is added in front.
+        assert_eq!(
+            prefer_update_action(detected.clone(), Some(UpdateManager::Bun), |_| true),
+            vec![
+                UpdateAction::BunGlobalLatest,
+                UpdateAction::NpmGlobalLatest,
+                UpdateAction::PnpmGlobalLatest
+            ]
+        );
+        assert_eq!(
+            prefer_update_action(detected.clone(), None, |_| true),
+            detected
+        );
+    }
+
+    #[test]
+    fn yarn_and_volta_can_be_preferred() {
+        let detected = detect_update_actions(true, false, false, None, false);
+        assert_eq!(
+            prefer_update_action(detected.clone(), Some(UpdateManager::Yarn), |_| true),
+            vec![
+                UpdateAction::YarnGlobalLatest,
+                UpdateAction::NpmGlobalLatest
+            ]
+        );
+        assert_eq!(
+            prefer_update_action(detected, Some(UpdateManager::Volta), |_| true),
+            vec![
+                UpdateAction::VoltaInstallLatest,
+                UpdateAction::NpmGlobalLatest
+            ]
+        );
+    }
+
+    #[test]
+    fn preferred_manager_is_ignored_when_not_installed() {
+        let detected = detect_update_actions(true, false, false, None, false);
+        assert_eq!(
+            prefer_update_action(detected.clone(), Some(UpdateManager::Pnpm), |command| {
+                command != "pnpm"
+            }),
+            detected
+        );
+    }
+
+    #[test]
+    fn package_is_read_from_node_modules_path() {
+        assert_eq!(
+            package_containing(Path::new(
+                "/usr/lib/node_modules/@echoflux537/codex/vendor/x86_64-unknown-linux-musl/codex/codex"
+            )),
+            Some(PACKAGE_NAME.to_string())
+        );
+        assert_eq!(
+            package_containing(Path::new("/opt/node_modules/codex-fork/bin/codex")),
+            Some("codex-fork".to_string())
+        );
+        assert_eq!(package_containing(Path::new("/usr/local/bin/codex")), None);
+    }
+
+    #[test]
+    fn matching_install_has_no_warnings() {
+        let source = InstallSource::from_parts(
+            true,
+            false,
+            Some(Path::new(
+                "/usr/lib/node_modules/@echoflux537/codex/bin/codex",
+            )),
+        );
+        assert!(verify_update_action(UpdateAction::NpmGlobalLatest, &source).is_empty());
+        assert!(verify_update_action(UpdateAction::PnpmGlobalLatest, &source).is_empty());
+    }
+
+    #[test]
+    fn mismatched_install_surfaces_warnings() {
+        let other_package = InstallSource::from_parts(
+            true,
+            false,
+            Some(Path::new("/usr/lib/node_modules/@openai/codex/bin/codex")),
+        );
+        assert_eq!(
+            verify_update_action(UpdateAction::NpmGlobalLatest, &other_package),
+            vec![
+                "This codex binary belongs to `@openai/codex`, but the update installs `@echoflux537/codex`."
+                    .to_string()
+            ]
+        );
+
+        let bun = InstallSource::from_parts(false, true, None);
+        assert_eq!(
+            verify_update_action(UpdateAction::NpmGlobalLatest, &bun),
+            vec!["codex was launched by bun, but the update runs npm.".to_string()]
+        );
+
+        let unknown = InstallSource::from_parts(false, false, None);
+        assert_eq!(
+            verify_update_action(UpdateAction::NpmGlobalLatest, &unknown).len(),
+            1
+        );
+    }
 }
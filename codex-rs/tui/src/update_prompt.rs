@@ -1,5 +1,5 @@
-#![cfg(not(debug_assertions))]
-
+use crate::color_mode::ColorMode;
+use crate::color_mode::strip_colors;
 use crate::history_cell::padded_emoji;
 use crate::key_hint;
 use crate::render::Insets;
@@ -7,11 +7,17 @@ use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableExt as _;
 use crate::selection_list::selection_option_row;
+use crate::self_update;
+use crate::self_update::Progress;
 use crate::tui::FrameRequester;
 use crate::tui::Tui;
 use crate::tui::TuiEvent;
+use crate::update_action::InstallSource;
 use crate::update_action::UpdateAction;
+use crate::update_action::verify_update_action;
 use crate::updates;
+use chrono::DateTime;
+use chrono::Utc;
 use codex_core::config::Config;
 use color_eyre::Result;
 use crossterm::event::KeyCode;
@@ -25,8 +31,12 @@ use ratatui::style::Stylize as _;
 use ratatui::text::Line;
 use ratatui::widgets::Clear;
 use ratatui::widgets::WidgetRef;
+use tokio::sync::mpsc::UnboundedSender;
 use tokio_stream::StreamExt;
 
+/// Maximum number of changelog lines shown inline before pointing at the release notes.
+const MAX_CHANGELOG_LINES: usize = 8;
+
 pub(crate) enum UpdatePromptOutcome {
     Continue,
     RunUpdate(UpdateAction),
@@ -39,29 +49,44 @@ pub(crate) async fn run_update_prompt_if_needed(
     let Some(latest_version) = updates::get_upgrade_version_for_popup(config) else {
         return Ok(UpdatePromptOutcome::Continue);
     };
-    let update_actions = crate::update_action::get_update_actions();
-    if update_actions.is_empty() {
+    let Some(screen) = UpdatePromptScreen::new(
+        tui.frame_requester(),
+        latest_version,
+        crate::update_action::get_update_actions(config.tui_preferred_update_manager),
+    ) else {
         return Ok(UpdatePromptOutcome::Continue);
     };
-
-    let mut screen = UpdatePromptScreen::new(
-        tui.frame_requester(),
-        latest_version.clone(),
-        update_actions,
-    );
+    let mut screen = screen
+        .with_changelog(updates::cached_release_notes(config))
+        .with_published_at(updates::cached_published_at(config))
+        .with_install_source(InstallSource::detect())
+        .with_color_mode(ColorMode::detect());
     tui.draw(u16::MAX, |frame| {
         frame.render_widget_ref(&screen, frame.area());
     })?;
 
     let events = tui.event_stream();
     tokio::pin!(events);
+    let (self_update_tx, mut self_update_rx) = tokio::sync::mpsc::unbounded_channel();
 
     while !screen.is_done() {
         if let Some(event) = events.next().await {
             match event {
-                TuiEvent::Key(key_event) => screen.handle_key(key_event),
+                TuiEvent::Key(key_event) => {
+                    screen.handle_key(key_event);
+                    if screen.take_self_update_request() {
+                        spawn_self_update(
+                            screen.latest_version().to_string(),
+                            tui.frame_requester(),
+                            self_update_tx.clone(),
+                        );
+                    }
+                }
                 TuiEvent::Paste(_) => {}
                 TuiEvent::Draw => {
+                    while let Ok(state) = self_update_rx.try_recv() {
+                        screen.set_self_update(state);
+                    }
                     tui.draw(u16::MAX, |frame| {
                         frame.render_widget_ref(&screen, frame.area());
                     })?;
@@ -73,6 +98,10 @@ pub(crate) async fn run_update_prompt_if_needed(
     }
 
     match screen.selection() {
+        // The binary was already replaced (or left alone) while the prompt was open.
+        Some(UpdateSelection::UpdateNow(UpdateAction::SelfReplace)) => {
+            Ok(UpdatePromptOutcome::Continue)
+        }
         Some(UpdateSelection::UpdateNow(action)) => {
             tui.terminal.clear()?;
             Ok(UpdatePromptOutcome::RunUpdate(action))
@@ -87,6 +116,33 @@ pub(crate) async fn run_update_prompt_if_needed(
     }
 }
 
+/// Replace the running binary in the background, reporting each stage to the prompt.
+fn spawn_self_update(
+    version: String,
+    frame_requester: FrameRequester,
+    tx: UnboundedSender<SelfUpdateState>,
+) {
+    tokio::spawn(async move {
+        let report_tx = tx.clone();
+        let report_frame = frame_requester.clone();
+        let report = move |progress| {
+            let _ = report_tx.send(SelfUpdateState::Running(progress));
+            report_frame.schedule_frame();
+        };
+        let result = match std::env::current_exe() {
+            Ok(exe) => self_update::self_update(&version, &exe, report)
+                .await
+                .map_err(|err| format!("{err:#}")),
+            Err(err) => Err(format!("could not locate the running binary: {err}")),
+        };
+        if let Err(err) = &result {
+            tracing::error!("self-update to {version} failed: {err}");
+        }
+        let _ = tx.send(SelfUpdateState::Finished(result));
+        frame_requester.schedule_frame();
+    });
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum UpdateSelection {
     UpdateNow(UpdateAction),
@@ -101,41 +157,163 @@ struct UpdatePromptScreen {
     options: Vec<UpdateSelection>,
     highlighted_idx: usize,
     selection: Option<UpdateSelection>,
+    /// Changelog lines for the latest release, already trimmed for display.
+    changelog: Vec<String>,
+    /// When the offered version was published, from the cached registry metadata.
+    published_at: Option<DateTime<Utc>>,
+    /// Detected origin of the running binary, checked before running an update.
+    /// `None` skips the check.
+    install_source: Option<InstallSource>,
+    /// Update awaiting a second confirmation because it doesn't match the install source.
+    pending_confirmation: Option<PendingConfirmation>,
+    /// Self-update started from the prompt, shown in place of the options.
+    self_update: Option<SelfUpdateState>,
+    /// The self-update was selected and still has to be spawned by the caller.
+    self_update_requested: bool,
+    /// Whether colors are drawn or replaced with text attributes.
+    color_mode: ColorMode,
+}
+
+/// Stage of a self-update started from the prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SelfUpdateState {
+    Running(Progress),
+    /// `Err` holds the reason; the installed binary is unchanged.
+    Finished(Result<(), String>),
+}
+
+/// An update action whose install source check failed.
+struct PendingConfirmation {
+    action: UpdateAction,
+    warnings: Vec<String>,
 }
 
 impl UpdatePromptScreen {
+    /// Builds the prompt, or returns `None` when there is no way to update
+    /// (so the caller skips the prompt entirely). Duplicate actions are
+    /// collapsed, keeping the first occurrence.
     fn new(
         request_frame: FrameRequester,
         latest_version: String,
         update_actions: Vec<UpdateAction>,
-    ) -> Self {
-        let mut options: Vec<UpdateSelection> = update_actions
-            .into_iter()
-            .map(UpdateSelection::UpdateNow)
-            .collect();
+    ) -> Option<Self> {
+        let mut options: Vec<UpdateSelection> = Vec::with_capacity(update_actions.len() + 2);
+        for action in update_actions {
+            let option = UpdateSelection::UpdateNow(action);
+            if !options.contains(&option) {
+                options.push(option);
+            }
+        }
+        if options.is_empty() {
+            return None;
+        }
         options.push(UpdateSelection::NotNow);
         options.push(UpdateSelection::DontRemind);
 
-        Self {
+        Some(Self {
             request_frame,
             latest_version,
             current_version: env!("CARGO_PKG_VERSION").to_string(),
             options,
             highlighted_idx: 0,
             selection: None,
+            changelog: Vec::new(),
+            published_at: None,
+            install_source: None,
+            pending_confirmation: None,
+            self_update: None,
+            self_update_requested: false,
+            color_mode: ColorMode::default(),
+        })
+    }
+
+    /// Check update actions against `install_source` before running them.
+    fn with_install_source(mut self, install_source: InstallSource) -> Self {
+        self.install_source = Some(install_source);
+        self
+    }
+
+    /// Draw with `color_mode`, normally [`ColorMode::detect`].
+    fn with_color_mode(mut self, color_mode: ColorMode) -> Self {
+        self.color_mode = color_mode;
+        self
+    }
+
+    /// Show `release_notes` inline; without notes only the releases link is shown.
+    fn with_changelog(mut self, release_notes: Option<String>) -> Self {
+        self.changelog = release_notes
+            .as_deref()
+            .map(changelog_lines)
+            .unwrap_or_default();
+        self
+    }
+
+    /// Show when the offered version was published.
+    fn with_published_at(mut self, published_at: Option<DateTime<Utc>>) -> Self {
+        self.published_at = published_at;
+        self
+    }
+
+    /// "0.1.0 via npm" for the running binary.
+    fn installed_description(&self) -> String {
+        let method = match self
+            .install_source
+            .as_ref()
+            .and_then(|source| source.manager)
+        {
+            Some(manager) => format!("via {}", manager.as_str()),
+            None => "(install method unknown)".to_string(),
+        };
+        format!("{} {method}", self.current_version)
+    }
+
+    /// "0.2.0 from the latest channel, published 2025-06-01" for the offered version.
+    fn offered_description(&self) -> String {
+        let mut description = format!(
+            "{} from the {} channel",
+            self.latest_version,
+            updates::UPDATE_CHANNEL
+        );
+        if let Some(published_at) = self.published_at {
+            description.push_str(&format!(", published {}", published_at.format("%Y-%m-%d")));
         }
+        description
     }
 
     fn handle_key(&mut self, key_event: KeyEvent) {
         if key_event.kind == KeyEventKind::Release {
             return;
         }
-        if key_event.modifiers.contains(KeyModifiers::CONTROL)
-            && matches!(key_event.code, KeyCode::Char('c') | KeyCode::Char('d'))
-        {
+        let ctrl_c = key_event.modifiers.contains(KeyModifiers::CONTROL)
+            && matches!(key_event.code, KeyCode::Char('c') | KeyCode::Char('d'));
+        if let Some(state) = self.self_update.as_ref() {
+            // Keys are ignored until the swap is done so the prompt never exits mid-install.
+            if matches!(state, SelfUpdateState::Finished(_))
+                && (ctrl_c || matches!(key_event.code, KeyCode::Enter | KeyCode::Esc))
+            {
+                self.selection = Some(UpdateSelection::UpdateNow(UpdateAction::SelfReplace));
+                self.request_frame.schedule_frame();
+            }
+            return;
+        }
+        if ctrl_c {
             self.select(UpdateSelection::NotNow);
             return;
         }
+        if let Some(pending) = self.pending_confirmation.as_ref() {
+            match key_event.code {
+                KeyCode::Enter | KeyCode::Char('y') => {
+                    self.selection = Some(UpdateSelection::UpdateNow(pending.action));
+                    self.request_frame.schedule_frame();
+                }
+                KeyCode::Esc | KeyCode::Char('n') => {
+                    self.pending_confirmation = None;
+                    self.request_frame.schedule_frame();
+                }
+                _ => {}
+            }
+            return;
+        }
         match key_event.code {
             KeyCode::Up | KeyCode::Char('k') => self.highlight_prev(),
             KeyCode::Down | KeyCode::Char('j') => self.highlight_next(),
@@ -188,10 +366,39 @@ impl UpdatePromptScreen {
         if let Some(idx) = self.options.iter().position(|opt| *opt == selection) {
             self.highlighted_idx = idx;
         }
+        if let UpdateSelection::UpdateNow(action) = selection
+            && let Some(install_source) = self.install_source.as_ref()
+        {
+            let warnings = verify_update_action(action, install_source);
+            if !warnings.is_empty() {
+                self.pending_confirmation = Some(PendingConfirmation { action, warnings });
+                self.request_frame.schedule_frame();
+                return;
+            }
+        }
+        if selection == UpdateSelection::UpdateNow(UpdateAction::SelfReplace) {
+            self.self_update = Some(SelfUpdateState::Running(Progress::Downloading {
+                received: 0,
+                total: None,
+            }));
+            self.self_update_requested = true;
+            self.request_frame.schedule_frame();
+            return;
+        }
         self.selection = Some(selection);
         self.request_frame.schedule_frame();
     }
 
+    /// Whether a self-update was just selected; the caller spawns it once.
+    fn take_self_update_request(&mut self) -> bool {
+        std::mem::take(&mut self.self_update_requested)
+    }
+
+    fn set_self_update(&mut self, state: SelfUpdateState) {
+        self.self_update = Some(state);
+        self.request_frame.schedule_frame();
+    }
+
     fn is_done(&self) -> bool {
         self.selection.is_some()
     }
@@ -205,11 +412,27 @@ impl UpdatePromptScreen {
     }
 }
 
+/// Non-empty lines of a release body, with Markdown heading markers removed and the
+/// list truncated to [`MAX_CHANGELOG_LINES`].
+fn changelog_lines(release_notes: &str) -> Vec<String> {
+    let mut lines: Vec<String> = release_notes
+        .lines()
+        .map(|line| line.trim_start_matches('#').trim())
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect();
+    if lines.len() > MAX_CHANGELOG_LINES {
+        lines.truncate(MAX_CHANGELOG_LINES - 1);
+        lines.push("…".to_string());
+    }
+    lines
+}
+
 impl UpdateSelection {
     fn label(self) -> String {
         match self {
             UpdateSelection::UpdateNow(action) => {
-                format!("Update now (runs `{}`)", action.command_str())
+                format!("Update now ({})", action.description())
             }
             UpdateSelection::NotNow => "Skip".to_string(),
             UpdateSelection::DontRemind => "Skip until next version".to_string(),
@@ -217,8 +440,66 @@ impl UpdateSelection {
     }
 }
 
+impl UpdatePromptScreen {
+    fn push_self_update(&self, column: &mut ColumnRenderable<'_>, state: &SelfUpdateState) {
+        match state {
+            SelfUpdateState::Running(progress) => {
+                column.push(
+                    Line::from(vec![
+                        format!("Updating to {}: ", self.latest_version).into(),
+                        progress.describe().cyan(),
+                    ])
+                    .inset(Insets::tlbr(0, 2, 0, 0)),
+                );
+                return;
+            }
+            SelfUpdateState::Finished(Ok(())) => {
+                column.push(
+                    Line::from(vec![
+                        "✔ ".green(),
+                        format!(
+                            "Updated to {}. Restart codex to use it.",
+                            self.latest_version
+                        )
+                        .into(),
+                    ])
+                    .inset(Insets::tlbr(0, 2, 0, 0)),
+                );
+            }
+            SelfUpdateState::Finished(Err(err)) => {
+                column.push(
+                    Line::from(vec!["✘ ".red(), format!("Update failed: {err}").red()])
+                        .inset(Insets::tlbr(0, 2, 0, 0)),
+                );
+                column.push(
+                    Line::from("The installed binary was not changed.".dim())
+                        .inset(Insets::tlbr(0, 4, 0, 0)),
+                );
+            }
+        }
+        column.push("");
+        column.push(
+            Line::from(vec![
+                "Press ".dim(),
+                key_hint::plain(KeyCode::Enter).into(),
+                " to continue".dim(),
+            ])
+            .inset(Insets::tlbr(0, 2, 0, 0)),
+        );
+    }
+}
+
 impl WidgetRef for &UpdatePromptScreen {
     fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        self.render_screen(area, buf);
+        if self.color_mode == ColorMode::Monochrome {
+            strip_colors(buf, area);
+        }
+    }
+}
+
+impl UpdatePromptScreen {
+    fn render_screen(&self, area: Rect, buf: &mut Buffer) {
         Clear.render(area, buf);
         let mut column = ColumnRenderable::new();
 
@@ -226,25 +507,74 @@ impl WidgetRef for &UpdatePromptScreen {
         column.push(Line::from(vec![
             padded_emoji("  ✨").bold().cyan(),
             "Update available!".bold(),
-            " ".into(),
-            format!(
-                "{current} -> {latest}",
-                current = self.current_version,
-                latest = self.latest_version
-            )
-            .dim(),
         ]));
         column.push("");
+        column.push(
+            Line::from(vec![
+                "Installed: ".dim(),
+                self.installed_description().into(),
+            ])
+            .inset(Insets::tlbr(0, 2, 0, 0)),
+        );
+        column.push(
+            Line::from(vec!["Offered:   ".dim(), self.offered_description().into()])
+                .inset(Insets::tlbr(0, 2, 0, 0)),
+        );
+        column.push("");
         column.push(
             Line::from(vec![
                 "Release notes: ".dim(),
-                "https://github.com/wenwen12345/codex/releases/latest"
-                    .dim()
-                    .underlined(),
+                format!(
+                    "https://github.com/{}/releases/latest",
+                    updates::GITHUB_REPO
+                )
+                .dim()
+                .underlined(),
             ])
             .inset(Insets::tlbr(0, 2, 0, 0)),
         );
+        if !self.changelog.is_empty() {
+            column.push("");
+            column.push(Line::from("What's new:".bold()).inset(Insets::tlbr(0, 2, 0, 0)));
+            for line in &self.changelog {
+                column.push(Line::from(line.clone().dim()).inset(Insets::tlbr(0, 4, 0, 0)));
+            }
+        }
         column.push("");
+        if let Some(state) = self.self_update.as_ref() {
+            self.push_self_update(&mut column, state);
+            column.render(area, buf);
+            return;
+        }
+        if let Some(pending) = self.pending_confirmation.as_ref() {
+            for warning in &pending.warnings {
+                column.push(
+                    Line::from(vec!["⚠ ".red(), warning.clone().into()])
+                        .inset(Insets::tlbr(0, 2, 0, 0)),
+                );
+            }
+            column.push("");
+            column.push(
+                Line::from(vec![
+                    "Command: ".dim(),
+                    pending.action.command_str().unwrap_or_default().cyan(),
+                ])
+                .inset(Insets::tlbr(0, 2, 0, 0)),
+            );
+            column.push("");
+            column.push(
+                Line::from(vec![
+                    "Press ".dim(),
+                    key_hint::plain(KeyCode::Enter).into(),
+                    " to run it anyway, ".dim(),
+                    key_hint::plain(KeyCode::Esc).into(),
+                    " to go back".dim(),
+                ])
+                .inset(Insets::tlbr(0, 2, 0, 0)),
+            );
+            column.render(area, buf);
+            return;
+        }
         for (idx, opt) in self.options.iter().copied().enumerate() {
             column.push(selection_option_row(
                 idx,
@@ -270,35 +600,125 @@ mod tests {
     use super::*;
     use crate::test_backend::VT100Backend;
     use crate::tui::FrameRequester;
+    use crate::update_action::PACKAGE_NAME;
+    use crate::update_action::PackageManager;
     use crossterm::event::KeyCode;
     use crossterm::event::KeyEvent;
     use crossterm::event::KeyModifiers;
     use ratatui::Terminal;
 
+    fn matching_source() -> InstallSource {
+        InstallSource {
+            manager: Some(PackageManager::Npm),
+            package: Some(PACKAGE_NAME.to_string()),
+        }
+    }
+
     fn new_prompt() -> UpdatePromptScreen {
-        UpdatePromptScreen::new(
+        let mut screen = UpdatePromptScreen::new(
             FrameRequester::test_dummy(),
             "9.9.9".into(),
             vec![UpdateAction::NpmGlobalLatest],
         )
+        .expect("prompt with an update action")
+        .with_install_source(matching_source());
+        // Keep snapshots stable across version bumps.
+        screen.current_version = "0.0.0".to_string();
+        screen
     }
 
     #[test]
     fn update_prompt_snapshot() {
         let screen = new_prompt();
-        let mut terminal = Terminal::new(VT100Backend::new(80, 12)).expect("terminal");
+        let mut terminal = Terminal::new(VT100Backend::new(80, 15)).expect("terminal");
         terminal
             .draw(|frame| frame.render_widget_ref(&screen, frame.area()))
             .expect("render update prompt");
         insta::assert_snapshot!("update_prompt_modal", terminal.backend());
     }
 
+    #[test]
+    fn update_prompt_with_changelog_snapshot() {
+        let screen = new_prompt().with_changelog(Some(
+            "## Changes\r\n\r\n- Faster startup\r\n- Fix translation overlay focus\r\n".to_string(),
+        ));
+        let mut terminal = Terminal::new(VT100Backend::new(80, 19)).expect("terminal");
+        terminal
+            .draw(|frame| frame.render_widget_ref(&screen, frame.area()))
+            .expect("render update prompt");
+        insta::assert_snapshot!("update_prompt_modal_with_changelog", terminal.backend());
+    }
+
+    #[test]
+    fn update_prompt_shows_install_method_and_publish_date() {
+        let screen = new_prompt()
+            .with_install_source(InstallSource::default())
+            .with_published_at(Some("2025-06-01T12:30:00Z".parse().unwrap()));
+        let mut terminal = Terminal::new(VT100Backend::new(80, 15)).expect("terminal");
+        terminal
+            .draw(|frame| frame.render_widget_ref(&screen, frame.area()))
+            .expect("render update prompt");
+        let rendered = terminal.backend().to_string();
+        assert!(rendered.contains("Installed: 0.0.0 (install method unknown)"));
+        assert!(
+            rendered.contains("Offered:   9.9.9 from the latest channel, published 2025-06-01")
+        );
+    }
+
+    #[test]
+    fn changelog_is_truncated() {
+        let notes = (1..=20)
+            .map(|i| format!("- change {i}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let lines = changelog_lines(&notes);
+        assert_eq!(lines.len(), MAX_CHANGELOG_LINES);
+        assert_eq!(lines.first().map(String::as_str), Some("- change 1"));
+        assert_eq!(lines.last().map(String::as_str), Some("…"));
+    }
+
     #[test]
     fn update_prompt_confirm_selects_update() {
         let mut screen = new_prompt();
         screen.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
         assert!(screen.is_done());
-        assert_eq!(screen.selection(), Some(UpdateSelection::UpdateNow));
+        assert_eq!(
+            screen.selection(),
+            Some(UpdateSelection::UpdateNow(UpdateAction::NpmGlobalLatest))
+        );
+    }
+
+    #[test]
+    fn update_prompt_source_mismatch_requires_confirmation() {
+        let mut screen = new_prompt().with_install_source(InstallSource {
+            manager: Some(PackageManager::Npm),
+            package: Some("@openai/codex".to_string()),
+        });
+        screen.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(!screen.is_done());
+
+        let mut terminal = Terminal::new(VT100Backend::new(100, 15)).expect("terminal");
+        terminal
+            .draw(|frame| frame.render_widget_ref(&screen, frame.area()))
+            .expect("render update prompt");
+        let rendered = terminal.backend().to_string();
+        assert!(rendered.contains("belongs to `@openai/codex`"));
+        assert!(rendered.contains("npm install -g @echoflux537/codex"));
+
+        screen.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(
+            screen.selection(),
+            Some(UpdateSelection::UpdateNow(UpdateAction::NpmGlobalLatest))
+        );
+    }
+
+    #[test]
+    fn update_prompt_source_mismatch_can_go_back() {
+        let mut screen = new_prompt().with_install_source(InstallSource::default());
+        screen.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        screen.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert!(!screen.is_done());
+        assert!(screen.pending_confirmation.is_none());
     }
 
     #[test]
@@ -337,6 +757,37 @@ mod tests {
         assert_eq!(screen.highlighted_idx, 0);
     }
 
+    #[test]
+    fn update_prompt_is_skipped_without_update_actions() {
+        assert!(
+            UpdatePromptScreen::new(FrameRequester::test_dummy(), "9.9.9".into(), Vec::new())
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn update_prompt_dedupes_repeated_actions() {
+        let screen = UpdatePromptScreen::new(
+            FrameRequester::test_dummy(),
+            "9.9.9".into(),
+            vec![
+                UpdateAction::NpmGlobalLatest,
+                UpdateAction::PnpmGlobalLatest,
+                UpdateAction::NpmGlobalLatest,
+            ],
+        )
+        .expect("prompt with update actions");
+        assert_eq!(
+            screen.options,
+            vec![
+                UpdateSelection::UpdateNow(UpdateAction::NpmGlobalLatest),
+                UpdateSelection::UpdateNow(UpdateAction::PnpmGlobalLatest),
+                UpdateSelection::NotNow,
+                UpdateSelection::DontRemind,
+            ]
+        );
+    }
+
     #[test]
     fn update_prompt_supports_multiple_update_actions() {
         let mut screen = UpdatePromptScreen::new(
@@ -346,7 +797,8 @@ mod tests {
                 UpdateAction::NpmGlobalLatest,
                 UpdateAction::PnpmGlobalLatest,
             ],
-        );
+        )
+        .expect("prompt with update actions");
         screen.handle_key(KeyEvent::new(KeyCode::Char('2'), KeyModifiers::NONE));
         assert!(screen.is_done());
         assert_eq!(
@@ -354,4 +806,78 @@ mod tests {
             Some(UpdateSelection::UpdateNow(UpdateAction::PnpmGlobalLatest))
         );
     }
+
+    fn render(screen: &UpdatePromptScreen) -> String {
+        let mut terminal = Terminal::new(VT100Backend::new(80, 15)).expect("terminal");
+        terminal
+            .draw(|frame| frame.render_widget_ref(screen, frame.area()))
+            .expect("render update prompt");
+        terminal.backend().to_string()
+    }
+
+    #[test]
+    fn self_update_shows_progress_until_it_finishes() {
+        let mut screen = UpdatePromptScreen::new(
+            FrameRequester::test_dummy(),
+            "9.9.9".into(),
+            vec![UpdateAction::SelfReplace, UpdateAction::NpmGlobalLatest],
+        )
+        .expect("prompt with update actions");
+        screen.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(!screen.is_done());
+        assert!(screen.take_self_update_request());
+        assert!(!screen.take_self_update_request());
+
+        screen.set_self_update(SelfUpdateState::Running(Progress::Verifying));
+        assert!(render(&screen).contains("Updating to 9.9.9: Verifying checksum…"));
+        screen.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        screen.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        assert!(!screen.is_done());
+
+        screen.set_self_update(SelfUpdateState::Finished(Ok(())));
+        assert!(render(&screen).contains("Updated to 9.9.9. Restart codex to use it."));
+        screen.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(
+            screen.selection(),
+            Some(UpdateSelection::UpdateNow(UpdateAction::SelfReplace))
+        );
+    }
+
+    #[test]
+    fn self_update_failure_is_reported() {
+        let mut screen = UpdatePromptScreen::new(
+            FrameRequester::test_dummy(),
+            "9.9.9".into(),
+            vec![UpdateAction::SelfReplace],
+        )
+        .expect("prompt with an update action");
+        screen.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        screen.set_self_update(SelfUpdateState::Finished(Err(
+            "checksum mismatch".to_string()
+        )));
+
+        let rendered = render(&screen);
+        assert!(rendered.contains("Update failed: checksum mismatch"));
+        assert!(rendered.contains("The installed binary was not changed."));
+    }
+
+    #[test]
+    fn update_prompt_no_color_snapshot() {
+        let screen = new_prompt().with_color_mode(ColorMode::Monochrome);
+        let mut terminal = Terminal::new(VT100Backend::new(80, 15)).expect("terminal");
+        terminal
+            .draw(|frame| frame.render_widget_ref(&screen, frame.area()))
+            .expect("render update prompt");
+        insta::assert_snapshot!("update_prompt_modal_no_color", terminal.backend());
+
+        let area = Rect::new(0, 0, 80, 15);
+        let mut buf = Buffer::empty(area);
+        (&screen).render_ref(area, &mut buf);
+        assert!(
+            buf.content()
+                .iter()
+                .all(|cell| cell.fg == ratatui::style::Color::Reset
+                    && cell.bg == ratatui::style::Color::Reset)
+        );
+    }
 }
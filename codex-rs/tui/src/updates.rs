@@ -1,5 +1,3 @@
-#![cfg(not(debug_assertions))]
-
 use chrono::DateTime;
 use chrono::Duration;
 use chrono::Utc;
@@ -7,9 +5,12 @@ use codex_core::config::Config;
 use codex_core::default_client::create_client;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
 use crate::version::CODEX_CLI_VERSION;
 
 /// The npm package name for version checking.
@@ -18,13 +19,57 @@ pub const NPM_PACKAGE_NAME: &str = "@echoflux537/codex";
 /// The GitHub repository for release notes.
 pub const GITHUB_REPO: &str = "wenwen12345/codex";
 
-pub fn get_upgrade_version(config: &Config) -> Option<String> {
-    if !config.check_for_update_on_startup {
+/// npm dist-tag that update checks follow.
+pub const UPDATE_CHANNEL: &str = "latest";
+
+/// Delays before each retry of a failed update check.
+const UPDATE_CHECK_RETRY_DELAYS: [std::time::Duration; 2] = [
+    std::time::Duration::from_secs(2),
+    std::time::Duration::from_secs(10),
+];
+
+/// Environment variable that enables update checks in debug builds.
+const FORCE_UPDATE_PROMPT_ENV: &str = "CODEX_FORCE_UPDATE_PROMPT";
+
+/// Returns whether update checks (and the resulting popup/banner) should run.
+///
+/// Release builds always check. Debug builds skip the check so local
+/// development isn't interrupted, unless `CODEX_FORCE_UPDATE_PROMPT=1`.
+pub fn update_checks_enabled() -> bool {
+    let force = std::env::var(FORCE_UPDATE_PROMPT_ENV).ok();
+    update_checks_enabled_for(cfg!(debug_assertions), force.as_deref())
+}
+
+fn update_checks_enabled_for(debug_build: bool, force: Option<&str>) -> bool {
+    !debug_build || force.is_some_and(|value| value.trim() == "1")
+}
+
+/// Returns the cached latest version if it is newer than the running binary.
+///
+/// When the cache is stale a background refresh is started. If `app_event_tx` is provided and
+/// the refresh finds a newer release, an [`AppEvent::UpdateVersionFetched`] is sent so the
+/// running TUI can show it immediately instead of on the next launch.
+pub fn get_upgrade_version(
+    config: &Config,
+    app_event_tx: Option<AppEventSender>,
+) -> Option<String> {
+    get_upgrade_version_with(config, app_event_tx, ReqwestVersionFetcher)
+}
+
+fn get_upgrade_version_with<F>(
+    config: &Config,
+    app_event_tx: Option<AppEventSender>,
+    fetcher: F,
+) -> Option<String>
+where
+    F: VersionFetcher + 'static,
+{
+    if !config.check_for_update_on_startup || !update_checks_enabled() {
         return None;
     }
 
     let version_file = version_filepath(config);
-    let info = read_version_info(&version_file).ok();
+    let info = load_version_info(&version_file);
 
     if match &info {
         None => true,
@@ -32,21 +77,33 @@ pub fn get_upgrade_version(config: &Config) -> Option<String> {
     } {
         // Refresh the cached latest version in the background so TUI startup
         // isn't blocked by a network call. The UI reads the previously cached
-        // value (if any) for this run; the next run shows the banner if needed.
+        // value (if any) for now and is notified once the refresh completes.
         tokio::spawn(async move {
-            check_for_update(&version_file)
+            match check_for_update_with_retry(&version_file, &fetcher, &UPDATE_CHECK_RETRY_DELAYS)
                 .await
-                .inspect_err(|e| tracing::error!("Failed to update version: {e}"))
+            {
+                Ok(latest_version) => {
+                    if let (Some(tx), Some(latest_version)) =
+                        (app_event_tx, newer_than_current(latest_version))
+                    {
+                        tx.send(AppEvent::UpdateVersionFetched(latest_version));
+                    }
+                }
+                Err(e) => tracing::error!("Failed to update version: {e}"),
+            }
         });
     }
 
-    info.and_then(|info| {
-        if is_newer(&info.latest_version, CODEX_CLI_VERSION).unwrap_or(false) {
-            Some(info.latest_version)
-        } else {
-            None
-        }
-    })
+    info.and_then(|info| newer_than_current(info.latest_version))
+}
+
+/// Returns `latest_version` if it is newer than the running binary.
+fn newer_than_current(latest_version: String) -> Option<String> {
+    if is_newer(&latest_version, CODEX_CLI_VERSION).unwrap_or(false) {
+        Some(latest_version)
+    } else {
+        None
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -56,18 +113,57 @@ struct VersionInfo {
     last_checked_at: DateTime<Utc>,
     #[serde(default)]
     dismissed_version: Option<String>,
+    /// Body of the latest GitHub release, shown in the update prompt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    release_notes: Option<String>,
+    /// When `latest_version` was published to the registry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    latest_published_at: Option<DateTime<Utc>>,
+    /// Validators of the registry response `latest_version` came from.
+    #[serde(default, skip_serializing_if = "CacheValidators::is_empty")]
+    registry_validators: CacheValidators,
+}
+
+/// `ETag` and `Last-Modified` of a response, sent back as `If-None-Match` and
+/// `If-Modified-Since` so an unchanged document comes back as an empty 304.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+struct CacheValidators {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_modified: Option<String>,
+}
+
+impl CacheValidators {
+    fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
 }
 
 const VERSION_FILENAME: &str = "version.json";
 
 /// npm registry API endpoint for package metadata.
-const NPM_REGISTRY_URL: &str = "https://registry.npmjs.org/@echoflux537/codex";
+fn npm_registry_url() -> String {
+    format!("https://registry.npmjs.org/{NPM_PACKAGE_NAME}")
+}
 
 /// Response structure from npm registry API (only fields we need).
 #[derive(Deserialize, Debug, Clone)]
 struct NpmPackageInfo {
     #[serde(rename = "dist-tags")]
     dist_tags: NpmDistTags,
+    /// Publish time of each version, plus `created` and `modified`.
+    #[serde(default)]
+    time: HashMap<String, String>,
+}
+
+impl NpmPackageInfo {
+    fn published_at(&self, version: &str) -> Option<DateTime<Utc>> {
+        let published = self.time.get(version)?;
+        DateTime::parse_from_rfc3339(published)
+            .ok()
+            .map(|published| published.with_timezone(&Utc))
+    }
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -75,36 +171,163 @@ struct NpmDistTags {
     latest: String,
 }
 
+/// GitHub API endpoint for the latest release.
+fn github_latest_release_url() -> String {
+    format!("https://api.github.com/repos/{GITHUB_REPO}/releases/latest")
+}
+
+/// Response structure from the GitHub releases API (only fields we need).
+#[derive(Deserialize, Debug, Clone)]
+struct GithubRelease {
+    #[serde(default)]
+    body: Option<String>,
+}
+
 fn version_filepath(config: &Config) -> PathBuf {
     config.codex_home.join(VERSION_FILENAME)
 }
 
-fn read_version_info(version_file: &Path) -> anyhow::Result<VersionInfo> {
-    let contents = std::fs::read_to_string(version_file)?;
-    Ok(serde_json::from_str(&contents)?)
+/// Read the cached version info, treating a missing or unreadable file as no cache.
+///
+/// A file that doesn't parse (truncated or hand-edited) is moved aside to
+/// `version.json.bak`, so it is reported once and the next successful check writes a fresh
+/// one instead of every launch tripping over it.
+fn load_version_info(version_file: &Path) -> Option<VersionInfo> {
+    let contents = match std::fs::read_to_string(version_file) {
+        Ok(contents) => contents,
+        Err(e) => {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!("Failed to read {}: {e}", version_file.display());
+            }
+            return None;
+        }
+    };
+    match serde_json::from_str(&contents) {
+        Ok(info) => Some(info),
+        Err(e) => {
+            let backup = version_file.with_extension("json.bak");
+            let moved = std::fs::rename(version_file, &backup)
+                .or_else(|_| std::fs::remove_file(version_file));
+            match moved {
+                Ok(()) => tracing::warn!(
+                    "Ignoring corrupt {} ({e}); it will be rewritten by the next update check",
+                    version_file.display()
+                ),
+                Err(remove_err) => tracing::warn!(
+                    "Ignoring corrupt {} ({e}) and failed to remove it: {remove_err}",
+                    version_file.display()
+                ),
+            }
+            None
+        }
+    }
+}
+
+/// Raw HTTP response returned by a [`VersionFetcher`].
+#[derive(Debug, Clone)]
+struct FetchedResponse {
+    status: u16,
+    body: String,
+    validators: CacheValidators,
+}
+
+/// Fetches package metadata over HTTP; abstracted so update checks can be tested offline.
+trait VersionFetcher: Send + Sync {
+    /// GET `url`, conditional on `validators` when they are set.
+    fn fetch(
+        &self,
+        url: &str,
+        validators: &CacheValidators,
+    ) -> impl Future<Output = anyhow::Result<FetchedResponse>> + Send;
+}
+
+/// [`VersionFetcher`] backed by the shared Codex HTTP client.
+struct ReqwestVersionFetcher;
+
+impl VersionFetcher for ReqwestVersionFetcher {
+    async fn fetch(
+        &self,
+        url: &str,
+        validators: &CacheValidators,
+    ) -> anyhow::Result<FetchedResponse> {
+        let mut request = create_client().get(url);
+        if let Some(etag) = validators.etag.as_deref() {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        if let Some(last_modified) = validators.last_modified.as_deref() {
+            request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+        }
+        let response = request.send().await?;
+        let status = response.status().as_u16();
+        let header = |name: reqwest::header::HeaderName| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        };
+        let validators = CacheValidators {
+            etag: header(reqwest::header::ETAG),
+            last_modified: header(reqwest::header::LAST_MODIFIED),
+        };
+        let body = response.text().await?;
+        Ok(FetchedResponse {
+            status,
+            body,
+            validators,
+        })
+    }
 }
 
-async fn check_for_update(version_file: &Path) -> anyhow::Result<()> {
-    // Fetch latest version from npm registry
-    let npm_info: NpmPackageInfo = create_client()
-        .get(NPM_REGISTRY_URL)
-        .send()
-        .await?
-        .error_for_status()?
-        .json()
-        .await?;
+/// HTTP status for a conditional request whose document hasn't changed.
+const NOT_MODIFIED: u16 = 304;
 
+/// Fetch the latest published version, record it in `version_file`, and return it.
+///
+/// The request is conditional on the validators of the previous check; when the registry
+/// answers 304 only `last_checked_at` is bumped. `version_file` is left untouched if the
+/// registry request or response parsing fails.
+async fn check_for_update<F: VersionFetcher>(
+    version_file: &Path,
+    fetcher: &F,
+) -> anyhow::Result<String> {
+    let prev_info = load_version_info(version_file);
+    let validators = prev_info
+        .as_ref()
+        .map(|info| info.registry_validators.clone())
+        .unwrap_or_default();
+    let response = fetcher.fetch(&npm_registry_url(), &validators).await?;
+    if response.status == NOT_MODIFIED {
+        let Some(mut info) = prev_info else {
+            anyhow::bail!("npm registry returned HTTP 304 without a cached version");
+        };
+        info.last_checked_at = Utc::now();
+        write_version_info(version_file, &info).await?;
+        return Ok(info.latest_version);
+    }
+    if !(200..300).contains(&response.status) {
+        anyhow::bail!("npm registry returned HTTP {}", response.status);
+    }
+    let npm_info: NpmPackageInfo = serde_json::from_str(&response.body)?;
+    let latest_published_at = npm_info.published_at(&npm_info.dist_tags.latest);
     let latest_version = npm_info.dist_tags.latest;
+    let release_notes = fetch_release_notes(fetcher).await;
 
     // Preserve any previously dismissed version if present.
-    let prev_info = read_version_info(version_file).ok();
     let info = VersionInfo {
-        latest_version,
+        latest_version: latest_version.clone(),
         last_checked_at: Utc::now(),
         dismissed_version: prev_info.and_then(|p| p.dismissed_version),
+        release_notes,
+        latest_published_at,
+        registry_validators: response.validators,
     };
+    write_version_info(version_file, &info).await?;
+    Ok(latest_version)
+}
 
-    let json_line = format!("{}\n", serde_json::to_string(&info)?);
+async fn write_version_info(version_file: &Path, info: &VersionInfo) -> anyhow::Result<()> {
+    let json_line = format!("{}\n", serde_json::to_string(info)?);
     if let Some(parent) = version_file.parent() {
         tokio::fs::create_dir_all(parent).await?;
     }
@@ -112,6 +335,55 @@ async fn check_for_update(version_file: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// [`check_for_update`], retried after each of `retry_delays` while it keeps failing.
+///
+/// `version_file` (and so `last_checked_at`) is only written by a successful attempt, so a
+/// check that fails every attempt is tried again on the next launch.
+async fn check_for_update_with_retry<F: VersionFetcher>(
+    version_file: &Path,
+    fetcher: &F,
+    retry_delays: &[std::time::Duration],
+) -> anyhow::Result<String> {
+    let mut delays = retry_delays.iter();
+    loop {
+        match check_for_update(version_file, fetcher).await {
+            Ok(latest_version) => return Ok(latest_version),
+            Err(e) => {
+                let Some(delay) = delays.next() else {
+                    return Err(e);
+                };
+                tracing::debug!("Update check failed, retrying in {delay:?}: {e}");
+                tokio::time::sleep(*delay).await;
+            }
+        }
+    }
+}
+
+/// Fetch the body of the latest GitHub release.
+///
+/// Release notes are optional: any failure is logged and yields `None`, in which case the
+/// update prompt only links to the releases page.
+async fn fetch_release_notes<F: VersionFetcher>(fetcher: &F) -> Option<String> {
+    let response = match fetcher
+        .fetch(&github_latest_release_url(), &CacheValidators::default())
+        .await
+    {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::debug!("Failed to fetch release notes: {e}");
+            return None;
+        }
+    };
+    if !(200..300).contains(&response.status) {
+        tracing::debug!("GitHub releases API returned HTTP {}", response.status);
+        return None;
+    }
+    serde_json::from_str::<GithubRelease>(&response.body)
+        .ok()?
+        .body
+        .filter(|body| !body.trim().is_empty())
+}
+
 fn is_newer(latest: &str, current: &str) -> Option<bool> {
     match (parse_version(latest), parse_version(current)) {
         (Some(l), Some(c)) => Some(l > c),
@@ -139,9 +411,9 @@ pub fn get_upgrade_version_for_popup(config: &Config) -> Option<String> {
     }
 
     let version_file = version_filepath(config);
-    let latest = get_upgrade_version(config)?;
+    let latest = get_upgrade_version(config, None)?;
     // If the user dismissed this exact version previously, do not show the popup.
-    if let Ok(info) = read_version_info(&version_file)
+    if let Some(info) = load_version_info(&version_file)
         && info.dismissed_version.as_deref() == Some(latest.as_str())
     {
         return None;
@@ -149,26 +421,42 @@ pub fn get_upgrade_version_for_popup(config: &Config) -> Option<String> {
     Some(latest)
 }
 
+/// Returns the release notes cached alongside the latest version, if any.
+pub fn cached_release_notes(config: &Config) -> Option<String> {
+    load_version_info(&version_filepath(config)).and_then(|info| info.release_notes)
+}
+
+/// Returns when the cached latest version was published, if the registry reported it.
+pub fn cached_published_at(config: &Config) -> Option<DateTime<Utc>> {
+    load_version_info(&version_filepath(config)).and_then(|info| info.latest_published_at)
+}
+
 /// Persist a dismissal for the current latest version so we don't show
 /// the update popup again for this version.
 pub async fn dismiss_version(config: &Config, version: &str) -> anyhow::Result<()> {
-    let version_file = version_filepath(config);
-    let mut info = match read_version_info(&version_file) {
-        Ok(info) => info,
-        Err(_) => return Ok(()),
-    };
+    record_dismissal(&version_filepath(config), version).await
+}
+
+/// Record `version` as dismissed in `version_file`. Without a usable cache a fresh one is
+/// written that still counts as never checked, so the next launch refreshes it.
+async fn record_dismissal(version_file: &Path, version: &str) -> anyhow::Result<()> {
+    let mut info = load_version_info(version_file).unwrap_or_else(|| VersionInfo {
+        latest_version: version.to_string(),
+        last_checked_at: DateTime::<Utc>::UNIX_EPOCH,
+        dismissed_version: None,
+        release_notes: None,
+        latest_published_at: None,
+        registry_validators: CacheValidators::default(),
+    });
     info.dismissed_version = Some(version.to_string());
-    let json_line = format!("{}\n", serde_json::to_string(&info)?);
-    if let Some(parent) = version_file.parent() {
-        tokio::fs::create_dir_all(parent).await?;
-    }
-    tokio::fs::write(version_file, json_line).await?;
-    Ok(())
+    write_version_info(version_file, &info).await
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
 
     #[test]
     fn plain_semver_comparisons_work() {
@@ -185,6 +473,14 @@ mod tests {
         assert_eq!(is_newer("0.93.0-cometix", "0.93.0"), Some(false));
     }
 
+    #[test]
+    fn npm_registry_url_uses_package_name() {
+        assert_eq!(
+            npm_registry_url(),
+            "https://registry.npmjs.org/@echoflux537/codex"
+        );
+    }
+
     #[test]
     fn whitespace_is_ignored() {
         assert_eq!(parse_version(" 1.2.3 \n"), Some((1, 2, 3)));
@@ -197,4 +493,404 @@ mod tests {
         assert_eq!(is_newer("0.93.0-cometix", "0.93.0-cometix"), Some(false));
         assert_eq!(parse_version("0.93.0-cometix"), Some((0, 93, 0)));
     }
+
+    #[test]
+    fn debug_builds_skip_update_checks_unless_forced() {
+        assert!(update_checks_enabled_for(false, None));
+        assert!(update_checks_enabled_for(false, Some("0")));
+        assert!(!update_checks_enabled_for(true, None));
+        assert!(!update_checks_enabled_for(true, Some("0")));
+        assert!(update_checks_enabled_for(true, Some("1")));
+    }
+
+    #[test]
+    fn only_newer_versions_are_announced() {
+        assert_eq!(
+            newer_than_current("999.0.0".to_string()),
+            Some("999.0.0".to_string())
+        );
+        assert_eq!(newer_than_current(CODEX_CLI_VERSION.to_string()), None);
+        assert_eq!(newer_than_current("not-a-version".to_string()), None);
+    }
+
+    /// Fetcher that returns canned registry and release responses (or errors).
+    struct CannedFetcher {
+        registry: Result<FetchedResponse, String>,
+        release: Result<FetchedResponse, String>,
+        /// Registry requests that fail before the canned response is returned.
+        registry_failures: AtomicUsize,
+        registry_calls: AtomicUsize,
+        /// Validators sent with the last registry request.
+        sent_validators: std::sync::Mutex<Option<CacheValidators>>,
+    }
+
+    impl CannedFetcher {
+        fn ok(status: u16, body: &str) -> Self {
+            Self {
+                registry: Ok(FetchedResponse {
+                    status,
+                    body: body.to_string(),
+                    validators: CacheValidators::default(),
+                }),
+                release: Err("release notes unavailable".to_string()),
+                registry_failures: AtomicUsize::new(0),
+                registry_calls: AtomicUsize::new(0),
+                sent_validators: std::sync::Mutex::new(None),
+            }
+        }
+
+        fn err(message: &str) -> Self {
+            Self {
+                registry: Err(message.to_string()),
+                release: Err(message.to_string()),
+                registry_failures: AtomicUsize::new(0),
+                registry_calls: AtomicUsize::new(0),
+                sent_validators: std::sync::Mutex::new(None),
+            }
+        }
+
+        fn failing_first(self, failures: usize) -> Self {
+            self.registry_failures.store(failures, Ordering::SeqCst);
+            self
+        }
+
+        fn registry_calls(&self) -> usize {
+            self.registry_calls.load(Ordering::SeqCst)
+        }
+
+        fn with_release(mut self, status: u16, body: &str) -> Self {
+            self.release = Ok(FetchedResponse {
+                status,
+                body: body.to_string(),
+                validators: CacheValidators::default(),
+            });
+            self
+        }
+
+        fn with_etag(mut self, etag: &str) -> Self {
+            if let Ok(response) = self.registry.as_mut() {
+                response.validators.etag = Some(etag.to_string());
+            }
+            self
+        }
+
+        fn sent_validators(&self) -> Option<CacheValidators> {
+            self.sent_validators.lock().unwrap().clone()
+        }
+    }
+
+    impl VersionFetcher for CannedFetcher {
+        async fn fetch(
+            &self,
+            url: &str,
+            validators: &CacheValidators,
+        ) -> anyhow::Result<FetchedResponse> {
+            let response = if url == npm_registry_url() {
+                self.registry_calls.fetch_add(1, Ordering::SeqCst);
+                *self.sent_validators.lock().unwrap() = Some(validators.clone());
+                let failed = self
+                    .registry_failures
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                    .is_ok();
+                if failed {
+                    anyhow::bail!("connection reset");
+                }
+                &self.registry
+            } else {
+                assert_eq!(url, github_latest_release_url());
+                assert!(validators.is_empty());
+                &self.release
+            };
+            response.clone().map_err(anyhow::Error::msg)
+        }
+    }
+
+    const PREVIOUS_VERSION_JSON: &str = r#"{"latest_version":"0.1.0","last_checked_at":"2024-01-01T00:00:00Z","dismissed_version":"0.1.0"}"#;
+
+    fn version_file_with_previous_info(dir: &tempfile::TempDir) -> PathBuf {
+        let version_file = dir.path().join(VERSION_FILENAME);
+        std::fs::write(&version_file, PREVIOUS_VERSION_JSON).unwrap();
+        version_file
+    }
+
+    #[tokio::test]
+    async fn check_for_update_records_registry_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = version_file_with_previous_info(&dir);
+        let fetcher = CannedFetcher::ok(
+            200,
+            r#"{"name":"@echoflux537/codex","dist-tags":{"latest":"999.0.0","next":"1000.0.0-beta"}}"#,
+        );
+
+        let latest = check_for_update(&version_file, &fetcher).await.unwrap();
+
+        assert_eq!(latest, "999.0.0");
+        let info = load_version_info(&version_file).unwrap();
+        assert_eq!(info.latest_version, "999.0.0");
+        assert_eq!(info.dismissed_version.as_deref(), Some("0.1.0"));
+        assert_eq!(info.release_notes, None);
+        assert_eq!(info.latest_published_at, None);
+    }
+
+    #[tokio::test]
+    async fn check_for_update_records_publish_time() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = dir.path().join(VERSION_FILENAME);
+        let fetcher = CannedFetcher::ok(
+            200,
+            r#"{"dist-tags":{"latest":"1.2.3"},"time":{"created":"2024-01-01T00:00:00.000Z","1.2.2":"2025-05-20T08:00:00.000Z","1.2.3":"2025-06-01T12:30:00.000Z"}}"#,
+        );
+
+        check_for_update(&version_file, &fetcher).await.unwrap();
+
+        let info = load_version_info(&version_file).unwrap();
+        assert_eq!(
+            info.latest_published_at,
+            Some("2025-06-01T12:30:00Z".parse().unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn check_for_update_caches_release_notes() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = dir.path().join(VERSION_FILENAME);
+        let fetcher = CannedFetcher::ok(200, r#"{"dist-tags":{"latest":"1.2.3"}}"#)
+            .with_release(200, r#"{"tag_name":"v1.2.3","body":"- Faster startup"}"#);
+
+        check_for_update(&version_file, &fetcher).await.unwrap();
+
+        let info = load_version_info(&version_file).unwrap();
+        assert_eq!(info.release_notes.as_deref(), Some("- Faster startup"));
+    }
+
+    #[tokio::test]
+    async fn release_notes_failure_still_records_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = dir.path().join(VERSION_FILENAME);
+        let fetcher = CannedFetcher::ok(200, r#"{"dist-tags":{"latest":"1.2.3"}}"#)
+            .with_release(403, r#"{"message":"API rate limit exceeded"}"#);
+
+        check_for_update(&version_file, &fetcher).await.unwrap();
+
+        let info = load_version_info(&version_file).unwrap();
+        assert_eq!(info.latest_version, "1.2.3");
+        assert_eq!(info.release_notes, None);
+    }
+
+    #[tokio::test]
+    async fn check_for_update_creates_missing_version_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = dir.path().join("nested").join(VERSION_FILENAME);
+        let fetcher = CannedFetcher::ok(200, r#"{"dist-tags":{"latest":"1.2.3"}}"#);
+
+        check_for_update(&version_file, &fetcher).await.unwrap();
+
+        let info = load_version_info(&version_file).unwrap();
+        assert_eq!(info.latest_version, "1.2.3");
+        assert_eq!(info.dismissed_version, None);
+    }
+
+    #[tokio::test]
+    async fn malformed_registry_json_leaves_version_file_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = version_file_with_previous_info(&dir);
+        let fetcher = CannedFetcher::ok(200, r#"{"dist-tags":{"next":"1.0.0"}"#);
+
+        assert!(check_for_update(&version_file, &fetcher).await.is_err());
+        assert_eq!(
+            std::fs::read_to_string(&version_file).unwrap(),
+            PREVIOUS_VERSION_JSON
+        );
+    }
+
+    #[tokio::test]
+    async fn http_error_leaves_version_file_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = version_file_with_previous_info(&dir);
+        let fetcher = CannedFetcher::ok(503, "Service Unavailable");
+
+        let err = check_for_update(&version_file, &fetcher).await.unwrap_err();
+
+        assert!(err.to_string().contains("503"));
+        assert_eq!(
+            std::fs::read_to_string(&version_file).unwrap(),
+            PREVIOUS_VERSION_JSON
+        );
+    }
+
+    #[tokio::test]
+    async fn network_error_leaves_version_file_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = dir.path().join(VERSION_FILENAME);
+        let fetcher = CannedFetcher::err("connection refused");
+
+        assert!(check_for_update(&version_file, &fetcher).await.is_err());
+        assert!(!version_file.exists());
+    }
+
+    #[tokio::test]
+    async fn retry_after_failure_records_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = dir.path().join(VERSION_FILENAME);
+        let fetcher =
+            CannedFetcher::ok(200, r#"{"dist-tags":{"latest":"1.2.3"}}"#).failing_first(1);
+
+        let latest =
+            check_for_update_with_retry(&version_file, &fetcher, &[std::time::Duration::ZERO; 2])
+                .await
+                .unwrap();
+
+        assert_eq!(latest, "1.2.3");
+        assert_eq!(fetcher.registry_calls(), 2);
+        let info = load_version_info(&version_file).unwrap();
+        assert_eq!(info.latest_version, "1.2.3");
+    }
+
+    #[tokio::test]
+    async fn retries_are_bounded_and_leave_version_file_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = version_file_with_previous_info(&dir);
+        let fetcher = CannedFetcher::err("connection refused");
+
+        let result =
+            check_for_update_with_retry(&version_file, &fetcher, &[std::time::Duration::ZERO; 2])
+                .await;
+
+        assert!(result.is_err());
+        assert_eq!(fetcher.registry_calls(), 3);
+        assert_eq!(
+            std::fs::read_to_string(&version_file).unwrap(),
+            PREVIOUS_VERSION_JSON
+        );
+    }
+
+    const CORRUPT_VERSION_JSON: &str = r#"{"latest_version":"0.1.0","last_checked_at":"2024-01-"#;
+
+    #[test]
+    fn corrupt_version_file_is_moved_aside() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = dir.path().join(VERSION_FILENAME);
+        std::fs::write(&version_file, CORRUPT_VERSION_JSON).unwrap();
+
+        assert!(load_version_info(&version_file).is_none());
+
+        assert!(!version_file.exists());
+        let backup = dir.path().join("version.json.bak");
+        assert_eq!(
+            std::fs::read_to_string(backup).unwrap(),
+            CORRUPT_VERSION_JSON
+        );
+        // Later reads see no cache rather than the same error again.
+        assert!(load_version_info(&version_file).is_none());
+    }
+
+    #[tokio::test]
+    async fn check_for_update_replaces_corrupt_version_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = dir.path().join(VERSION_FILENAME);
+        std::fs::write(&version_file, "\0\0garbage").unwrap();
+        let fetcher = CannedFetcher::ok(200, r#"{"dist-tags":{"latest":"1.2.3"}}"#);
+
+        check_for_update(&version_file, &fetcher).await.unwrap();
+
+        let info = load_version_info(&version_file).unwrap();
+        assert_eq!(info.latest_version, "1.2.3");
+        assert_eq!(info.dismissed_version, None);
+    }
+
+    #[tokio::test]
+    async fn dismissal_is_recorded_despite_corrupt_version_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = dir.path().join(VERSION_FILENAME);
+        std::fs::write(&version_file, CORRUPT_VERSION_JSON).unwrap();
+
+        record_dismissal(&version_file, "1.2.3").await.unwrap();
+
+        let info = load_version_info(&version_file).unwrap();
+        assert_eq!(info.dismissed_version.as_deref(), Some("1.2.3"));
+        // The rebuilt cache is stale, so the next launch checks the registry again.
+        assert_eq!(info.last_checked_at, DateTime::<Utc>::UNIX_EPOCH);
+    }
+
+    #[tokio::test]
+    async fn dismissal_keeps_cached_version_info() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = version_file_with_previous_info(&dir);
+
+        record_dismissal(&version_file, "0.2.0").await.unwrap();
+
+        let info = load_version_info(&version_file).unwrap();
+        assert_eq!(info.latest_version, "0.1.0");
+        assert_eq!(info.dismissed_version.as_deref(), Some("0.2.0"));
+    }
+
+    const ETAG_VERSION_JSON: &str = r#"{"latest_version":"1.2.3","last_checked_at":"2024-01-01T00:00:00Z","dismissed_version":"1.2.2","release_notes":"- Faster startup","registry_validators":{"etag":"W/\"abc\""}}"#;
+
+    #[tokio::test]
+    async fn unchanged_registry_only_bumps_last_checked_at() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = dir.path().join(VERSION_FILENAME);
+        std::fs::write(&version_file, ETAG_VERSION_JSON).unwrap();
+        let fetcher = CannedFetcher::ok(304, "");
+
+        let latest = check_for_update(&version_file, &fetcher).await.unwrap();
+
+        assert_eq!(latest, "1.2.3");
+        assert_eq!(
+            fetcher.sent_validators().and_then(|v| v.etag).as_deref(),
+            Some("W/\"abc\"")
+        );
+        let info = load_version_info(&version_file).unwrap();
+        assert!(info.last_checked_at > Utc::now() - Duration::minutes(1));
+        assert_eq!(info.latest_version, "1.2.3");
+        assert_eq!(info.dismissed_version.as_deref(), Some("1.2.2"));
+        assert_eq!(info.release_notes.as_deref(), Some("- Faster startup"));
+        assert_eq!(info.registry_validators.etag.as_deref(), Some("W/\"abc\""));
+    }
+
+    #[tokio::test]
+    async fn changed_registry_replaces_the_etag() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = dir.path().join(VERSION_FILENAME);
+        std::fs::write(&version_file, ETAG_VERSION_JSON).unwrap();
+        let fetcher =
+            CannedFetcher::ok(200, r#"{"dist-tags":{"latest":"1.3.0"}}"#).with_etag("W/\"def\"");
+
+        let latest = check_for_update(&version_file, &fetcher).await.unwrap();
+
+        assert_eq!(latest, "1.3.0");
+        let info = load_version_info(&version_file).unwrap();
+        assert_eq!(info.registry_validators.etag.as_deref(), Some("W/\"def\""));
+
+        // A response without an ETag clears it, so the next check is unconditional.
+        let fetcher = CannedFetcher::ok(200, r#"{"dist-tags":{"latest":"1.3.1"}}"#);
+        check_for_update(&version_file, &fetcher).await.unwrap();
+        assert_eq!(
+            fetcher.sent_validators().and_then(|v| v.etag).as_deref(),
+            Some("W/\"def\"")
+        );
+        let info = load_version_info(&version_file).unwrap();
+        assert_eq!(info.registry_validators, CacheValidators::default());
+    }
+
+    #[tokio::test]
+    async fn first_check_is_unconditional() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = dir.path().join(VERSION_FILENAME);
+        let fetcher = CannedFetcher::ok(200, r#"{"dist-tags":{"latest":"1.2.3"}}"#);
+
+        check_for_update(&version_file, &fetcher).await.unwrap();
+
+        assert_eq!(fetcher.sent_validators(), Some(CacheValidators::default()));
+    }
+
+    #[tokio::test]
+    async fn not_modified_without_a_cache_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_file = dir.path().join(VERSION_FILENAME);
+        let fetcher = CannedFetcher::ok(304, "");
+
+        assert!(check_for_update(&version_file, &fetcher).await.is_err());
+        assert!(!version_file.exists());
+    }
 }
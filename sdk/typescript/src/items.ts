@@ -76,6 +76,8 @@ export type AgentMessageItem = {
   type: "agent_message";
   /** Either natural-language text or JSON when structured output is requested. */
   text: string;
+  /** Translation of `text`, when run with `--translate`. */
+  translation?: string;
 };
 
 /** Agent's reasoning summary. */
@@ -83,6 +85,8 @@ export type ReasoningItem = {
   id: string;
   type: "reasoning";
   text: string;
+  /** Translation of `text`, when run with `--translate`. */
+  translation?: string;
 };
 
 /** Captures a web search request. Completes when results are returned to the agent. */